@@ -36,3 +36,20 @@ fn read_dublincore(b: &mut Bencher) {
         let _ = Channel::read_from(input).expect("failed to parse feed");
     });
 }
+
+#[bench]
+fn read_large(b: &mut Bencher) {
+    let item = include_str!("../tests/data/item.xml");
+    let start = item.find("<item>").expect("item missing");
+    let end = item.rfind("</item>").expect("item missing") + "</item>".len();
+
+    let mut input = String::from("<rss version=\"2.0\"><channel><title>Title</title>");
+    for _ in 0..1000 {
+        input.push_str(&item[start..end]);
+    }
+    input.push_str("</channel></rss>");
+
+    b.iter(|| {
+        let _ = Channel::read_from(input.as_bytes()).expect("failed to parse feed");
+    });
+}
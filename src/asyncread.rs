@@ -0,0 +1,618 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::io::{self, BufRead, Read};
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+
+use futures_io::AsyncBufRead;
+
+use channel::Channel;
+use error::Error;
+use item::Item;
+use itemreader::ItemReader;
+use parseoptions::{ParseOptions, ParseWarning};
+
+impl Channel {
+    /// Attempt to read an RSS channel from an asynchronous reader.
+    ///
+    /// The input is parsed with the same parser as `Channel::read_from` while it arrives, so the
+    /// resulting channel and errors are the same. Each item is parsed as soon as all of it has
+    /// been received, and the input it was read from is then dropped, so only about one item
+    /// of the input is held in memory at a time. Read errors are returned as `Error::Io`.
+    ///
+    /// UTF-16 input is the exception: it is only parsed once all of it has been received.
+    ///
+    /// Any reader implementing the `futures_io::AsyncBufRead` trait can be used. The returned
+    /// future does not depend on a particular runtime.
+    ///
+    /// **Note**: This requires enabling the `async` feature.
+    ///
+    /// # Examples
+    ///
+    /// `block_on` stands for the executor of the runtime in use, such as
+    /// `futures::executor::block_on`.
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// # use std::future::Future;
+    /// # use std::pin::Pin;
+    /// # use std::ptr;
+    /// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    /// #
+    /// # fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    /// #     fn clone(_: *const ()) -> RawWaker {
+    /// #         RawWaker::new(ptr::null(), &VTABLE)
+    /// #     }
+    /// #     fn noop(_: *const ()) {}
+    /// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    /// #     let waker = unsafe { Waker::from_raw(clone(ptr::null())) };
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     loop {
+    /// #         if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// let input: &[u8] = b"<rss version=\"2.0\"><channel><title>Title</title></channel></rss>";
+    /// let channel = block_on(Channel::read_from_async(input)).unwrap();
+    /// assert_eq!(channel.title(), "Title");
+    /// ```
+    pub fn read_from_async<R>(reader: R) -> ReadFromAsync<R>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        ReadFromAsync::new(reader, ParseOptions::default())
+    }
+
+    /// Attempt to read an RSS channel from an asynchronous reader using the provided options.
+    ///
+    /// This reads the input like `Channel::read_from_with_options`, returning the channel along
+    /// with the problems that were skipped when parsing is lenient.
+    ///
+    /// **Note**: This requires enabling the `async` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, ParseOptionsBuilder};
+    /// # use std::future::Future;
+    /// # use std::pin::Pin;
+    /// # use std::ptr;
+    /// # use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    /// #
+    /// # fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    /// #     fn clone(_: *const ()) -> RawWaker {
+    /// #         RawWaker::new(ptr::null(), &VTABLE)
+    /// #     }
+    /// #     fn noop(_: *const ()) {}
+    /// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    /// #     let waker = unsafe { Waker::from_raw(clone(ptr::null())) };
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     loop {
+    /// #         if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// let options = ParseOptionsBuilder::default().max_items(1usize).build().unwrap();
+    /// let input: &[u8] = b"<rss version=\"2.0\"><channel><item><title>First</title></item>\
+    ///                      <item><title>Second</title></item></channel></rss>";
+    /// let future = Channel::read_from_async_with_options(input, &options);
+    /// assert!(block_on(future).is_err());
+    /// ```
+    pub fn read_from_async_with_options<R>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> ReadFromAsyncWithOptions<R>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        ReadFromAsyncWithOptions {
+            inner: ReadFromAsync::new(reader, options.clone()),
+        }
+    }
+}
+
+/// A future that reads an RSS channel from an asynchronous reader.
+///
+/// This is created by `Channel::read_from_async`.
+pub struct ReadFromAsync<R> {
+    reader: R,
+    options: ParseOptions,
+    shared: Arc<Shared>,
+    scanner: Scanner,
+    item_reader: Option<ItemReader<Received>>,
+    items: Vec<Item>,
+}
+
+impl<R: fmt::Debug> fmt::Debug for ReadFromAsync<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadFromAsync")
+            .field("reader", &self.reader)
+            .field("items", &self.items.len())
+            .finish()
+    }
+}
+
+impl<R> ReadFromAsync<R> {
+    fn new(reader: R, options: ParseOptions) -> Self {
+        ReadFromAsync {
+            reader: reader,
+            options: options,
+            shared: Arc::new(Shared::default()),
+            scanner: Scanner::default(),
+            item_reader: None,
+            items: Vec::new(),
+        }
+    }
+
+    /// Parse as much of the input received so far as the parser can read without reaching the
+    /// end of it, returning the channel once all of it has been read.
+    fn parse(&mut self) -> Result<Option<(Channel, Vec<ParseWarning>)>, Error> {
+        let eof = self.shared.pending.lock().expect("the parser does not panic").eof;
+
+        loop {
+            if self.item_reader.as_ref().map_or(false, |reader| reader.is_done()) {
+                break;
+            }
+
+            // the parser stops at the end of an item or of the root element, so it never reads
+            // past a point where the scanner has seen one end
+            let consumed = self.shared.consumed.load(Ordering::SeqCst);
+            if !eof && !self.scanner.has_end_after(consumed) {
+                return Ok(None);
+            }
+
+            // a skipped item is also a stop, since the next one may not have been received
+            let item = match self.item_reader {
+                Some(ref mut item_reader) => item_reader.next_or_skipped(),
+                None => {
+                    let input = Received {
+                        shared: self.shared.clone(),
+                        chunk: Vec::new(),
+                        pos: 0,
+                    };
+                    self.item_reader = Some(ItemReader::with_options(input, &self.options)?);
+                    continue;
+                }
+            };
+
+            match item {
+                Some(item) => self.items.extend(item?),
+                None => break,
+            }
+        }
+
+        let item_reader = self.item_reader.take().expect("the parser was created");
+        let (mut channel, warnings) = item_reader.into_parts();
+        channel.set_items(mem::replace(&mut self.items, Vec::new()));
+        Ok(Some((channel, warnings)))
+    }
+}
+
+impl<R> ReadFromAsync<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Receive as much of the input as is ready, parsing it as it arrives.
+    fn poll_parts(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<Result<(Channel, Vec<ParseWarning>), Error>> {
+        loop {
+            let len = {
+                let chunk = match Pin::new(&mut self.reader).poll_fill_buf(cx) {
+                    Poll::Ready(Ok(chunk)) => chunk,
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                    Poll::Pending => return Poll::Pending,
+                };
+                self.scanner.scan(chunk);
+                let mut pending = self.shared.pending.lock().expect("the parser does not panic");
+                pending.bytes.extend_from_slice(chunk);
+                pending.eof = chunk.is_empty();
+                chunk.len()
+            };
+
+            Pin::new(&mut self.reader).consume(len);
+
+            match self.parse() {
+                Ok(Some(parts)) => return Poll::Ready(Ok(parts)),
+                Ok(None) => {}
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+}
+
+impl<R> Future for ReadFromAsync<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Output = Result<Channel, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.get_mut()
+            .poll_parts(cx)
+            .map(|result| result.map(|(channel, _)| channel))
+    }
+}
+
+/// A future that reads an RSS channel from an asynchronous reader using the provided options.
+///
+/// This is created by `Channel::read_from_async_with_options`.
+pub struct ReadFromAsyncWithOptions<R> {
+    inner: ReadFromAsync<R>,
+}
+
+impl<R: fmt::Debug> fmt::Debug for ReadFromAsyncWithOptions<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadFromAsyncWithOptions")
+            .field("reader", &self.inner.reader)
+            .field("options", &self.inner.options)
+            .field("items", &self.inner.items.len())
+            .finish()
+    }
+}
+
+impl<R> Future for ReadFromAsyncWithOptions<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Output = Result<(Channel, Vec<ParseWarning>), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.get_mut().inner.poll_parts(cx)
+    }
+}
+
+/// The input shared between the future, which receives it, and the parser, which reads it.
+#[derive(Debug, Default)]
+struct Shared {
+    pending: Mutex<Pending>,
+    /// The number of bytes that the parser has read.
+    consumed: AtomicUsize,
+}
+
+/// The input that has been received but not yet handed to the parser.
+#[derive(Debug, Default)]
+struct Pending {
+    bytes: Vec<u8>,
+    eof: bool,
+}
+
+/// The reader that the parser reads the received input from.
+struct Received {
+    shared: Arc<Shared>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl Read for Received {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = {
+            let available = self.fill_buf()?;
+            let len = available.len().min(buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            len
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for Received {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.chunk.len() {
+            let mut pending = self.shared.pending.lock().expect("the parser does not panic");
+            if pending.bytes.is_empty() && !pending.eof {
+                // the scanner keeps the parser from getting here, but reporting the end of the
+                // input would silently cut the channel short
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "the input has not been received yet",
+                ));
+            }
+
+            self.chunk.clear();
+            mem::swap(&mut self.chunk, &mut pending.bytes);
+            self.pos = 0;
+        }
+
+        Ok(&self.chunk[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+        self.shared.consumed.fetch_add(amt, Ordering::SeqCst);
+    }
+}
+
+/// The markup that the scanner is in the middle of, following the tokenizer of the XML parser.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Markup {
+    Text,
+    /// After a `<`.
+    Open,
+    StartTag { quote: Option<u8> },
+    EndTag,
+    /// Before the first `>` of markup starting with `<!`.
+    Bang,
+    Comment,
+    CData,
+    DocType { open: usize },
+    Instruction,
+}
+
+/// The kind of an element at one of the first three levels of the document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Channel,
+    Item,
+    Other,
+}
+
+/// Finds the positions in the input where an item or the root element ends, which are the
+/// points that the parser stops at.
+///
+/// The input is scanned as it arrives. Input that the scanner cannot follow, such as UTF-16
+/// or markup that the parser rejects, is read to the end before it is parsed.
+#[derive(Debug)]
+struct Scanner {
+    /// The number of bytes scanned.
+    position: usize,
+    markup: Markup,
+    /// The number of bytes in the markup so far, not counting the `<`.
+    len: usize,
+    /// The last two bytes of the markup.
+    last: [u8; 2],
+    /// The number of `<` in the markup.
+    opens: usize,
+    /// The first bytes of the markup, enough to tell the kind of `<!` markup or the name of an
+    /// element.
+    head: Vec<u8>,
+    /// Whether the name of a start tag has ended.
+    name_done: bool,
+    depth: usize,
+    kinds: [Kind; 3],
+    ends: VecDeque<usize>,
+    opaque: bool,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Scanner {
+            position: 0,
+            markup: Markup::Text,
+            len: 0,
+            last: [0; 2],
+            opens: 0,
+            head: Vec::new(),
+            name_done: false,
+            depth: 0,
+            kinds: [Kind::Other; 3],
+            ends: VecDeque::new(),
+            opaque: false,
+        }
+    }
+}
+
+/// The number of bytes of markup that are kept to recognize it.
+const HEAD_LENGTH: usize = 8;
+
+impl Scanner {
+    /// Return `true` if an item or the root element ends after `position`.
+    fn has_end_after(&mut self, position: usize) -> bool {
+        while self.ends.front().map_or(false, |&end| end <= position) {
+            self.ends.pop_front();
+        }
+        !self.ends.is_empty()
+    }
+
+    fn scan(&mut self, input: &[u8]) {
+        if self.opaque {
+            return;
+        }
+
+        for &byte in input {
+            self.position += 1;
+            // UTF-16 input is transcoded before it is parsed, so its bytes do not match the
+            // markup, and it always has a byte order mark or a nul byte next to the first `<`
+            if byte == 0 || byte >= 0xFE {
+                self.opaque = true;
+                return;
+            }
+            self.scan_byte(byte);
+            if self.opaque {
+                return;
+            }
+        }
+    }
+
+    fn scan_byte(&mut self, byte: u8) {
+        match self.markup {
+            Markup::Text => if byte == b'<' {
+                self.markup = Markup::Open;
+                self.len = 0;
+                self.last = [0; 2];
+                self.opens = 0;
+                self.head.clear();
+                self.name_done = false;
+            },
+            Markup::Open => {
+                self.markup = match byte {
+                    b'/' => Markup::EndTag,
+                    b'!' => Markup::Bang,
+                    b'?' => Markup::Instruction,
+                    b'>' => {
+                        self.opaque = true;
+                        return;
+                    }
+                    _ => Markup::StartTag { quote: None },
+                };
+                // the first byte is part of the markup, and of the name of a start tag
+                self.markup_byte(byte);
+                if let Markup::StartTag { .. } = self.markup {
+                    self.name_byte(byte);
+                }
+            }
+            Markup::StartTag { quote } => match (quote, byte) {
+                (None, b'>') => {
+                    let empty = self.last[1] == b'/';
+                    if empty && !self.name_done {
+                        self.head.pop();
+                    }
+                    self.markup = Markup::Text;
+                    self.start();
+                    if empty {
+                        self.end();
+                    }
+                }
+                (None, b'"') | (None, b'\'') => {
+                    self.name_done = true;
+                    self.markup = Markup::StartTag { quote: Some(byte) };
+                    self.markup_byte(byte);
+                }
+                (Some(quote), byte) if quote == byte => {
+                    self.markup = Markup::StartTag { quote: None };
+                    self.markup_byte(byte);
+                }
+                _ => {
+                    self.markup_byte(byte);
+                    if quote.is_none() {
+                        self.name_byte(byte);
+                    }
+                }
+            },
+            Markup::EndTag => if byte == b'>' {
+                self.markup = Markup::Text;
+                self.end();
+            } else {
+                self.markup_byte(byte);
+            },
+            Markup::Instruction => if byte == b'>' {
+                self.markup = Markup::Text;
+            } else {
+                self.markup_byte(byte);
+            },
+            Markup::Bang => if byte == b'>' {
+                self.bang_close();
+            } else {
+                self.markup_byte(byte);
+            },
+            Markup::Comment => if byte == b'>' && self.len >= 5 && &self.last == b"--" {
+                self.markup = Markup::Text;
+            } else {
+                self.markup_byte(byte);
+            },
+            Markup::CData => if byte == b'>' && self.len >= 10 && &self.last == b"]]" {
+                self.markup = Markup::Text;
+            } else {
+                self.markup_byte(byte);
+            },
+            Markup::DocType { open } => match byte {
+                b'>' if open == 1 => self.markup = Markup::Text,
+                b'>' => self.markup = Markup::DocType { open: open - 1 },
+                b'<' => self.markup = Markup::DocType { open: open + 1 },
+                _ => {}
+            },
+        }
+    }
+
+    /// Record a byte of the current markup.
+    fn markup_byte(&mut self, byte: u8) {
+        self.len += 1;
+        self.last = [self.last[1], byte];
+        if byte == b'<' {
+            self.opens += 1;
+        }
+        if self.head.len() < HEAD_LENGTH && self.markup == Markup::Bang {
+            self.head.push(byte);
+        }
+    }
+
+    /// Record a byte of the name of a start tag.
+    fn name_byte(&mut self, byte: u8) {
+        if self.name_done {
+            return;
+        }
+
+        match byte {
+            b' ' | b'\t' | b'\r' | b'\n' => self.name_done = true,
+            // a longer name is neither `channel` nor `item`
+            byte if self.head.len() <= HEAD_LENGTH => self.head.push(byte),
+            _ => {}
+        }
+    }
+
+    /// Handle the first `>` of markup starting with `<!`, which decides what kind it is.
+    fn bang_close(&mut self) {
+        let head = &self.head[..];
+        if head.starts_with(b"!--") {
+            self.markup = if self.len >= 5 && &self.last == b"--" {
+                Markup::Text
+            } else {
+                // the `>` is part of the comment
+                self.markup_byte(b'>');
+                Markup::Comment
+            };
+        } else if head.starts_with(b"![CDATA[") {
+            self.markup = if self.len >= 10 && &self.last == b"]]" {
+                Markup::Text
+            } else {
+                self.markup_byte(b'>');
+                Markup::CData
+            };
+        } else if head.starts_with(b"!DOCTYPE") {
+            // every `<` in the declaration is closed by a later `>`
+            self.markup = if self.opens == 0 {
+                Markup::Text
+            } else {
+                Markup::DocType { open: self.opens }
+            };
+        } else {
+            self.opaque = true;
+        }
+    }
+
+    /// Handle the start of an element whose name is in `head`.
+    fn start(&mut self) {
+        let kind = match self.depth {
+            1 if self.head == b"item" => Kind::Item,
+            1 if self.head == b"channel" => Kind::Channel,
+            2 if self.kinds[1] == Kind::Channel && self.head == b"item" => Kind::Item,
+            _ => Kind::Other,
+        };
+
+        if self.depth < self.kinds.len() {
+            self.kinds[self.depth] = kind;
+        }
+        self.depth += 1;
+    }
+
+    /// Handle the end of an element.
+    fn end(&mut self) {
+        if self.depth == 0 {
+            self.opaque = true;
+            return;
+        }
+
+        self.depth -= 1;
+        let item = self.depth < self.kinds.len() && self.kinds[self.depth] == Kind::Item;
+        if self.depth == 0 || item {
+            self.ends.push_back(self.position);
+        }
+    }
+}
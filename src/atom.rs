@@ -0,0 +1,567 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Cursor, Read};
+
+use atom_syndication as atom;
+use chrono::DateTime;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+
+use channel::Channel;
+use category::Category;
+use date::parse_rfc822;
+use enclosure::Enclosure;
+use error::{Error, FeedKind};
+use extension::{Extension, ExtensionBuilder, ExtensionMap};
+use guid::Guid;
+use image::Image;
+use item::Item;
+use itemreader::feed_kind;
+
+impl Channel {
+    /// Convert this channel into an Atom feed.
+    ///
+    /// The elements of the channel correspond to the elements of the feed as follows:
+    ///
+    /// | RSS                        | Atom                               |
+    /// |----------------------------|------------------------------------|
+    /// | `title`                    | `title`                            |
+    /// | `link`                     | `id` and `link` (`rel="alternate"`)|
+    /// | `description`              | `subtitle`                         |
+    /// | `lastBuildDate`, `pubDate` | `updated`                          |
+    /// | `copyright`                | `rights`                           |
+    /// | `managingEditor`           | `author`                           |
+    /// | `generator`                | `generator`                        |
+    /// | `image` url                | `logo`                             |
+    /// | `category`                 | `category`                         |
+    /// | `item`                     | `entry`                            |
+    ///
+    /// Dates are converted to RFC 3339 timestamps when they can be parsed and are copied as is
+    /// otherwise. The extension map and namespaces are copied to the feed, but the iTunes,
+    /// Dublin Core and Media RSS extension structs are not. Elements without an Atom
+    /// equivalent, such as `ttl`, `cloud` and `skipHours`, are not included in the feed.
+    ///
+    /// **Note**: This requires enabling the `atom` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ChannelBuilder;
+    ///
+    /// let channel = ChannelBuilder::default()
+    ///     .title("Title")
+    ///     .link("http://example.com/")
+    ///     .description("Description")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let feed = channel.to_atom();
+    /// assert_eq!(feed.title(), "Title");
+    /// assert_eq!(feed.id(), "http://example.com/");
+    /// assert_eq!(feed.subtitle(), Some("Description"));
+    /// ```
+    pub fn to_atom(&self) -> atom::Feed {
+        let mut feed = atom::Feed::default();
+        feed.set_title(self.title());
+        feed.set_id(self.link());
+        feed.set_subtitle(non_empty(self.description()));
+        feed.set_rights(self.copyright().map(|s| s.to_string()));
+        feed.set_logo(self.image().map(|image| image.url().to_string()));
+
+        if let Some(updated) = self.last_build_date().or_else(|| self.pub_date()) {
+            feed.set_updated(convert_date(updated));
+        }
+
+        if !self.link().is_empty() {
+            feed.set_links(vec![link(self.link(), "alternate")]);
+        }
+
+        if let Some(managing_editor) = self.managing_editor() {
+            feed.set_authors(vec![person(managing_editor)]);
+        }
+
+        if let Some(generator) = self.generator() {
+            let mut atom_generator = atom::Generator::default();
+            atom_generator.set_value(generator);
+            feed.set_generator(atom_generator);
+        }
+
+        feed.set_categories(self.categories().iter().map(category).collect::<Vec<_>>());
+        feed.set_entries(self.items().iter().map(Item::to_atom).collect::<Vec<_>>());
+        feed.set_extensions(to_atom_extensions(self.extensions()));
+        feed.set_namespaces(self.namespaces().clone());
+        feed
+    }
+
+    /// Create a channel from an Atom feed.
+    ///
+    /// This is the reverse of `to_atom`. Where Atom allows more than one value the first one is
+    /// used: the first author becomes the managing editor and the first alternate link becomes
+    /// the link, falling back to the id of the feed. Authors are written as `email (name)` when
+    /// they have an email address. RFC 3339 timestamps are converted to RFC 822 dates when
+    /// they can be parsed and are copied as is otherwise.
+    ///
+    /// iTunes and Dublin Core elements in the extensions of the feed are moved into the
+    /// extension structs, as when reading a channel.
+    ///
+    /// **Note**: This requires enabling the `atom` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate atom_syndication;
+    /// extern crate rss;
+    ///
+    /// use rss::Channel;
+    ///
+    /// # fn main() {
+    /// let mut feed = atom_syndication::Feed::default();
+    /// feed.set_title("Title");
+    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6");
+    /// feed.set_updated("2017-01-01T12:00:00+00:00");
+    ///
+    /// let channel = Channel::from_atom(&feed);
+    /// assert_eq!(channel.title(), "Title");
+    /// assert_eq!(channel.last_build_date(), Some("Sun, 1 Jan 2017 12:00:00 +0000"));
+    /// # }
+    /// ```
+    pub fn from_atom(feed: &atom::Feed) -> Channel {
+        let mut channel = Channel::default();
+        channel.set_title(feed.title());
+        channel.set_link(alternate_link(feed.links()).unwrap_or_else(|| feed.id()));
+        channel.set_description(feed.subtitle().unwrap_or_default());
+        channel.set_copyright(feed.rights().map(|s| s.to_string()));
+        channel.set_managing_editor(feed.authors().first().map(from_person));
+        channel.set_generator(feed.generator().map(|generator| generator.value().to_string()));
+
+        if !feed.updated().is_empty() {
+            channel.set_last_build_date(from_date(feed.updated()));
+        }
+
+        if let Some(logo) = feed.logo() {
+            let mut image = Image::default();
+            image.set_url(logo);
+            image.set_title(feed.title());
+            image.set_link(channel.link().to_string());
+            channel.set_image(image);
+        }
+
+        channel.set_categories(feed.categories().iter().map(from_category).collect::<Vec<_>>());
+        channel.set_items(feed.entries().iter().map(Item::from_atom).collect::<Vec<_>>());
+        channel.set_namespaces(feed.namespaces().clone());
+        channel.remove_implied_namespaces();
+        channel.set_extensions(from_atom_extensions(feed.extensions()));
+        // the extension structs never fail to read elements that were already parsed
+        channel.extract_extensions().ok();
+        channel
+    }
+
+    /// Attempt to read a channel from either an RSS channel or an Atom feed.
+    ///
+    /// The format is detected from the root element, so only the input up to the end of its
+    /// start tag is held in memory before the rest is parsed. An Atom feed is converted with
+    /// `from_atom`, and any other input is read as with `read_from`.
+    ///
+    /// **Note**: This requires enabling the `atom` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    ///     <feed xmlns="http://www.w3.org/2005/Atom">
+    ///         <title>Title</title>
+    ///         <id>urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6</id>
+    ///         <updated>2017-01-01T12:00:00+00:00</updated>
+    ///     </feed>
+    /// "#;
+    /// let channel = Channel::read_any(input.as_bytes()).unwrap();
+    /// assert_eq!(channel.title(), "Title");
+    ///
+    /// let input = "<rss><channel><title>Title</title></channel></rss>";
+    /// let channel = Channel::read_any(input.as_bytes()).unwrap();
+    /// assert_eq!(channel.title(), "Title");
+    /// ```
+    pub fn read_any<R: BufRead>(reader: R) -> Result<Channel, Error> {
+        let mut recording = Recording {
+            inner: reader,
+            recorded: Vec::new(),
+        };
+        let kind = peek_feed_kind(&mut recording);
+
+        // the input is read again from the start, now that the format is known
+        let reader = Cursor::new(recording.recorded).chain(recording.inner);
+        match kind {
+            Some(FeedKind::Atom) => atom::Feed::read_from(reader)
+                .map(|feed| Channel::from_atom(&feed))
+                .map_err(Error::Atom),
+            None => Channel::read_from(reader),
+        }
+    }
+}
+
+impl From<Channel> for atom::Feed {
+    fn from(channel: Channel) -> Self {
+        channel.to_atom()
+    }
+}
+
+impl From<atom::Feed> for Channel {
+    /// Create a channel from an Atom feed. See `Channel::from_atom`.
+    ///
+    /// The conversion never fails, so `TryFrom<atom::Feed>` is also implemented for `Channel`.
+    fn from(feed: atom::Feed) -> Self {
+        Channel::from_atom(&feed)
+    }
+}
+
+impl Item {
+    /// Convert this item into an Atom entry.
+    ///
+    /// The elements of the item correspond to the elements of the entry as follows:
+    ///
+    /// | RSS                | Atom                                |
+    /// |--------------------|-------------------------------------|
+    /// | `title`            | `title`                             |
+    /// | `guid`, or `link`  | `id`                                |
+    /// | `link`             | `link` (`rel="alternate"`)          |
+    /// | `enclosure`        | `link` (`rel="enclosure"`)          |
+    /// | `description`      | `summary`                           |
+    /// | `content:encoded`  | `content` (`type="html"`)           |
+    /// | `pubDate`          | `published` and `updated`           |
+    /// | `author`           | `author`                            |
+    /// | `category`         | `category`                          |
+    ///
+    /// **Note**: This requires enabling the `atom` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ItemBuilder;
+    ///
+    /// let item = ItemBuilder::default()
+    ///     .title("Title".to_string())
+    ///     .pub_date("Sun, 01 Jan 2017 12:00:00 GMT".to_string())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let entry = item.to_atom();
+    /// assert_eq!(entry.title(), "Title");
+    /// assert_eq!(entry.published(), Some("2017-01-01T12:00:00+00:00"));
+    /// ```
+    pub fn to_atom(&self) -> atom::Entry {
+        let mut entry = atom::Entry::default();
+        entry.set_title(self.title().unwrap_or_default());
+        entry.set_summary(self.description().map(|s| s.to_string()));
+
+        let id = self.guid()
+            .map(|guid| guid.value())
+            .or_else(|| self.link())
+            .unwrap_or_default();
+        entry.set_id(id);
+
+        if let Some(pub_date) = self.pub_date() {
+            let date = convert_date(pub_date);
+            entry.set_updated(date.as_str());
+            entry.set_published(date);
+        }
+
+        let mut links = Vec::new();
+
+        if let Some(item_link) = self.link() {
+            links.push(link(item_link, "alternate"));
+        }
+
+        if let Some(enclosure) = self.enclosure() {
+            links.push(enclosure_link(enclosure));
+        }
+
+        entry.set_links(links);
+
+        if let Some(author) = self.author() {
+            entry.set_authors(vec![person(author)]);
+        }
+
+        if let Some(content) = self.content() {
+            let mut atom_content = atom::Content::default();
+            atom_content.set_value(content.to_string());
+            atom_content.set_content_type("html".to_string());
+            entry.set_content(atom_content);
+        }
+
+        entry.set_categories(self.categories().iter().map(category).collect::<Vec<_>>());
+        entry.set_extensions(to_atom_extensions(self.extensions()));
+        entry
+    }
+
+    /// Create an item from an Atom entry.
+    ///
+    /// This is the reverse of `to_atom`. The id becomes the GUID, which is a permalink only if
+    /// it is the same as the alternate link. The first author, the first alternate link and the
+    /// first enclosure link are used. The publication date falls back to the updated date.
+    ///
+    /// iTunes, Dublin Core and Media RSS elements in the extensions of the entry are moved into
+    /// the extension structs, as when reading an item.
+    ///
+    /// **Note**: This requires enabling the `atom` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate atom_syndication;
+    /// extern crate rss;
+    ///
+    /// use rss::Item;
+    ///
+    /// # fn main() {
+    /// let mut entry = atom_syndication::Entry::default();
+    /// entry.set_title("Title");
+    /// entry.set_id("urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a");
+    /// entry.set_summary("Summary".to_string());
+    ///
+    /// let item = Item::from_atom(&entry);
+    /// assert_eq!(item.title(), Some("Title"));
+    /// assert_eq!(item.description(), Some("Summary"));
+    /// assert_eq!(item.guid().map(|guid| guid.is_permalink()), Some(false));
+    /// # }
+    /// ```
+    pub fn from_atom(entry: &atom::Entry) -> Item {
+        let mut item = Item::default();
+        item.set_title(non_empty(entry.title()));
+        item.set_link(alternate_link(entry.links()).map(|s| s.to_string()));
+        item.set_description(entry.summary().map(|s| s.to_string()));
+        item.set_author(entry.authors().first().map(from_person));
+        item.set_content(
+            entry
+                .content()
+                .and_then(|content| content.value())
+                .map(|s| s.to_string()),
+        );
+
+        if !entry.id().is_empty() {
+            let mut guid = Guid::default();
+            guid.set_value(entry.id());
+            guid.set_permalink(item.link() == Some(entry.id()));
+            item.set_guid(guid);
+        }
+
+        let pub_date = entry.published().unwrap_or_else(|| entry.updated());
+        if !pub_date.is_empty() {
+            item.set_pub_date(from_date(pub_date));
+        }
+
+        let enclosure = entry.links().iter().find(|link| link.rel() == "enclosure");
+        if let Some(link) = enclosure {
+            let mut enclosure = Enclosure::default();
+            enclosure.set_url(link.href());
+            enclosure.set_length(link.length().unwrap_or_default());
+            enclosure.set_mime_type(link.mime_type().unwrap_or_default());
+            item.set_enclosure(enclosure);
+        }
+
+        item.set_categories(entry.categories().iter().map(from_category).collect::<Vec<_>>());
+        item.set_extensions(from_atom_extensions(entry.extensions()));
+        item.extract_extensions();
+        item
+    }
+}
+
+impl From<Item> for atom::Entry {
+    fn from(item: Item) -> Self {
+        item.to_atom()
+    }
+}
+
+impl From<atom::Entry> for Item {
+    /// Create an item from an Atom entry. See `Item::from_atom`.
+    fn from(entry: atom::Entry) -> Self {
+        Item::from_atom(&entry)
+    }
+}
+
+fn convert_date(value: &str) -> String {
+    match parse_rfc822(value) {
+        Some(date) => date.to_rfc3339(),
+        None => value.to_string(),
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn link(href: &str, rel: &str) -> atom::Link {
+    let mut link = atom::Link::default();
+    link.set_href(href);
+    link.set_rel(rel);
+    link
+}
+
+fn enclosure_link(enclosure: &Enclosure) -> atom::Link {
+    let mut link = link(enclosure.url(), "enclosure");
+    link.set_mime_type(non_empty(enclosure.mime_type()));
+    link.set_length(non_empty(enclosure.length()));
+    link
+}
+
+fn person(name: &str) -> atom::Person {
+    let mut person = atom::Person::default();
+    person.set_name(name);
+    person
+}
+
+fn category(category: &Category) -> atom::Category {
+    let mut atom_category = atom::Category::default();
+    atom_category.set_term(category.name());
+    atom_category.set_scheme(category.domain().map(|s| s.to_string()));
+    atom_category
+}
+
+fn from_date(value: &str) -> String {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(date) => date.to_rfc2822(),
+        Err(_) => value.to_string(),
+    }
+}
+
+fn alternate_link(links: &[atom::Link]) -> Option<&str> {
+    links
+        .iter()
+        .find(|link| link.rel() == "alternate")
+        .map(|link| link.href())
+}
+
+fn from_person(person: &atom::Person) -> String {
+    match person.email() {
+        Some(email) => format!("{} ({})", email, person.name()),
+        None => person.name().to_string(),
+    }
+}
+
+fn from_category(atom_category: &atom::Category) -> Category {
+    let mut category = Category::default();
+    category.set_name(atom_category.term());
+    category.set_domain(atom_category.scheme().map(|s| s.to_string()));
+    category
+}
+
+fn to_atom_extensions(extensions: &ExtensionMap) -> atom::extension::ExtensionMap {
+    extensions
+        .iter()
+        .map(|(prefix, map)| (prefix.clone(), to_atom_extension_map(map)))
+        .collect()
+}
+
+fn to_atom_extension_map(
+    map: &HashMap<String, Vec<Extension>>,
+) -> HashMap<String, Vec<atom::extension::Extension>> {
+    map.iter()
+        .map(|(name, extensions)| {
+            let extensions = extensions.iter().map(to_atom_extension).collect();
+            (name.clone(), extensions)
+        })
+        .collect()
+}
+
+fn to_atom_extension(extension: &Extension) -> atom::extension::Extension {
+    let mut atom_extension = atom::extension::Extension::default();
+    atom_extension.set_name(extension.name());
+    atom_extension.set_value(extension.value().map(|s| s.to_string()));
+    atom_extension.set_attrs(extension.attrs().clone());
+    atom_extension.set_children(to_atom_extension_map(extension.children()));
+    atom_extension
+}
+
+fn from_atom_extensions(extensions: &atom::extension::ExtensionMap) -> ExtensionMap {
+    extensions
+        .iter()
+        .map(|(prefix, map)| (prefix.clone(), from_atom_extension_map(map)))
+        .collect()
+}
+
+fn from_atom_extension_map(
+    map: &HashMap<String, Vec<atom::extension::Extension>>,
+) -> HashMap<String, Vec<Extension>> {
+    map.iter()
+        .map(|(name, extensions)| {
+            let extensions = extensions.iter().map(from_atom_extension).collect();
+            (name.clone(), extensions)
+        })
+        .collect()
+}
+
+fn from_atom_extension(atom_extension: &atom::extension::Extension) -> Extension {
+    ExtensionBuilder::default()
+        .name(atom_extension.name())
+        .value(atom_extension.value().map(|s| s.to_string()))
+        .attrs(atom_extension.attrs().clone())
+        .children(from_atom_extension_map(atom_extension.children()))
+        .build()
+        .unwrap()
+}
+
+/// Return the format of the feed that a reader contains, if its root element is not RSS.
+///
+/// Reading stops at the root element. Input that is not valid XML, or is not UTF-8, is left to
+/// the RSS reader to report.
+fn peek_feed_kind<R: BufRead>(reader: R) -> Option<FeedKind> {
+    let mut reader = Reader::from_reader(reader);
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(ref element)) | Ok(Event::Empty(ref element)) => {
+                return feed_kind(element);
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// A reader that keeps a copy of the bytes that are consumed from it.
+struct Recording<R> {
+    inner: R,
+    recorded: Vec<u8>,
+}
+
+impl<R: BufRead> Read for Recording<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let len = {
+            let buf = self.fill_buf()?;
+            let len = cmp::min(buf.len(), out.len());
+            out[..len].copy_from_slice(&buf[..len]);
+            len
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for Recording<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // the buffer is the one that was just filled, so this does not read any more input
+        if let Ok(buf) = self.inner.fill_buf() {
+            let amt = cmp::min(amt, buf.len());
+            self.recorded.extend_from_slice(&buf[..amt]);
+        }
+        self.inner.consume(amt);
+    }
+}
@@ -8,23 +8,26 @@
 use std::io::{BufRead, Write};
 
 use quick_xml::errors::Error as XmlError;
-use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
+use quick_xml::events::{Event, BytesStart, BytesEnd};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::ToXml;
-use util::element_text;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer, escape, write_untrimmed_text};
+use util::{attr_values, element_text_with_context};
 
 /// Represents a category in an RSS feed.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Category {
     /// The name of the category.
     name: String,
     /// The domain for the category.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     domain: Option<String>,
 }
 
@@ -92,22 +95,53 @@ impl Category {
     {
         self.domain = domain.into();
     }
+
+    /// Return whether this category has the given name, ignoring case and surrounding
+    /// whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_name(" Rust ");
+    /// assert!(category.matches("rust"));
+    /// assert!(!category.matches("rusty"));
+    /// ```
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.trim().to_lowercase() == name.trim().to_lowercase()
+    }
+
+    /// Return the domain and name of this category in the form that is compared when duplicate
+    /// categories are removed.
+    pub(crate) fn dedup_key(&self) -> (Option<String>, String) {
+        let domain = self.domain
+            .as_ref()
+            .map(|domain| domain.trim().to_lowercase());
+        (domain, self.name.trim().to_lowercase())
+    }
 }
 
 impl FromXml for Category {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, mut atts: Attributes) -> Result<Self, Error> {
-        let mut category = Category::default();
-
-        for attr in atts.with_checks(false) {
-            if let Ok(attr) = attr {
-                if attr.key == b"domain" {
-                    category.domain = Some(attr.unescape_and_decode_value(reader)?);
-                    break;
-                }
-            }
-        }
+    /// Parse a category. An absent `domain` attribute is kept apart from an empty one, and the
+    /// whitespace around the name is trimmed unless it is inside a CDATA section.
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Category::from_xml_with_context(reader, atts, &mut ParseContext::default())
+    }
+}
 
-        category.name = element_text(reader)?.unwrap_or_default();
+impl Category {
+    /// Parse a `<category>` element, resolving character references as the context requires.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
+        let mut category = Category::default();
+        let mut attrs = attr_values(reader, b"category", atts, &[b"domain"], context)?;
+        category.domain = attrs.take(b"domain");
+        category.name = element_text_with_context(reader, context)?.unwrap_or_default();
         Ok(category)
     }
 }
@@ -117,11 +151,10 @@ impl ToXml for Category {
         let name = b"category";
         let mut element = BytesStart::borrowed(name, name.len());
         if let Some(ref domain) = self.domain {
-            element.push_attribute(("domain", &**domain));
+            element.push_attribute((b"domain" as &[u8], &*escape(domain.as_bytes())));
         }
         writer.write_event(Event::Start(element))?;
-        writer
-            .write_event(Event::Text(BytesText::borrowed(self.name.as_bytes())))?;
+        write_untrimmed_text(writer, self.name.as_bytes())?;
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
         Ok(())
     }
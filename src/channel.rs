@@ -5,32 +5,98 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
-use std::collections::HashMap;
-use std::io::{BufRead, Write};
-use std::str::{self, FromStr};
+#[cfg(feature = "chrono")]
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "chrono")]
+use std::fmt::Display;
+use std::fmt;
+use std::fs::File;
+use std::iter::FromIterator;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Timelike, Utc, Weekday};
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use category::Category;
 use cloud::Cloud;
+#[cfg(feature = "chrono")]
+use date::parse_rfc822;
 use error::Error;
-use extension::{self, ExtensionMap};
+use extension::{self, Extension, ExtensionMap};
+use extension::custom::CustomExtensionMap;
 use extension::dublincore::DublinCoreExtension;
 use extension::itunes::ITunesChannelExtension;
-use extension::util::{extension_name, parse_extension};
+use extension::podcast::{self, PodcastChannelExtension};
+use extension::slash;
+use extension::syndication::{self, SyndicationExtension};
+use extension::util::{declares_prefix, extension_name, find_extension, parse_extension,
+                      parse_extension_element};
 use fromxml::FromXml;
 use image::Image;
-use item::Item;
+use item::{CONTENT_NAMESPACE, Item};
+use itemreader::{ATOM_NAMESPACE, ItemReader};
+use link::Link;
+use parseoptions::{ParseContext, ParseOptions, ParseWarning};
+use stylesheet::Stylesheet;
 use textinput::TextInput;
-use toxml::{ToXml, WriterExt};
-use util::element_text;
+use toxml::{CharFilter, ToXml, Writer, WriterExt, escape};
+use util::{element_text, element_text_with_context, xml_base};
+use writeoptions::{Version, WriteOptions};
+
+/// The largest hour that can be skipped.
+const MAX_SKIP_HOUR: u8 = 23;
+
+/// The usual prefixes and the URIs of the namespaces of the supported extensions.
+const SUPPORTED_NAMESPACES: [(&'static str, &'static str); 8] = [
+    ("itunes", extension::itunes::NAMESPACE),
+    ("dc", extension::dublincore::NAMESPACE),
+    ("media", extension::media::NAMESPACE),
+    ("content", CONTENT_NAMESPACE),
+    ("podcast", podcast::NAMESPACE),
+    ("sy", syndication::NAMESPACE),
+    ("slash", slash::NAMESPACE),
+    ("atom", ATOM_NAMESPACE),
+];
+
+/// The names of the elements of a channel that are matched ignoring case, if parsing is
+/// case-insensitive.
+static ELEMENTS: &'static [&'static [u8]] = &[
+    b"title",
+    b"link",
+    b"description",
+    b"language",
+    b"copyright",
+    b"managingEditor",
+    b"webMaster",
+    b"pubDate",
+    b"lastBuildDate",
+    b"category",
+    b"generator",
+    b"docs",
+    b"cloud",
+    b"ttl",
+    b"image",
+    b"rating",
+    b"textInput",
+    b"textinput",
+    b"skipHours",
+    b"skipDays",
+    b"item",
+];
 
 /// Represents the channel of an RSS feed.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Channel {
     /// The name of the channel.
@@ -40,47 +106,133 @@ pub struct Channel {
     /// A description of the channel.
     description: String,
     /// The language of the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     language: Option<String>,
     /// The copyright notice for the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     copyright: Option<String>,
     /// The email address for the managing editor.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     managing_editor: Option<String>,
     /// The email address for the webmaster.
+    #[cfg_attr(feature = "serialization", serde(rename = "webMaster"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     webmaster: Option<String>,
     /// The publication date for the content of the channel as an RFC822 timestamp.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     pub_date: Option<String>,
     /// The date that the contents of the channel last changed as an RFC822 timestamp.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     last_build_date: Option<String>,
     /// The categories the channel belongs to.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     categories: Vec<Category>,
     /// A string indicating the program used to generate the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     generator: Option<String>,
     /// A URL that points to the documentation for the RSS format.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     docs: Option<String>,
     /// The cloud to register with to be notified of updates to the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     cloud: Option<Cloud>,
     /// The PICS rating for the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     rating: Option<String>,
     /// The number of minutes the channel can be cached before refreshing.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     ttl: Option<String>,
     /// An image that can be displayed with the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     image: Option<Image>,
     /// A text input box that can be displayed with the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     text_input: Option<TextInput>,
     /// A hint to tell the aggregator which hours it can skip.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     skip_hours: Vec<String>,
     /// A hint to tell the aggregator which days it can skip.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     skip_days: Vec<String>,
     /// The items in the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     items: Vec<Item>,
     /// The extensions for the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "HashMap::is_empty"))]
     extensions: ExtensionMap,
     /// The iTunes extension for the channel.
+    #[cfg_attr(feature = "serialization", serde(rename = "itunes"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     itunes_ext: Option<ITunesChannelExtension>,
     /// The Dublin Core extension for the channel.
+    #[cfg_attr(feature = "serialization", serde(rename = "dublinCore"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     dublin_core_ext: Option<DublinCoreExtension>,
-    /// The namespaces present in the RSS tag.
+    /// The Podcasting 2.0 extension for the channel.
+    #[cfg_attr(feature = "serialization", serde(rename = "podcast"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    podcast_ext: Option<PodcastChannelExtension>,
+    /// The syndication module extension for the channel.
+    #[cfg_attr(feature = "serialization", serde(rename = "syndication"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    syndication_ext: Option<SyndicationExtension>,
+    /// The Atom links of the channel, such as the `self` link and the paging links.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    atom_links: Vec<Link>,
+    /// The values of the custom extensions for the channel, keyed by namespace URI.
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    custom_extensions: CustomExtensionMap,
+    /// The namespaces declared on the RSS tag, keyed by prefix.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "HashMap::is_empty"))]
     namespaces: HashMap<String, String>,
+    /// The elements of the channel that are not otherwise supported.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    unknown_elements: Vec<Extension>,
+    /// The base URI for the relative URLs in the channel.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    base: Option<String>,
+    /// The stylesheets referenced before the root element of the feed.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    stylesheets: Vec<Stylesheet>,
+}
+
+/// How items with the same `Item::dedup_key` are handled when channels are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep every item, including duplicates.
+    KeepAll,
+    /// Keep the first of the items with the same key.
+    KeepFirst,
+    /// Keep the item with the latest parsed publication date of the items with the same key.
+    ///
+    /// Dates that cannot be parsed are older than any date that can, and the first item is kept
+    /// if several share the latest date.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    KeepNewest,
+}
+
+/// The order to sort items in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Oldest first.
+    Ascending,
+    /// Newest first.
+    Descending,
+}
+
+impl MergeStrategy {
+    /// Return whether `item` should replace `kept`, an earlier item with the same key.
+    #[cfg(feature = "chrono")]
+    fn prefers(&self, item: &Item, kept: &Item) -> bool {
+        *self == MergeStrategy::KeepNewest && item.pub_date_parsed() > kept.pub_date_parsed()
+    }
+
+    #[cfg(not(feature = "chrono"))]
+    fn prefers(&self, _: &Item, _: &Item) -> bool {
+        false
+    }
 }
 
 impl Channel {
@@ -341,6 +493,56 @@ impl Channel {
         self.pub_date = pub_date.into();
     }
 
+    /// Return the publication date for the content of this channel as a `DateTime`.
+    ///
+    /// Common deviations from RFC822 found in real-world feeds, such as single-digit days or
+    /// `UT` and `UTC` zones, are tolerated. `None` is returned if the date is missing or cannot
+    /// be parsed.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_pub_date("Sun, 1 Jan 2017 12:00:00 GMT".to_string());
+    /// assert_eq!(channel.pub_date_parsed().unwrap().timestamp(), 1483272000);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn pub_date_parsed(&self) -> Option<DateTime<FixedOffset>> {
+        self.pub_date.as_ref().and_then(|s| parse_rfc822(s))
+    }
+
+    /// Set the publication date for the content of this channel from a `DateTime`.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate chrono;
+    /// # extern crate rss;
+    ///
+    /// use chrono::{TimeZone, Utc};
+    /// use rss::Channel;
+    ///
+    /// # fn main() {
+    /// let mut channel = Channel::default();
+    /// channel.set_pub_date_from(Utc.ymd(2017, 1, 1).and_hms(12, 0, 0));
+    /// assert_eq!(channel.pub_date(), Some("Sun, 1 Jan 2017 12:00:00 +0000"));
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn set_pub_date_from<Tz>(&mut self, pub_date: DateTime<Tz>)
+    where
+        Tz: TimeZone,
+        Tz::Offset: Display,
+    {
+        self.pub_date = Some(pub_date.to_rfc2822());
+    }
+
     /// Return the time that the content of this channel was last changed as an RFC822 timestamp.
     ///
     /// # Examples
@@ -373,6 +575,55 @@ impl Channel {
         self.last_build_date = last_build_date.into();
     }
 
+    /// Return the time that the content of this channel was last changed as a `DateTime`.
+    ///
+    /// Common deviations from RFC822 found in real-world feeds are tolerated. `None` is returned
+    /// if the date is missing or cannot be parsed.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_last_build_date("Sun, 1 Jan 2017 12:00:00 GMT".to_string());
+    /// assert_eq!(channel.last_build_date_parsed().unwrap().timestamp(), 1483272000);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn last_build_date_parsed(&self) -> Option<DateTime<FixedOffset>> {
+        self.last_build_date.as_ref().and_then(|s| parse_rfc822(s))
+    }
+
+    /// Set the time that the content of this channel was last changed from a `DateTime`.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate chrono;
+    /// # extern crate rss;
+    ///
+    /// use chrono::{TimeZone, Utc};
+    /// use rss::Channel;
+    ///
+    /// # fn main() {
+    /// let mut channel = Channel::default();
+    /// channel.set_last_build_date_from(Utc.ymd(2017, 1, 1).and_hms(12, 0, 0));
+    /// assert_eq!(channel.last_build_date(), Some("Sun, 1 Jan 2017 12:00:00 +0000"));
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn set_last_build_date_from<Tz>(&mut self, last_build_date: DateTime<Tz>)
+    where
+        Tz: TimeZone,
+        Tz::Offset: Display,
+    {
+        self.last_build_date = Some(last_build_date.to_rfc2822());
+    }
+
     /// Return the categories that this channel belongs to.
     ///
     /// # Examples
@@ -388,8 +639,8 @@ impl Channel {
         &self.categories
     }
 
-    /// Return a mutable slice of the categories that this channel belongs to.
-    pub fn categories_mut(&mut self) -> &mut [Category] {
+    /// Return a mutable reference to the categories that this channel belongs to.
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
         &mut self.categories
     }
 
@@ -490,6 +741,11 @@ impl Channel {
         self.cloud.as_ref()
     }
 
+    /// Return a mutable reference to the cloud information for this channel.
+    pub fn cloud_mut(&mut self) -> Option<&mut Cloud> {
+        self.cloud.as_mut()
+    }
+
     /// Set the information used to register with a cloud for notifications of updates to the
     /// channel.
     ///
@@ -542,6 +798,88 @@ impl Channel {
         self.ttl = ttl.into();
     }
 
+    /// Return the time to live of this channel as a `Duration`.
+    ///
+    /// Returns `None` if there is no time to live or it is not a whole number of minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_ttl("60".to_string());
+    /// assert_eq!(channel.ttl_duration(), Some(Duration::from_secs(3600)));
+    ///
+    /// channel.set_ttl("-1".to_string());
+    /// assert_eq!(channel.ttl_duration(), None);
+    /// ```
+    pub fn ttl_duration(&self) -> Option<Duration> {
+        self.ttl
+            .as_ref()
+            .and_then(|ttl| ttl.trim().parse::<u64>().ok())
+            .and_then(|minutes| minutes.checked_mul(60))
+            .map(Duration::from_secs)
+    }
+
+    /// Set the time to live of this channel from a `Duration`.
+    ///
+    /// The duration is rounded to the nearest whole minute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_ttl_duration(Duration::from_secs(90 * 60 + 20));
+    /// assert_eq!(channel.ttl(), Some("90"));
+    /// ```
+    pub fn set_ttl_duration(&mut self, ttl: Duration) {
+        let mut minutes = ttl.as_secs() / 60;
+        if ttl.as_secs() % 60 >= 30 {
+            minutes += 1;
+        }
+        self.ttl = Some(minutes.to_string());
+    }
+
+    /// Return the time to live of this channel in minutes.
+    ///
+    /// Returns `None` if there is no time to live or it is not a whole number of minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_ttl("60".to_string());
+    /// assert_eq!(channel.ttl_minutes(), Some(60));
+    ///
+    /// channel.set_ttl("an hour".to_string());
+    /// assert_eq!(channel.ttl_minutes(), None);
+    /// ```
+    pub fn ttl_minutes(&self) -> Option<u32> {
+        self.ttl.as_ref().and_then(|ttl| ttl.trim().parse().ok())
+    }
+
+    /// Set the time to live of this channel in minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_ttl_minutes(60);
+    /// assert_eq!(channel.ttl(), Some("60"));
+    /// ```
+    pub fn set_ttl_minutes(&mut self, ttl: u32) {
+        self.ttl = Some(ttl.to_string());
+    }
+
     /// Return the image to be displayed with this channel.
     ///
     /// # Examples
@@ -557,6 +895,11 @@ impl Channel {
         self.image.as_ref()
     }
 
+    /// Return a mutable reference to the image to be displayed with this channel.
+    pub fn image_mut(&mut self) -> Option<&mut Image> {
+        self.image.as_mut()
+    }
+
     /// Set the image to be displayed with this channel.
     ///
     /// # Examples
@@ -602,6 +945,11 @@ impl Channel {
         self.text_input.as_ref()
     }
 
+    /// Return a mutable reference to the text input information for this channel.
+    pub fn text_input_mut(&mut self) -> Option<&mut TextInput> {
+        self.text_input.as_mut()
+    }
+
     /// Set the information for a text box to be displayed with this channel.
     ///
     /// # Examples
@@ -636,8 +984,8 @@ impl Channel {
         &self.skip_hours
     }
 
-    /// Return a mutable slice of the hours that aggregators can skip for refreshing content.
-    pub fn skip_hours_mut(&mut self) -> &mut [String] {
+    /// Return a mutable reference to the hours that aggregators can skip for refreshing content.
+    pub fn skip_hours_mut(&mut self) -> &mut Vec<String> {
         &mut self.skip_hours
     }
 
@@ -658,6 +1006,57 @@ impl Channel {
         self.skip_hours = skip_hours.into();
     }
 
+    /// Return the hours that aggregators can skip as numbers, sorted and without duplicates.
+    ///
+    /// Hours that are not numbers between 0 and 23 are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_skip_hours(vec!["22".to_string(), " 6".to_string(), "24".to_string(),
+    ///                             "six".to_string(), "6".to_string()]);
+    /// assert_eq!(channel.skip_hours_parsed(), vec![6, 22]);
+    /// ```
+    pub fn skip_hours_parsed(&self) -> Vec<u8> {
+        let mut hours = self.skip_hours
+            .iter()
+            .filter_map(|hour| hour.trim().parse::<u8>().ok())
+            .filter(|&hour| hour <= MAX_SKIP_HOUR)
+            .collect::<Vec<_>>();
+        hours.sort();
+        hours.dedup();
+        hours
+    }
+
+    /// Set the hours that aggregators can skip from numbers.
+    ///
+    /// The hours are sorted and duplicates are removed. Hours greater than 23 are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_skip_hours_from(vec![22, 6, 30, 6]);
+    /// assert_eq!(channel.skip_hours(), &["6".to_string(), "22".to_string()]);
+    /// ```
+    pub fn set_skip_hours_from<I>(&mut self, skip_hours: I)
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let mut hours = skip_hours
+            .into_iter()
+            .filter(|&hour| hour <= MAX_SKIP_HOUR)
+            .collect::<Vec<_>>();
+        hours.sort();
+        hours.dedup();
+        self.skip_hours = hours.into_iter().map(|hour| hour.to_string()).collect();
+    }
+
     /// Return the days that aggregators can skip for refreshing content.
     ///
     /// # Examples
@@ -673,8 +1072,8 @@ impl Channel {
         &self.skip_days
     }
 
-    /// Return a mutable slice of the days that aggregators can skip for refreshing content.
-    pub fn skip_days_mut(&mut self) -> &mut [String] {
+    /// Return a mutable reference to the days that aggregators can skip for refreshing content.
+    pub fn skip_days_mut(&mut self) -> &mut Vec<String> {
         &mut self.skip_days
     }
 
@@ -695,167 +1094,1181 @@ impl Channel {
         self.skip_days = skip_days.into();
     }
 
-    /// Return the items in this channel.
+    /// Return the days that aggregators can skip as `Weekday` values.
+    ///
+    /// Days are matched case-insensitively. Days that are not recognized are left out.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rss::{Channel, Item};
+    /// extern crate chrono;
+    /// # extern crate rss;
+    ///
+    /// use chrono::Weekday;
+    /// use rss::Channel;
     ///
+    /// # fn main() {
     /// let mut channel = Channel::default();
-    /// channel.set_items(vec![Item::default()]);
-    /// assert_eq!(channel.items().len(), 1);
-    /// ```
-    pub fn items(&self) -> &[Item] {
-        &self.items
-    }
-
-    /// Return a mutable slice of the items in this channel.
-    pub fn items_mut(&mut self) -> &mut [Item] {
-        &mut self.items
+    /// channel.set_skip_days(vec!["saturday".to_string(), "Someday".to_string()]);
+    /// assert_eq!(channel.skip_days_parsed(), vec![Weekday::Sat]);
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn skip_days_parsed(&self) -> Vec<Weekday> {
+        self.skip_days
+            .iter()
+            .filter_map(|day| parse_weekday(day))
+            .collect()
     }
 
-    /// Set the items in this channel.
+    /// Set the days that aggregators can skip from `Weekday` values.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rss::{Channel, Item};
+    /// extern crate chrono;
+    /// # extern crate rss;
     ///
+    /// use chrono::Weekday;
+    /// use rss::Channel;
+    ///
+    /// # fn main() {
     /// let mut channel = Channel::default();
-    /// channel.set_items(vec![Item::default()]);
+    /// channel.set_skip_days_from(vec![Weekday::Sat, Weekday::Sun]);
+    /// assert_eq!(channel.skip_days(), &["Saturday".to_string(), "Sunday".to_string()]);
+    /// # }
     /// ```
-    pub fn set_items<V>(&mut self, items: V)
+    #[cfg(feature = "chrono")]
+    pub fn set_skip_days_from<I>(&mut self, skip_days: I)
     where
-        V: Into<Vec<Item>>,
+        I: IntoIterator<Item = Weekday>,
     {
-        self.items = items.into();
+        self.skip_days = skip_days
+            .into_iter()
+            .map(|day| weekday_name(day).to_string())
+            .collect();
     }
 
-    /// Return the iTunes extension for this channel.
+    /// Return whether aggregators can skip refreshing the channel at the given time.
+    ///
+    /// The time is skipped if its hour in GMT is one of the hours in `skip_hours_parsed` or its
+    /// day in GMT is one of the days in `skip_days_parsed`.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
     ///
     /// # Examples
     ///
     /// ```
+    /// extern crate chrono;
+    /// # extern crate rss;
+    ///
+    /// use chrono::{FixedOffset, TimeZone, Utc, Weekday};
     /// use rss::Channel;
-    /// use rss::extension::itunes::ITunesChannelExtension;
     ///
+    /// # fn main() {
     /// let mut channel = Channel::default();
-    /// channel.set_itunes_ext(ITunesChannelExtension::default());
-    /// assert!(channel.itunes_ext().is_some());
-    /// ```
-    pub fn itunes_ext(&self) -> Option<&ITunesChannelExtension> {
-        self.itunes_ext.as_ref()
+    /// channel.set_skip_hours_from(vec![6]);
+    /// channel.set_skip_days_from(vec![Weekday::Sun]);
+    ///
+    /// // Monday at 06:30 GMT
+    /// assert!(channel.should_skip(&Utc.ymd(2017, 1, 2).and_hms(6, 30, 0)));
+    /// assert!(channel.should_skip(&FixedOffset::east(3600).ymd(2017, 1, 2).and_hms(7, 30, 0)));
+    /// // Monday at 07:30 GMT
+    /// assert!(!channel.should_skip(&Utc.ymd(2017, 1, 2).and_hms(7, 30, 0)));
+    /// // Sunday at 12:00 GMT
+    /// assert!(channel.should_skip(&Utc.ymd(2017, 1, 1).and_hms(12, 0, 0)));
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn should_skip<Tz>(&self, datetime: &DateTime<Tz>) -> bool
+    where
+        Tz: TimeZone,
+    {
+        let datetime = datetime.with_timezone(&Utc);
+        self.skip_hours_parsed().contains(&(datetime.hour() as u8)) ||
+            self.skip_days_parsed().contains(&datetime.weekday())
     }
 
-    /// Set the iTunes extension for this channel.
+    /// Return the items in this channel.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rss::Channel;
-    /// use rss::extension::itunes::ITunesChannelExtension;
+    /// use rss::{Channel, Item};
     ///
     /// let mut channel = Channel::default();
-    /// channel.set_itunes_ext(ITunesChannelExtension::default());
+    /// channel.set_items(vec![Item::default()]);
+    /// assert_eq!(channel.items().len(), 1);
     /// ```
-    pub fn set_itunes_ext<V>(&mut self, itunes_ext: V)
-    where
-        V: Into<Option<ITunesChannelExtension>>,
-    {
-        self.itunes_ext = itunes_ext.into();
+    pub fn items(&self) -> &[Item] {
+        &self.items
     }
 
-    /// Return the Dublin Core extension for this channel.
+    /// Return a mutable reference to the items in this channel.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rss::Channel;
-    /// use rss::extension::dublincore::DublinCoreExtension;
+    /// use rss::{Channel, ItemBuilder};
     ///
     /// let mut channel = Channel::default();
-    /// channel.set_dublin_core_ext(DublinCoreExtension::default());
-    /// assert!(channel.dublin_core_ext().is_some());
+    /// channel.set_items(vec![
+    ///     ItemBuilder::default().title("Keep".to_string()).build().unwrap(),
+    ///     ItemBuilder::default().title("Drop".to_string()).build().unwrap(),
+    /// ]);
+    /// channel.items_mut().retain(|item| item.title() != Some("Drop"));
+    /// assert_eq!(channel.items().len(), 1);
     /// ```
-    pub fn dublin_core_ext(&self) -> Option<&DublinCoreExtension> {
-        self.dublin_core_ext.as_ref()
+    pub fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.items
     }
 
-    /// Set the Dublin Core extension for this channel.
+    /// Set the items in this channel.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rss::Channel;
-    /// use rss::extension::dublincore::DublinCoreExtension;
+    /// use rss::{Channel, Item};
     ///
     /// let mut channel = Channel::default();
-    /// channel.set_dublin_core_ext(DublinCoreExtension::default());
+    /// channel.set_items(vec![Item::default()]);
     /// ```
-    pub fn set_dublin_core_ext<V>(&mut self, dublin_core_ext: V)
+    pub fn set_items<V>(&mut self, items: V)
     where
-        V: Into<Option<DublinCoreExtension>>,
+        V: Into<Vec<Item>>,
     {
-        self.dublin_core_ext = dublin_core_ext.into();
+        self.items = items.into();
     }
 
-    /// Return the extensions for this channel.
+    /// Append the items of another channel to this channel.
+    ///
+    /// The metadata of this channel is left intact, so when both channels set a field such as
+    /// the title or the iTunes extension, the value of this channel wins. Namespaces that are
+    /// declared by `other` but not by this channel are added so that the merged items can still
+    /// be written.
+    ///
+    /// Unless the strategy is `MergeStrategy::KeepAll`, items with the same `Item::dedup_key`
+    /// are then removed from the merged items according to the strategy. The retained items keep
+    /// their order and items without a key are never removed. Use `sort_items_by_date`
+    /// afterwards to order the merged items by date.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use rss::Channel;
-    /// use rss::extension::{ExtensionMap, Extension};
+    /// use rss::{Channel, Item, MergeStrategy};
     ///
-    /// let extension = Extension::default();
+    /// let item = |link: &str| {
+    ///     let mut item = Item::default();
+    ///     item.set_link(link.to_string());
+    ///     item
+    /// };
     ///
-    /// let mut item_map = HashMap::<String, Vec<Extension>>::new();
-    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    /// let mut channel = Channel::default();
+    /// channel.set_title("Combined");
+    /// channel.set_items(vec![item("http://example.com/1")]);
     ///
-    /// let mut extension_map = ExtensionMap::default();
-    /// extension_map.insert("ext".to_string(), item_map);
+    /// let mut other = Channel::default();
+    /// other.set_title("Other");
+    /// other.set_items(vec![item("http://example.com/1"), item("http://example.com/2")]);
     ///
-    /// let mut channel = Channel::default();
-    /// channel.set_extensions(extension_map);
-    /// assert_eq!(channel.extensions()
-    ///                   .get("ext")
-    ///                   .and_then(|m| m.get("ext:name"))
-    ///                   .map(|v| v.len()),
-    ///            Some(1));
+    /// channel.merge(other, MergeStrategy::KeepFirst);
+    /// assert_eq!(channel.title(), "Combined");
+    /// assert_eq!(channel.items().len(), 2);
     /// ```
-    pub fn extensions(&self) -> &ExtensionMap {
-        &self.extensions
+    pub fn merge(&mut self, other: Channel, strategy: MergeStrategy) {
+        for (prefix, namespace) in other.namespaces {
+            self.namespaces.entry(prefix).or_insert(namespace);
+        }
+        self.items.extend(other.items);
+
+        if strategy == MergeStrategy::KeepAll {
+            return;
+        }
+
+        let keep = {
+            let mut kept = HashMap::new();
+            for (index, item) in self.items.iter().enumerate() {
+                let key = match item.dedup_key() {
+                    Some(key) => key,
+                    None => continue,
+                };
+
+                let entry = kept.entry(key).or_insert(index);
+                if strategy.prefers(item, &self.items[*entry]) {
+                    *entry = index;
+                }
+            }
+            kept.values().cloned().collect::<HashSet<_>>()
+        };
+
+        let mut index = 0;
+        self.items.retain(|item| {
+            let retain = keep.contains(&index) || item.dedup_key().is_none();
+            index += 1;
+            retain
+        });
     }
 
-    /// Set the extensions for this channel.
+    /// Append the items of another channel to this channel, prefixing the title of each merged
+    /// item with the title of `other`.
+    ///
+    /// Titles are written as `"<channel title>: <item title>"`, and items without a title are
+    /// given the title of `other`. If `other` has an empty title, the items are merged unchanged.
+    /// Otherwise this behaves like `merge`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rss::Channel;
-    /// use rss::extension::ExtensionMap;
+    /// use rss::{Channel, Item, MergeStrategy};
+    ///
+    /// let mut item = Item::default();
+    /// item.set_title("Item".to_string());
+    ///
+    /// let mut other = Channel::default();
+    /// other.set_title("Other");
+    /// other.set_items(vec![item]);
     ///
     /// let mut channel = Channel::default();
-    /// channel.set_extensions(ExtensionMap::default());
-    /// ```
-    pub fn set_extensions<V>(&mut self, extensions: V)
-    where
-        V: Into<ExtensionMap>,
-    {
-        self.extensions = extensions.into()
+    /// channel.merge_prefixed(other, MergeStrategy::KeepAll);
+    /// assert_eq!(channel.items()[0].title(), Some("Other: Item"));
+    /// ```
+    pub fn merge_prefixed(&mut self, mut other: Channel, strategy: MergeStrategy) {
+        if !other.title.is_empty() {
+            for item in &mut other.items {
+                let title = match item.title() {
+                    Some(title) => format!("{}: {}", other.title, title),
+                    None => other.title.clone(),
+                };
+                item.set_title(title);
+            }
+        }
+        self.merge(other, strategy);
     }
 
-    /// Return the namespaces for this channel.
+    /// Return the items that belong to a category with the given name, ignoring case and
+    /// surrounding whitespace.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use rss::Channel;
+    /// use rss::{CategoryBuilder, Channel, Item};
     ///
-    /// let mut namespaces = HashMap::new();
+    /// let item = |title: &str, category: &str| {
+    ///     let mut item = Item::default();
+    ///     item.set_title(title.to_string());
+    ///     item.set_categories(vec![CategoryBuilder::default().name(category).build().unwrap()]);
+    ///     item
+    /// };
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![item("First", "Rust"), item("Second", "Go"), item("Third", "rust")]);
+    ///
+    /// let titles = channel
+    ///     .items_with_category("Rust")
+    ///     .map(|item| item.title().unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(titles, vec!["First", "Third"]);
+    /// ```
+    pub fn items_with_category<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Item> {
+        self.items.iter().filter(move |item| item.has_category(name))
+    }
+
+    /// Remove the categories of the channel and of each item that have the same domain and name
+    /// as an earlier category in the same list.
+    ///
+    /// The domains and names are compared ignoring case and surrounding whitespace. The first
+    /// category of each kind is kept and the retained categories keep their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{CategoryBuilder, Channel};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_categories(vec![
+    ///     CategoryBuilder::default().name("Rust").build().unwrap(),
+    ///     CategoryBuilder::default().name(" rust ").build().unwrap(),
+    ///     CategoryBuilder::default().name("Rust").domain("http://example.com/".to_string())
+    ///         .build().unwrap(),
+    /// ]);
+    /// channel.categories_dedup();
+    /// assert_eq!(channel.categories().len(), 2);
+    /// ```
+    pub fn categories_dedup(&mut self) {
+        dedup_categories(&mut self.categories);
+        for item in &mut self.items {
+            dedup_categories(item.categories_mut());
+        }
+    }
+
+    /// Remove the items whose GUID is the same as that of an earlier item.
+    ///
+    /// The first item with each GUID is kept and the retained items keep their order. Items
+    /// without a GUID are never removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Guid, Item};
+    ///
+    /// let item = |guid: &str, title: &str| {
+    ///     let mut item = Item::default();
+    ///     let mut value = Guid::default();
+    ///     value.set_value(guid);
+    ///     item.set_guid(value);
+    ///     item.set_title(title.to_string());
+    ///     item
+    /// };
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![item("1", "First"), item("2", "Second"), item("1", "Again")]);
+    /// channel.dedup_by_guid();
+    ///
+    /// let titles = channel.items().iter().map(|item| item.title().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(titles, vec!["First", "Second"]);
+    /// ```
+    pub fn dedup_by_guid(&mut self) {
+        let mut seen = HashSet::new();
+        self.items.retain(|item| match item.guid() {
+            Some(guid) => seen.insert(guid.value().to_string()),
+            None => true,
+        });
+    }
+
+    /// Remove the items whose GUID is the same as that of a more recently published item.
+    ///
+    /// Of the items with each GUID, the one with the latest parsed publication date is kept,
+    /// and the first of them if none of their dates can be parsed or several share the latest
+    /// date. The retained items keep their order. Items without a GUID are never removed.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Guid, Item};
+    ///
+    /// let item = |guid: &str, date: &str| {
+    ///     let mut item = Item::default();
+    ///     let mut value = Guid::default();
+    ///     value.set_value(guid);
+    ///     item.set_guid(value);
+    ///     item.set_pub_date(date.to_string());
+    ///     item
+    /// };
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![
+    ///     item("1", "Sun, 01 Jan 2017 12:00:00 GMT"),
+    ///     item("2", "Sun, 01 Jan 2017 12:00:00 GMT"),
+    ///     item("1", "Mon, 02 Jan 2017 12:00:00 GMT"),
+    /// ]);
+    /// channel.dedup_by_guid_latest();
+    ///
+    /// let dates = channel.items().iter().map(|item| item.pub_date().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(dates, vec!["Sun, 01 Jan 2017 12:00:00 GMT", "Mon, 02 Jan 2017 12:00:00 GMT"]);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn dedup_by_guid_latest(&mut self) {
+        let mut latest = HashMap::new();
+        for (index, item) in self.items.iter().enumerate() {
+            let guid = match item.guid() {
+                Some(guid) => guid.value(),
+                None => continue,
+            };
+
+            // dates that cannot be parsed are older than any date that can
+            let date = item.pub_date_parsed();
+            let entry = latest.entry(guid).or_insert((index, date));
+            if date > entry.1 {
+                *entry = (index, date);
+            }
+        }
+
+        let keep = latest
+            .values()
+            .map(|&(index, _)| index)
+            .collect::<HashSet<_>>();
+        let mut index = 0;
+        self.items.retain(|item| {
+            let retain = item.guid().is_none() || keep.contains(&index);
+            index += 1;
+            retain
+        });
+    }
+
+    /// Sort the items by their parsed publication date.
+    ///
+    /// Dates in different time zones are compared as instants. The sort is stable, so items
+    /// with the same date keep their order. Items whose date is missing or cannot be parsed keep
+    /// their order and are moved to the end in either direction.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Item, SortOrder};
+    ///
+    /// let item = |date: &str| {
+    ///     let mut item = Item::default();
+    ///     item.set_pub_date(date.to_string());
+    ///     item
+    /// };
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![
+    ///     item("Sun, 01 Jan 2017 12:00:00 GMT"),
+    ///     item("yesterday"),
+    ///     item("Mon, 02 Jan 2017 12:00:00 GMT"),
+    /// ]);
+    /// channel.sort_items_by_date(SortOrder::Descending);
+    ///
+    /// let dates = channel.items().iter().map(|item| item.pub_date().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(dates[0], "Mon, 02 Jan 2017 12:00:00 GMT");
+    /// assert_eq!(dates[2], "yesterday");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn sort_items_by_date(&mut self, order: SortOrder) {
+        let mut items = self.items
+            .drain(..)
+            .map(|item| (item.pub_date_parsed(), item))
+            .collect::<Vec<_>>();
+
+        items.sort_by(|&(ref a, _), &(ref b, _)| match (a, b) {
+            (&Some(ref a), &Some(ref b)) => match order {
+                SortOrder::Ascending => a.cmp(b),
+                SortOrder::Descending => b.cmp(a),
+            },
+            (&Some(_), &None) => Ordering::Less,
+            (&None, &Some(_)) => Ordering::Greater,
+            (&None, &None) => Ordering::Equal,
+        });
+
+        self.items = items.into_iter().map(|(_, item)| item).collect();
+    }
+
+    /// Sort the items by their parsed publication date, newest first if `descending` is true.
+    ///
+    /// This is `sort_items_by_date` with the order given as a `bool`.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Item};
+    ///
+    /// let mut first = Item::default();
+    /// first.set_pub_date("Sun, 01 Jan 2017 12:00:00 GMT".to_string());
+    /// let mut second = Item::default();
+    /// second.set_pub_date("Mon, 02 Jan 2017 12:00:00 GMT".to_string());
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![first, second.clone()]);
+    /// channel.sort_by_date(true);
+    /// assert_eq!(channel.items()[0], second);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn sort_by_date(&mut self, descending: bool) {
+        let order = if descending { SortOrder::Descending } else { SortOrder::Ascending };
+        self.sort_items_by_date(order);
+    }
+
+    /// Keep only the first `len` items of this channel.
+    ///
+    /// Nothing happens if the channel has `len` items or fewer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Item};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![Item::default(); 3]);
+    ///
+    /// channel.truncate_items(2);
+    /// assert_eq!(channel.items().len(), 2);
+    /// ```
+    pub fn truncate_items(&mut self, len: usize) {
+        self.items.truncate(len);
+    }
+
+    /// Keep only the items of this channel for which `f` returns `true`, preserving their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, ItemBuilder};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![
+    ///     ItemBuilder::default().title("Keep".to_string()).build().unwrap(),
+    ///     ItemBuilder::default().title("Drop".to_string()).build().unwrap(),
+    /// ]);
+    ///
+    /// channel.retain_items(|item| item.title() != Some("Drop"));
+    /// assert_eq!(channel.items().len(), 1);
+    /// ```
+    pub fn retain_items<F>(&mut self, f: F)
+    where
+        F: FnMut(&Item) -> bool,
+    {
+        self.items.retain(f);
+    }
+
+    /// Remove the item at `index` from this channel and return it.
+    ///
+    /// `None` is returned if there is no item at `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Item};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![Item::default()]);
+    ///
+    /// assert_eq!(channel.remove_item(1), None);
+    /// assert_eq!(channel.remove_item(0), Some(Item::default()));
+    /// assert!(channel.items().is_empty());
+    /// ```
+    pub fn remove_item(&mut self, index: usize) -> Option<Item> {
+        if index < self.items.len() {
+            Some(self.items.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Return the iTunes extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::itunes::ITunesChannelExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_itunes_ext(ITunesChannelExtension::default());
+    /// assert!(channel.itunes_ext().is_some());
+    /// ```
+    pub fn itunes_ext(&self) -> Option<&ITunesChannelExtension> {
+        self.itunes_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the iTunes extension for this channel.
+    pub fn itunes_ext_mut(&mut self) -> Option<&mut ITunesChannelExtension> {
+        self.itunes_ext.as_mut()
+    }
+
+    /// Set the iTunes extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::itunes::ITunesChannelExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_itunes_ext(ITunesChannelExtension::default());
+    /// ```
+    pub fn set_itunes_ext<V>(&mut self, itunes_ext: V)
+    where
+        V: Into<Option<ITunesChannelExtension>>,
+    {
+        self.itunes_ext = itunes_ext.into();
+    }
+
+    /// Return the Dublin Core extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::dublincore::DublinCoreExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_dublin_core_ext(DublinCoreExtension::default());
+    /// assert!(channel.dublin_core_ext().is_some());
+    /// ```
+    pub fn dublin_core_ext(&self) -> Option<&DublinCoreExtension> {
+        self.dublin_core_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the Dublin Core extension for this channel.
+    pub fn dublin_core_ext_mut(&mut self) -> Option<&mut DublinCoreExtension> {
+        self.dublin_core_ext.as_mut()
+    }
+
+    /// Set the Dublin Core extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::dublincore::DublinCoreExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_dublin_core_ext(DublinCoreExtension::default());
+    /// ```
+    pub fn set_dublin_core_ext<V>(&mut self, dublin_core_ext: V)
+    where
+        V: Into<Option<DublinCoreExtension>>,
+    {
+        self.dublin_core_ext = dublin_core_ext.into();
+    }
+
+    /// Return the Podcasting 2.0 extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::podcast::PodcastChannelExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_podcast_ext(PodcastChannelExtension::default());
+    /// assert!(channel.podcast_ext().is_some());
+    /// ```
+    pub fn podcast_ext(&self) -> Option<&PodcastChannelExtension> {
+        self.podcast_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the Podcasting 2.0 extension for this channel.
+    pub fn podcast_ext_mut(&mut self) -> Option<&mut PodcastChannelExtension> {
+        self.podcast_ext.as_mut()
+    }
+
+    /// Set the Podcasting 2.0 extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::podcast::PodcastChannelExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_podcast_ext(PodcastChannelExtension::default());
+    /// ```
+    pub fn set_podcast_ext<V>(&mut self, podcast_ext: V)
+    where
+        V: Into<Option<PodcastChannelExtension>>,
+    {
+        self.podcast_ext = podcast_ext.into();
+    }
+
+    /// Return the syndication module extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::syndication::SyndicationExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_syndication_ext(SyndicationExtension::default());
+    /// assert!(channel.syndication_ext().is_some());
+    /// ```
+    pub fn syndication_ext(&self) -> Option<&SyndicationExtension> {
+        self.syndication_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the syndication module extension for this channel.
+    pub fn syndication_ext_mut(&mut self) -> Option<&mut SyndicationExtension> {
+        self.syndication_ext.as_mut()
+    }
+
+    /// Set the syndication module extension for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::syndication::SyndicationExtension;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_syndication_ext(SyndicationExtension::default());
+    /// ```
+    pub fn set_syndication_ext<V>(&mut self, syndication_ext: V)
+    where
+        V: Into<Option<SyndicationExtension>>,
+    {
+        self.syndication_ext = syndication_ext.into();
+    }
+
+    /// Return the `<atom:link>` elements of this channel, in the order they were read in.
+    ///
+    /// Links with any relationship are kept, including the `self` link and the paging links of
+    /// RFC 5005. They are written in the Atom namespace, which is declared on the `<rss>` element
+    /// when the channel has any. An `<atom:link>` with content or with attributes other than
+    /// `href`, `rel` and `type` is kept in the extensions instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+    ///     <channel>
+    ///         <atom:link rel="self" href="http://example.com/feed?page=2"/>
+    ///         <atom:link rel="next" href="http://example.com/feed?page=3"/>
+    ///     </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let channel = input.parse::<Channel>().unwrap();
+    /// assert_eq!(channel.atom_links().len(), 2);
+    /// assert_eq!(channel.atom_links()[0].rel(), Some("self"));
+    /// ```
+    pub fn atom_links(&self) -> &[Link] {
+        &self.atom_links
+    }
+
+    /// Return a mutable reference to the `<atom:link>` elements of this channel.
+    pub fn atom_links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.atom_links
+    }
+
+    /// Set the `<atom:link>` elements of this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Link};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_atom_links(vec![Link::default()]);
+    /// ```
+    pub fn set_atom_links<V>(&mut self, atom_links: V)
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.atom_links = atom_links.into();
+    }
+
+    /// Return the URL of the next page of this channel, given by the `<atom:link>` with the `next`
+    /// relationship.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_next_page_url("http://example.com/feed?page=3".to_string());
+    /// assert_eq!(channel.next_page_url(), Some("http://example.com/feed?page=3"));
+    /// ```
+    pub fn next_page_url(&self) -> Option<&str> {
+        self.atom_link_href(&["next"])
+    }
+
+    /// Set the URL of the next page of this channel, replacing the `<atom:link>` with the `next`
+    /// relationship. `None` removes the link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_next_page_url("http://example.com/feed?page=3".to_string());
+    /// channel.set_next_page_url(None);
+    /// assert!(channel.atom_links().is_empty());
+    /// ```
+    pub fn set_next_page_url<V>(&mut self, url: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_atom_link_href(&["next"], url.into());
+    }
+
+    /// Return the URL of the previous page of this channel, given by the `<atom:link>` with
+    /// the `previous` relationship.
+    ///
+    /// Both the `previous` relationship of RFC 5005 and its `prev` synonym are recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_prev_page_url("http://example.com/feed?page=1".to_string());
+    /// assert_eq!(channel.prev_page_url(), Some("http://example.com/feed?page=1"));
+    /// ```
+    pub fn prev_page_url(&self) -> Option<&str> {
+        self.atom_link_href(&["previous", "prev"])
+    }
+
+    /// Set the URL of the previous page of this channel, replacing the `<atom:link>` with the
+    /// `previous` or `prev` relationship by one with the `previous` relationship. `None` removes
+    /// the link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_prev_page_url("http://example.com/feed?page=1".to_string());
+    /// channel.set_prev_page_url(None);
+    /// assert!(channel.atom_links().is_empty());
+    /// ```
+    pub fn set_prev_page_url<V>(&mut self, url: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_atom_link_href(&["previous", "prev"], url.into());
+    }
+
+    /// Return the URL of the first page of this channel, given by the `<atom:link>` with
+    /// the `first` relationship.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_first_page_url("http://example.com/feed?page=1".to_string());
+    /// assert_eq!(channel.first_page_url(), Some("http://example.com/feed?page=1"));
+    /// ```
+    pub fn first_page_url(&self) -> Option<&str> {
+        self.atom_link_href(&["first"])
+    }
+
+    /// Set the URL of the first page of this channel, replacing the `<atom:link>` with the `first`
+    /// relationship. `None` removes the link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_first_page_url("http://example.com/feed?page=1".to_string());
+    /// channel.set_first_page_url(None);
+    /// assert!(channel.atom_links().is_empty());
+    /// ```
+    pub fn set_first_page_url<V>(&mut self, url: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_atom_link_href(&["first"], url.into());
+    }
+
+    /// Return the URL of the last page of this channel, given by the `<atom:link>` with the `last`
+    /// relationship.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_last_page_url("http://example.com/feed?page=9".to_string());
+    /// assert_eq!(channel.last_page_url(), Some("http://example.com/feed?page=9"));
+    /// ```
+    pub fn last_page_url(&self) -> Option<&str> {
+        self.atom_link_href(&["last"])
+    }
+
+    /// Set the URL of the last page of this channel, replacing the `<atom:link>` with the `last`
+    /// relationship. `None` removes the link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_last_page_url("http://example.com/feed?page=9".to_string());
+    /// channel.set_last_page_url(None);
+    /// assert!(channel.atom_links().is_empty());
+    /// ```
+    pub fn set_last_page_url<V>(&mut self, url: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_atom_link_href(&["last"], url.into());
+    }
+
+    /// Return the URLs of the WebSub hubs of this channel, given by the `<atom:link>` elements
+    /// with the `hub` relationship.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+    ///     <channel>
+    ///         <atom:link rel="hub" href="https://hub.example.com/"/>
+    ///         <atom:link rel="self" href="http://example.com/feed"/>
+    ///     </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let channel = input.parse::<Channel>().unwrap();
+    /// assert_eq!(channel.hubs(), vec!["https://hub.example.com/"]);
+    /// assert_eq!(channel.atom_link_self(), Some("http://example.com/feed"));
+    /// ```
+    pub fn hubs(&self) -> Vec<&str> {
+        self.atom_links
+            .iter()
+            .filter(|link| link.rel() == Some("hub"))
+            .map(|link| link.href())
+            .collect()
+    }
+
+    /// Set the URLs of the WebSub hubs of this channel, replacing the `<atom:link>` elements
+    /// with the `hub` relationship.
+    ///
+    /// WebSub subscribers also need the `self` link, which `set_atom_link_self` sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_hubs(vec!["https://hub.example.com/", "https://other.example.com/"]);
+    /// channel.set_atom_link_self("http://example.com/feed".to_string());
+    ///
+    /// let output = channel.to_string();
+    /// assert!(output.contains(r#"<atom:link href="https://hub.example.com/" rel="hub"/>"#));
+    /// assert!(output.contains(r#"<atom:link href="http://example.com/feed" rel="self"/>"#));
+    /// ```
+    pub fn set_hubs<I, V>(&mut self, hubs: I)
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        let hubs = hubs.into_iter().map(Into::into).collect();
+        self.replace_atom_links(&["hub"], hubs);
+    }
+
+    /// Return the URL of this channel itself, given by the `<atom:link>` with the `self`
+    /// relationship.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_atom_link_self("http://example.com/feed".to_string());
+    /// assert_eq!(channel.atom_link_self(), Some("http://example.com/feed"));
+    /// ```
+    pub fn atom_link_self(&self) -> Option<&str> {
+        self.atom_link_href(&["self"])
+    }
+
+    /// Set the URL of this channel itself, replacing the `<atom:link>` with the `self`
+    /// relationship. `None` removes the link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_atom_link_self("http://example.com/feed".to_string());
+    /// channel.set_atom_link_self(None);
+    /// assert!(channel.atom_links().is_empty());
+    /// ```
+    pub fn set_atom_link_self<V>(&mut self, url: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_atom_link_href(&["self"], url.into());
+    }
+
+    /// Return the URL of the first `<atom:link>` with one of the given relationships.
+    fn atom_link_href(&self, rels: &[&str]) -> Option<&str> {
+        self.atom_links
+            .iter()
+            .find(|link| link.rel().map_or(false, |rel| rels.contains(&rel)))
+            .map(|link| link.href())
+    }
+
+    /// Replace the `<atom:link>` elements with one of the given relationships by a single link
+    /// with the first of them.
+    fn set_atom_link_href(&mut self, rels: &[&str], href: Option<String>) {
+        self.replace_atom_links(rels, href.into_iter().collect());
+    }
+
+    /// Replace the `<atom:link>` elements with one of the given relationships by links with the
+    /// first of them, at the position of the first link that is replaced.
+    fn replace_atom_links(&mut self, rels: &[&str], hrefs: Vec<String>) {
+        let is_rel = |link: &Link| link.rel().map_or(false, |rel| rels.contains(&rel));
+        let index = self.atom_links
+            .iter()
+            .position(&is_rel)
+            .unwrap_or_else(|| self.atom_links.len());
+        self.atom_links.retain(|link| !is_rel(link));
+
+        let links = hrefs.into_iter().map(|href| {
+            let mut link = Link::default();
+            link.set_href(href);
+            link.set_rel(rels[0].to_string());
+            link
+        });
+        let tail = self.atom_links.split_off(index);
+        self.atom_links.extend(links);
+        self.atom_links.extend(tail);
+    }
+
+    /// Return the values of the custom extensions for this channel.
+    ///
+    /// Values are added by the handlers registered with `ParseOptions::register_extension`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let channel = Channel::default();
+    /// assert!(channel.custom_extensions().is_empty());
+    /// ```
+    pub fn custom_extensions(&self) -> &CustomExtensionMap {
+        &self.custom_extensions
+    }
+
+    /// Return a mutable reference to the values of the custom extensions for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.custom_extensions_mut().remove("http://example.com/ns");
+    /// ```
+    pub fn custom_extensions_mut(&mut self) -> &mut CustomExtensionMap {
+        &mut self.custom_extensions
+    }
+
+    /// Set the values of the custom extensions for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::custom::CustomExtensionMap;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_custom_extensions(CustomExtensionMap::default());
+    /// ```
+    pub fn set_custom_extensions<V>(&mut self, custom_extensions: V)
+    where
+        V: Into<CustomExtensionMap>,
+    {
+        self.custom_extensions = custom_extensions.into();
+    }
+
+    /// Return the extensions for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use rss::Channel;
+    /// use rss::extension::{ExtensionMap, Extension};
+    ///
+    /// let extension = Extension::default();
+    ///
+    /// let mut item_map = HashMap::<String, Vec<Extension>>::new();
+    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    ///
+    /// let mut extension_map = ExtensionMap::default();
+    /// extension_map.insert("ext".to_string(), item_map);
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_extensions(extension_map);
+    /// assert_eq!(channel.extensions()
+    ///                   .get("ext")
+    ///                   .and_then(|m| m.get("ext:name"))
+    ///                   .map(|v| v.len()),
+    ///            Some(1));
+    /// ```
+    pub fn extensions(&self) -> &ExtensionMap {
+        &self.extensions
+    }
+
+    /// Return a mutable reference to the extensions for this channel.
+    pub fn extensions_mut(&mut self) -> &mut ExtensionMap {
+        &mut self.extensions
+    }
+
+    /// Set the extensions for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// use rss::extension::ExtensionMap;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_extensions(ExtensionMap::default());
+    /// ```
+    pub fn set_extensions<V>(&mut self, extensions: V)
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.extensions = extensions.into()
+    }
+
+    /// Return the first extension element of this channel with the given local name in the
+    /// namespace with the given URI.
+    ///
+    /// The namespace is resolved through the namespaces of this channel, so the element is found
+    /// whichever prefix the feed declared the namespace with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0" xmlns:ex="http://example.com/ns">
+    ///   <channel>
+    ///     <ex:rating>5</ex:rating>
+    ///     <ex:rating>4</ex:rating>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let channel = input.parse::<Channel>().unwrap();
+    /// let rating = channel.extension("http://example.com/ns", "rating").unwrap();
+    /// assert_eq!(rating.value(), Some("5"));
+    /// assert!(channel.extension("http://example.com/other", "rating").is_none());
+    /// ```
+    pub fn extension(&self, namespace: &str, name: &str) -> Option<&Extension> {
+        find_extension(&self.extensions, &self.namespaces, namespace, name)
+    }
+
+    /// Return the namespaces for this channel, keyed by prefix.
+    ///
+    /// A parsed channel has every `xmlns:` declaration of the root element, and each of them is
+    /// declared again with the same URI when the channel is written. The exception is a supported
+    /// extension declared with its usual prefix, such as `itunes` or `dc`: that declaration is
+    /// left out, since the writer makes it itself whenever the channel uses the extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use rss::Channel;
+    ///
+    /// let mut namespaces = HashMap::new();
     /// namespaces.insert("ext".to_string(), "http://example.com".to_string());
     ///
     /// let mut channel = Channel::default();
@@ -866,330 +2279,1053 @@ impl Channel {
         &self.namespaces
     }
 
-    /// Set the namespaces for this channel.
+    /// Return a mutable reference to the namespaces for this channel.
+    pub fn namespaces_mut(&mut self) -> &mut HashMap<String, String> {
+        &mut self.namespaces
+    }
+
+    /// Set the namespaces for this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_namespaces(HashMap::new());
+    /// ```
+    pub fn set_namespaces<V>(&mut self, namespaces: V)
+    where
+        V: Into<HashMap<String, String>>,
+    {
+        self.namespaces = namespaces.into()
+    }
+
+    /// Register a namespace to be declared on the `<rss>` element when this channel is written,
+    /// replacing any namespace previously registered with the same prefix.
+    ///
+    /// Extension elements can only be written under a prefix that is registered here, except
+    /// for the iTunes, Dublin Core, Media RSS and content prefixes which are declared
+    /// automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.add_namespace("custom", "https://example.com/ns");
+    ///
+    /// let output = channel.to_string();
+    /// assert!(output.contains(r#"xmlns:custom="https://example.com/ns""#));
+    /// ```
+    pub fn add_namespace<P, U>(&mut self, prefix: P, uri: U)
+    where
+        P: Into<String>,
+        U: Into<String>,
+    {
+        self.namespaces.insert(prefix.into(), uri.into());
+    }
+
+    /// Return the elements of this channel that are not otherwise supported.
+    ///
+    /// Unprefixed child elements that are not part of the RSS specification are kept here so
+    /// that they are written back out when the channel is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0">
+    ///   <channel>
+    ///     <title>Title</title>
+    ///     <rank>5</rank>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let channel = input.parse::<Channel>().unwrap();
+    /// assert_eq!(channel.unknown_elements()[0].name(), "rank");
+    /// assert_eq!(channel.unknown_elements()[0].value(), Some("5"));
+    /// ```
+    pub fn unknown_elements(&self) -> &[Extension] {
+        &self.unknown_elements
+    }
+
+    /// Set the elements of this channel that are not otherwise supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_unknown_elements(Vec::new());
+    /// ```
+    pub fn set_unknown_elements<V>(&mut self, unknown_elements: V)
+    where
+        V: Into<Vec<Extension>>,
+    {
+        self.unknown_elements = unknown_elements.into()
+    }
+
+    /// Return the base URI for the relative URLs in this channel.
+    ///
+    /// This is the value of the `xml:base` attribute of the `<channel>` element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_base("http://example.com/blog/".to_string());
+    /// assert_eq!(channel.base(), Some("http://example.com/blog/"));
+    /// ```
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the base URI for the relative URLs in this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_base("http://example.com/blog/".to_string());
+    /// ```
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into();
+    }
+
+    /// Return the stylesheets referenced by `xml-stylesheet` processing instructions before the
+    /// root element of the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Stylesheet};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_stylesheets(vec![Stylesheet::default()]);
+    /// assert_eq!(channel.stylesheets().len(), 1);
+    /// ```
+    pub fn stylesheets(&self) -> &[Stylesheet] {
+        &self.stylesheets
+    }
+
+    /// Return a mutable reference to the stylesheets of the feed.
+    pub fn stylesheets_mut(&mut self) -> &mut Vec<Stylesheet> {
+        &mut self.stylesheets
+    }
+
+    /// Set the stylesheets referenced before the root element of the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Stylesheet};
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_stylesheets(vec![Stylesheet::default()]);
+    /// ```
+    pub fn set_stylesheets<V>(&mut self, stylesheets: V)
+    where
+        V: Into<Vec<Stylesheet>>,
+    {
+        self.stylesheets = stylesheets.into();
+    }
+}
+
+impl Channel {
+    /// Attempt to read an RSS channel from a URL.
+    ///
+    /// Note: The `from_url` method can only be used by enabling the `from_url` feature in
+    /// your `Cargo.toml` as follows:
+    ///
+    /// ```toml
+    /// [dependencies]
+    /// rss = { version = "*", features = ["from_url"] }
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    /// let channel = Channel::from_url("https://feedpress.me/usererror.xml");
+    /// ```
+    #[cfg(feature = "from_url")]
+    pub fn from_url(url: &str) -> Result<Channel, Error> {
+        use std::io::Read;
+
+        let mut content = String::new();
+        ::reqwest::get(url)?.read_to_string(&mut content)?;
+        Ok(Channel::from_str(content.as_str())?)
+    }
+
+    /// Attempt to read an RSS channel from a file.
+    ///
+    /// Errors while opening the file are returned as `Error::File` along with the path of the
+    /// file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let channel = Channel::from_file("tests/data/rss2sample.xml").unwrap();
+    /// assert_eq!(channel.title(), "Liftoff News");
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Channel, Error> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| Error::File(path.to_path_buf(), err))?;
+        Channel::read_from(BufReader::new(file))
+    }
+
+    /// Attempt to read an RSS channel from a byte slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = b"<rss><channel><title>Title</title></channel></rss>";
+    /// let channel = Channel::from_bytes(input).unwrap();
+    /// assert_eq!(channel.title(), "Title");
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Channel, Error> {
+        Channel::read_from_slice(bytes)
+    }
+
+    /// Attempt to read an RSS channel from a byte slice.
+    ///
+    /// The slice is read in place without being copied into an intermediate buffer. A string
+    /// can also be parsed with `str::parse`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> Result<(), rss::Error> {
+    /// let input = "<rss><channel><title>Title</title></channel></rss>";
+    ///
+    /// let channel = Channel::read_from_slice(input.as_bytes())?;
+    /// assert_eq!(channel.title(), "Title");
+    ///
+    /// let channel = input.parse::<Channel>()?;
+    /// assert_eq!(channel.title(), "Title");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_from_slice(bytes: &[u8]) -> Result<Channel, Error> {
+        Channel::read_from(bytes)
+    }
+
+    /// Attempt to read an RSS channel from a reader.
+    ///
+    /// The input is decoded using the encoding in its XML declaration, or as UTF-16 if it
+    /// begins with a UTF-16 byte order mark. `Error::UnsupportedEncoding` is returned if the
+    /// declared encoding is not known.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let reader: BufRead = ...;
+    /// let channel = Channel::read_from(reader).unwrap();
+    /// ```
+    pub fn read_from<R: BufRead>(reader: R) -> Result<Channel, Error> {
+        let mut reader = ItemReader::new(reader)?;
+        let mut items = Vec::new();
+
+        for item in &mut reader {
+            items.push(item?);
+        }
+
+        let mut channel = reader.into_channel();
+        channel.items = items;
+        Ok(channel)
+    }
+
+    /// Attempt to read an RSS channel from a reader, skipping items that fail to parse.
+    ///
+    /// The returned channel contains every item that parsed cleanly, along with the errors for
+    /// the items that were skipped. Errors outside of an item still abort the parse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0">
+    ///   <channel>
+    ///     <title>Title</title>
+    ///     <item><title>Good</title></item>
+    ///     <item><description>Fish & Chips</description></item>
+    ///     <item><title>Also Good</title></item>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let (channel, errors) = Channel::read_from_lenient(input.as_bytes()).unwrap();
+    /// assert_eq!(channel.items().len(), 2);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn read_from_lenient<R: BufRead>(reader: R) -> Result<(Channel, Vec<Error>), Error> {
+        let mut reader = ItemReader::new(reader)?;
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+
+        while let Some(item) = reader.next() {
+            match item {
+                Ok(item) => items.push(item),
+                Err(err) => {
+                    if reader.is_done() {
+                        return Err(err);
+                    }
+                    errors.push(err);
+                }
+            }
+        }
+
+        let mut channel = reader.into_channel();
+        channel.items = items;
+        Ok((channel, errors))
+    }
+
+    /// Attempt to read an RSS channel from a reader using the given options.
+    ///
+    /// Along with the channel, this returns the recoverable problems that were found. When
+    /// parsing is not lenient, the channel is read exactly as with `read_from` and no warnings are
+    /// reported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rss::{Channel, ParseOptionsBuilder};
+    ///
+    /// let input = r#"
+    /// <rss version="2.0">
+    ///   <channel>
+    ///     <title>Title</title>
+    ///     <item>
+    ///       <guid>first</guid>
+    ///       <guid>second</guid>
+    ///       <description>Fish & Chips</description>
+    ///     </item>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let options = ParseOptionsBuilder::default().lenient(true).build().unwrap();
+    /// let (channel, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    ///
+    /// let item = &channel.items()[0];
+    /// assert_eq!(item.guid().map(|guid| guid.value()), Some("first"));
+    /// assert_eq!(item.description(), Some("Fish & Chips"));
+    /// assert_eq!(warnings.len(), 2);
+    /// ```
+    pub fn read_from_with_options<R: BufRead>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<(Channel, Vec<ParseWarning>), Error> {
+        let mut reader = ItemReader::with_options(reader, options)?;
+        let mut items = Vec::new();
+
+        for item in &mut reader {
+            items.push(item?);
+        }
+
+        let (mut channel, warnings) = reader.into_parts();
+        channel.items = items;
+        Ok((channel, warnings))
+    }
+
+    /// Attempt to read the metadata of an RSS channel from a reader, counting the items without
+    /// parsing them.
+    ///
+    /// This is much faster than `read_from` for large channels when only the channel elements
+    /// are needed. The items of the returned channel are intentionally left empty, and the
+    /// number of items is returned alongside it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0">
+    ///   <channel>
+    ///     <title>Title</title>
+    ///     <item><title>First</title></item>
+    ///     <item><title>Second</title></item>
+    ///     <lastBuildDate>Sat, 01 Jan 2000 12:00:00 GMT</lastBuildDate>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let (channel, count) = Channel::read_metadata_from(input.as_bytes()).unwrap();
+    /// assert_eq!(channel.title(), "Title");
+    /// assert_eq!(channel.last_build_date(), Some("Sat, 01 Jan 2000 12:00:00 GMT"));
+    /// assert!(channel.items().is_empty());
+    /// assert_eq!(count, 2);
+    /// ```
+    pub fn read_metadata_from<R: BufRead>(reader: R) -> Result<(Channel, usize), Error> {
+        let mut reader = ItemReader::new(reader)?;
+        let count = reader.skip_items()?;
+        Ok((reader.into_channel(), count))
+    }
+
+    /// Attempt to write the RSS channel as XML to a writer.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let channel: Channel = ...;
+    /// let writer: Write = ...;
+    /// channel.write_to(writer).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UndeclaredNamespace` if an extension uses a prefix that has not been
+    /// registered with `add_namespace` or `set_namespaces`.
+    ///
+    /// The control characters that XML does not allow are left out; use `write_with_options` to
+    /// choose another `InvalidCharPolicy`.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<W, Error> {
+        self.write_with_options(writer, &WriteOptions::default())
+    }
+
+    /// Write the stylesheets and the `rss` element of the channel.
+    fn write_rss<W: Write>(&self, writer: &mut Writer<W>, version: Version) -> Result<(), Error> {
+        self.write_rss_start(writer, version)?;
+        self.to_xml(writer)?;
+        writer.write_event(Event::End(BytesEnd::borrowed(b"rss")))?;
+        Ok(())
+    }
+
+    /// Write the stylesheets and the start tag of the `rss` element, which declares the
+    /// namespaces used by the channel and its items.
+    ///
+    /// Return the namespace prefixes that were declared.
+    pub(crate) fn write_rss_start<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        version: Version,
+    ) -> Result<HashSet<String>, Error> {
+        for stylesheet in &self.stylesheets {
+            stylesheet.to_xml(writer)?;
+        }
+
+        let name = b"rss";
+        let mut element = BytesStart::borrowed(name, name.len());
+        element.push_attribute(("version", version.as_str()));
+
+        let used = self.used_namespaces();
+        let extensions = self.extensions
+            .iter()
+            .chain(self.items.iter().flat_map(|item| item.extensions().iter()));
+
+        for (prefix, extensions) in extensions {
+            if !used.iter().any(|&(used, _)| used == prefix) &&
+                !self.namespaces.contains_key(prefix) &&
+                !declares_prefix(prefix, extensions)
+            {
+                return Err(Error::UndeclaredNamespace(prefix.to_string()));
+            }
+        }
+
+        for &(prefix, namespace) in &used {
+            if !self.namespaces.contains_key(prefix) {
+                let name = format!("xmlns:{}", prefix);
+                element.push_attribute((name.as_bytes(), namespace.as_bytes()));
+            }
+        }
+
+        let custom_namespaces = self.custom_extensions
+            .iter()
+            .chain(self.items.iter().flat_map(|item| item.custom_extensions().iter()))
+            .map(|(namespace, &(ref prefix, _))| (prefix, namespace))
+            .filter(|&(prefix, _)| !self.namespaces.contains_key(prefix))
+            .collect::<HashMap<_, _>>();
+
+        for (prefix, namespace) in custom_namespaces {
+            element.push_attribute((
+                format!("xmlns:{}", prefix).as_bytes(),
+                &*escape(namespace.as_bytes()),
+            ));
+        }
+
+        // every declared namespace is written, so a known prefix keeps the URI it was declared
+        // with and the declarations of the source document survive a round trip, except for an
+        // unused prefix of a supported namespace that is already declared with its own prefix
+        let custom_prefixes = self.custom_extensions
+            .iter()
+            .chain(self.items.iter().flat_map(|item| item.custom_extensions().iter()))
+            .map(|(_, &(ref prefix, _))| prefix);
+        let used_prefixes = self.extensions
+            .keys()
+            .chain(self.items.iter().flat_map(|item| item.extensions().keys()))
+            .chain(custom_prefixes)
+            .map(|prefix| prefix.as_str())
+            .chain(self.atom_links.first().map(|_| self.atom_prefix()))
+            .collect::<HashSet<_>>();
+
+        for (name, url) in &self.namespaces {
+            let redeclared = !used_prefixes.contains(name.as_str()) &&
+                used.iter().any(|&(prefix, namespace)| {
+                    prefix != name && namespace == url &&
+                        self.namespaces.get(prefix).map_or(true, |uri| uri == url)
+                });
+            if !redeclared {
+                let name = format!("xmlns:{}", name);
+                element.push_attribute((name.as_bytes(), &*escape(url.as_bytes())));
+            }
+        }
+
+        let prefixes = element
+            .attributes()
+            .with_checks(false)
+            .filter_map(|attr| attr.ok())
+            .filter(|attr| attr.key.starts_with(b"xmlns:"))
+            .map(|attr| String::from_utf8_lossy(&attr.key[6..]).into_owned())
+            .collect();
+
+        writer.write_event(Event::Start(element))?;
+        Ok(prefixes)
+    }
+
+    /// Return the prefixes and URIs of the supported namespaces that the channel and its items
+    /// use, which the writer declares unless their prefix is already declared.
+    fn used_namespaces(&self) -> Vec<(&'static str, &'static str)> {
+        let mut itunes_ns = self.itunes_ext.is_some();
+        let mut dc_ns = self.dublin_core_ext.is_some();
+        let mut media_ns = false;
+        let mut content_ns = false;
+        let mut podcast_ns = self.podcast_ext.is_some();
+        let mut sy_ns = self.syndication_ext.is_some();
+        let mut slash_ns = false;
+        let mut atom_ns = !self.atom_links.is_empty() && self.atom_prefix() == "atom";
+
+        for item in &self.items {
+            if itunes_ns && dc_ns && media_ns && content_ns && podcast_ns && slash_ns {
+                break;
+            }
+
+            if !itunes_ns {
+                itunes_ns = item.itunes_ext().is_some();
+            }
+
+            if !dc_ns {
+                dc_ns = item.dublin_core_ext().is_some();
+            }
+
+            if !media_ns {
+                media_ns = item.media_ext().is_some();
+            }
+
+            if !content_ns {
+                content_ns = item.content().is_some();
+            }
+
+            if !podcast_ns {
+                podcast_ns = item.podcast_ext().is_some();
+            }
+
+            if !slash_ns {
+                slash_ns = item.slash_ext().is_some();
+            }
+        }
+
+        let prefixes = self.extensions
+            .keys()
+            .chain(self.items.iter().flat_map(|item| item.extensions().keys()));
+
+        for prefix in prefixes {
+            match prefix.as_str() {
+                "itunes" => itunes_ns = true,
+                "dc" => dc_ns = true,
+                "media" => media_ns = true,
+                "content" => content_ns = true,
+                "podcast" => podcast_ns = true,
+                "sy" => sy_ns = true,
+                "slash" => slash_ns = true,
+                "atom" => atom_ns = true,
+                _ => {}
+            }
+        }
+
+        let used = [
+            itunes_ns, dc_ns, media_ns, content_ns, podcast_ns, sy_ns, slash_ns, atom_ns,
+        ];
+        SUPPORTED_NAMESPACES
+            .iter()
+            .zip(&used)
+            .filter(|&(_, &used)| used)
+            .map(|(&namespace, _)| namespace)
+            .collect()
+    }
+
+    /// Return the prefix that the `<atom:link>` elements are written with, which is a prefix
+    /// that the Atom namespace is declared with or else `atom`.
+    fn atom_prefix(&self) -> &str {
+        if self.namespaces.get("atom").map_or(false, |uri| uri == ATOM_NAMESPACE) {
+            return "atom";
+        }
+
+        self.namespaces
+            .iter()
+            .filter(|&(_, uri)| uri == ATOM_NAMESPACE)
+            .map(|(prefix, _)| prefix.as_str())
+            .min()
+            .unwrap_or("atom")
+    }
+
+    /// Forget the declarations of the supported namespaces with their usual prefixes, which the
+    /// writer makes itself whenever the channel uses them, so that a channel that is written and
+    /// read back is equal to the original.
+    pub(crate) fn remove_implied_namespaces(&mut self) {
+        self.namespaces.retain(|prefix, namespace| {
+            !SUPPORTED_NAMESPACES.contains(&(prefix.as_str(), namespace.as_str()))
+        });
+    }
+
+    /// Attempt to write the RSS channel as indented XML to a writer.
     ///
-    /// # Examples
+    /// Each element is placed on its own line and indented by `indent_size` repetitions of
+    /// `indent_char` per level of nesting. Whitespace is never added inside elements that contain
+    /// text, so the content of the channel is written exactly as with `write_to`.
+    ///
+    /// # Example
     ///
     /// ```
-    /// use std::collections::HashMap;
-    /// use rss::Channel;
+    /// use rss::ChannelBuilder;
+    ///
+    /// let channel = ChannelBuilder::default()
+    ///     .title("Title")
+    ///     .link("http://example.com/")
+    ///     .description("Description")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let output = channel.pretty_write_to(Vec::new(), b' ', 2).unwrap();
+    /// let expected = r#"<rss version="2.0">
+    ///   <channel>
+    ///     <title>Title</title>
+    ///     <link>http://example.com/</link>
+    ///     <description>Description</description>
+    ///   </channel>
+    /// </rss>"#;
+    /// assert_eq!(String::from_utf8(output).unwrap(), expected);
+    /// ```
+    pub fn pretty_write_to<W: Write>(
+        &self,
+        writer: W,
+        indent_char: u8,
+        indent_size: usize,
+    ) -> Result<W, Error> {
+        let mut options = WriteOptions::default();
+        options.set_indent((indent_char, indent_size));
+        self.write_with_options(writer, &options)
+    }
+
+    /// Attempt to write the RSS channel as XML to a writer using the given options.
+    ///
+    /// # Example
     ///
-    /// let mut channel = Channel::default();
-    /// channel.set_namespaces(HashMap::new());
     /// ```
-    pub fn set_namespaces<V>(&mut self, namespaces: V)
-    where
-        V: Into<HashMap<String, String>>,
-    {
-        self.namespaces = namespaces.into()
+    /// use rss::{ChannelBuilder, WriteOptionsBuilder};
+    ///
+    /// let channel = ChannelBuilder::default()
+    ///     .title("Title")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let options = WriteOptionsBuilder::default()
+    ///     .indent((b' ', 2))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains("\n    <title>Title</title>"));
+    /// ```
+    pub fn write_with_options<W: Write>(
+        &self,
+        writer: W,
+        options: &WriteOptions,
+    ) -> Result<W, Error> {
+        let downlevel;
+        let channel = match options.version() {
+            Version::Rss2_0 => self,
+            Version::Rss0_92 => {
+                downlevel = self.downlevel();
+                &downlevel
+            }
+        };
+
+        write_filtered(writer, options, |writer| channel.write_rss(writer, options.version()))
     }
-}
 
-impl Channel {
-    /// Attempt to read an RSS channel from a URL.
+    /// Attempt to write the RSS channel as XML to a writer, preceded by an `xml-stylesheet`
+    /// processing instruction for the given stylesheet.
     ///
-    /// Note: The `from_url` method can only be used by enabling the `from_url` feature in
-    /// your `Cargo.toml` as follows:
+    /// The instruction is written before the stylesheets of the channel. Anything already
+    /// written to the writer, such as an XML declaration, is left before it.
+    ///
+    /// # Example
     ///
-    /// ```toml
-    /// [dependencies]
-    /// rss = { version = "*", features = ["from_url"] }
     /// ```
+    /// use rss::Channel;
+    ///
+    /// let channel = Channel::default();
+    /// let output = channel.write_with_stylesheet(Vec::new(), "feed.xsl", "text/xsl").unwrap();
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.starts_with(r#"<?xml-stylesheet type="text/xsl" href="feed.xsl"?><rss"#));
+    /// ```
+    pub fn write_with_stylesheet<W: Write>(
+        &self,
+        writer: W,
+        href: &str,
+        media_type: &str,
+    ) -> Result<W, Error> {
+        let mut stylesheet = Stylesheet::default();
+        stylesheet.set_href(href);
+        stylesheet.set_media_type(media_type);
+
+        let mut writer = Writer::new(writer);
+        stylesheet.to_xml(&mut writer)?;
+        self.write_to(writer.into_inner())
+    }
+
+    /// Return a copy of this channel with only the elements that RSS 0.92 has.
+    fn downlevel(&self) -> Channel {
+        Channel {
+            title: self.title.clone(),
+            link: self.link.clone(),
+            description: self.description.clone(),
+            language: self.language.clone(),
+            copyright: self.copyright.clone(),
+            managing_editor: self.managing_editor.clone(),
+            webmaster: self.webmaster.clone(),
+            pub_date: self.pub_date.clone(),
+            last_build_date: self.last_build_date.clone(),
+            docs: self.docs.clone(),
+            cloud: self.cloud.clone(),
+            rating: self.rating.clone(),
+            image: self.image.clone(),
+            text_input: self.text_input.clone(),
+            skip_hours: self.skip_hours.clone(),
+            skip_days: self.skip_days.clone(),
+            items: self.items.iter().map(Item::downlevel).collect(),
+            stylesheets: self.stylesheets.clone(),
+            ..Channel::default()
+        }
+    }
+
+    /// Attempt to write the RSS channel as XML to a string.
     ///
     /// # Examples
     ///
     /// ```
     /// use rss::Channel;
-    /// let channel = Channel::from_url("https://feedpress.me/usererror.xml");
+    ///
+    /// let input = "<rss version=\"2.0\"><channel><title>Title</title></channel></rss>";
+    /// let channel = input.parse::<Channel>().unwrap();
+    ///
+    /// let output = channel.try_to_string().unwrap();
+    /// assert!(output.contains("<title>Title</title>"));
     /// ```
-    #[cfg(feature = "from_url")]
-    pub fn from_url(url: &str) -> Result<Channel, Error> {
-        use std::io::Read;
-
-        let mut content = String::new();
-        ::reqwest::get(url)?.read_to_string(&mut content)?;
-        Ok(Channel::from_str(content.as_str())?)
+    pub fn try_to_string(&self) -> Result<String, Error> {
+        let buf = self.write_to(Vec::new())?;
+        String::from_utf8(buf).map_err(|err| Error::Utf8(err.utf8_error()))
     }
+}
 
-    /// Attempt to read an RSS channel from a reader.
+impl fmt::Display for Channel {
+    /// Write the RSS channel as XML, exactly as `write_to` does.
     ///
-    /// # Example
+    /// Nothing is written if the channel cannot be written, so `to_string` returns an empty
+    /// string instead of panicking; use `try_to_string` to get the error instead.
+    ///
+    /// # Examples
     ///
-    /// ```rust,ignore
-    /// let reader: BufRead = ...;
-    /// let channel = Channel::read_from(reader).unwrap();
     /// ```
-    pub fn read_from<R: BufRead>(reader: R) -> Result<Channel, Error> {
-        let mut reader = Reader::from_reader(reader);
-        reader.trim_text(true).expand_empty_elements(true);
-        let mut namespaces = HashMap::new();
-        let mut buf = Vec::new();
-        let mut skip_buf = Vec::new();
-
-        let mut channel: Option<Channel> = None;
-
-        // for parsing RSS 0.9, 1.0 feeds
-        let mut items: Option<Vec<Item>> = None;
-        let mut image: Option<Image> = None;
-        let mut text_input: Option<TextInput> = None;
+    /// use rss::Channel;
+    ///
+    /// let input = "<rss version=\"2.0\"><channel><title>Title</title></channel></rss>";
+    /// let channel = input.parse::<Channel>().unwrap();
+    ///
+    /// let output = format!("{}", channel);
+    /// assert!(output.contains("<title>Title</title>"));
+    /// assert_eq!(output, channel.try_to_string().unwrap());
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.try_to_string().unwrap_or_default())
+    }
+}
 
-        // find opening element
-        loop {
-            match reader.read_event(&mut buf)? {
-                Event::Start(element) => {
-                    if element.name() == b"rss" || element.name() == b"rdf:RDF" {
-                        for attr in element.attributes().with_checks(false) {
-                            if let Ok(attr) = attr {
-
-                                if !attr.key.starts_with(b"xmlns:") ||
-                                    attr.key == b"xmlns:itunes" ||
-                                    attr.key == b"xmlns:dc"
-                                {
-                                    continue;
+impl Channel {
+    /// Parse a child element of `<channel>` into this channel.
+    pub(crate) fn read_child<R: BufRead>(
+        &mut self,
+        reader: &mut Reader<R>,
+        element: &BytesStart,
+        skip_buf: &mut Vec<u8>,
+        context: &mut ParseContext,
+    ) -> Result<(), Error> {
+        context.check_length(element.len())?;
+
+        let name = element.name();
+        match context.canonical_name(name, ELEMENTS) {
+            b"category" => {
+                let position = reader.buffer_position();
+                let attributes = context.normalize_attributes(position, element, &[b"domain"]);
+                let category =
+                    Category::from_xml_with_context(reader, attributes.attributes(), context)?;
+                self.categories.push(category);
+            }
+            b"cloud" => {
+                let keys = [
+                    b"domain" as &[u8],
+                    b"port",
+                    b"path",
+                    b"registerProcedure",
+                    b"protocol",
+                ];
+                let position = reader.buffer_position();
+                let attributes = context.normalize_attributes(position, element, &keys);
+                let cloud = Cloud::from_xml_with_context(reader, attributes.attributes(), context)?;
+                if context.is_lenient() && cloud.port_u16().is_none() {
+                    let message = format!("cloud port is not a port number: {}", cloud.port());
+                    context.warn(reader.buffer_position(), message);
+                }
+                self.cloud = Some(cloud);
+            }
+            b"image" => {
+                let image = Image::from_xml(reader, element.attributes())?;
+                self.image = Some(image);
+            }
+            b"textInput" => {
+                let text_input = TextInput::from_xml(reader, element.attributes())?;
+                self.text_input = Some(text_input);
+            }
+            b"textinput" => {
+                // an RSS 1.0 channel only refers to its text input, which follows the channel
+                let text_input = TextInput::from_xml(reader, element.attributes())?;
+                if text_input != TextInput::default() {
+                    self.text_input = Some(text_input);
+                }
+            }
+            b"item" => {
+                context.check_items(self.items.len() + 1)?;
+                let item = Item::from_xml_with_context(reader, element.attributes(), context)?;
+                self.items.push(item);
+            }
+            b"title" => {
+                if !context.skip_duplicate(reader, name, !self.title.is_empty())? {
+                    if let Some(content) = element_text_with_context(reader, context)? {
+                        self.title = content;
+                    }
+                }
+            }
+            b"link" => {
+                if !context.skip_duplicate(reader, name, !self.link.is_empty())? {
+                    if let Some(content) = element_text_with_context(reader, context)? {
+                        self.link = content;
+                    }
+                }
+            }
+            b"description" => {
+                let present = !self.description.is_empty();
+                if !context.skip_duplicate(reader, name, present)? {
+                    if let Some(content) = element_text_with_context(reader, context)? {
+                        self.description = content;
+                    }
+                }
+            }
+            b"language" => context.read_text(reader, name, &mut self.language)?,
+            b"copyright" => context.read_text(reader, name, &mut self.copyright)?,
+            b"managingEditor" => context.read_text(reader, name, &mut self.managing_editor)?,
+            b"webMaster" => context.read_text(reader, name, &mut self.webmaster)?,
+            b"pubDate" => context.read_text(reader, name, &mut self.pub_date)?,
+            b"lastBuildDate" => context.read_text(reader, name, &mut self.last_build_date)?,
+            b"generator" => context.read_text(reader, name, &mut self.generator)?,
+            b"rating" => context.read_text(reader, name, &mut self.rating)?,
+            b"docs" => context.read_text(reader, name, &mut self.docs)?,
+            b"ttl" => context.read_text(reader, name, &mut self.ttl)?,
+            b"skipHours" => {
+                loop {
+                    skip_buf.clear();
+                    match reader.read_event(skip_buf)? {
+                        Event::Start(element) => {
+                            if context.canonical_name(element.name(), &[b"hour"]) == b"hour" {
+                                if let Some(content) = element_text(reader)? {
+                                    self.skip_hours.push(content);
                                 }
-
-                                let key = str::from_utf8(&attr.key[6..])?.to_string();
-                                let value = attr.unescape_and_decode_value(&reader)?;
-                                namespaces.insert(key, value);
+                            } else {
+                                reader.read_to_end(element.name(), &mut Vec::new())?;
                             }
                         }
-
-                        break;
-                    } else {
-                        return Err(Error::InvalidStartTag);
+                        Event::End(_) | Event::Eof => break,
+                        _ => {}
                     }
                 }
-                Event::Eof => return Err(Error::Eof),
-                _ => continue,
             }
-        }
-
-        loop {
-            match reader.read_event(&mut buf)? {
-                Event::Start(element) => {
-                    match element.name() {
-                        b"channel" => {
-                            let inner = Channel::from_xml(&mut reader, element.attributes())?;
-                            channel = Some(inner);
-                        }
-                        b"item" => {
-                            let item = Item::from_xml(&mut reader, element.attributes())?;
-                            if items.is_none() {
-                                items = Some(Vec::new());
+            b"skipDays" => {
+                loop {
+                    skip_buf.clear();
+                    match reader.read_event(skip_buf)? {
+                        Event::Start(element) => {
+                            if context.canonical_name(element.name(), &[b"day"]) == b"day" {
+                                if let Some(content) = element_text(reader)? {
+                                    self.skip_days.push(content);
+                                }
+                            } else {
+                                reader.read_to_end(element.name(), &mut Vec::new())?;
                             }
-                            items.as_mut().unwrap().push(item);
-                        }
-                        b"image" => {
-                            let inner = Image::from_xml(&mut reader, element.attributes())?;
-                            image = Some(inner);
-                        }
-                        b"textinput" => {
-                            let inner = TextInput::from_xml(&mut reader, element.attributes())?;
-                            text_input = Some(inner);
                         }
-                        name => reader.read_to_end(name, &mut skip_buf)?,
+                        Event::End(_) | Event::Eof => break,
+                        _ => {}
                     }
                 }
-                Event::End(_) | Event::Eof => break,
-                _ => {}
             }
-            buf.clear();
+            n => {
+                if let Some((ns, name)) = extension_name(n) {
+                    let extensions = &mut self.extensions;
+                    parse_extension(reader, element, ns, name, extensions, context, 3)?;
+                } else {
+                    let element = parse_extension_element(reader, element, context, 3)?;
+                    self.unknown_elements.push(element);
+                }
+            }
         }
 
-        if let Some(mut channel) = channel {
-            if let Some(mut items) = items {
-                channel.items.append(&mut items);
-            }
+        Ok(())
+    }
 
-            if image.is_some() {
-                channel.image = image;
+    /// Move the elements of namespaces with a registered handler into the custom extensions,
+    /// and then extract the Podcasting 2.0, syndication module, Atom link, iTunes and Dublin Core
+    /// extensions.
+    ///
+    /// The Podcasting 2.0, syndication module and Atom link extensions are only extracted if
+    /// their namespaces were declared.
+    pub(crate) fn extract_all_extensions(&mut self, context: &ParseContext) -> Result<(), Error> {
+        context.read_custom_extensions(&mut self.extensions, &mut self.custom_extensions)?;
+
+        // a namespace may be declared with several prefixes, and the elements of every one of
+        // them are extracted
+        for prefix in context.namespace_prefixes(podcast::NAMESPACE) {
+            if let Some(mut map) = self.extensions.remove(prefix) {
+                self.podcast_ext
+                    .get_or_insert_with(PodcastChannelExtension::default)
+                    .read_map(&mut map);
+                if !map.is_empty() {
+                    self.extensions.insert(prefix.to_string(), map);
+                }
             }
+        }
 
-            if text_input.is_some() {
-                channel.text_input = text_input;
+        for prefix in context.namespace_prefixes(syndication::NAMESPACE) {
+            if let Some(mut map) = self.extensions.remove(prefix) {
+                self.syndication_ext
+                    .get_or_insert_with(SyndicationExtension::default)
+                    .read_map(&mut map);
+                if !map.is_empty() {
+                    self.extensions.insert(prefix.to_string(), map);
+                }
             }
-
-            channel.namespaces = namespaces;
-
-            Ok(channel)
-        } else {
-            Err(Error::Eof)
         }
-    }
-
-    /// Attempt to write the RSS channel as XML to a writer.
-    ///
-    /// # Example
-    ///
-    /// ```rust,ignore
-    /// let channel: Channel = ...;
-    /// let writer: Write = ...;
-    /// channel.write_to(writer).unwrap();
-    /// ```
-    pub fn write_to<W: Write>(&self, writer: W) -> Result<W, Error> {
-        let mut writer = ::quick_xml::Writer::new(writer);
-
-        let name = b"rss";
-        let mut element = BytesStart::borrowed(name, name.len());
-        element.push_attribute(("version", "2.0"));
 
-        let mut itunes_ns = self.itunes_ext.is_some();
-        let mut dc_ns = self.dublin_core_ext.is_some();
-
-        if !itunes_ns || !dc_ns {
-            for item in &self.items {
-                if !itunes_ns {
-                    itunes_ns = item.itunes_ext().is_some();
-                }
+        for prefix in context.namespace_prefixes(ATOM_NAMESPACE) {
+            self.extract_atom_links(prefix);
+        }
 
-                if !dc_ns {
-                    dc_ns = item.dublin_core_ext().is_some();
-                }
+        self.extract_extensions()
+    }
 
-                if itunes_ns && dc_ns {
-                    break;
-                }
+    /// Move the `<atom:link>` elements with the given prefix out of the extension map, leaving
+    /// those that a `Link` cannot represent.
+    fn extract_atom_links(&mut self, prefix: &str) {
+        let links = match self.extensions.get_mut(prefix).and_then(|map| map.remove("link")) {
+            Some(links) => links,
+            None => return,
+        };
+
+        let mut kept = Vec::new();
+        for extension in links {
+            match Link::from_atom_extension(&extension) {
+                Some(link) => self.atom_links.push(link),
+                None => kept.push(extension),
             }
         }
 
-        if itunes_ns {
-            element.push_attribute(("xmlns:itunes", extension::itunes::NAMESPACE));
+        let map = self.extensions.get_mut(prefix).expect("the links were in the map");
+        if !kept.is_empty() {
+            map.insert("link".to_string(), kept);
         }
-
-        if dc_ns {
-            element.push_attribute(("xmlns:dc", extension::dublincore::NAMESPACE));
-        }
-        for (name, url) in &self.namespaces {
-            element.push_attribute((format!("xmlns:{}", &**name).as_bytes(), url.as_bytes()));
+        if map.is_empty() {
+            self.extensions.remove(prefix);
         }
+    }
 
-        writer.write_event(Event::Start(element))?;
-
-        self.to_xml(&mut writer)?;
-
-        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+    /// Move the iTunes and Dublin Core elements out of the extension map and into their
+    /// dedicated extension structs.
+    ///
+    /// Elements that the extension structs do not support are left in the extension map so
+    /// that they are written back out. Elements found after an extension has already been
+    /// extracted are added to it.
+    pub(crate) fn extract_extensions(&mut self) -> Result<(), Error> {
+        if !self.extensions.is_empty() {
+            if let Some(mut map) = self.extensions.remove("itunes") {
+                self.itunes_ext
+                    .get_or_insert_with(ITunesChannelExtension::default)
+                    .read_map(&mut map)?;
+                if !map.is_empty() {
+                    self.extensions.insert("itunes".to_string(), map);
+                }
+            }
 
-        Ok(writer.into_inner())
-    }
-}
+            if let Some(mut map) = self.extensions.remove("dc") {
+                self.dublin_core_ext
+                    .get_or_insert_with(DublinCoreExtension::default)
+                    .read_map(&mut map);
+                if !map.is_empty() {
+                    self.extensions.insert("dc".to_string(), map);
+                }
+            }
+        }
 
-impl ToString for Channel {
-    fn to_string(&self) -> String {
-        let buf = self.write_to(Vec::new()).unwrap_or_default();
-        // this unwrap should be safe since the bytes written from the Channel are all valid utf8
-        String::from_utf8(buf).unwrap()
+        Ok(())
     }
 }
 
 impl FromXml for Channel {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, _: Attributes) -> Result<Self, Error> {
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
         let mut channel = Channel::default();
+        let mut context = ParseContext::default();
+        channel.base = xml_base(reader, b"channel", atts, &mut context)?;
         let mut buf = Vec::new();
         let mut skip_buf = Vec::new();
 
         loop {
-            match reader.read_event(&mut buf)? {
+            match reader.read_event(&mut buf).map_err(|err| {
+                Error::from(err).in_element(b"channel", reader.buffer_position())
+            })? {
                 Event::Start(element) => {
-                    match element.name() {
-                        b"category" => {
-                            let category = Category::from_xml(reader, element.attributes())?;
-                            channel.categories.push(category);
-                        }
-                        b"cloud" => {
-                            let cloud = Cloud::from_xml(reader, element.attributes())?;
-                            channel.cloud = Some(cloud);
-                        }
-                        b"image" => {
-                            let image = Image::from_xml(reader, element.attributes())?;
-                            channel.image = Some(image);
-                        }
-                        b"textInput" => {
-                            let text_input = TextInput::from_xml(reader, element.attributes())?;
-                            channel.text_input = Some(text_input);
-                        }
-                        b"item" => {
-                            let item = Item::from_xml(reader, element.attributes())?;
-                            channel.items.push(item);
-                        }
-                        b"title" => {
-                            if let Some(content) = element_text(reader)? {
-                                channel.title = content;
-                            }
-                        }
-                        b"link" => {
-                            if let Some(content) = element_text(reader)? {
-                                channel.link = content;
-                            }
-                        }
-                        b"description" => {
-                            if let Some(content) = element_text(reader)? {
-                                channel.description = content;
-                            }
-                        }
-                        b"language" => channel.language = element_text(reader)?,
-                        b"copyright" => channel.copyright = element_text(reader)?,
-                        b"managingEditor" => {
-                            channel.managing_editor = element_text(reader)?;
-                        }
-                        b"webMaster" => channel.webmaster = element_text(reader)?,
-                        b"pubDate" => channel.pub_date = element_text(reader)?,
-                        b"lastBuildDate" => {
-                            channel.last_build_date = element_text(reader)?;
-                        }
-                        b"generator" => channel.generator = element_text(reader)?,
-                        b"rating" => channel.rating = element_text(reader)?,
-                        b"docs" => channel.docs = element_text(reader)?,
-                        b"ttl" => channel.ttl = element_text(reader)?,
-                        b"skipHours" => {
-                            loop {
-                                skip_buf.clear();
-                                match reader.read_event(&mut skip_buf)? {
-                                    Event::Start(element) => {
-                                        if element.name() == b"hour" {
-                                            if let Some(content) = element_text(reader)? {
-                                                channel.skip_hours.push(content);
-                                            }
-                                        } else {
-                                            reader.read_to_end(element.name(), &mut Vec::new())?;
-                                        }
-                                    }
-                                    Event::End(_) | Event::Eof => break,
-                                    _ => {}
-                                }
-                            }
-                        }
-                        b"skipDays" => {
-                            loop {
-                                skip_buf.clear();
-                                match reader.read_event(&mut skip_buf)? {
-                                    Event::Start(element) => {
-                                        if element.name() == b"day" {
-                                            if let Some(content) = element_text(reader)? {
-                                                channel.skip_days.push(content);
-                                            }
-                                        } else {
-                                            reader.read_to_end(element.name(), &mut Vec::new())?;
-                                        }
-                                    }
-                                    Event::End(_) | Event::Eof => break,
-                                    _ => {}
-                                }
-                            }
-                        }
-                        n => {
-                            if let Some((ns, name)) = extension_name(element.name()) {
-                                parse_extension(
-                                    reader,
-                                    element.attributes(),
-                                    ns,
-                                    name,
-                                    &mut channel.extensions,
-                                )?;
-                            } else {
-                                reader.read_to_end(n, &mut skip_buf)?;
-                            }
-                        }
-                    }
+                    channel
+                        .read_child(reader, &element, &mut skip_buf, &mut context)
+                        .map_err(|err| err.in_element(element.name(), reader.buffer_position()))?
                 }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
@@ -1199,26 +3335,32 @@ impl FromXml for Channel {
             buf.clear();
         }
 
-        if !channel.extensions.is_empty() {
-            if let Some(map) = channel.extensions.remove("itunes") {
-                channel.itunes_ext = Some(ITunesChannelExtension::from_map(map)?);
-            }
-
-            if let Some(map) = channel.extensions.remove("dc") {
-                channel.dublin_core_ext = Some(DublinCoreExtension::from_map(map));
-            }
-        }
-
+        channel.extract_extensions()?;
         Ok(channel)
     }
 }
 
 impl ToXml for Channel {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        self.write_channel_start(writer)?;
+        writer.write_objects(&self.items)?;
+        self.write_channel_end(writer)
+    }
+}
+
+impl Channel {
+    /// Write the start tag of the `channel` element and the elements that precede the items.
+    pub(crate) fn write_channel_start<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+    ) -> Result<(), XmlError> {
         let name = b"channel";
+        let mut element = BytesStart::borrowed(name, name.len());
+        if let Some(base) = self.base.as_ref() {
+            element.push_attribute((b"xml:base" as &[u8], &*escape(base.as_bytes())));
+        }
 
-        writer
-            .write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
+        writer.write_event(Event::Start(element))?;
 
         writer.write_text_element(b"title", &self.title)?;
         writer.write_text_element(b"link", &self.link)?;
@@ -1301,7 +3443,18 @@ impl ToXml for Channel {
             writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
         }
 
-        writer.write_objects(&self.items)?;
+        Ok(())
+    }
+
+    /// Write the elements that follow the items and the end tag of the `channel` element.
+    pub(crate) fn write_channel_end<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+    ) -> Result<(), XmlError> {
+        let atom_prefix = self.atom_prefix();
+        for link in &self.atom_links {
+            link.write_atom(writer, atom_prefix)?;
+        }
 
         for map in self.extensions.values() {
             for extensions in map.values() {
@@ -1311,6 +3464,10 @@ impl ToXml for Channel {
             }
         }
 
+        for (_, &(ref prefix, ref value)) in self.custom_extensions.iter() {
+            writer.write_objects(&value.to_extensions(prefix))?;
+        }
+
         if let Some(ext) = self.itunes_ext.as_ref() {
             ext.to_xml(writer)?;
         }
@@ -1319,7 +3476,17 @@ impl ToXml for Channel {
             ext.to_xml(writer)?;
         }
 
-        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        if let Some(ext) = self.podcast_ext.as_ref() {
+            ext.to_xml(writer)?;
+        }
+
+        if let Some(ext) = self.syndication_ext.as_ref() {
+            ext.to_xml(writer)?;
+        }
+
+        writer.write_objects(&self.unknown_elements)?;
+
+        writer.write_event(Event::End(BytesEnd::borrowed(b"channel")))?;
         Ok(())
     }
 }
@@ -1329,6 +3496,75 @@ impl FromStr for Channel {
 
     #[inline]
     fn from_str(s: &str) -> Result<Channel, Error> {
-        Channel::read_from(s.as_bytes())
+        Channel::read_from_slice(s.as_bytes())
+    }
+}
+
+impl FromIterator<Channel> for Channel {
+    /// Merge channels into the first one with `Channel::merge`, keeping every item.
+    ///
+    /// An empty iterator results in a default channel.
+    fn from_iter<I>(iter: I) -> Channel
+    where
+        I: IntoIterator<Item = Channel>,
+    {
+        let mut iter = iter.into_iter();
+        let mut channel = iter.next().unwrap_or_default();
+        for other in iter {
+            channel.merge(other, MergeStrategy::KeepAll);
+        }
+        channel
+    }
+}
+
+/// Write with the given options to a writer that handles the characters that XML does not
+/// allow according to their `InvalidCharPolicy`.
+fn write_filtered<W, F>(writer: W, options: &WriteOptions, write: F) -> Result<W, Error>
+where
+    W: Write,
+    F: FnOnce(&mut Writer<CharFilter<W>>) -> Result<(), Error>,
+{
+    let filter = CharFilter::new(writer, options.invalid_char_policy());
+    let mut writer = Writer::with_options(filter, options);
+    let result = write(&mut writer);
+    let filter = writer.into_inner();
+
+    match (result, filter.invalid()) {
+        (Ok(()), _) => Ok(filter.into_inner()),
+        (Err(_), Some(c)) => Err(Error::InvalidCharacter(c)),
+        (Err(err), None) => Err(err),
+    }
+}
+
+/// Remove the categories that have the same domain and name as an earlier category.
+fn dedup_categories(categories: &mut Vec<Category>) {
+    let mut seen = HashSet::new();
+    categories.retain(|category| seen.insert(category.dedup_key()));
+}
+
+#[cfg(feature = "chrono")]
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    match day.trim().to_lowercase().as_str() {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
     }
 }
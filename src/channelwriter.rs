@@ -0,0 +1,191 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use quick_xml::errors::Error as XmlError;
+use quick_xml::events::{Event, BytesEnd};
+
+use channel::Channel;
+use error::Error;
+use extension::util::declares_prefix;
+use item::Item;
+use toxml::{CharFilter, ToXml, Writer, WriterExt};
+use writeoptions::{InvalidCharPolicy, Version};
+
+/// A writer that writes an RSS channel one item at a time.
+///
+/// The `rss` element and the channel metadata are written when the writer is created, followed by
+/// the items of the metadata channel, if any. Further items are written as they are passed to
+/// `write_item`, so only a single item needs to be held in memory at a time. `finish` writes the
+/// channel extensions and closes the elements.
+///
+/// The elements are written exactly as `Channel::write_to` writes a channel with the same
+/// metadata and items. No XML declaration is written, as with `write_to`.
+///
+/// The namespaces are declared on the `rss` element before any item is written, so an item may
+/// only use the extension prefixes that the metadata channel declares, either by having an
+/// extension with that prefix itself or through `Channel::add_namespace`.
+///
+/// A writer that is dropped without calling `finish` closes the elements on a best-effort basis,
+/// ignoring any error.
+///
+/// # Examples
+///
+/// ```
+/// use rss::{ChannelBuilder, ChannelWriter, ItemBuilder};
+///
+/// let channel = ChannelBuilder::default()
+///     .title("Title")
+///     .link("http://example.com/")
+///     .description("Description")
+///     .build()
+///     .unwrap();
+///
+/// let mut writer = ChannelWriter::new(Vec::new(), &channel).unwrap();
+/// for title in &["First", "Second"] {
+///     let item = ItemBuilder::default().title(title.to_string()).build().unwrap();
+///     writer.write_item(&item).unwrap();
+/// }
+///
+/// let output = writer.into_inner().unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "<rss version=\"2.0\"><channel><title>Title</title><link>http://example.com/</link>\
+///      <description>Description</description><item><title>First</title></item>\
+///      <item><title>Second</title></item></channel></rss>"
+/// );
+/// ```
+pub struct ChannelWriter<W: Write> {
+    writer: Option<CharFilter<W>>,
+    channel: Channel,
+    prefixes: HashSet<String>,
+    finished: bool,
+}
+
+impl<W: Write> ChannelWriter<W> {
+    /// Create a writer for a channel with the metadata of the given channel and write the
+    /// `rss` element, the channel metadata and the items of the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UndeclaredNamespace` if an extension of the channel uses a prefix that has
+    /// not been declared.
+    pub fn new(writer: W, channel: &Channel) -> Result<Self, Error> {
+        let mut filter = CharFilter::new(writer, InvalidCharPolicy::Strip);
+        let prefixes = {
+            let mut writer = Writer::new(&mut filter);
+            let prefixes = channel.write_rss_start(&mut writer, Version::Rss2_0)?;
+            channel.write_channel_start(&mut writer)?;
+            writer.write_objects(channel.items())?;
+            prefixes
+        };
+
+        let mut metadata = channel.clone();
+        metadata.set_items(Vec::new());
+
+        Ok(ChannelWriter {
+            writer: Some(filter),
+            channel: metadata,
+            prefixes: prefixes,
+            finished: false,
+        })
+    }
+
+    /// Write an item of the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::WriterFinished` if `finish` has already been called, and
+    /// `Error::UndeclaredNamespace` if the item uses an extension prefix that was not declared
+    /// when the writer was created.
+    pub fn write_item(&mut self, item: &Item) -> Result<(), Error> {
+        if self.finished {
+            return Err(Error::WriterFinished);
+        }
+
+        for prefix in item_prefixes(item) {
+            if !self.prefixes.contains(prefix) {
+                return Err(Error::UndeclaredNamespace(prefix.to_string()));
+            }
+        }
+
+        let filter = self.writer.as_mut().expect("writer is present until into_inner");
+        item.to_xml(&mut Writer::new(filter))?;
+        Ok(())
+    }
+
+    /// Write the channel extensions, close the `channel` and `rss` elements and flush the
+    /// underlying writer.
+    ///
+    /// Calling `finish` again has no effect.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        let filter = self.writer.as_mut().expect("writer is present until into_inner");
+        {
+            let mut writer = Writer::new(&mut *filter);
+            self.channel.write_channel_end(&mut writer)?;
+            writer.write_event(Event::End(BytesEnd::borrowed(b"rss")))?;
+        }
+        filter.flush().map_err(XmlError::from)?;
+        Ok(())
+    }
+
+    /// Finish the channel and return the underlying writer.
+    pub fn into_inner(mut self) -> Result<W, Error> {
+        self.finish()?;
+        let filter = self.writer.take().expect("writer is present until into_inner");
+        Ok(filter.into_inner())
+    }
+}
+
+impl<W: Write> Drop for ChannelWriter<W> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.finish();
+        }
+    }
+}
+
+/// Return the namespace prefixes of the extensions of an item.
+///
+/// The `<atom:link>` elements of an item are kept with its extensions, so their prefix is one
+/// of the prefixes of the extension map.
+fn item_prefixes(item: &Item) -> Vec<&str> {
+    let mut prefixes = item.extensions()
+        .iter()
+        .filter(|&(prefix, extensions)| !declares_prefix(prefix, extensions))
+        .map(|(prefix, _)| prefix.as_str())
+        .chain(item.custom_extensions().iter().map(|(_, &(ref prefix, _))| prefix.as_str()))
+        .collect::<Vec<_>>();
+
+    if item.itunes_ext().is_some() {
+        prefixes.push("itunes");
+    }
+    if item.dublin_core_ext().is_some() {
+        prefixes.push("dc");
+    }
+    if item.media_ext().is_some() {
+        prefixes.push("media");
+    }
+    if item.content().is_some() {
+        prefixes.push("content");
+    }
+    if item.podcast_ext().is_some() {
+        prefixes.push("podcast");
+    }
+    if item.slash_ext().is_some() {
+        prefixes.push("slash");
+    }
+
+    prefixes
+}
@@ -5,21 +5,50 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
+use std::fmt;
 use std::io::{BufRead, Write};
+use std::str::FromStr;
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::ToXml;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer, escape};
+use util::{attr_values, skip_element};
 
 /// Represents a cloud in an RSS feed.
+///
+/// A cloud that is missing any of its attributes cannot be registered with, so
+/// `CloudBuilder::build` returns an error unless the domain, port, path and protocol are
+/// non-empty and the register procedure has been set. The register procedure may be empty, as
+/// it is for the `http-post` and `soap` protocols.
+///
+/// # Examples
+///
+/// ```
+/// use rss::{CloudBuilder, CloudProtocol};
+///
+/// let cloud = CloudBuilder::default()
+///     .domain("rpc.example.com")
+///     .port("80")
+///     .path("/RPC2")
+///     .register_procedure("pingMe")
+///     .protocol(CloudProtocol::XmlRpc)
+///     .build()
+///     .unwrap();
+/// assert_eq!(cloud.protocol(), "xml-rpc");
+///
+/// let result = CloudBuilder::default().domain("rpc.example.com").build();
+/// assert_eq!(result.unwrap_err(), "the cloud port is missing");
+/// ```
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
-#[builder(setter(into), default)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default, build_fn(validate = "Self::validate"))]
 pub struct Cloud {
     /// The domain to register with.
     domain: String,
@@ -98,6 +127,41 @@ impl Cloud {
         self.port = port.into();
     }
 
+    /// Return the port for this cloud as a number.
+    ///
+    /// `None` is returned if the port is not a valid port number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Cloud;
+    ///
+    /// let mut cloud = Cloud::default();
+    /// cloud.set_port("80");
+    /// assert_eq!(cloud.port_u16(), Some(80));
+    ///
+    /// cloud.set_port("http");
+    /// assert_eq!(cloud.port_u16(), None);
+    /// ```
+    pub fn port_u16(&self) -> Option<u16> {
+        self.port.trim().parse().ok()
+    }
+
+    /// Set the port for this cloud as a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Cloud;
+    ///
+    /// let mut cloud = Cloud::default();
+    /// cloud.set_port_u16(80);
+    /// assert_eq!(cloud.port(), "80");
+    /// ```
+    pub fn set_port_u16(&mut self, port: u16) {
+        self.port = port.to_string();
+    }
+
     /// Return the path for this cloud.
     ///
     /// # Examples
@@ -193,36 +257,144 @@ impl Cloud {
     {
         self.protocol = protocol.into();
     }
+
+    /// Return the protocol for this cloud if it is one of the known protocols.
+    ///
+    /// `None` is returned for any other value, which is still available from `protocol`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Cloud, CloudProtocol};
+    ///
+    /// let mut cloud = Cloud::default();
+    /// cloud.set_protocol("xml-rpc");
+    /// assert_eq!(cloud.protocol_parsed(), Some(CloudProtocol::XmlRpc));
+    ///
+    /// cloud.set_protocol(CloudProtocol::HttpPost);
+    /// assert_eq!(cloud.protocol(), "http-post");
+    ///
+    /// cloud.set_protocol("carrier-pigeon");
+    /// assert_eq!(cloud.protocol_parsed(), None);
+    /// ```
+    pub fn protocol_parsed(&self) -> Option<CloudProtocol> {
+        self.protocol.parse().ok()
+    }
 }
 
-impl FromXml for Cloud {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, mut atts: Attributes) -> Result<Self, Error> {
-        let mut cloud = Cloud::default();
+/// The protocol used to register with a cloud.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CloudProtocol {
+    /// XML-RPC, written as `xml-rpc`.
+    XmlRpc,
+    /// SOAP 1.1, written as `soap`.
+    Soap,
+    /// An HTTP POST request, written as `http-post`.
+    HttpPost,
+}
+
+impl CloudProtocol {
+    /// Return the value that is written for this protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::CloudProtocol;
+    ///
+    /// assert_eq!(CloudProtocol::XmlRpc.as_str(), "xml-rpc");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            CloudProtocol::XmlRpc => "xml-rpc",
+            CloudProtocol::Soap => "soap",
+            CloudProtocol::HttpPost => "http-post",
+        }
+    }
+}
+
+impl FromStr for CloudProtocol {
+    type Err = ();
+
+    /// Parse one of the known protocols, ignoring case.
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("xml-rpc") {
+            Ok(CloudProtocol::XmlRpc)
+        } else if s.eq_ignore_ascii_case("soap") {
+            Ok(CloudProtocol::Soap)
+        } else if s.eq_ignore_ascii_case("http-post") {
+            Ok(CloudProtocol::HttpPost)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl fmt::Display for CloudProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-        for attr in atts.with_checks(false) {
-            if let Ok(att) = attr {
-                match att.key {
-                    b"domain" => {
-                        cloud.domain = att.unescape_and_decode_value(reader)?;
-                    }
-                    b"port" => {
-                        cloud.port = att.unescape_and_decode_value(reader)?;
-                    }
-                    b"path" => {
-                        cloud.path = att.unescape_and_decode_value(reader)?;
-                    }
-                    b"registerProcedure" => {
-                        cloud.register_procedure = att.unescape_and_decode_value(reader)?;
-                    }
-                    b"protocol" => {
-                        cloud.protocol = att.unescape_and_decode_value(reader)?;
-                    }
-                    _ => {}
-                }
+impl From<CloudProtocol> for String {
+    fn from(protocol: CloudProtocol) -> String {
+        protocol.as_str().to_string()
+    }
+}
+
+impl CloudBuilder {
+    /// Check that every attribute of the cloud has been given a value.
+    fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("domain", &self.domain),
+            ("port", &self.port),
+            ("path", &self.path),
+            ("protocol", &self.protocol),
+        ];
+
+        for &(name, value) in &fields {
+            if value.as_ref().map_or(true, |value| value.is_empty()) {
+                return Err(format!("the cloud {} is missing", name));
             }
         }
 
-        reader.read_to_end(b"cloud", &mut Vec::new())?;
+        if self.register_procedure.is_none() {
+            return Err("the cloud registerProcedure is missing".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl FromXml for Cloud {
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Cloud::from_xml_with_context(reader, atts, &mut ParseContext::default())
+    }
+}
+
+impl Cloud {
+    /// Parse a `<cloud>` element, decoding its attributes as the context requires.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
+        let mut cloud = Cloud::default();
+        let keys = [
+            b"domain" as &[u8],
+            b"port",
+            b"path",
+            b"registerProcedure",
+            b"protocol",
+        ];
+        let mut attrs = attr_values(reader, b"cloud", atts, &keys, context)?;
+        cloud.domain = attrs.take(b"domain").unwrap_or_default();
+        cloud.port = attrs.take(b"port").unwrap_or_default();
+        cloud.path = attrs.take(b"path").unwrap_or_default();
+        cloud.register_procedure = attrs.take(b"registerProcedure").unwrap_or_default();
+        cloud.protocol = attrs.take(b"protocol").unwrap_or_default();
+
+        skip_element(reader)?;
 
         Ok(cloud)
     }
@@ -234,13 +406,13 @@ impl ToXml for Cloud {
         let mut element = BytesStart::borrowed(name, name.len());
 
         let attrs = &[
-            (b"domain" as &[u8], self.domain.as_bytes()),
-            (b"port", self.port.as_bytes()),
-            (b"path", self.path.as_bytes()),
-            (b"registerProcedure", self.register_procedure.as_bytes()),
-            (b"protocol", self.protocol.as_bytes()),
+            (b"domain" as &[u8], escape(self.domain.as_bytes())),
+            (b"port", escape(self.port.as_bytes())),
+            (b"path", escape(self.path.as_bytes())),
+            (b"registerProcedure", escape(self.register_procedure.as_bytes())),
+            (b"protocol", escape(self.protocol.as_bytes())),
         ];
-        element.extend_attributes(attrs.into_iter().cloned());
+        element.extend_attributes(attrs.iter().map(|&(name, ref value)| (name, &**value)));
 
         writer.write_event(Event::Empty(element))?;
         Ok(())
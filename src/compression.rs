@@ -0,0 +1,161 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::io::{self, BufRead, Read};
+
+use flate2::bufread::{GzDecoder, ZlibDecoder};
+use quick_xml::errors::Error as XmlError;
+
+use channel::Channel;
+use error::{Error, LimitKind};
+use parseoptions::{ParseOptions, ParseWarning};
+
+/// A compression format that can be detected from the first bytes of the input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compression {
+    Gzip,
+    Zlib,
+}
+
+impl Compression {
+    /// Detect the compression format from the first bytes of the input.
+    fn detect(header: &[u8]) -> Option<Compression> {
+        match *header {
+            [0x1f, 0x8b, ..] => Some(Compression::Gzip),
+            // a zlib header uses the deflate method with a window of at most 32 KiB, and is a
+            // multiple of 31 when read as a big-endian number
+            [cmf, flg, ..] if cmf & 0x0f == 8 && cmf >> 4 <= 7 &&
+                (u16::from(cmf) << 8 | u16::from(flg)) % 31 == 0 => Some(Compression::Zlib),
+            _ => None,
+        }
+    }
+}
+
+impl Channel {
+    /// Attempt to read an RSS channel from a reader whose input may be compressed with gzip
+    /// or zlib.
+    ///
+    /// The compression is detected from the first bytes of the input rather than from any
+    /// header, so servers that send compressed input without saying so are handled too. Input
+    /// that is not compressed is read exactly as with `read_from`.
+    ///
+    /// Compressed input is decompressed with the default `ParseOptions::max_decompressed_size`
+    /// before it is parsed. Use `read_from_maybe_compressed_with_options` to choose the limit.
+    ///
+    /// **Note**: This requires enabling the `compression` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Decompression` if the input cannot be decompressed, and
+    /// `Error::LimitExceeded` if it decompresses to more bytes than the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let reader: BufRead = ...;
+    /// let channel = Channel::read_from_maybe_compressed(reader).unwrap();
+    /// ```
+    pub fn read_from_maybe_compressed<R: BufRead>(reader: R) -> Result<Channel, Error> {
+        let options = ParseOptions::default();
+        Channel::read_from_maybe_compressed_with_options(reader, &options)
+            .map(|(channel, _)| channel)
+    }
+
+    /// Attempt to read an RSS channel from a reader whose input may be compressed with gzip or
+    /// zlib, using the given options.
+    ///
+    /// This is `read_from_maybe_compressed` with the options of `read_from_with_options`.
+    /// Compressed input that decompresses to more than `options.max_decompressed_size()` bytes
+    /// is rejected with `Error::LimitExceeded` without being decompressed any further.
+    ///
+    /// **Note**: This requires enabling the `compression` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let reader: BufRead = ...;
+    /// let options = ParseOptionsBuilder::default()
+    ///     .max_decompressed_size(1024 * 1024)
+    ///     .build()
+    ///     .unwrap();
+    /// let (channel, warnings) =
+    ///     Channel::read_from_maybe_compressed_with_options(reader, &options).unwrap();
+    /// ```
+    pub fn read_from_maybe_compressed_with_options<R: BufRead>(
+        mut reader: R,
+        options: &ParseOptions,
+    ) -> Result<(Channel, Vec<ParseWarning>), Error> {
+        // the input is only peeked at when enough of it is buffered, so that input that is not
+        // compressed is read unchanged
+        if reader.fill_buf().map_err(XmlError::from)?.len() >= 2 {
+            let compression = Compression::detect(reader.fill_buf().map_err(XmlError::from)?);
+            return read_decompressed(reader, compression, options);
+        }
+
+        // otherwise the first bytes are read and put back in front of the rest of the input
+        let mut header = [0; 2];
+        let len = read_header(&mut reader, &mut header).map_err(XmlError::from)?;
+        let compression = Compression::detect(&header[..len]);
+        read_decompressed((&header[..len]).chain(reader), compression, options)
+    }
+}
+
+/// Read a channel from input compressed with the given format, if any.
+fn read_decompressed<R: BufRead>(
+    reader: R,
+    compression: Option<Compression>,
+    options: &ParseOptions,
+) -> Result<(Channel, Vec<ParseWarning>), Error> {
+    let limit = options.max_decompressed_size();
+
+    match compression {
+        Some(Compression::Gzip) => {
+            let input = decompress(GzDecoder::new(reader), limit)?;
+            Channel::read_from_with_options(&input[..], options)
+        }
+        Some(Compression::Zlib) => {
+            let input = decompress(ZlibDecoder::new(reader), limit)?;
+            Channel::read_from_with_options(&input[..], options)
+        }
+        None => Channel::read_from_with_options(reader, options),
+    }
+}
+
+/// Fill `header` with the first bytes of the input, however few bytes each read returns.
+///
+/// Return the number of bytes that were read, which is less than the length of `header` only at
+/// the end of the input.
+fn read_header<R: Read>(reader: &mut R, header: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < header.len() {
+        match reader.read(&mut header[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(len)
+}
+
+/// Read all of the output of a decoder, failing as soon as it is longer than `limit` bytes.
+fn decompress<D: Read>(decoder: D, limit: usize) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::new();
+    decoder
+        .take(limit as u64 + 1)
+        .read_to_end(&mut output)
+        .map_err(Error::Decompression)?;
+
+    if output.len() > limit {
+        return Err(Error::LimitExceeded {
+            kind: LimitKind::DecompressedSize,
+            limit: limit,
+        });
+    }
+
+    Ok(output)
+}
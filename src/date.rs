@@ -0,0 +1,190 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
+
+/// Parse an RFC 822 timestamp, tolerating the deviations commonly found in real-world feeds.
+pub fn parse_rfc822(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date);
+    }
+
+    parse_lenient(value)
+}
+
+/// Parse a W3C date and time, the profile of ISO 8601 used by RSS 1.0 modules.
+///
+/// The month, day and time are optional, and a date without a time is midnight UTC. Fractional
+/// seconds are discarded.
+pub fn parse_w3cdtf(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    let (date, time) = match value.find(|c| c == 'T' || c == 't') {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+
+    let mut parts = date.split('-');
+    let year = parts.next()?;
+    if year.len() != 4 || !year.chars().all(|c| c.is_digit(10)) {
+        return None;
+    }
+    let year = year.parse::<i32>().ok()?;
+    let month = match parts.next() {
+        Some(month) => month.parse::<u32>().ok()?,
+        None => 1,
+    };
+    let day = match parts.next() {
+        Some(day) => day.parse::<u32>().ok()?,
+        None => 1,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let (hour, minute, second, offset) = match time {
+        Some(time) => {
+            // the zone is required when there is a time
+            let index = time.find(|c| c == 'Z' || c == 'z' || c == '+' || c == '-')?;
+            let (hour, minute, second) = parse_time(&time[..index])?;
+            (hour, minute, second, parse_zone(&time[index..])?)
+        }
+        None => (0, 0, 0, FixedOffset::east_opt(0)?),
+    };
+
+    let date = NaiveDate::from_ymd_opt(year, month, day)?
+        .and_hms_opt(hour, minute, second)?;
+
+    offset.from_local_datetime(&date).single()
+}
+
+fn parse_lenient(value: &str) -> Option<DateTime<FixedOffset>> {
+    // the day of the week is redundant, frequently wrong and sometimes localized, so it is ignored
+    let value = match value.find(',') {
+        Some(index) => &value[index + 1..],
+        None => value,
+    };
+
+    let mut tokens = value.split_whitespace();
+
+    let mut token = tokens.next()?;
+    if token.chars().all(|c| c.is_alphabetic()) {
+        token = tokens.next()?;
+    }
+
+    let day = token.parse::<u32>().ok()?;
+    let month = parse_month(tokens.next()?)?;
+    let year = parse_year(tokens.next()?)?;
+    let (hour, minute, second) = parse_time(tokens.next()?)?;
+
+    // a missing zone is assumed to be UTC
+    let offset = match tokens.next() {
+        Some(zone) => parse_zone(zone)?,
+        None => FixedOffset::east_opt(0)?,
+    };
+
+    let date = NaiveDate::from_ymd_opt(year, month, day)?
+        .and_hms_opt(hour, minute, second)?;
+
+    offset.from_local_datetime(&date).single()
+}
+
+fn parse_month(value: &str) -> Option<u32> {
+    let value = value.to_lowercase();
+    let month = match value.get(..3)? {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+fn parse_year(value: &str) -> Option<i32> {
+    if !value.chars().all(|c| c.is_digit(10)) {
+        return None;
+    }
+
+    let year = value.parse::<i32>().ok()?;
+
+    // two digit years are interpreted as in RFC 2822
+    let year = match value.len() {
+        1 | 2 if year < 50 => year + 2000,
+        1 | 2 | 3 => year + 1900,
+        _ => year,
+    };
+    Some(year)
+}
+
+fn parse_time(value: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = value.split(':');
+    let hour = parts.next()?.parse::<u32>().ok()?;
+    let minute = parts.next()?.parse::<u32>().ok()?;
+    let second = match parts.next() {
+        // fractional seconds are discarded
+        Some(second) => second.split('.').next()?.parse::<u32>().ok()?,
+        None => 0,
+    };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((hour, minute, second))
+}
+
+fn parse_zone(value: &str) -> Option<FixedOffset> {
+    let hours = match value.to_uppercase().as_str() {
+        "GMT" | "UT" | "UTC" | "Z" => 0,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        _ => return parse_military_zone(value).or_else(|| parse_numeric_zone(value)),
+    };
+    FixedOffset::east_opt(hours * 3600)
+}
+
+fn parse_military_zone(value: &str) -> Option<FixedOffset> {
+    let mut chars = value.chars();
+    let letter = chars.next()?.to_ascii_uppercase();
+    if chars.next().is_some() || !letter.is_ascii_alphabetic() || letter == 'J' {
+        return None;
+    }
+
+    // the offsets of military zones were defined with the wrong sign in RFC 822, so RFC 2822
+    // treats them all as UTC
+    FixedOffset::east_opt(0)
+}
+
+fn parse_numeric_zone(value: &str) -> Option<FixedOffset> {
+    let sign = match value.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+
+    let digits = value[1..].replace(':', "");
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_digit(10)) {
+        return None;
+    }
+
+    let hours = digits[..2].parse::<i32>().ok()?;
+    let minutes = digits[2..].parse::<i32>().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
@@ -0,0 +1,169 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use channel::Channel;
+use item::{DedupKey, Item};
+
+/// The differences between the items of a channel and those of a previous version of it.
+///
+/// See `Channel::diff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelDiff<'a> {
+    /// The items that are new.
+    added: Vec<&'a Item>,
+    /// The items that are no longer present.
+    removed: Vec<&'a Item>,
+    /// The previous and current versions of the items that changed.
+    changed: Vec<(&'a Item, &'a Item)>,
+}
+
+impl<'a> ChannelDiff<'a> {
+    /// Return the items that are new, in the order they appear in the channel.
+    pub fn added(&self) -> &[&'a Item] {
+        &self.added
+    }
+
+    /// Return the items that are no longer present, in the order they appeared in the previous
+    /// channel.
+    pub fn removed(&self) -> &[&'a Item] {
+        &self.removed
+    }
+
+    /// Return the previous and current versions of the items that changed, in the order they
+    /// appear in the channel.
+    pub fn changed(&self) -> &[(&'a Item, &'a Item)] {
+        &self.changed
+    }
+
+    /// Return whether there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Channel {
+    /// Compare the items of this channel with those of a previous version of it.
+    ///
+    /// Items are matched using the same key as `Item::dedup_key`: the GUID, otherwise the link,
+    /// and otherwise the title and publication date. A GUID that is used by more than one item
+    /// within either channel does not identify an item, as happens with some feeds that use
+    /// `isPermaLink="false"` GUIDs, so those items are matched by their link instead. Items
+    /// without any key are matched if they are equal.
+    ///
+    /// A matched item has changed if its title, description, enclosure URL or publication date
+    /// differs. The order of the items is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Item};
+    ///
+    /// let item = |link: &str, title: &str| {
+    ///     let mut item = Item::default();
+    ///     item.set_link(link.to_string());
+    ///     item.set_title(title.to_string());
+    ///     item
+    /// };
+    ///
+    /// let mut previous = Channel::default();
+    /// previous.set_items(vec![item("/1", "One"), item("/2", "Two")]);
+    ///
+    /// let mut channel = Channel::default();
+    /// channel.set_items(vec![item("/3", "Three"), item("/2", "2")]);
+    ///
+    /// let diff = channel.diff(&previous);
+    /// assert_eq!(diff.added()[0].title(), Some("Three"));
+    /// assert_eq!(diff.removed()[0].title(), Some("One"));
+    /// assert_eq!(diff.changed()[0].1.title(), Some("2"));
+    /// ```
+    pub fn diff<'a>(&'a self, previous: &'a Channel) -> ChannelDiff<'a> {
+        let (items, previous) = (self.items(), previous.items());
+
+        // a GUID that is not unique in either channel is not used to match items in both
+        let mut duplicates = duplicate_guids(items);
+        duplicates.extend(duplicate_guids(previous));
+
+        // the indices of the previous items with each key, which are matched in order
+        let mut index = HashMap::new();
+        let mut unkeyed = Vec::new();
+        for (position, key) in diff_keys(previous, &duplicates).into_iter().enumerate() {
+            match key {
+                Some(key) => index.entry(key).or_insert_with(VecDeque::new).push_back(position),
+                None => unkeyed.push(position),
+            }
+        }
+
+        let mut diff = ChannelDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        let mut matched = vec![false; previous.len()];
+
+        for (item, key) in items.iter().zip(diff_keys(items, &duplicates)) {
+            let position = match key {
+                Some(key) => index.get_mut(&key).and_then(|positions| positions.pop_front()),
+                None => unkeyed
+                    .iter()
+                    .position(|&position| previous[position] == *item)
+                    .map(|found| unkeyed.remove(found)),
+            };
+
+            match position {
+                Some(position) => {
+                    matched[position] = true;
+                    if has_changed(&previous[position], item) {
+                        diff.changed.push((&previous[position], item));
+                    }
+                }
+                None => diff.added.push(item),
+            }
+        }
+
+        diff.removed = previous
+            .iter()
+            .zip(matched)
+            .filter(|&(_, matched)| !matched)
+            .map(|(item, _)| item)
+            .collect();
+        diff
+    }
+}
+
+/// Return the GUIDs that are used by more than one of the items.
+fn duplicate_guids(items: &[Item]) -> HashSet<&str> {
+    let mut seen = HashSet::new();
+    items
+        .iter()
+        .filter_map(|item| item.guid())
+        .map(|guid| guid.value())
+        .filter(|guid| !seen.insert(*guid))
+        .collect()
+}
+
+/// Return the key that identifies each item, falling back from the GUIDs that are not unique.
+fn diff_keys<'a>(items: &'a [Item], duplicates: &HashSet<&str>) -> Vec<Option<DedupKey<'a>>> {
+    items
+        .iter()
+        .map(|item| match item.dedup_key() {
+            Some(DedupKey::Guid(guid)) if duplicates.contains(guid) => match item.link() {
+                Some(link) if !link.is_empty() => Some(DedupKey::Link(link)),
+                _ => Some(DedupKey::TitleDate(item.title(), item.pub_date())),
+            },
+            key => key,
+        })
+        .collect()
+}
+
+fn has_changed(previous: &Item, item: &Item) -> bool {
+    previous.title() != item.title() || previous.description() != item.description() ||
+        previous.enclosure().map(|enclosure| enclosure.url()) !=
+            item.enclosure().map(|enclosure| enclosure.url()) ||
+        previous.pub_date() != item.pub_date()
+}
@@ -11,14 +11,17 @@ use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::ToXml;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer, escape};
+use util::{attr_values, skip_element};
 
 /// Represents an enclosure in an RSS item.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Enclosure {
     /// The URL of the enclosure.
@@ -26,6 +29,7 @@ pub struct Enclosure {
     /// The length of the enclosure in bytes.
     length: String,
     /// The MIME type of the enclosure.
+    #[cfg_attr(feature = "serialization", serde(rename = "type"))]
     mime_type: String,
 }
 
@@ -94,6 +98,41 @@ impl Enclosure {
         self.length = length.into();
     }
 
+    /// Return the content length of this enclosure in bytes.
+    ///
+    /// Returns `None` if the length is not a whole number of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Enclosure;
+    ///
+    /// let mut enclosure = Enclosure::default();
+    /// enclosure.set_length("1000");
+    /// assert_eq!(enclosure.length_bytes(), Some(1000));
+    ///
+    /// enclosure.set_length("1 KB");
+    /// assert_eq!(enclosure.length_bytes(), None);
+    /// ```
+    pub fn length_bytes(&self) -> Option<u64> {
+        self.length.trim().parse().ok()
+    }
+
+    /// Set the content length of this enclosure in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Enclosure;
+    ///
+    /// let mut enclosure = Enclosure::default();
+    /// enclosure.set_length_bytes(1000);
+    /// assert_eq!(enclosure.length(), "1000");
+    /// ```
+    pub fn set_length_bytes(&mut self, length: u64) {
+        self.length = length.to_string();
+    }
+
     /// Return the MIME type of this enclosure.
     ///
     /// # Examples
@@ -125,30 +164,130 @@ impl Enclosure {
     {
         self.mime_type = mime_type.into();
     }
+
+    /// Return whether the MIME type of this enclosure is a recognized audio or video type.
+    ///
+    /// The type must be one of the types most podcast directories accept, written in lowercase
+    /// without surrounding whitespace or parameters. Use `normalize_mime` to fix common mistakes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Enclosure;
+    ///
+    /// let mut enclosure = Enclosure::default();
+    /// enclosure.set_mime_type("audio/mpeg");
+    /// assert!(enclosure.is_valid_mime());
+    ///
+    /// enclosure.set_mime_type("audio/mp3");
+    /// assert!(!enclosure.is_valid_mime());
+    /// ```
+    pub fn is_valid_mime(&self) -> bool {
+        MIME_TYPES.contains(&self.mime_type.as_str())
+    }
+
+    /// Fix common mistakes in the MIME type of this enclosure.
+    ///
+    /// A type that is already valid is left untouched. Otherwise surrounding whitespace is
+    /// removed, the type is lowercased and then replaced according to this table:
+    ///
+    /// | Type | Replacement |
+    /// |------|-------------|
+    /// | `audio/mp3`, `audio/mpeg3`, `audio/x-mp3`, `audio/x-mpeg`, `audio/mpg` | `audio/mpeg` |
+    /// | `audio/m4a` | `audio/x-m4a` |
+    /// | `audio/x-wav`, `audio/wave` | `audio/wav` |
+    /// | `audio/x-flac` | `audio/flac` |
+    /// | `video/mov` | `video/quicktime` |
+    /// | `video/m4v` | `video/x-m4v` |
+    /// | `audio`, `audio/` | `audio/mpeg` |
+    /// | `video`, `video/` | `video/mp4` |
+    ///
+    /// Types that are not in the table are only trimmed and lowercased, so they may still not be
+    /// valid afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Enclosure;
+    ///
+    /// let mut enclosure = Enclosure::default();
+    /// enclosure.set_mime_type("Audio/MP3 ");
+    /// enclosure.normalize_mime();
+    /// assert_eq!(enclosure.mime_type(), "audio/mpeg");
+    /// assert!(enclosure.is_valid_mime());
+    /// ```
+    pub fn normalize_mime(&mut self) {
+        if self.is_valid_mime() {
+            return;
+        }
+
+        let mime_type = self.mime_type.trim().to_lowercase();
+        self.mime_type = match MIME_TYPE_FIXES.iter().find(|&&(from, _)| from == mime_type) {
+            Some(&(_, to)) => to.to_string(),
+            None => mime_type,
+        };
+    }
 }
 
+/// The audio and video types recognized by `Enclosure::is_valid_mime`.
+static MIME_TYPES: &'static [&'static str] = &[
+    "audio/aac",
+    "audio/flac",
+    "audio/mp4",
+    "audio/mpeg",
+    "audio/ogg",
+    "audio/opus",
+    "audio/wav",
+    "audio/webm",
+    "audio/x-m4a",
+    "video/mp4",
+    "video/mpeg",
+    "video/ogg",
+    "video/quicktime",
+    "video/webm",
+    "video/x-m4v",
+];
+
+/// The replacements made by `Enclosure::normalize_mime` after trimming and lowercasing.
+static MIME_TYPE_FIXES: &'static [(&'static str, &'static str)] = &[
+    ("audio/mp3", "audio/mpeg"),
+    ("audio/mpeg3", "audio/mpeg"),
+    ("audio/x-mp3", "audio/mpeg"),
+    ("audio/x-mpeg", "audio/mpeg"),
+    ("audio/mpg", "audio/mpeg"),
+    ("audio/m4a", "audio/x-m4a"),
+    ("audio/x-wav", "audio/wav"),
+    ("audio/wave", "audio/wav"),
+    ("audio/x-flac", "audio/flac"),
+    ("video/mov", "video/quicktime"),
+    ("video/m4v", "video/x-m4v"),
+    ("audio", "audio/mpeg"),
+    ("audio/", "audio/mpeg"),
+    ("video", "video/mp4"),
+    ("video/", "video/mp4"),
+];
+
 impl FromXml for Enclosure {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, mut atts: Attributes) -> Result<Self, Error> {
-        let mut enclosure = Enclosure::default();
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Enclosure::from_xml_with_context(reader, atts, &mut ParseContext::default())
+    }
+}
 
-        for attr in atts.with_checks(false) {
-            if let Ok(attr) = attr {
-                match attr.key {
-                    b"url" => {
-                        enclosure.url = attr.unescape_and_decode_value(reader)?;
-                    }
-                    b"length" => {
-                        enclosure.length = attr.unescape_and_decode_value(reader)?;
-                    }
-                    b"type" => {
-                        enclosure.mime_type = attr.unescape_and_decode_value(reader)?;
-                    }
-                    _ => {}
-                }
-            }
-        }
+impl Enclosure {
+    /// Parse an `<enclosure>` element, decoding its attributes as the context requires.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
+        let mut enclosure = Enclosure::default();
+        let keys = [b"url" as &[u8], b"length", b"type"];
+        let mut attrs = attr_values(reader, b"enclosure", atts, &keys, context)?;
+        enclosure.url = attrs.take(b"url").unwrap_or_default();
+        enclosure.length = attrs.take(b"length").unwrap_or_default();
+        enclosure.mime_type = attrs.take(b"type").unwrap_or_default();
 
-        reader.read_to_end(b"enclosure", &mut Vec::new())?;
+        skip_element(reader)?;
 
         Ok(enclosure)
     }
@@ -161,11 +300,11 @@ impl ToXml for Enclosure {
         let mut element = BytesStart::borrowed(name, name.len());
 
         let attrs = &[
-            (b"url" as &[u8], self.url.as_bytes()),
-            (b"length", self.length.as_bytes()),
-            (b"type", self.mime_type.as_bytes()),
+            (b"url" as &[u8], escape(self.url.as_bytes())),
+            (b"length", escape(self.length.as_bytes())),
+            (b"type", escape(self.mime_type.as_bytes())),
         ];
-        element.extend_attributes(attrs.into_iter().cloned());
+        element.extend_attributes(attrs.iter().map(|&(name, ref value)| (name, &**value)));
 
         writer.write_event(Event::Empty(element))?;
         Ok(())
@@ -0,0 +1,130 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::cmp;
+use std::io::{self, BufRead, Read};
+
+use encoding_rs::{Decoder, Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+/// The maximum number of bytes transcoded at a time.
+const CHUNK_SIZE: usize = 8192;
+
+/// A reader that removes a leading byte order mark and transcodes UTF-16 input to UTF-8.
+///
+/// The XML parser only understands encodings that are compatible with ASCII, which the
+/// remaining encodings are decoded from once the declaration has been read.
+pub struct DecodingReader<R> {
+    inner: R,
+    sniffed: bool,
+    decoder: Option<Decoder>,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+impl<R: BufRead> DecodingReader<R> {
+    pub fn new(inner: R) -> Self {
+        DecodingReader {
+            inner: inner,
+            sniffed: false,
+            decoder: None,
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+        }
+    }
+
+    /// Detect the encoding of the input from its byte order mark or its first character.
+    fn sniff(&mut self) -> io::Result<()> {
+        let (encoding, bom_len) = {
+            let bytes = self.inner.fill_buf()?;
+            if bytes.starts_with(b"\xEF\xBB\xBF") {
+                (None, 3)
+            } else if bytes.starts_with(b"\xFF\xFE") {
+                (Some(UTF_16LE), 2)
+            } else if bytes.starts_with(b"\xFE\xFF") {
+                (Some(UTF_16BE), 2)
+            } else if bytes.starts_with(b"<\0") {
+                (Some(UTF_16LE), 0)
+            } else if bytes.starts_with(b"\0<") {
+                (Some(UTF_16BE), 0)
+            } else {
+                (None, 0)
+            }
+        };
+
+        self.inner.consume(bom_len);
+        self.decoder = encoding.map(Encoding::new_decoder_without_bom_handling);
+        self.sniffed = true;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = {
+            let available = self.fill_buf()?;
+            let len = cmp::min(available.len(), buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            len
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for DecodingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if !self.sniffed {
+            self.sniff()?;
+        }
+
+        let decoder = match self.decoder {
+            Some(ref mut decoder) => decoder,
+            None => return self.inner.fill_buf(),
+        };
+
+        while self.pos == self.buf.len() && !self.eof {
+            let read = {
+                let src = self.inner.fill_buf()?;
+                let src = &src[..cmp::min(src.len(), CHUNK_SIZE)];
+                let last = src.is_empty();
+                // the length of a single chunk cannot overflow
+                let capacity = decoder.max_utf8_buffer_length(src.len()).unwrap();
+                self.buf.resize(capacity, 0);
+                let (_, read, written, _) = decoder.decode_to_utf8(src, &mut self.buf, last);
+                self.buf.truncate(written);
+                self.pos = 0;
+                self.eof = last;
+                read
+            };
+            self.inner.consume(read);
+        }
+
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.decoder.is_some() {
+            self.pos = cmp::min(self.pos + amt, self.buf.len());
+        } else {
+            self.inner.consume(amt);
+        }
+    }
+}
+
+/// Return the encoding that text read by the XML parser is in, given the declared encoding.
+///
+/// UTF-16 input is transcoded to UTF-8 by `DecodingReader`, and input declared as UTF-16 that
+/// could be read by the parser must be compatible with ASCII in the first place.
+pub fn parsed_encoding(declared: &'static Encoding) -> &'static Encoding {
+    if declared == UTF_16LE || declared == UTF_16BE {
+        UTF_8
+    } else {
+        declared
+    }
+}
@@ -0,0 +1,2224 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::borrow::Cow;
+use std::char;
+use std::str;
+
+/// The longest name of an HTML named character reference.
+const MAX_NAME_LENGTH: usize = 31;
+
+/// Unescape the character references of text, resolving the HTML named character references as
+/// well as the XML ones.
+///
+/// A reference that cannot be resolved, including an `&` that does not start a reference, is
+/// kept as it is and added to `unknown`.
+pub fn unescape_html<'a>(raw: &'a [u8], unknown: &mut Vec<String>) -> Cow<'a, [u8]> {
+    if !raw.contains(&b'&') {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut text = Vec::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(start) = rest.iter().position(|b| *b == b'&') {
+        text.extend_from_slice(&rest[..start]);
+        rest = &rest[start..];
+
+        // a name ends at the first character that cannot be part of it
+        let end = rest.iter()
+            .skip(1)
+            .take(MAX_NAME_LENGTH + 1)
+            .position(|b| !b.is_ascii_alphanumeric() && *b != b'#')
+            .map(|end| end + 1)
+            .filter(|end| rest[*end] == b';');
+        let resolved = end.and_then(|end| resolve(&rest[1..end]).map(|value| (end, value)));
+
+        match resolved {
+            Some((end, value)) => {
+                let mut buf = [0; 4];
+                match value {
+                    Resolved::Char(c) => text.extend_from_slice(c.encode_utf8(&mut buf).as_bytes()),
+                    Resolved::Str(s) => text.extend_from_slice(s.as_bytes()),
+                }
+                rest = &rest[end + 1..];
+            }
+            None => {
+                let literal = &rest[..end.map_or(1, |end| end + 1)];
+                unknown.push(String::from_utf8_lossy(literal).into_owned());
+                text.push(b'&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    text.extend_from_slice(rest);
+    Cow::Owned(text)
+}
+
+enum Resolved {
+    Char(char),
+    Str(&'static str),
+}
+
+/// Resolve the name of a character reference, without the `&` and `;`.
+fn resolve(name: &[u8]) -> Option<Resolved> {
+    if name.starts_with(b"#x") || name.starts_with(b"#X") {
+        return parse_code(&name[2..], 16);
+    }
+
+    if name.starts_with(b"#") {
+        return parse_code(&name[1..], 10);
+    }
+
+    ENTITIES
+        .binary_search_by(|&(entity, _)| entity.cmp(name))
+        .ok()
+        .map(|index| Resolved::Str(ENTITIES[index].1))
+}
+
+fn parse_code(digits: &[u8], radix: u32) -> Option<Resolved> {
+    if digits.is_empty() || !digits.iter().all(|b| (*b as char).is_digit(radix)) {
+        return None;
+    }
+
+    str::from_utf8(digits)
+        .ok()
+        .and_then(|digits| u32::from_str_radix(digits, radix).ok())
+        .filter(|code| *code != 0)
+        .and_then(char::from_u32)
+        .map(Resolved::Char)
+}
+
+/// The named character references of HTML5, sorted by name.
+static ENTITIES: &'static [(&'static [u8], &'static str)] = &[
+    (b"AElig", "\u{c6}"),
+    (b"AMP", "&"),
+    (b"Aacute", "\u{c1}"),
+    (b"Abreve", "\u{102}"),
+    (b"Acirc", "\u{c2}"),
+    (b"Acy", "\u{410}"),
+    (b"Afr", "\u{1d504}"),
+    (b"Agrave", "\u{c0}"),
+    (b"Alpha", "\u{391}"),
+    (b"Amacr", "\u{100}"),
+    (b"And", "\u{2a53}"),
+    (b"Aogon", "\u{104}"),
+    (b"Aopf", "\u{1d538}"),
+    (b"ApplyFunction", "\u{2061}"),
+    (b"Aring", "\u{c5}"),
+    (b"Ascr", "\u{1d49c}"),
+    (b"Assign", "\u{2254}"),
+    (b"Atilde", "\u{c3}"),
+    (b"Auml", "\u{c4}"),
+    (b"Backslash", "\u{2216}"),
+    (b"Barv", "\u{2ae7}"),
+    (b"Barwed", "\u{2306}"),
+    (b"Bcy", "\u{411}"),
+    (b"Because", "\u{2235}"),
+    (b"Bernoullis", "\u{212c}"),
+    (b"Beta", "\u{392}"),
+    (b"Bfr", "\u{1d505}"),
+    (b"Bopf", "\u{1d539}"),
+    (b"Breve", "\u{2d8}"),
+    (b"Bscr", "\u{212c}"),
+    (b"Bumpeq", "\u{224e}"),
+    (b"CHcy", "\u{427}"),
+    (b"COPY", "\u{a9}"),
+    (b"Cacute", "\u{106}"),
+    (b"Cap", "\u{22d2}"),
+    (b"CapitalDifferentialD", "\u{2145}"),
+    (b"Cayleys", "\u{212d}"),
+    (b"Ccaron", "\u{10c}"),
+    (b"Ccedil", "\u{c7}"),
+    (b"Ccirc", "\u{108}"),
+    (b"Cconint", "\u{2230}"),
+    (b"Cdot", "\u{10a}"),
+    (b"Cedilla", "\u{b8}"),
+    (b"CenterDot", "\u{b7}"),
+    (b"Cfr", "\u{212d}"),
+    (b"Chi", "\u{3a7}"),
+    (b"CircleDot", "\u{2299}"),
+    (b"CircleMinus", "\u{2296}"),
+    (b"CirclePlus", "\u{2295}"),
+    (b"CircleTimes", "\u{2297}"),
+    (b"ClockwiseContourIntegral", "\u{2232}"),
+    (b"CloseCurlyDoubleQuote", "\u{201d}"),
+    (b"CloseCurlyQuote", "\u{2019}"),
+    (b"Colon", "\u{2237}"),
+    (b"Colone", "\u{2a74}"),
+    (b"Congruent", "\u{2261}"),
+    (b"Conint", "\u{222f}"),
+    (b"ContourIntegral", "\u{222e}"),
+    (b"Copf", "\u{2102}"),
+    (b"Coproduct", "\u{2210}"),
+    (b"CounterClockwiseContourIntegral", "\u{2233}"),
+    (b"Cross", "\u{2a2f}"),
+    (b"Cscr", "\u{1d49e}"),
+    (b"Cup", "\u{22d3}"),
+    (b"CupCap", "\u{224d}"),
+    (b"DD", "\u{2145}"),
+    (b"DDotrahd", "\u{2911}"),
+    (b"DJcy", "\u{402}"),
+    (b"DScy", "\u{405}"),
+    (b"DZcy", "\u{40f}"),
+    (b"Dagger", "\u{2021}"),
+    (b"Darr", "\u{21a1}"),
+    (b"Dashv", "\u{2ae4}"),
+    (b"Dcaron", "\u{10e}"),
+    (b"Dcy", "\u{414}"),
+    (b"Del", "\u{2207}"),
+    (b"Delta", "\u{394}"),
+    (b"Dfr", "\u{1d507}"),
+    (b"DiacriticalAcute", "\u{b4}"),
+    (b"DiacriticalDot", "\u{2d9}"),
+    (b"DiacriticalDoubleAcute", "\u{2dd}"),
+    (b"DiacriticalGrave", "`"),
+    (b"DiacriticalTilde", "\u{2dc}"),
+    (b"Diamond", "\u{22c4}"),
+    (b"DifferentialD", "\u{2146}"),
+    (b"Dopf", "\u{1d53b}"),
+    (b"Dot", "\u{a8}"),
+    (b"DotDot", "\u{20dc}"),
+    (b"DotEqual", "\u{2250}"),
+    (b"DoubleContourIntegral", "\u{222f}"),
+    (b"DoubleDot", "\u{a8}"),
+    (b"DoubleDownArrow", "\u{21d3}"),
+    (b"DoubleLeftArrow", "\u{21d0}"),
+    (b"DoubleLeftRightArrow", "\u{21d4}"),
+    (b"DoubleLeftTee", "\u{2ae4}"),
+    (b"DoubleLongLeftArrow", "\u{27f8}"),
+    (b"DoubleLongLeftRightArrow", "\u{27fa}"),
+    (b"DoubleLongRightArrow", "\u{27f9}"),
+    (b"DoubleRightArrow", "\u{21d2}"),
+    (b"DoubleRightTee", "\u{22a8}"),
+    (b"DoubleUpArrow", "\u{21d1}"),
+    (b"DoubleUpDownArrow", "\u{21d5}"),
+    (b"DoubleVerticalBar", "\u{2225}"),
+    (b"DownArrow", "\u{2193}"),
+    (b"DownArrowBar", "\u{2913}"),
+    (b"DownArrowUpArrow", "\u{21f5}"),
+    (b"DownBreve", "\u{311}"),
+    (b"DownLeftRightVector", "\u{2950}"),
+    (b"DownLeftTeeVector", "\u{295e}"),
+    (b"DownLeftVector", "\u{21bd}"),
+    (b"DownLeftVectorBar", "\u{2956}"),
+    (b"DownRightTeeVector", "\u{295f}"),
+    (b"DownRightVector", "\u{21c1}"),
+    (b"DownRightVectorBar", "\u{2957}"),
+    (b"DownTee", "\u{22a4}"),
+    (b"DownTeeArrow", "\u{21a7}"),
+    (b"Downarrow", "\u{21d3}"),
+    (b"Dscr", "\u{1d49f}"),
+    (b"Dstrok", "\u{110}"),
+    (b"ENG", "\u{14a}"),
+    (b"ETH", "\u{d0}"),
+    (b"Eacute", "\u{c9}"),
+    (b"Ecaron", "\u{11a}"),
+    (b"Ecirc", "\u{ca}"),
+    (b"Ecy", "\u{42d}"),
+    (b"Edot", "\u{116}"),
+    (b"Efr", "\u{1d508}"),
+    (b"Egrave", "\u{c8}"),
+    (b"Element", "\u{2208}"),
+    (b"Emacr", "\u{112}"),
+    (b"EmptySmallSquare", "\u{25fb}"),
+    (b"EmptyVerySmallSquare", "\u{25ab}"),
+    (b"Eogon", "\u{118}"),
+    (b"Eopf", "\u{1d53c}"),
+    (b"Epsilon", "\u{395}"),
+    (b"Equal", "\u{2a75}"),
+    (b"EqualTilde", "\u{2242}"),
+    (b"Equilibrium", "\u{21cc}"),
+    (b"Escr", "\u{2130}"),
+    (b"Esim", "\u{2a73}"),
+    (b"Eta", "\u{397}"),
+    (b"Euml", "\u{cb}"),
+    (b"Exists", "\u{2203}"),
+    (b"ExponentialE", "\u{2147}"),
+    (b"Fcy", "\u{424}"),
+    (b"Ffr", "\u{1d509}"),
+    (b"FilledSmallSquare", "\u{25fc}"),
+    (b"FilledVerySmallSquare", "\u{25aa}"),
+    (b"Fopf", "\u{1d53d}"),
+    (b"ForAll", "\u{2200}"),
+    (b"Fouriertrf", "\u{2131}"),
+    (b"Fscr", "\u{2131}"),
+    (b"GJcy", "\u{403}"),
+    (b"GT", ">"),
+    (b"Gamma", "\u{393}"),
+    (b"Gammad", "\u{3dc}"),
+    (b"Gbreve", "\u{11e}"),
+    (b"Gcedil", "\u{122}"),
+    (b"Gcirc", "\u{11c}"),
+    (b"Gcy", "\u{413}"),
+    (b"Gdot", "\u{120}"),
+    (b"Gfr", "\u{1d50a}"),
+    (b"Gg", "\u{22d9}"),
+    (b"Gopf", "\u{1d53e}"),
+    (b"GreaterEqual", "\u{2265}"),
+    (b"GreaterEqualLess", "\u{22db}"),
+    (b"GreaterFullEqual", "\u{2267}"),
+    (b"GreaterGreater", "\u{2aa2}"),
+    (b"GreaterLess", "\u{2277}"),
+    (b"GreaterSlantEqual", "\u{2a7e}"),
+    (b"GreaterTilde", "\u{2273}"),
+    (b"Gscr", "\u{1d4a2}"),
+    (b"Gt", "\u{226b}"),
+    (b"HARDcy", "\u{42a}"),
+    (b"Hacek", "\u{2c7}"),
+    (b"Hat", "^"),
+    (b"Hcirc", "\u{124}"),
+    (b"Hfr", "\u{210c}"),
+    (b"HilbertSpace", "\u{210b}"),
+    (b"Hopf", "\u{210d}"),
+    (b"HorizontalLine", "\u{2500}"),
+    (b"Hscr", "\u{210b}"),
+    (b"Hstrok", "\u{126}"),
+    (b"HumpDownHump", "\u{224e}"),
+    (b"HumpEqual", "\u{224f}"),
+    (b"IEcy", "\u{415}"),
+    (b"IJlig", "\u{132}"),
+    (b"IOcy", "\u{401}"),
+    (b"Iacute", "\u{cd}"),
+    (b"Icirc", "\u{ce}"),
+    (b"Icy", "\u{418}"),
+    (b"Idot", "\u{130}"),
+    (b"Ifr", "\u{2111}"),
+    (b"Igrave", "\u{cc}"),
+    (b"Im", "\u{2111}"),
+    (b"Imacr", "\u{12a}"),
+    (b"ImaginaryI", "\u{2148}"),
+    (b"Implies", "\u{21d2}"),
+    (b"Int", "\u{222c}"),
+    (b"Integral", "\u{222b}"),
+    (b"Intersection", "\u{22c2}"),
+    (b"InvisibleComma", "\u{2063}"),
+    (b"InvisibleTimes", "\u{2062}"),
+    (b"Iogon", "\u{12e}"),
+    (b"Iopf", "\u{1d540}"),
+    (b"Iota", "\u{399}"),
+    (b"Iscr", "\u{2110}"),
+    (b"Itilde", "\u{128}"),
+    (b"Iukcy", "\u{406}"),
+    (b"Iuml", "\u{cf}"),
+    (b"Jcirc", "\u{134}"),
+    (b"Jcy", "\u{419}"),
+    (b"Jfr", "\u{1d50d}"),
+    (b"Jopf", "\u{1d541}"),
+    (b"Jscr", "\u{1d4a5}"),
+    (b"Jsercy", "\u{408}"),
+    (b"Jukcy", "\u{404}"),
+    (b"KHcy", "\u{425}"),
+    (b"KJcy", "\u{40c}"),
+    (b"Kappa", "\u{39a}"),
+    (b"Kcedil", "\u{136}"),
+    (b"Kcy", "\u{41a}"),
+    (b"Kfr", "\u{1d50e}"),
+    (b"Kopf", "\u{1d542}"),
+    (b"Kscr", "\u{1d4a6}"),
+    (b"LJcy", "\u{409}"),
+    (b"LT", "<"),
+    (b"Lacute", "\u{139}"),
+    (b"Lambda", "\u{39b}"),
+    (b"Lang", "\u{27ea}"),
+    (b"Laplacetrf", "\u{2112}"),
+    (b"Larr", "\u{219e}"),
+    (b"Lcaron", "\u{13d}"),
+    (b"Lcedil", "\u{13b}"),
+    (b"Lcy", "\u{41b}"),
+    (b"LeftAngleBracket", "\u{27e8}"),
+    (b"LeftArrow", "\u{2190}"),
+    (b"LeftArrowBar", "\u{21e4}"),
+    (b"LeftArrowRightArrow", "\u{21c6}"),
+    (b"LeftCeiling", "\u{2308}"),
+    (b"LeftDoubleBracket", "\u{27e6}"),
+    (b"LeftDownTeeVector", "\u{2961}"),
+    (b"LeftDownVector", "\u{21c3}"),
+    (b"LeftDownVectorBar", "\u{2959}"),
+    (b"LeftFloor", "\u{230a}"),
+    (b"LeftRightArrow", "\u{2194}"),
+    (b"LeftRightVector", "\u{294e}"),
+    (b"LeftTee", "\u{22a3}"),
+    (b"LeftTeeArrow", "\u{21a4}"),
+    (b"LeftTeeVector", "\u{295a}"),
+    (b"LeftTriangle", "\u{22b2}"),
+    (b"LeftTriangleBar", "\u{29cf}"),
+    (b"LeftTriangleEqual", "\u{22b4}"),
+    (b"LeftUpDownVector", "\u{2951}"),
+    (b"LeftUpTeeVector", "\u{2960}"),
+    (b"LeftUpVector", "\u{21bf}"),
+    (b"LeftUpVectorBar", "\u{2958}"),
+    (b"LeftVector", "\u{21bc}"),
+    (b"LeftVectorBar", "\u{2952}"),
+    (b"Leftarrow", "\u{21d0}"),
+    (b"Leftrightarrow", "\u{21d4}"),
+    (b"LessEqualGreater", "\u{22da}"),
+    (b"LessFullEqual", "\u{2266}"),
+    (b"LessGreater", "\u{2276}"),
+    (b"LessLess", "\u{2aa1}"),
+    (b"LessSlantEqual", "\u{2a7d}"),
+    (b"LessTilde", "\u{2272}"),
+    (b"Lfr", "\u{1d50f}"),
+    (b"Ll", "\u{22d8}"),
+    (b"Lleftarrow", "\u{21da}"),
+    (b"Lmidot", "\u{13f}"),
+    (b"LongLeftArrow", "\u{27f5}"),
+    (b"LongLeftRightArrow", "\u{27f7}"),
+    (b"LongRightArrow", "\u{27f6}"),
+    (b"Longleftarrow", "\u{27f8}"),
+    (b"Longleftrightarrow", "\u{27fa}"),
+    (b"Longrightarrow", "\u{27f9}"),
+    (b"Lopf", "\u{1d543}"),
+    (b"LowerLeftArrow", "\u{2199}"),
+    (b"LowerRightArrow", "\u{2198}"),
+    (b"Lscr", "\u{2112}"),
+    (b"Lsh", "\u{21b0}"),
+    (b"Lstrok", "\u{141}"),
+    (b"Lt", "\u{226a}"),
+    (b"Map", "\u{2905}"),
+    (b"Mcy", "\u{41c}"),
+    (b"MediumSpace", "\u{205f}"),
+    (b"Mellintrf", "\u{2133}"),
+    (b"Mfr", "\u{1d510}"),
+    (b"MinusPlus", "\u{2213}"),
+    (b"Mopf", "\u{1d544}"),
+    (b"Mscr", "\u{2133}"),
+    (b"Mu", "\u{39c}"),
+    (b"NJcy", "\u{40a}"),
+    (b"Nacute", "\u{143}"),
+    (b"Ncaron", "\u{147}"),
+    (b"Ncedil", "\u{145}"),
+    (b"Ncy", "\u{41d}"),
+    (b"NegativeMediumSpace", "\u{200b}"),
+    (b"NegativeThickSpace", "\u{200b}"),
+    (b"NegativeThinSpace", "\u{200b}"),
+    (b"NegativeVeryThinSpace", "\u{200b}"),
+    (b"NestedGreaterGreater", "\u{226b}"),
+    (b"NestedLessLess", "\u{226a}"),
+    (b"NewLine", "\u{a}"),
+    (b"Nfr", "\u{1d511}"),
+    (b"NoBreak", "\u{2060}"),
+    (b"NonBreakingSpace", "\u{a0}"),
+    (b"Nopf", "\u{2115}"),
+    (b"Not", "\u{2aec}"),
+    (b"NotCongruent", "\u{2262}"),
+    (b"NotCupCap", "\u{226d}"),
+    (b"NotDoubleVerticalBar", "\u{2226}"),
+    (b"NotElement", "\u{2209}"),
+    (b"NotEqual", "\u{2260}"),
+    (b"NotEqualTilde", "\u{2242}\u{338}"),
+    (b"NotExists", "\u{2204}"),
+    (b"NotGreater", "\u{226f}"),
+    (b"NotGreaterEqual", "\u{2271}"),
+    (b"NotGreaterFullEqual", "\u{2267}\u{338}"),
+    (b"NotGreaterGreater", "\u{226b}\u{338}"),
+    (b"NotGreaterLess", "\u{2279}"),
+    (b"NotGreaterSlantEqual", "\u{2a7e}\u{338}"),
+    (b"NotGreaterTilde", "\u{2275}"),
+    (b"NotHumpDownHump", "\u{224e}\u{338}"),
+    (b"NotHumpEqual", "\u{224f}\u{338}"),
+    (b"NotLeftTriangle", "\u{22ea}"),
+    (b"NotLeftTriangleBar", "\u{29cf}\u{338}"),
+    (b"NotLeftTriangleEqual", "\u{22ec}"),
+    (b"NotLess", "\u{226e}"),
+    (b"NotLessEqual", "\u{2270}"),
+    (b"NotLessGreater", "\u{2278}"),
+    (b"NotLessLess", "\u{226a}\u{338}"),
+    (b"NotLessSlantEqual", "\u{2a7d}\u{338}"),
+    (b"NotLessTilde", "\u{2274}"),
+    (b"NotNestedGreaterGreater", "\u{2aa2}\u{338}"),
+    (b"NotNestedLessLess", "\u{2aa1}\u{338}"),
+    (b"NotPrecedes", "\u{2280}"),
+    (b"NotPrecedesEqual", "\u{2aaf}\u{338}"),
+    (b"NotPrecedesSlantEqual", "\u{22e0}"),
+    (b"NotReverseElement", "\u{220c}"),
+    (b"NotRightTriangle", "\u{22eb}"),
+    (b"NotRightTriangleBar", "\u{29d0}\u{338}"),
+    (b"NotRightTriangleEqual", "\u{22ed}"),
+    (b"NotSquareSubset", "\u{228f}\u{338}"),
+    (b"NotSquareSubsetEqual", "\u{22e2}"),
+    (b"NotSquareSuperset", "\u{2290}\u{338}"),
+    (b"NotSquareSupersetEqual", "\u{22e3}"),
+    (b"NotSubset", "\u{2282}\u{20d2}"),
+    (b"NotSubsetEqual", "\u{2288}"),
+    (b"NotSucceeds", "\u{2281}"),
+    (b"NotSucceedsEqual", "\u{2ab0}\u{338}"),
+    (b"NotSucceedsSlantEqual", "\u{22e1}"),
+    (b"NotSucceedsTilde", "\u{227f}\u{338}"),
+    (b"NotSuperset", "\u{2283}\u{20d2}"),
+    (b"NotSupersetEqual", "\u{2289}"),
+    (b"NotTilde", "\u{2241}"),
+    (b"NotTildeEqual", "\u{2244}"),
+    (b"NotTildeFullEqual", "\u{2247}"),
+    (b"NotTildeTilde", "\u{2249}"),
+    (b"NotVerticalBar", "\u{2224}"),
+    (b"Nscr", "\u{1d4a9}"),
+    (b"Ntilde", "\u{d1}"),
+    (b"Nu", "\u{39d}"),
+    (b"OElig", "\u{152}"),
+    (b"Oacute", "\u{d3}"),
+    (b"Ocirc", "\u{d4}"),
+    (b"Ocy", "\u{41e}"),
+    (b"Odblac", "\u{150}"),
+    (b"Ofr", "\u{1d512}"),
+    (b"Ograve", "\u{d2}"),
+    (b"Omacr", "\u{14c}"),
+    (b"Omega", "\u{3a9}"),
+    (b"Omicron", "\u{39f}"),
+    (b"Oopf", "\u{1d546}"),
+    (b"OpenCurlyDoubleQuote", "\u{201c}"),
+    (b"OpenCurlyQuote", "\u{2018}"),
+    (b"Or", "\u{2a54}"),
+    (b"Oscr", "\u{1d4aa}"),
+    (b"Oslash", "\u{d8}"),
+    (b"Otilde", "\u{d5}"),
+    (b"Otimes", "\u{2a37}"),
+    (b"Ouml", "\u{d6}"),
+    (b"OverBar", "\u{203e}"),
+    (b"OverBrace", "\u{23de}"),
+    (b"OverBracket", "\u{23b4}"),
+    (b"OverParenthesis", "\u{23dc}"),
+    (b"PartialD", "\u{2202}"),
+    (b"Pcy", "\u{41f}"),
+    (b"Pfr", "\u{1d513}"),
+    (b"Phi", "\u{3a6}"),
+    (b"Pi", "\u{3a0}"),
+    (b"PlusMinus", "\u{b1}"),
+    (b"Poincareplane", "\u{210c}"),
+    (b"Popf", "\u{2119}"),
+    (b"Pr", "\u{2abb}"),
+    (b"Precedes", "\u{227a}"),
+    (b"PrecedesEqual", "\u{2aaf}"),
+    (b"PrecedesSlantEqual", "\u{227c}"),
+    (b"PrecedesTilde", "\u{227e}"),
+    (b"Prime", "\u{2033}"),
+    (b"Product", "\u{220f}"),
+    (b"Proportion", "\u{2237}"),
+    (b"Proportional", "\u{221d}"),
+    (b"Pscr", "\u{1d4ab}"),
+    (b"Psi", "\u{3a8}"),
+    (b"QUOT", "\""),
+    (b"Qfr", "\u{1d514}"),
+    (b"Qopf", "\u{211a}"),
+    (b"Qscr", "\u{1d4ac}"),
+    (b"RBarr", "\u{2910}"),
+    (b"REG", "\u{ae}"),
+    (b"Racute", "\u{154}"),
+    (b"Rang", "\u{27eb}"),
+    (b"Rarr", "\u{21a0}"),
+    (b"Rarrtl", "\u{2916}"),
+    (b"Rcaron", "\u{158}"),
+    (b"Rcedil", "\u{156}"),
+    (b"Rcy", "\u{420}"),
+    (b"Re", "\u{211c}"),
+    (b"ReverseElement", "\u{220b}"),
+    (b"ReverseEquilibrium", "\u{21cb}"),
+    (b"ReverseUpEquilibrium", "\u{296f}"),
+    (b"Rfr", "\u{211c}"),
+    (b"Rho", "\u{3a1}"),
+    (b"RightAngleBracket", "\u{27e9}"),
+    (b"RightArrow", "\u{2192}"),
+    (b"RightArrowBar", "\u{21e5}"),
+    (b"RightArrowLeftArrow", "\u{21c4}"),
+    (b"RightCeiling", "\u{2309}"),
+    (b"RightDoubleBracket", "\u{27e7}"),
+    (b"RightDownTeeVector", "\u{295d}"),
+    (b"RightDownVector", "\u{21c2}"),
+    (b"RightDownVectorBar", "\u{2955}"),
+    (b"RightFloor", "\u{230b}"),
+    (b"RightTee", "\u{22a2}"),
+    (b"RightTeeArrow", "\u{21a6}"),
+    (b"RightTeeVector", "\u{295b}"),
+    (b"RightTriangle", "\u{22b3}"),
+    (b"RightTriangleBar", "\u{29d0}"),
+    (b"RightTriangleEqual", "\u{22b5}"),
+    (b"RightUpDownVector", "\u{294f}"),
+    (b"RightUpTeeVector", "\u{295c}"),
+    (b"RightUpVector", "\u{21be}"),
+    (b"RightUpVectorBar", "\u{2954}"),
+    (b"RightVector", "\u{21c0}"),
+    (b"RightVectorBar", "\u{2953}"),
+    (b"Rightarrow", "\u{21d2}"),
+    (b"Ropf", "\u{211d}"),
+    (b"RoundImplies", "\u{2970}"),
+    (b"Rrightarrow", "\u{21db}"),
+    (b"Rscr", "\u{211b}"),
+    (b"Rsh", "\u{21b1}"),
+    (b"RuleDelayed", "\u{29f4}"),
+    (b"SHCHcy", "\u{429}"),
+    (b"SHcy", "\u{428}"),
+    (b"SOFTcy", "\u{42c}"),
+    (b"Sacute", "\u{15a}"),
+    (b"Sc", "\u{2abc}"),
+    (b"Scaron", "\u{160}"),
+    (b"Scedil", "\u{15e}"),
+    (b"Scirc", "\u{15c}"),
+    (b"Scy", "\u{421}"),
+    (b"Sfr", "\u{1d516}"),
+    (b"ShortDownArrow", "\u{2193}"),
+    (b"ShortLeftArrow", "\u{2190}"),
+    (b"ShortRightArrow", "\u{2192}"),
+    (b"ShortUpArrow", "\u{2191}"),
+    (b"Sigma", "\u{3a3}"),
+    (b"SmallCircle", "\u{2218}"),
+    (b"Sopf", "\u{1d54a}"),
+    (b"Sqrt", "\u{221a}"),
+    (b"Square", "\u{25a1}"),
+    (b"SquareIntersection", "\u{2293}"),
+    (b"SquareSubset", "\u{228f}"),
+    (b"SquareSubsetEqual", "\u{2291}"),
+    (b"SquareSuperset", "\u{2290}"),
+    (b"SquareSupersetEqual", "\u{2292}"),
+    (b"SquareUnion", "\u{2294}"),
+    (b"Sscr", "\u{1d4ae}"),
+    (b"Star", "\u{22c6}"),
+    (b"Sub", "\u{22d0}"),
+    (b"Subset", "\u{22d0}"),
+    (b"SubsetEqual", "\u{2286}"),
+    (b"Succeeds", "\u{227b}"),
+    (b"SucceedsEqual", "\u{2ab0}"),
+    (b"SucceedsSlantEqual", "\u{227d}"),
+    (b"SucceedsTilde", "\u{227f}"),
+    (b"SuchThat", "\u{220b}"),
+    (b"Sum", "\u{2211}"),
+    (b"Sup", "\u{22d1}"),
+    (b"Superset", "\u{2283}"),
+    (b"SupersetEqual", "\u{2287}"),
+    (b"Supset", "\u{22d1}"),
+    (b"THORN", "\u{de}"),
+    (b"TRADE", "\u{2122}"),
+    (b"TSHcy", "\u{40b}"),
+    (b"TScy", "\u{426}"),
+    (b"Tab", "\u{9}"),
+    (b"Tau", "\u{3a4}"),
+    (b"Tcaron", "\u{164}"),
+    (b"Tcedil", "\u{162}"),
+    (b"Tcy", "\u{422}"),
+    (b"Tfr", "\u{1d517}"),
+    (b"Therefore", "\u{2234}"),
+    (b"Theta", "\u{398}"),
+    (b"ThickSpace", "\u{205f}\u{200a}"),
+    (b"ThinSpace", "\u{2009}"),
+    (b"Tilde", "\u{223c}"),
+    (b"TildeEqual", "\u{2243}"),
+    (b"TildeFullEqual", "\u{2245}"),
+    (b"TildeTilde", "\u{2248}"),
+    (b"Topf", "\u{1d54b}"),
+    (b"TripleDot", "\u{20db}"),
+    (b"Tscr", "\u{1d4af}"),
+    (b"Tstrok", "\u{166}"),
+    (b"Uacute", "\u{da}"),
+    (b"Uarr", "\u{219f}"),
+    (b"Uarrocir", "\u{2949}"),
+    (b"Ubrcy", "\u{40e}"),
+    (b"Ubreve", "\u{16c}"),
+    (b"Ucirc", "\u{db}"),
+    (b"Ucy", "\u{423}"),
+    (b"Udblac", "\u{170}"),
+    (b"Ufr", "\u{1d518}"),
+    (b"Ugrave", "\u{d9}"),
+    (b"Umacr", "\u{16a}"),
+    (b"UnderBar", "_"),
+    (b"UnderBrace", "\u{23df}"),
+    (b"UnderBracket", "\u{23b5}"),
+    (b"UnderParenthesis", "\u{23dd}"),
+    (b"Union", "\u{22c3}"),
+    (b"UnionPlus", "\u{228e}"),
+    (b"Uogon", "\u{172}"),
+    (b"Uopf", "\u{1d54c}"),
+    (b"UpArrow", "\u{2191}"),
+    (b"UpArrowBar", "\u{2912}"),
+    (b"UpArrowDownArrow", "\u{21c5}"),
+    (b"UpDownArrow", "\u{2195}"),
+    (b"UpEquilibrium", "\u{296e}"),
+    (b"UpTee", "\u{22a5}"),
+    (b"UpTeeArrow", "\u{21a5}"),
+    (b"Uparrow", "\u{21d1}"),
+    (b"Updownarrow", "\u{21d5}"),
+    (b"UpperLeftArrow", "\u{2196}"),
+    (b"UpperRightArrow", "\u{2197}"),
+    (b"Upsi", "\u{3d2}"),
+    (b"Upsilon", "\u{3a5}"),
+    (b"Uring", "\u{16e}"),
+    (b"Uscr", "\u{1d4b0}"),
+    (b"Utilde", "\u{168}"),
+    (b"Uuml", "\u{dc}"),
+    (b"VDash", "\u{22ab}"),
+    (b"Vbar", "\u{2aeb}"),
+    (b"Vcy", "\u{412}"),
+    (b"Vdash", "\u{22a9}"),
+    (b"Vdashl", "\u{2ae6}"),
+    (b"Vee", "\u{22c1}"),
+    (b"Verbar", "\u{2016}"),
+    (b"Vert", "\u{2016}"),
+    (b"VerticalBar", "\u{2223}"),
+    (b"VerticalLine", "|"),
+    (b"VerticalSeparator", "\u{2758}"),
+    (b"VerticalTilde", "\u{2240}"),
+    (b"VeryThinSpace", "\u{200a}"),
+    (b"Vfr", "\u{1d519}"),
+    (b"Vopf", "\u{1d54d}"),
+    (b"Vscr", "\u{1d4b1}"),
+    (b"Vvdash", "\u{22aa}"),
+    (b"Wcirc", "\u{174}"),
+    (b"Wedge", "\u{22c0}"),
+    (b"Wfr", "\u{1d51a}"),
+    (b"Wopf", "\u{1d54e}"),
+    (b"Wscr", "\u{1d4b2}"),
+    (b"Xfr", "\u{1d51b}"),
+    (b"Xi", "\u{39e}"),
+    (b"Xopf", "\u{1d54f}"),
+    (b"Xscr", "\u{1d4b3}"),
+    (b"YAcy", "\u{42f}"),
+    (b"YIcy", "\u{407}"),
+    (b"YUcy", "\u{42e}"),
+    (b"Yacute", "\u{dd}"),
+    (b"Ycirc", "\u{176}"),
+    (b"Ycy", "\u{42b}"),
+    (b"Yfr", "\u{1d51c}"),
+    (b"Yopf", "\u{1d550}"),
+    (b"Yscr", "\u{1d4b4}"),
+    (b"Yuml", "\u{178}"),
+    (b"ZHcy", "\u{416}"),
+    (b"Zacute", "\u{179}"),
+    (b"Zcaron", "\u{17d}"),
+    (b"Zcy", "\u{417}"),
+    (b"Zdot", "\u{17b}"),
+    (b"ZeroWidthSpace", "\u{200b}"),
+    (b"Zeta", "\u{396}"),
+    (b"Zfr", "\u{2128}"),
+    (b"Zopf", "\u{2124}"),
+    (b"Zscr", "\u{1d4b5}"),
+    (b"aacute", "\u{e1}"),
+    (b"abreve", "\u{103}"),
+    (b"ac", "\u{223e}"),
+    (b"acE", "\u{223e}\u{333}"),
+    (b"acd", "\u{223f}"),
+    (b"acirc", "\u{e2}"),
+    (b"acute", "\u{b4}"),
+    (b"acy", "\u{430}"),
+    (b"aelig", "\u{e6}"),
+    (b"af", "\u{2061}"),
+    (b"afr", "\u{1d51e}"),
+    (b"agrave", "\u{e0}"),
+    (b"alefsym", "\u{2135}"),
+    (b"aleph", "\u{2135}"),
+    (b"alpha", "\u{3b1}"),
+    (b"amacr", "\u{101}"),
+    (b"amalg", "\u{2a3f}"),
+    (b"amp", "&"),
+    (b"and", "\u{2227}"),
+    (b"andand", "\u{2a55}"),
+    (b"andd", "\u{2a5c}"),
+    (b"andslope", "\u{2a58}"),
+    (b"andv", "\u{2a5a}"),
+    (b"ang", "\u{2220}"),
+    (b"ange", "\u{29a4}"),
+    (b"angle", "\u{2220}"),
+    (b"angmsd", "\u{2221}"),
+    (b"angmsdaa", "\u{29a8}"),
+    (b"angmsdab", "\u{29a9}"),
+    (b"angmsdac", "\u{29aa}"),
+    (b"angmsdad", "\u{29ab}"),
+    (b"angmsdae", "\u{29ac}"),
+    (b"angmsdaf", "\u{29ad}"),
+    (b"angmsdag", "\u{29ae}"),
+    (b"angmsdah", "\u{29af}"),
+    (b"angrt", "\u{221f}"),
+    (b"angrtvb", "\u{22be}"),
+    (b"angrtvbd", "\u{299d}"),
+    (b"angsph", "\u{2222}"),
+    (b"angst", "\u{c5}"),
+    (b"angzarr", "\u{237c}"),
+    (b"aogon", "\u{105}"),
+    (b"aopf", "\u{1d552}"),
+    (b"ap", "\u{2248}"),
+    (b"apE", "\u{2a70}"),
+    (b"apacir", "\u{2a6f}"),
+    (b"ape", "\u{224a}"),
+    (b"apid", "\u{224b}"),
+    (b"apos", "'"),
+    (b"approx", "\u{2248}"),
+    (b"approxeq", "\u{224a}"),
+    (b"aring", "\u{e5}"),
+    (b"ascr", "\u{1d4b6}"),
+    (b"ast", "*"),
+    (b"asymp", "\u{2248}"),
+    (b"asympeq", "\u{224d}"),
+    (b"atilde", "\u{e3}"),
+    (b"auml", "\u{e4}"),
+    (b"awconint", "\u{2233}"),
+    (b"awint", "\u{2a11}"),
+    (b"bNot", "\u{2aed}"),
+    (b"backcong", "\u{224c}"),
+    (b"backepsilon", "\u{3f6}"),
+    (b"backprime", "\u{2035}"),
+    (b"backsim", "\u{223d}"),
+    (b"backsimeq", "\u{22cd}"),
+    (b"barvee", "\u{22bd}"),
+    (b"barwed", "\u{2305}"),
+    (b"barwedge", "\u{2305}"),
+    (b"bbrk", "\u{23b5}"),
+    (b"bbrktbrk", "\u{23b6}"),
+    (b"bcong", "\u{224c}"),
+    (b"bcy", "\u{431}"),
+    (b"bdquo", "\u{201e}"),
+    (b"becaus", "\u{2235}"),
+    (b"because", "\u{2235}"),
+    (b"bemptyv", "\u{29b0}"),
+    (b"bepsi", "\u{3f6}"),
+    (b"bernou", "\u{212c}"),
+    (b"beta", "\u{3b2}"),
+    (b"beth", "\u{2136}"),
+    (b"between", "\u{226c}"),
+    (b"bfr", "\u{1d51f}"),
+    (b"bigcap", "\u{22c2}"),
+    (b"bigcirc", "\u{25ef}"),
+    (b"bigcup", "\u{22c3}"),
+    (b"bigodot", "\u{2a00}"),
+    (b"bigoplus", "\u{2a01}"),
+    (b"bigotimes", "\u{2a02}"),
+    (b"bigsqcup", "\u{2a06}"),
+    (b"bigstar", "\u{2605}"),
+    (b"bigtriangledown", "\u{25bd}"),
+    (b"bigtriangleup", "\u{25b3}"),
+    (b"biguplus", "\u{2a04}"),
+    (b"bigvee", "\u{22c1}"),
+    (b"bigwedge", "\u{22c0}"),
+    (b"bkarow", "\u{290d}"),
+    (b"blacklozenge", "\u{29eb}"),
+    (b"blacksquare", "\u{25aa}"),
+    (b"blacktriangle", "\u{25b4}"),
+    (b"blacktriangledown", "\u{25be}"),
+    (b"blacktriangleleft", "\u{25c2}"),
+    (b"blacktriangleright", "\u{25b8}"),
+    (b"blank", "\u{2423}"),
+    (b"blk12", "\u{2592}"),
+    (b"blk14", "\u{2591}"),
+    (b"blk34", "\u{2593}"),
+    (b"block", "\u{2588}"),
+    (b"bne", "=\u{20e5}"),
+    (b"bnequiv", "\u{2261}\u{20e5}"),
+    (b"bnot", "\u{2310}"),
+    (b"bopf", "\u{1d553}"),
+    (b"bot", "\u{22a5}"),
+    (b"bottom", "\u{22a5}"),
+    (b"bowtie", "\u{22c8}"),
+    (b"boxDL", "\u{2557}"),
+    (b"boxDR", "\u{2554}"),
+    (b"boxDl", "\u{2556}"),
+    (b"boxDr", "\u{2553}"),
+    (b"boxH", "\u{2550}"),
+    (b"boxHD", "\u{2566}"),
+    (b"boxHU", "\u{2569}"),
+    (b"boxHd", "\u{2564}"),
+    (b"boxHu", "\u{2567}"),
+    (b"boxUL", "\u{255d}"),
+    (b"boxUR", "\u{255a}"),
+    (b"boxUl", "\u{255c}"),
+    (b"boxUr", "\u{2559}"),
+    (b"boxV", "\u{2551}"),
+    (b"boxVH", "\u{256c}"),
+    (b"boxVL", "\u{2563}"),
+    (b"boxVR", "\u{2560}"),
+    (b"boxVh", "\u{256b}"),
+    (b"boxVl", "\u{2562}"),
+    (b"boxVr", "\u{255f}"),
+    (b"boxbox", "\u{29c9}"),
+    (b"boxdL", "\u{2555}"),
+    (b"boxdR", "\u{2552}"),
+    (b"boxdl", "\u{2510}"),
+    (b"boxdr", "\u{250c}"),
+    (b"boxh", "\u{2500}"),
+    (b"boxhD", "\u{2565}"),
+    (b"boxhU", "\u{2568}"),
+    (b"boxhd", "\u{252c}"),
+    (b"boxhu", "\u{2534}"),
+    (b"boxminus", "\u{229f}"),
+    (b"boxplus", "\u{229e}"),
+    (b"boxtimes", "\u{22a0}"),
+    (b"boxuL", "\u{255b}"),
+    (b"boxuR", "\u{2558}"),
+    (b"boxul", "\u{2518}"),
+    (b"boxur", "\u{2514}"),
+    (b"boxv", "\u{2502}"),
+    (b"boxvH", "\u{256a}"),
+    (b"boxvL", "\u{2561}"),
+    (b"boxvR", "\u{255e}"),
+    (b"boxvh", "\u{253c}"),
+    (b"boxvl", "\u{2524}"),
+    (b"boxvr", "\u{251c}"),
+    (b"bprime", "\u{2035}"),
+    (b"breve", "\u{2d8}"),
+    (b"brvbar", "\u{a6}"),
+    (b"bscr", "\u{1d4b7}"),
+    (b"bsemi", "\u{204f}"),
+    (b"bsim", "\u{223d}"),
+    (b"bsime", "\u{22cd}"),
+    (b"bsol", "\\"),
+    (b"bsolb", "\u{29c5}"),
+    (b"bsolhsub", "\u{27c8}"),
+    (b"bull", "\u{2022}"),
+    (b"bullet", "\u{2022}"),
+    (b"bump", "\u{224e}"),
+    (b"bumpE", "\u{2aae}"),
+    (b"bumpe", "\u{224f}"),
+    (b"bumpeq", "\u{224f}"),
+    (b"cacute", "\u{107}"),
+    (b"cap", "\u{2229}"),
+    (b"capand", "\u{2a44}"),
+    (b"capbrcup", "\u{2a49}"),
+    (b"capcap", "\u{2a4b}"),
+    (b"capcup", "\u{2a47}"),
+    (b"capdot", "\u{2a40}"),
+    (b"caps", "\u{2229}\u{fe00}"),
+    (b"caret", "\u{2041}"),
+    (b"caron", "\u{2c7}"),
+    (b"ccaps", "\u{2a4d}"),
+    (b"ccaron", "\u{10d}"),
+    (b"ccedil", "\u{e7}"),
+    (b"ccirc", "\u{109}"),
+    (b"ccups", "\u{2a4c}"),
+    (b"ccupssm", "\u{2a50}"),
+    (b"cdot", "\u{10b}"),
+    (b"cedil", "\u{b8}"),
+    (b"cemptyv", "\u{29b2}"),
+    (b"cent", "\u{a2}"),
+    (b"centerdot", "\u{b7}"),
+    (b"cfr", "\u{1d520}"),
+    (b"chcy", "\u{447}"),
+    (b"check", "\u{2713}"),
+    (b"checkmark", "\u{2713}"),
+    (b"chi", "\u{3c7}"),
+    (b"cir", "\u{25cb}"),
+    (b"cirE", "\u{29c3}"),
+    (b"circ", "\u{2c6}"),
+    (b"circeq", "\u{2257}"),
+    (b"circlearrowleft", "\u{21ba}"),
+    (b"circlearrowright", "\u{21bb}"),
+    (b"circledR", "\u{ae}"),
+    (b"circledS", "\u{24c8}"),
+    (b"circledast", "\u{229b}"),
+    (b"circledcirc", "\u{229a}"),
+    (b"circleddash", "\u{229d}"),
+    (b"cire", "\u{2257}"),
+    (b"cirfnint", "\u{2a10}"),
+    (b"cirmid", "\u{2aef}"),
+    (b"cirscir", "\u{29c2}"),
+    (b"clubs", "\u{2663}"),
+    (b"clubsuit", "\u{2663}"),
+    (b"colon", ":"),
+    (b"colone", "\u{2254}"),
+    (b"coloneq", "\u{2254}"),
+    (b"comma", ","),
+    (b"commat", "@"),
+    (b"comp", "\u{2201}"),
+    (b"compfn", "\u{2218}"),
+    (b"complement", "\u{2201}"),
+    (b"complexes", "\u{2102}"),
+    (b"cong", "\u{2245}"),
+    (b"congdot", "\u{2a6d}"),
+    (b"conint", "\u{222e}"),
+    (b"copf", "\u{1d554}"),
+    (b"coprod", "\u{2210}"),
+    (b"copy", "\u{a9}"),
+    (b"copysr", "\u{2117}"),
+    (b"crarr", "\u{21b5}"),
+    (b"cross", "\u{2717}"),
+    (b"cscr", "\u{1d4b8}"),
+    (b"csub", "\u{2acf}"),
+    (b"csube", "\u{2ad1}"),
+    (b"csup", "\u{2ad0}"),
+    (b"csupe", "\u{2ad2}"),
+    (b"ctdot", "\u{22ef}"),
+    (b"cudarrl", "\u{2938}"),
+    (b"cudarrr", "\u{2935}"),
+    (b"cuepr", "\u{22de}"),
+    (b"cuesc", "\u{22df}"),
+    (b"cularr", "\u{21b6}"),
+    (b"cularrp", "\u{293d}"),
+    (b"cup", "\u{222a}"),
+    (b"cupbrcap", "\u{2a48}"),
+    (b"cupcap", "\u{2a46}"),
+    (b"cupcup", "\u{2a4a}"),
+    (b"cupdot", "\u{228d}"),
+    (b"cupor", "\u{2a45}"),
+    (b"cups", "\u{222a}\u{fe00}"),
+    (b"curarr", "\u{21b7}"),
+    (b"curarrm", "\u{293c}"),
+    (b"curlyeqprec", "\u{22de}"),
+    (b"curlyeqsucc", "\u{22df}"),
+    (b"curlyvee", "\u{22ce}"),
+    (b"curlywedge", "\u{22cf}"),
+    (b"curren", "\u{a4}"),
+    (b"curvearrowleft", "\u{21b6}"),
+    (b"curvearrowright", "\u{21b7}"),
+    (b"cuvee", "\u{22ce}"),
+    (b"cuwed", "\u{22cf}"),
+    (b"cwconint", "\u{2232}"),
+    (b"cwint", "\u{2231}"),
+    (b"cylcty", "\u{232d}"),
+    (b"dArr", "\u{21d3}"),
+    (b"dHar", "\u{2965}"),
+    (b"dagger", "\u{2020}"),
+    (b"daleth", "\u{2138}"),
+    (b"darr", "\u{2193}"),
+    (b"dash", "\u{2010}"),
+    (b"dashv", "\u{22a3}"),
+    (b"dbkarow", "\u{290f}"),
+    (b"dblac", "\u{2dd}"),
+    (b"dcaron", "\u{10f}"),
+    (b"dcy", "\u{434}"),
+    (b"dd", "\u{2146}"),
+    (b"ddagger", "\u{2021}"),
+    (b"ddarr", "\u{21ca}"),
+    (b"ddotseq", "\u{2a77}"),
+    (b"deg", "\u{b0}"),
+    (b"delta", "\u{3b4}"),
+    (b"demptyv", "\u{29b1}"),
+    (b"dfisht", "\u{297f}"),
+    (b"dfr", "\u{1d521}"),
+    (b"dharl", "\u{21c3}"),
+    (b"dharr", "\u{21c2}"),
+    (b"diam", "\u{22c4}"),
+    (b"diamond", "\u{22c4}"),
+    (b"diamondsuit", "\u{2666}"),
+    (b"diams", "\u{2666}"),
+    (b"die", "\u{a8}"),
+    (b"digamma", "\u{3dd}"),
+    (b"disin", "\u{22f2}"),
+    (b"div", "\u{f7}"),
+    (b"divide", "\u{f7}"),
+    (b"divideontimes", "\u{22c7}"),
+    (b"divonx", "\u{22c7}"),
+    (b"djcy", "\u{452}"),
+    (b"dlcorn", "\u{231e}"),
+    (b"dlcrop", "\u{230d}"),
+    (b"dollar", "$"),
+    (b"dopf", "\u{1d555}"),
+    (b"dot", "\u{2d9}"),
+    (b"doteq", "\u{2250}"),
+    (b"doteqdot", "\u{2251}"),
+    (b"dotminus", "\u{2238}"),
+    (b"dotplus", "\u{2214}"),
+    (b"dotsquare", "\u{22a1}"),
+    (b"doublebarwedge", "\u{2306}"),
+    (b"downarrow", "\u{2193}"),
+    (b"downdownarrows", "\u{21ca}"),
+    (b"downharpoonleft", "\u{21c3}"),
+    (b"downharpoonright", "\u{21c2}"),
+    (b"drbkarow", "\u{2910}"),
+    (b"drcorn", "\u{231f}"),
+    (b"drcrop", "\u{230c}"),
+    (b"dscr", "\u{1d4b9}"),
+    (b"dscy", "\u{455}"),
+    (b"dsol", "\u{29f6}"),
+    (b"dstrok", "\u{111}"),
+    (b"dtdot", "\u{22f1}"),
+    (b"dtri", "\u{25bf}"),
+    (b"dtrif", "\u{25be}"),
+    (b"duarr", "\u{21f5}"),
+    (b"duhar", "\u{296f}"),
+    (b"dwangle", "\u{29a6}"),
+    (b"dzcy", "\u{45f}"),
+    (b"dzigrarr", "\u{27ff}"),
+    (b"eDDot", "\u{2a77}"),
+    (b"eDot", "\u{2251}"),
+    (b"eacute", "\u{e9}"),
+    (b"easter", "\u{2a6e}"),
+    (b"ecaron", "\u{11b}"),
+    (b"ecir", "\u{2256}"),
+    (b"ecirc", "\u{ea}"),
+    (b"ecolon", "\u{2255}"),
+    (b"ecy", "\u{44d}"),
+    (b"edot", "\u{117}"),
+    (b"ee", "\u{2147}"),
+    (b"efDot", "\u{2252}"),
+    (b"efr", "\u{1d522}"),
+    (b"eg", "\u{2a9a}"),
+    (b"egrave", "\u{e8}"),
+    (b"egs", "\u{2a96}"),
+    (b"egsdot", "\u{2a98}"),
+    (b"el", "\u{2a99}"),
+    (b"elinters", "\u{23e7}"),
+    (b"ell", "\u{2113}"),
+    (b"els", "\u{2a95}"),
+    (b"elsdot", "\u{2a97}"),
+    (b"emacr", "\u{113}"),
+    (b"empty", "\u{2205}"),
+    (b"emptyset", "\u{2205}"),
+    (b"emptyv", "\u{2205}"),
+    (b"emsp", "\u{2003}"),
+    (b"emsp13", "\u{2004}"),
+    (b"emsp14", "\u{2005}"),
+    (b"eng", "\u{14b}"),
+    (b"ensp", "\u{2002}"),
+    (b"eogon", "\u{119}"),
+    (b"eopf", "\u{1d556}"),
+    (b"epar", "\u{22d5}"),
+    (b"eparsl", "\u{29e3}"),
+    (b"eplus", "\u{2a71}"),
+    (b"epsi", "\u{3b5}"),
+    (b"epsilon", "\u{3b5}"),
+    (b"epsiv", "\u{3f5}"),
+    (b"eqcirc", "\u{2256}"),
+    (b"eqcolon", "\u{2255}"),
+    (b"eqsim", "\u{2242}"),
+    (b"eqslantgtr", "\u{2a96}"),
+    (b"eqslantless", "\u{2a95}"),
+    (b"equals", "="),
+    (b"equest", "\u{225f}"),
+    (b"equiv", "\u{2261}"),
+    (b"equivDD", "\u{2a78}"),
+    (b"eqvparsl", "\u{29e5}"),
+    (b"erDot", "\u{2253}"),
+    (b"erarr", "\u{2971}"),
+    (b"escr", "\u{212f}"),
+    (b"esdot", "\u{2250}"),
+    (b"esim", "\u{2242}"),
+    (b"eta", "\u{3b7}"),
+    (b"eth", "\u{f0}"),
+    (b"euml", "\u{eb}"),
+    (b"euro", "\u{20ac}"),
+    (b"excl", "!"),
+    (b"exist", "\u{2203}"),
+    (b"expectation", "\u{2130}"),
+    (b"exponentiale", "\u{2147}"),
+    (b"fallingdotseq", "\u{2252}"),
+    (b"fcy", "\u{444}"),
+    (b"female", "\u{2640}"),
+    (b"ffilig", "\u{fb03}"),
+    (b"fflig", "\u{fb00}"),
+    (b"ffllig", "\u{fb04}"),
+    (b"ffr", "\u{1d523}"),
+    (b"filig", "\u{fb01}"),
+    (b"fjlig", "fj"),
+    (b"flat", "\u{266d}"),
+    (b"fllig", "\u{fb02}"),
+    (b"fltns", "\u{25b1}"),
+    (b"fnof", "\u{192}"),
+    (b"fopf", "\u{1d557}"),
+    (b"forall", "\u{2200}"),
+    (b"fork", "\u{22d4}"),
+    (b"forkv", "\u{2ad9}"),
+    (b"fpartint", "\u{2a0d}"),
+    (b"frac12", "\u{bd}"),
+    (b"frac13", "\u{2153}"),
+    (b"frac14", "\u{bc}"),
+    (b"frac15", "\u{2155}"),
+    (b"frac16", "\u{2159}"),
+    (b"frac18", "\u{215b}"),
+    (b"frac23", "\u{2154}"),
+    (b"frac25", "\u{2156}"),
+    (b"frac34", "\u{be}"),
+    (b"frac35", "\u{2157}"),
+    (b"frac38", "\u{215c}"),
+    (b"frac45", "\u{2158}"),
+    (b"frac56", "\u{215a}"),
+    (b"frac58", "\u{215d}"),
+    (b"frac78", "\u{215e}"),
+    (b"frasl", "\u{2044}"),
+    (b"frown", "\u{2322}"),
+    (b"fscr", "\u{1d4bb}"),
+    (b"gE", "\u{2267}"),
+    (b"gEl", "\u{2a8c}"),
+    (b"gacute", "\u{1f5}"),
+    (b"gamma", "\u{3b3}"),
+    (b"gammad", "\u{3dd}"),
+    (b"gap", "\u{2a86}"),
+    (b"gbreve", "\u{11f}"),
+    (b"gcirc", "\u{11d}"),
+    (b"gcy", "\u{433}"),
+    (b"gdot", "\u{121}"),
+    (b"ge", "\u{2265}"),
+    (b"gel", "\u{22db}"),
+    (b"geq", "\u{2265}"),
+    (b"geqq", "\u{2267}"),
+    (b"geqslant", "\u{2a7e}"),
+    (b"ges", "\u{2a7e}"),
+    (b"gescc", "\u{2aa9}"),
+    (b"gesdot", "\u{2a80}"),
+    (b"gesdoto", "\u{2a82}"),
+    (b"gesdotol", "\u{2a84}"),
+    (b"gesl", "\u{22db}\u{fe00}"),
+    (b"gesles", "\u{2a94}"),
+    (b"gfr", "\u{1d524}"),
+    (b"gg", "\u{226b}"),
+    (b"ggg", "\u{22d9}"),
+    (b"gimel", "\u{2137}"),
+    (b"gjcy", "\u{453}"),
+    (b"gl", "\u{2277}"),
+    (b"glE", "\u{2a92}"),
+    (b"gla", "\u{2aa5}"),
+    (b"glj", "\u{2aa4}"),
+    (b"gnE", "\u{2269}"),
+    (b"gnap", "\u{2a8a}"),
+    (b"gnapprox", "\u{2a8a}"),
+    (b"gne", "\u{2a88}"),
+    (b"gneq", "\u{2a88}"),
+    (b"gneqq", "\u{2269}"),
+    (b"gnsim", "\u{22e7}"),
+    (b"gopf", "\u{1d558}"),
+    (b"grave", "`"),
+    (b"gscr", "\u{210a}"),
+    (b"gsim", "\u{2273}"),
+    (b"gsime", "\u{2a8e}"),
+    (b"gsiml", "\u{2a90}"),
+    (b"gt", ">"),
+    (b"gtcc", "\u{2aa7}"),
+    (b"gtcir", "\u{2a7a}"),
+    (b"gtdot", "\u{22d7}"),
+    (b"gtlPar", "\u{2995}"),
+    (b"gtquest", "\u{2a7c}"),
+    (b"gtrapprox", "\u{2a86}"),
+    (b"gtrarr", "\u{2978}"),
+    (b"gtrdot", "\u{22d7}"),
+    (b"gtreqless", "\u{22db}"),
+    (b"gtreqqless", "\u{2a8c}"),
+    (b"gtrless", "\u{2277}"),
+    (b"gtrsim", "\u{2273}"),
+    (b"gvertneqq", "\u{2269}\u{fe00}"),
+    (b"gvnE", "\u{2269}\u{fe00}"),
+    (b"hArr", "\u{21d4}"),
+    (b"hairsp", "\u{200a}"),
+    (b"half", "\u{bd}"),
+    (b"hamilt", "\u{210b}"),
+    (b"hardcy", "\u{44a}"),
+    (b"harr", "\u{2194}"),
+    (b"harrcir", "\u{2948}"),
+    (b"harrw", "\u{21ad}"),
+    (b"hbar", "\u{210f}"),
+    (b"hcirc", "\u{125}"),
+    (b"hearts", "\u{2665}"),
+    (b"heartsuit", "\u{2665}"),
+    (b"hellip", "\u{2026}"),
+    (b"hercon", "\u{22b9}"),
+    (b"hfr", "\u{1d525}"),
+    (b"hksearow", "\u{2925}"),
+    (b"hkswarow", "\u{2926}"),
+    (b"hoarr", "\u{21ff}"),
+    (b"homtht", "\u{223b}"),
+    (b"hookleftarrow", "\u{21a9}"),
+    (b"hookrightarrow", "\u{21aa}"),
+    (b"hopf", "\u{1d559}"),
+    (b"horbar", "\u{2015}"),
+    (b"hscr", "\u{1d4bd}"),
+    (b"hslash", "\u{210f}"),
+    (b"hstrok", "\u{127}"),
+    (b"hybull", "\u{2043}"),
+    (b"hyphen", "\u{2010}"),
+    (b"iacute", "\u{ed}"),
+    (b"ic", "\u{2063}"),
+    (b"icirc", "\u{ee}"),
+    (b"icy", "\u{438}"),
+    (b"iecy", "\u{435}"),
+    (b"iexcl", "\u{a1}"),
+    (b"iff", "\u{21d4}"),
+    (b"ifr", "\u{1d526}"),
+    (b"igrave", "\u{ec}"),
+    (b"ii", "\u{2148}"),
+    (b"iiiint", "\u{2a0c}"),
+    (b"iiint", "\u{222d}"),
+    (b"iinfin", "\u{29dc}"),
+    (b"iiota", "\u{2129}"),
+    (b"ijlig", "\u{133}"),
+    (b"imacr", "\u{12b}"),
+    (b"image", "\u{2111}"),
+    (b"imagline", "\u{2110}"),
+    (b"imagpart", "\u{2111}"),
+    (b"imath", "\u{131}"),
+    (b"imof", "\u{22b7}"),
+    (b"imped", "\u{1b5}"),
+    (b"in", "\u{2208}"),
+    (b"incare", "\u{2105}"),
+    (b"infin", "\u{221e}"),
+    (b"infintie", "\u{29dd}"),
+    (b"inodot", "\u{131}"),
+    (b"int", "\u{222b}"),
+    (b"intcal", "\u{22ba}"),
+    (b"integers", "\u{2124}"),
+    (b"intercal", "\u{22ba}"),
+    (b"intlarhk", "\u{2a17}"),
+    (b"intprod", "\u{2a3c}"),
+    (b"iocy", "\u{451}"),
+    (b"iogon", "\u{12f}"),
+    (b"iopf", "\u{1d55a}"),
+    (b"iota", "\u{3b9}"),
+    (b"iprod", "\u{2a3c}"),
+    (b"iquest", "\u{bf}"),
+    (b"iscr", "\u{1d4be}"),
+    (b"isin", "\u{2208}"),
+    (b"isinE", "\u{22f9}"),
+    (b"isindot", "\u{22f5}"),
+    (b"isins", "\u{22f4}"),
+    (b"isinsv", "\u{22f3}"),
+    (b"isinv", "\u{2208}"),
+    (b"it", "\u{2062}"),
+    (b"itilde", "\u{129}"),
+    (b"iukcy", "\u{456}"),
+    (b"iuml", "\u{ef}"),
+    (b"jcirc", "\u{135}"),
+    (b"jcy", "\u{439}"),
+    (b"jfr", "\u{1d527}"),
+    (b"jmath", "\u{237}"),
+    (b"jopf", "\u{1d55b}"),
+    (b"jscr", "\u{1d4bf}"),
+    (b"jsercy", "\u{458}"),
+    (b"jukcy", "\u{454}"),
+    (b"kappa", "\u{3ba}"),
+    (b"kappav", "\u{3f0}"),
+    (b"kcedil", "\u{137}"),
+    (b"kcy", "\u{43a}"),
+    (b"kfr", "\u{1d528}"),
+    (b"kgreen", "\u{138}"),
+    (b"khcy", "\u{445}"),
+    (b"kjcy", "\u{45c}"),
+    (b"kopf", "\u{1d55c}"),
+    (b"kscr", "\u{1d4c0}"),
+    (b"lAarr", "\u{21da}"),
+    (b"lArr", "\u{21d0}"),
+    (b"lAtail", "\u{291b}"),
+    (b"lBarr", "\u{290e}"),
+    (b"lE", "\u{2266}"),
+    (b"lEg", "\u{2a8b}"),
+    (b"lHar", "\u{2962}"),
+    (b"lacute", "\u{13a}"),
+    (b"laemptyv", "\u{29b4}"),
+    (b"lagran", "\u{2112}"),
+    (b"lambda", "\u{3bb}"),
+    (b"lang", "\u{27e8}"),
+    (b"langd", "\u{2991}"),
+    (b"langle", "\u{27e8}"),
+    (b"lap", "\u{2a85}"),
+    (b"laquo", "\u{ab}"),
+    (b"larr", "\u{2190}"),
+    (b"larrb", "\u{21e4}"),
+    (b"larrbfs", "\u{291f}"),
+    (b"larrfs", "\u{291d}"),
+    (b"larrhk", "\u{21a9}"),
+    (b"larrlp", "\u{21ab}"),
+    (b"larrpl", "\u{2939}"),
+    (b"larrsim", "\u{2973}"),
+    (b"larrtl", "\u{21a2}"),
+    (b"lat", "\u{2aab}"),
+    (b"latail", "\u{2919}"),
+    (b"late", "\u{2aad}"),
+    (b"lates", "\u{2aad}\u{fe00}"),
+    (b"lbarr", "\u{290c}"),
+    (b"lbbrk", "\u{2772}"),
+    (b"lbrace", "{"),
+    (b"lbrack", "["),
+    (b"lbrke", "\u{298b}"),
+    (b"lbrksld", "\u{298f}"),
+    (b"lbrkslu", "\u{298d}"),
+    (b"lcaron", "\u{13e}"),
+    (b"lcedil", "\u{13c}"),
+    (b"lceil", "\u{2308}"),
+    (b"lcub", "{"),
+    (b"lcy", "\u{43b}"),
+    (b"ldca", "\u{2936}"),
+    (b"ldquo", "\u{201c}"),
+    (b"ldquor", "\u{201e}"),
+    (b"ldrdhar", "\u{2967}"),
+    (b"ldrushar", "\u{294b}"),
+    (b"ldsh", "\u{21b2}"),
+    (b"le", "\u{2264}"),
+    (b"leftarrow", "\u{2190}"),
+    (b"leftarrowtail", "\u{21a2}"),
+    (b"leftharpoondown", "\u{21bd}"),
+    (b"leftharpoonup", "\u{21bc}"),
+    (b"leftleftarrows", "\u{21c7}"),
+    (b"leftrightarrow", "\u{2194}"),
+    (b"leftrightarrows", "\u{21c6}"),
+    (b"leftrightharpoons", "\u{21cb}"),
+    (b"leftrightsquigarrow", "\u{21ad}"),
+    (b"leftthreetimes", "\u{22cb}"),
+    (b"leg", "\u{22da}"),
+    (b"leq", "\u{2264}"),
+    (b"leqq", "\u{2266}"),
+    (b"leqslant", "\u{2a7d}"),
+    (b"les", "\u{2a7d}"),
+    (b"lescc", "\u{2aa8}"),
+    (b"lesdot", "\u{2a7f}"),
+    (b"lesdoto", "\u{2a81}"),
+    (b"lesdotor", "\u{2a83}"),
+    (b"lesg", "\u{22da}\u{fe00}"),
+    (b"lesges", "\u{2a93}"),
+    (b"lessapprox", "\u{2a85}"),
+    (b"lessdot", "\u{22d6}"),
+    (b"lesseqgtr", "\u{22da}"),
+    (b"lesseqqgtr", "\u{2a8b}"),
+    (b"lessgtr", "\u{2276}"),
+    (b"lesssim", "\u{2272}"),
+    (b"lfisht", "\u{297c}"),
+    (b"lfloor", "\u{230a}"),
+    (b"lfr", "\u{1d529}"),
+    (b"lg", "\u{2276}"),
+    (b"lgE", "\u{2a91}"),
+    (b"lhard", "\u{21bd}"),
+    (b"lharu", "\u{21bc}"),
+    (b"lharul", "\u{296a}"),
+    (b"lhblk", "\u{2584}"),
+    (b"ljcy", "\u{459}"),
+    (b"ll", "\u{226a}"),
+    (b"llarr", "\u{21c7}"),
+    (b"llcorner", "\u{231e}"),
+    (b"llhard", "\u{296b}"),
+    (b"lltri", "\u{25fa}"),
+    (b"lmidot", "\u{140}"),
+    (b"lmoust", "\u{23b0}"),
+    (b"lmoustache", "\u{23b0}"),
+    (b"lnE", "\u{2268}"),
+    (b"lnap", "\u{2a89}"),
+    (b"lnapprox", "\u{2a89}"),
+    (b"lne", "\u{2a87}"),
+    (b"lneq", "\u{2a87}"),
+    (b"lneqq", "\u{2268}"),
+    (b"lnsim", "\u{22e6}"),
+    (b"loang", "\u{27ec}"),
+    (b"loarr", "\u{21fd}"),
+    (b"lobrk", "\u{27e6}"),
+    (b"longleftarrow", "\u{27f5}"),
+    (b"longleftrightarrow", "\u{27f7}"),
+    (b"longmapsto", "\u{27fc}"),
+    (b"longrightarrow", "\u{27f6}"),
+    (b"looparrowleft", "\u{21ab}"),
+    (b"looparrowright", "\u{21ac}"),
+    (b"lopar", "\u{2985}"),
+    (b"lopf", "\u{1d55d}"),
+    (b"loplus", "\u{2a2d}"),
+    (b"lotimes", "\u{2a34}"),
+    (b"lowast", "\u{2217}"),
+    (b"lowbar", "_"),
+    (b"loz", "\u{25ca}"),
+    (b"lozenge", "\u{25ca}"),
+    (b"lozf", "\u{29eb}"),
+    (b"lpar", "("),
+    (b"lparlt", "\u{2993}"),
+    (b"lrarr", "\u{21c6}"),
+    (b"lrcorner", "\u{231f}"),
+    (b"lrhar", "\u{21cb}"),
+    (b"lrhard", "\u{296d}"),
+    (b"lrm", "\u{200e}"),
+    (b"lrtri", "\u{22bf}"),
+    (b"lsaquo", "\u{2039}"),
+    (b"lscr", "\u{1d4c1}"),
+    (b"lsh", "\u{21b0}"),
+    (b"lsim", "\u{2272}"),
+    (b"lsime", "\u{2a8d}"),
+    (b"lsimg", "\u{2a8f}"),
+    (b"lsqb", "["),
+    (b"lsquo", "\u{2018}"),
+    (b"lsquor", "\u{201a}"),
+    (b"lstrok", "\u{142}"),
+    (b"lt", "<"),
+    (b"ltcc", "\u{2aa6}"),
+    (b"ltcir", "\u{2a79}"),
+    (b"ltdot", "\u{22d6}"),
+    (b"lthree", "\u{22cb}"),
+    (b"ltimes", "\u{22c9}"),
+    (b"ltlarr", "\u{2976}"),
+    (b"ltquest", "\u{2a7b}"),
+    (b"ltrPar", "\u{2996}"),
+    (b"ltri", "\u{25c3}"),
+    (b"ltrie", "\u{22b4}"),
+    (b"ltrif", "\u{25c2}"),
+    (b"lurdshar", "\u{294a}"),
+    (b"luruhar", "\u{2966}"),
+    (b"lvertneqq", "\u{2268}\u{fe00}"),
+    (b"lvnE", "\u{2268}\u{fe00}"),
+    (b"mDDot", "\u{223a}"),
+    (b"macr", "\u{af}"),
+    (b"male", "\u{2642}"),
+    (b"malt", "\u{2720}"),
+    (b"maltese", "\u{2720}"),
+    (b"map", "\u{21a6}"),
+    (b"mapsto", "\u{21a6}"),
+    (b"mapstodown", "\u{21a7}"),
+    (b"mapstoleft", "\u{21a4}"),
+    (b"mapstoup", "\u{21a5}"),
+    (b"marker", "\u{25ae}"),
+    (b"mcomma", "\u{2a29}"),
+    (b"mcy", "\u{43c}"),
+    (b"mdash", "\u{2014}"),
+    (b"measuredangle", "\u{2221}"),
+    (b"mfr", "\u{1d52a}"),
+    (b"mho", "\u{2127}"),
+    (b"micro", "\u{b5}"),
+    (b"mid", "\u{2223}"),
+    (b"midast", "*"),
+    (b"midcir", "\u{2af0}"),
+    (b"middot", "\u{b7}"),
+    (b"minus", "\u{2212}"),
+    (b"minusb", "\u{229f}"),
+    (b"minusd", "\u{2238}"),
+    (b"minusdu", "\u{2a2a}"),
+    (b"mlcp", "\u{2adb}"),
+    (b"mldr", "\u{2026}"),
+    (b"mnplus", "\u{2213}"),
+    (b"models", "\u{22a7}"),
+    (b"mopf", "\u{1d55e}"),
+    (b"mp", "\u{2213}"),
+    (b"mscr", "\u{1d4c2}"),
+    (b"mstpos", "\u{223e}"),
+    (b"mu", "\u{3bc}"),
+    (b"multimap", "\u{22b8}"),
+    (b"mumap", "\u{22b8}"),
+    (b"nGg", "\u{22d9}\u{338}"),
+    (b"nGt", "\u{226b}\u{20d2}"),
+    (b"nGtv", "\u{226b}\u{338}"),
+    (b"nLeftarrow", "\u{21cd}"),
+    (b"nLeftrightarrow", "\u{21ce}"),
+    (b"nLl", "\u{22d8}\u{338}"),
+    (b"nLt", "\u{226a}\u{20d2}"),
+    (b"nLtv", "\u{226a}\u{338}"),
+    (b"nRightarrow", "\u{21cf}"),
+    (b"nVDash", "\u{22af}"),
+    (b"nVdash", "\u{22ae}"),
+    (b"nabla", "\u{2207}"),
+    (b"nacute", "\u{144}"),
+    (b"nang", "\u{2220}\u{20d2}"),
+    (b"nap", "\u{2249}"),
+    (b"napE", "\u{2a70}\u{338}"),
+    (b"napid", "\u{224b}\u{338}"),
+    (b"napos", "\u{149}"),
+    (b"napprox", "\u{2249}"),
+    (b"natur", "\u{266e}"),
+    (b"natural", "\u{266e}"),
+    (b"naturals", "\u{2115}"),
+    (b"nbsp", "\u{a0}"),
+    (b"nbump", "\u{224e}\u{338}"),
+    (b"nbumpe", "\u{224f}\u{338}"),
+    (b"ncap", "\u{2a43}"),
+    (b"ncaron", "\u{148}"),
+    (b"ncedil", "\u{146}"),
+    (b"ncong", "\u{2247}"),
+    (b"ncongdot", "\u{2a6d}\u{338}"),
+    (b"ncup", "\u{2a42}"),
+    (b"ncy", "\u{43d}"),
+    (b"ndash", "\u{2013}"),
+    (b"ne", "\u{2260}"),
+    (b"neArr", "\u{21d7}"),
+    (b"nearhk", "\u{2924}"),
+    (b"nearr", "\u{2197}"),
+    (b"nearrow", "\u{2197}"),
+    (b"nedot", "\u{2250}\u{338}"),
+    (b"nequiv", "\u{2262}"),
+    (b"nesear", "\u{2928}"),
+    (b"nesim", "\u{2242}\u{338}"),
+    (b"nexist", "\u{2204}"),
+    (b"nexists", "\u{2204}"),
+    (b"nfr", "\u{1d52b}"),
+    (b"ngE", "\u{2267}\u{338}"),
+    (b"nge", "\u{2271}"),
+    (b"ngeq", "\u{2271}"),
+    (b"ngeqq", "\u{2267}\u{338}"),
+    (b"ngeqslant", "\u{2a7e}\u{338}"),
+    (b"nges", "\u{2a7e}\u{338}"),
+    (b"ngsim", "\u{2275}"),
+    (b"ngt", "\u{226f}"),
+    (b"ngtr", "\u{226f}"),
+    (b"nhArr", "\u{21ce}"),
+    (b"nharr", "\u{21ae}"),
+    (b"nhpar", "\u{2af2}"),
+    (b"ni", "\u{220b}"),
+    (b"nis", "\u{22fc}"),
+    (b"nisd", "\u{22fa}"),
+    (b"niv", "\u{220b}"),
+    (b"njcy", "\u{45a}"),
+    (b"nlArr", "\u{21cd}"),
+    (b"nlE", "\u{2266}\u{338}"),
+    (b"nlarr", "\u{219a}"),
+    (b"nldr", "\u{2025}"),
+    (b"nle", "\u{2270}"),
+    (b"nleftarrow", "\u{219a}"),
+    (b"nleftrightarrow", "\u{21ae}"),
+    (b"nleq", "\u{2270}"),
+    (b"nleqq", "\u{2266}\u{338}"),
+    (b"nleqslant", "\u{2a7d}\u{338}"),
+    (b"nles", "\u{2a7d}\u{338}"),
+    (b"nless", "\u{226e}"),
+    (b"nlsim", "\u{2274}"),
+    (b"nlt", "\u{226e}"),
+    (b"nltri", "\u{22ea}"),
+    (b"nltrie", "\u{22ec}"),
+    (b"nmid", "\u{2224}"),
+    (b"nopf", "\u{1d55f}"),
+    (b"not", "\u{ac}"),
+    (b"notin", "\u{2209}"),
+    (b"notinE", "\u{22f9}\u{338}"),
+    (b"notindot", "\u{22f5}\u{338}"),
+    (b"notinva", "\u{2209}"),
+    (b"notinvb", "\u{22f7}"),
+    (b"notinvc", "\u{22f6}"),
+    (b"notni", "\u{220c}"),
+    (b"notniva", "\u{220c}"),
+    (b"notnivb", "\u{22fe}"),
+    (b"notnivc", "\u{22fd}"),
+    (b"npar", "\u{2226}"),
+    (b"nparallel", "\u{2226}"),
+    (b"nparsl", "\u{2afd}\u{20e5}"),
+    (b"npart", "\u{2202}\u{338}"),
+    (b"npolint", "\u{2a14}"),
+    (b"npr", "\u{2280}"),
+    (b"nprcue", "\u{22e0}"),
+    (b"npre", "\u{2aaf}\u{338}"),
+    (b"nprec", "\u{2280}"),
+    (b"npreceq", "\u{2aaf}\u{338}"),
+    (b"nrArr", "\u{21cf}"),
+    (b"nrarr", "\u{219b}"),
+    (b"nrarrc", "\u{2933}\u{338}"),
+    (b"nrarrw", "\u{219d}\u{338}"),
+    (b"nrightarrow", "\u{219b}"),
+    (b"nrtri", "\u{22eb}"),
+    (b"nrtrie", "\u{22ed}"),
+    (b"nsc", "\u{2281}"),
+    (b"nsccue", "\u{22e1}"),
+    (b"nsce", "\u{2ab0}\u{338}"),
+    (b"nscr", "\u{1d4c3}"),
+    (b"nshortmid", "\u{2224}"),
+    (b"nshortparallel", "\u{2226}"),
+    (b"nsim", "\u{2241}"),
+    (b"nsime", "\u{2244}"),
+    (b"nsimeq", "\u{2244}"),
+    (b"nsmid", "\u{2224}"),
+    (b"nspar", "\u{2226}"),
+    (b"nsqsube", "\u{22e2}"),
+    (b"nsqsupe", "\u{22e3}"),
+    (b"nsub", "\u{2284}"),
+    (b"nsubE", "\u{2ac5}\u{338}"),
+    (b"nsube", "\u{2288}"),
+    (b"nsubset", "\u{2282}\u{20d2}"),
+    (b"nsubseteq", "\u{2288}"),
+    (b"nsubseteqq", "\u{2ac5}\u{338}"),
+    (b"nsucc", "\u{2281}"),
+    (b"nsucceq", "\u{2ab0}\u{338}"),
+    (b"nsup", "\u{2285}"),
+    (b"nsupE", "\u{2ac6}\u{338}"),
+    (b"nsupe", "\u{2289}"),
+    (b"nsupset", "\u{2283}\u{20d2}"),
+    (b"nsupseteq", "\u{2289}"),
+    (b"nsupseteqq", "\u{2ac6}\u{338}"),
+    (b"ntgl", "\u{2279}"),
+    (b"ntilde", "\u{f1}"),
+    (b"ntlg", "\u{2278}"),
+    (b"ntriangleleft", "\u{22ea}"),
+    (b"ntrianglelefteq", "\u{22ec}"),
+    (b"ntriangleright", "\u{22eb}"),
+    (b"ntrianglerighteq", "\u{22ed}"),
+    (b"nu", "\u{3bd}"),
+    (b"num", "#"),
+    (b"numero", "\u{2116}"),
+    (b"numsp", "\u{2007}"),
+    (b"nvDash", "\u{22ad}"),
+    (b"nvHarr", "\u{2904}"),
+    (b"nvap", "\u{224d}\u{20d2}"),
+    (b"nvdash", "\u{22ac}"),
+    (b"nvge", "\u{2265}\u{20d2}"),
+    (b"nvgt", ">\u{20d2}"),
+    (b"nvinfin", "\u{29de}"),
+    (b"nvlArr", "\u{2902}"),
+    (b"nvle", "\u{2264}\u{20d2}"),
+    (b"nvlt", "<\u{20d2}"),
+    (b"nvltrie", "\u{22b4}\u{20d2}"),
+    (b"nvrArr", "\u{2903}"),
+    (b"nvrtrie", "\u{22b5}\u{20d2}"),
+    (b"nvsim", "\u{223c}\u{20d2}"),
+    (b"nwArr", "\u{21d6}"),
+    (b"nwarhk", "\u{2923}"),
+    (b"nwarr", "\u{2196}"),
+    (b"nwarrow", "\u{2196}"),
+    (b"nwnear", "\u{2927}"),
+    (b"oS", "\u{24c8}"),
+    (b"oacute", "\u{f3}"),
+    (b"oast", "\u{229b}"),
+    (b"ocir", "\u{229a}"),
+    (b"ocirc", "\u{f4}"),
+    (b"ocy", "\u{43e}"),
+    (b"odash", "\u{229d}"),
+    (b"odblac", "\u{151}"),
+    (b"odiv", "\u{2a38}"),
+    (b"odot", "\u{2299}"),
+    (b"odsold", "\u{29bc}"),
+    (b"oelig", "\u{153}"),
+    (b"ofcir", "\u{29bf}"),
+    (b"ofr", "\u{1d52c}"),
+    (b"ogon", "\u{2db}"),
+    (b"ograve", "\u{f2}"),
+    (b"ogt", "\u{29c1}"),
+    (b"ohbar", "\u{29b5}"),
+    (b"ohm", "\u{3a9}"),
+    (b"oint", "\u{222e}"),
+    (b"olarr", "\u{21ba}"),
+    (b"olcir", "\u{29be}"),
+    (b"olcross", "\u{29bb}"),
+    (b"oline", "\u{203e}"),
+    (b"olt", "\u{29c0}"),
+    (b"omacr", "\u{14d}"),
+    (b"omega", "\u{3c9}"),
+    (b"omicron", "\u{3bf}"),
+    (b"omid", "\u{29b6}"),
+    (b"ominus", "\u{2296}"),
+    (b"oopf", "\u{1d560}"),
+    (b"opar", "\u{29b7}"),
+    (b"operp", "\u{29b9}"),
+    (b"oplus", "\u{2295}"),
+    (b"or", "\u{2228}"),
+    (b"orarr", "\u{21bb}"),
+    (b"ord", "\u{2a5d}"),
+    (b"order", "\u{2134}"),
+    (b"orderof", "\u{2134}"),
+    (b"ordf", "\u{aa}"),
+    (b"ordm", "\u{ba}"),
+    (b"origof", "\u{22b6}"),
+    (b"oror", "\u{2a56}"),
+    (b"orslope", "\u{2a57}"),
+    (b"orv", "\u{2a5b}"),
+    (b"oscr", "\u{2134}"),
+    (b"oslash", "\u{f8}"),
+    (b"osol", "\u{2298}"),
+    (b"otilde", "\u{f5}"),
+    (b"otimes", "\u{2297}"),
+    (b"otimesas", "\u{2a36}"),
+    (b"ouml", "\u{f6}"),
+    (b"ovbar", "\u{233d}"),
+    (b"par", "\u{2225}"),
+    (b"para", "\u{b6}"),
+    (b"parallel", "\u{2225}"),
+    (b"parsim", "\u{2af3}"),
+    (b"parsl", "\u{2afd}"),
+    (b"part", "\u{2202}"),
+    (b"pcy", "\u{43f}"),
+    (b"percnt", "%"),
+    (b"period", "."),
+    (b"permil", "\u{2030}"),
+    (b"perp", "\u{22a5}"),
+    (b"pertenk", "\u{2031}"),
+    (b"pfr", "\u{1d52d}"),
+    (b"phi", "\u{3c6}"),
+    (b"phiv", "\u{3d5}"),
+    (b"phmmat", "\u{2133}"),
+    (b"phone", "\u{260e}"),
+    (b"pi", "\u{3c0}"),
+    (b"pitchfork", "\u{22d4}"),
+    (b"piv", "\u{3d6}"),
+    (b"planck", "\u{210f}"),
+    (b"planckh", "\u{210e}"),
+    (b"plankv", "\u{210f}"),
+    (b"plus", "+"),
+    (b"plusacir", "\u{2a23}"),
+    (b"plusb", "\u{229e}"),
+    (b"pluscir", "\u{2a22}"),
+    (b"plusdo", "\u{2214}"),
+    (b"plusdu", "\u{2a25}"),
+    (b"pluse", "\u{2a72}"),
+    (b"plusmn", "\u{b1}"),
+    (b"plussim", "\u{2a26}"),
+    (b"plustwo", "\u{2a27}"),
+    (b"pm", "\u{b1}"),
+    (b"pointint", "\u{2a15}"),
+    (b"popf", "\u{1d561}"),
+    (b"pound", "\u{a3}"),
+    (b"pr", "\u{227a}"),
+    (b"prE", "\u{2ab3}"),
+    (b"prap", "\u{2ab7}"),
+    (b"prcue", "\u{227c}"),
+    (b"pre", "\u{2aaf}"),
+    (b"prec", "\u{227a}"),
+    (b"precapprox", "\u{2ab7}"),
+    (b"preccurlyeq", "\u{227c}"),
+    (b"preceq", "\u{2aaf}"),
+    (b"precnapprox", "\u{2ab9}"),
+    (b"precneqq", "\u{2ab5}"),
+    (b"precnsim", "\u{22e8}"),
+    (b"precsim", "\u{227e}"),
+    (b"prime", "\u{2032}"),
+    (b"primes", "\u{2119}"),
+    (b"prnE", "\u{2ab5}"),
+    (b"prnap", "\u{2ab9}"),
+    (b"prnsim", "\u{22e8}"),
+    (b"prod", "\u{220f}"),
+    (b"profalar", "\u{232e}"),
+    (b"profline", "\u{2312}"),
+    (b"profsurf", "\u{2313}"),
+    (b"prop", "\u{221d}"),
+    (b"propto", "\u{221d}"),
+    (b"prsim", "\u{227e}"),
+    (b"prurel", "\u{22b0}"),
+    (b"pscr", "\u{1d4c5}"),
+    (b"psi", "\u{3c8}"),
+    (b"puncsp", "\u{2008}"),
+    (b"qfr", "\u{1d52e}"),
+    (b"qint", "\u{2a0c}"),
+    (b"qopf", "\u{1d562}"),
+    (b"qprime", "\u{2057}"),
+    (b"qscr", "\u{1d4c6}"),
+    (b"quaternions", "\u{210d}"),
+    (b"quatint", "\u{2a16}"),
+    (b"quest", "?"),
+    (b"questeq", "\u{225f}"),
+    (b"quot", "\""),
+    (b"rAarr", "\u{21db}"),
+    (b"rArr", "\u{21d2}"),
+    (b"rAtail", "\u{291c}"),
+    (b"rBarr", "\u{290f}"),
+    (b"rHar", "\u{2964}"),
+    (b"race", "\u{223d}\u{331}"),
+    (b"racute", "\u{155}"),
+    (b"radic", "\u{221a}"),
+    (b"raemptyv", "\u{29b3}"),
+    (b"rang", "\u{27e9}"),
+    (b"rangd", "\u{2992}"),
+    (b"range", "\u{29a5}"),
+    (b"rangle", "\u{27e9}"),
+    (b"raquo", "\u{bb}"),
+    (b"rarr", "\u{2192}"),
+    (b"rarrap", "\u{2975}"),
+    (b"rarrb", "\u{21e5}"),
+    (b"rarrbfs", "\u{2920}"),
+    (b"rarrc", "\u{2933}"),
+    (b"rarrfs", "\u{291e}"),
+    (b"rarrhk", "\u{21aa}"),
+    (b"rarrlp", "\u{21ac}"),
+    (b"rarrpl", "\u{2945}"),
+    (b"rarrsim", "\u{2974}"),
+    (b"rarrtl", "\u{21a3}"),
+    (b"rarrw", "\u{219d}"),
+    (b"ratail", "\u{291a}"),
+    (b"ratio", "\u{2236}"),
+    (b"rationals", "\u{211a}"),
+    (b"rbarr", "\u{290d}"),
+    (b"rbbrk", "\u{2773}"),
+    (b"rbrace", "}"),
+    (b"rbrack", "]"),
+    (b"rbrke", "\u{298c}"),
+    (b"rbrksld", "\u{298e}"),
+    (b"rbrkslu", "\u{2990}"),
+    (b"rcaron", "\u{159}"),
+    (b"rcedil", "\u{157}"),
+    (b"rceil", "\u{2309}"),
+    (b"rcub", "}"),
+    (b"rcy", "\u{440}"),
+    (b"rdca", "\u{2937}"),
+    (b"rdldhar", "\u{2969}"),
+    (b"rdquo", "\u{201d}"),
+    (b"rdquor", "\u{201d}"),
+    (b"rdsh", "\u{21b3}"),
+    (b"real", "\u{211c}"),
+    (b"realine", "\u{211b}"),
+    (b"realpart", "\u{211c}"),
+    (b"reals", "\u{211d}"),
+    (b"rect", "\u{25ad}"),
+    (b"reg", "\u{ae}"),
+    (b"rfisht", "\u{297d}"),
+    (b"rfloor", "\u{230b}"),
+    (b"rfr", "\u{1d52f}"),
+    (b"rhard", "\u{21c1}"),
+    (b"rharu", "\u{21c0}"),
+    (b"rharul", "\u{296c}"),
+    (b"rho", "\u{3c1}"),
+    (b"rhov", "\u{3f1}"),
+    (b"rightarrow", "\u{2192}"),
+    (b"rightarrowtail", "\u{21a3}"),
+    (b"rightharpoondown", "\u{21c1}"),
+    (b"rightharpoonup", "\u{21c0}"),
+    (b"rightleftarrows", "\u{21c4}"),
+    (b"rightleftharpoons", "\u{21cc}"),
+    (b"rightrightarrows", "\u{21c9}"),
+    (b"rightsquigarrow", "\u{219d}"),
+    (b"rightthreetimes", "\u{22cc}"),
+    (b"ring", "\u{2da}"),
+    (b"risingdotseq", "\u{2253}"),
+    (b"rlarr", "\u{21c4}"),
+    (b"rlhar", "\u{21cc}"),
+    (b"rlm", "\u{200f}"),
+    (b"rmoust", "\u{23b1}"),
+    (b"rmoustache", "\u{23b1}"),
+    (b"rnmid", "\u{2aee}"),
+    (b"roang", "\u{27ed}"),
+    (b"roarr", "\u{21fe}"),
+    (b"robrk", "\u{27e7}"),
+    (b"ropar", "\u{2986}"),
+    (b"ropf", "\u{1d563}"),
+    (b"roplus", "\u{2a2e}"),
+    (b"rotimes", "\u{2a35}"),
+    (b"rpar", ")"),
+    (b"rpargt", "\u{2994}"),
+    (b"rppolint", "\u{2a12}"),
+    (b"rrarr", "\u{21c9}"),
+    (b"rsaquo", "\u{203a}"),
+    (b"rscr", "\u{1d4c7}"),
+    (b"rsh", "\u{21b1}"),
+    (b"rsqb", "]"),
+    (b"rsquo", "\u{2019}"),
+    (b"rsquor", "\u{2019}"),
+    (b"rthree", "\u{22cc}"),
+    (b"rtimes", "\u{22ca}"),
+    (b"rtri", "\u{25b9}"),
+    (b"rtrie", "\u{22b5}"),
+    (b"rtrif", "\u{25b8}"),
+    (b"rtriltri", "\u{29ce}"),
+    (b"ruluhar", "\u{2968}"),
+    (b"rx", "\u{211e}"),
+    (b"sacute", "\u{15b}"),
+    (b"sbquo", "\u{201a}"),
+    (b"sc", "\u{227b}"),
+    (b"scE", "\u{2ab4}"),
+    (b"scap", "\u{2ab8}"),
+    (b"scaron", "\u{161}"),
+    (b"sccue", "\u{227d}"),
+    (b"sce", "\u{2ab0}"),
+    (b"scedil", "\u{15f}"),
+    (b"scirc", "\u{15d}"),
+    (b"scnE", "\u{2ab6}"),
+    (b"scnap", "\u{2aba}"),
+    (b"scnsim", "\u{22e9}"),
+    (b"scpolint", "\u{2a13}"),
+    (b"scsim", "\u{227f}"),
+    (b"scy", "\u{441}"),
+    (b"sdot", "\u{22c5}"),
+    (b"sdotb", "\u{22a1}"),
+    (b"sdote", "\u{2a66}"),
+    (b"seArr", "\u{21d8}"),
+    (b"searhk", "\u{2925}"),
+    (b"searr", "\u{2198}"),
+    (b"searrow", "\u{2198}"),
+    (b"sect", "\u{a7}"),
+    (b"semi", ";"),
+    (b"seswar", "\u{2929}"),
+    (b"setminus", "\u{2216}"),
+    (b"setmn", "\u{2216}"),
+    (b"sext", "\u{2736}"),
+    (b"sfr", "\u{1d530}"),
+    (b"sfrown", "\u{2322}"),
+    (b"sharp", "\u{266f}"),
+    (b"shchcy", "\u{449}"),
+    (b"shcy", "\u{448}"),
+    (b"shortmid", "\u{2223}"),
+    (b"shortparallel", "\u{2225}"),
+    (b"shy", "\u{ad}"),
+    (b"sigma", "\u{3c3}"),
+    (b"sigmaf", "\u{3c2}"),
+    (b"sigmav", "\u{3c2}"),
+    (b"sim", "\u{223c}"),
+    (b"simdot", "\u{2a6a}"),
+    (b"sime", "\u{2243}"),
+    (b"simeq", "\u{2243}"),
+    (b"simg", "\u{2a9e}"),
+    (b"simgE", "\u{2aa0}"),
+    (b"siml", "\u{2a9d}"),
+    (b"simlE", "\u{2a9f}"),
+    (b"simne", "\u{2246}"),
+    (b"simplus", "\u{2a24}"),
+    (b"simrarr", "\u{2972}"),
+    (b"slarr", "\u{2190}"),
+    (b"smallsetminus", "\u{2216}"),
+    (b"smashp", "\u{2a33}"),
+    (b"smeparsl", "\u{29e4}"),
+    (b"smid", "\u{2223}"),
+    (b"smile", "\u{2323}"),
+    (b"smt", "\u{2aaa}"),
+    (b"smte", "\u{2aac}"),
+    (b"smtes", "\u{2aac}\u{fe00}"),
+    (b"softcy", "\u{44c}"),
+    (b"sol", "/"),
+    (b"solb", "\u{29c4}"),
+    (b"solbar", "\u{233f}"),
+    (b"sopf", "\u{1d564}"),
+    (b"spades", "\u{2660}"),
+    (b"spadesuit", "\u{2660}"),
+    (b"spar", "\u{2225}"),
+    (b"sqcap", "\u{2293}"),
+    (b"sqcaps", "\u{2293}\u{fe00}"),
+    (b"sqcup", "\u{2294}"),
+    (b"sqcups", "\u{2294}\u{fe00}"),
+    (b"sqsub", "\u{228f}"),
+    (b"sqsube", "\u{2291}"),
+    (b"sqsubset", "\u{228f}"),
+    (b"sqsubseteq", "\u{2291}"),
+    (b"sqsup", "\u{2290}"),
+    (b"sqsupe", "\u{2292}"),
+    (b"sqsupset", "\u{2290}"),
+    (b"sqsupseteq", "\u{2292}"),
+    (b"squ", "\u{25a1}"),
+    (b"square", "\u{25a1}"),
+    (b"squarf", "\u{25aa}"),
+    (b"squf", "\u{25aa}"),
+    (b"srarr", "\u{2192}"),
+    (b"sscr", "\u{1d4c8}"),
+    (b"ssetmn", "\u{2216}"),
+    (b"ssmile", "\u{2323}"),
+    (b"sstarf", "\u{22c6}"),
+    (b"star", "\u{2606}"),
+    (b"starf", "\u{2605}"),
+    (b"straightepsilon", "\u{3f5}"),
+    (b"straightphi", "\u{3d5}"),
+    (b"strns", "\u{af}"),
+    (b"sub", "\u{2282}"),
+    (b"subE", "\u{2ac5}"),
+    (b"subdot", "\u{2abd}"),
+    (b"sube", "\u{2286}"),
+    (b"subedot", "\u{2ac3}"),
+    (b"submult", "\u{2ac1}"),
+    (b"subnE", "\u{2acb}"),
+    (b"subne", "\u{228a}"),
+    (b"subplus", "\u{2abf}"),
+    (b"subrarr", "\u{2979}"),
+    (b"subset", "\u{2282}"),
+    (b"subseteq", "\u{2286}"),
+    (b"subseteqq", "\u{2ac5}"),
+    (b"subsetneq", "\u{228a}"),
+    (b"subsetneqq", "\u{2acb}"),
+    (b"subsim", "\u{2ac7}"),
+    (b"subsub", "\u{2ad5}"),
+    (b"subsup", "\u{2ad3}"),
+    (b"succ", "\u{227b}"),
+    (b"succapprox", "\u{2ab8}"),
+    (b"succcurlyeq", "\u{227d}"),
+    (b"succeq", "\u{2ab0}"),
+    (b"succnapprox", "\u{2aba}"),
+    (b"succneqq", "\u{2ab6}"),
+    (b"succnsim", "\u{22e9}"),
+    (b"succsim", "\u{227f}"),
+    (b"sum", "\u{2211}"),
+    (b"sung", "\u{266a}"),
+    (b"sup", "\u{2283}"),
+    (b"sup1", "\u{b9}"),
+    (b"sup2", "\u{b2}"),
+    (b"sup3", "\u{b3}"),
+    (b"supE", "\u{2ac6}"),
+    (b"supdot", "\u{2abe}"),
+    (b"supdsub", "\u{2ad8}"),
+    (b"supe", "\u{2287}"),
+    (b"supedot", "\u{2ac4}"),
+    (b"suphsol", "\u{27c9}"),
+    (b"suphsub", "\u{2ad7}"),
+    (b"suplarr", "\u{297b}"),
+    (b"supmult", "\u{2ac2}"),
+    (b"supnE", "\u{2acc}"),
+    (b"supne", "\u{228b}"),
+    (b"supplus", "\u{2ac0}"),
+    (b"supset", "\u{2283}"),
+    (b"supseteq", "\u{2287}"),
+    (b"supseteqq", "\u{2ac6}"),
+    (b"supsetneq", "\u{228b}"),
+    (b"supsetneqq", "\u{2acc}"),
+    (b"supsim", "\u{2ac8}"),
+    (b"supsub", "\u{2ad4}"),
+    (b"supsup", "\u{2ad6}"),
+    (b"swArr", "\u{21d9}"),
+    (b"swarhk", "\u{2926}"),
+    (b"swarr", "\u{2199}"),
+    (b"swarrow", "\u{2199}"),
+    (b"swnwar", "\u{292a}"),
+    (b"szlig", "\u{df}"),
+    (b"target", "\u{2316}"),
+    (b"tau", "\u{3c4}"),
+    (b"tbrk", "\u{23b4}"),
+    (b"tcaron", "\u{165}"),
+    (b"tcedil", "\u{163}"),
+    (b"tcy", "\u{442}"),
+    (b"tdot", "\u{20db}"),
+    (b"telrec", "\u{2315}"),
+    (b"tfr", "\u{1d531}"),
+    (b"there4", "\u{2234}"),
+    (b"therefore", "\u{2234}"),
+    (b"theta", "\u{3b8}"),
+    (b"thetasym", "\u{3d1}"),
+    (b"thetav", "\u{3d1}"),
+    (b"thickapprox", "\u{2248}"),
+    (b"thicksim", "\u{223c}"),
+    (b"thinsp", "\u{2009}"),
+    (b"thkap", "\u{2248}"),
+    (b"thksim", "\u{223c}"),
+    (b"thorn", "\u{fe}"),
+    (b"tilde", "\u{2dc}"),
+    (b"times", "\u{d7}"),
+    (b"timesb", "\u{22a0}"),
+    (b"timesbar", "\u{2a31}"),
+    (b"timesd", "\u{2a30}"),
+    (b"tint", "\u{222d}"),
+    (b"toea", "\u{2928}"),
+    (b"top", "\u{22a4}"),
+    (b"topbot", "\u{2336}"),
+    (b"topcir", "\u{2af1}"),
+    (b"topf", "\u{1d565}"),
+    (b"topfork", "\u{2ada}"),
+    (b"tosa", "\u{2929}"),
+    (b"tprime", "\u{2034}"),
+    (b"trade", "\u{2122}"),
+    (b"triangle", "\u{25b5}"),
+    (b"triangledown", "\u{25bf}"),
+    (b"triangleleft", "\u{25c3}"),
+    (b"trianglelefteq", "\u{22b4}"),
+    (b"triangleq", "\u{225c}"),
+    (b"triangleright", "\u{25b9}"),
+    (b"trianglerighteq", "\u{22b5}"),
+    (b"tridot", "\u{25ec}"),
+    (b"trie", "\u{225c}"),
+    (b"triminus", "\u{2a3a}"),
+    (b"triplus", "\u{2a39}"),
+    (b"trisb", "\u{29cd}"),
+    (b"tritime", "\u{2a3b}"),
+    (b"trpezium", "\u{23e2}"),
+    (b"tscr", "\u{1d4c9}"),
+    (b"tscy", "\u{446}"),
+    (b"tshcy", "\u{45b}"),
+    (b"tstrok", "\u{167}"),
+    (b"twixt", "\u{226c}"),
+    (b"twoheadleftarrow", "\u{219e}"),
+    (b"twoheadrightarrow", "\u{21a0}"),
+    (b"uArr", "\u{21d1}"),
+    (b"uHar", "\u{2963}"),
+    (b"uacute", "\u{fa}"),
+    (b"uarr", "\u{2191}"),
+    (b"ubrcy", "\u{45e}"),
+    (b"ubreve", "\u{16d}"),
+    (b"ucirc", "\u{fb}"),
+    (b"ucy", "\u{443}"),
+    (b"udarr", "\u{21c5}"),
+    (b"udblac", "\u{171}"),
+    (b"udhar", "\u{296e}"),
+    (b"ufisht", "\u{297e}"),
+    (b"ufr", "\u{1d532}"),
+    (b"ugrave", "\u{f9}"),
+    (b"uharl", "\u{21bf}"),
+    (b"uharr", "\u{21be}"),
+    (b"uhblk", "\u{2580}"),
+    (b"ulcorn", "\u{231c}"),
+    (b"ulcorner", "\u{231c}"),
+    (b"ulcrop", "\u{230f}"),
+    (b"ultri", "\u{25f8}"),
+    (b"umacr", "\u{16b}"),
+    (b"uml", "\u{a8}"),
+    (b"uogon", "\u{173}"),
+    (b"uopf", "\u{1d566}"),
+    (b"uparrow", "\u{2191}"),
+    (b"updownarrow", "\u{2195}"),
+    (b"upharpoonleft", "\u{21bf}"),
+    (b"upharpoonright", "\u{21be}"),
+    (b"uplus", "\u{228e}"),
+    (b"upsi", "\u{3c5}"),
+    (b"upsih", "\u{3d2}"),
+    (b"upsilon", "\u{3c5}"),
+    (b"upuparrows", "\u{21c8}"),
+    (b"urcorn", "\u{231d}"),
+    (b"urcorner", "\u{231d}"),
+    (b"urcrop", "\u{230e}"),
+    (b"uring", "\u{16f}"),
+    (b"urtri", "\u{25f9}"),
+    (b"uscr", "\u{1d4ca}"),
+    (b"utdot", "\u{22f0}"),
+    (b"utilde", "\u{169}"),
+    (b"utri", "\u{25b5}"),
+    (b"utrif", "\u{25b4}"),
+    (b"uuarr", "\u{21c8}"),
+    (b"uuml", "\u{fc}"),
+    (b"uwangle", "\u{29a7}"),
+    (b"vArr", "\u{21d5}"),
+    (b"vBar", "\u{2ae8}"),
+    (b"vBarv", "\u{2ae9}"),
+    (b"vDash", "\u{22a8}"),
+    (b"vangrt", "\u{299c}"),
+    (b"varepsilon", "\u{3f5}"),
+    (b"varkappa", "\u{3f0}"),
+    (b"varnothing", "\u{2205}"),
+    (b"varphi", "\u{3d5}"),
+    (b"varpi", "\u{3d6}"),
+    (b"varpropto", "\u{221d}"),
+    (b"varr", "\u{2195}"),
+    (b"varrho", "\u{3f1}"),
+    (b"varsigma", "\u{3c2}"),
+    (b"varsubsetneq", "\u{228a}\u{fe00}"),
+    (b"varsubsetneqq", "\u{2acb}\u{fe00}"),
+    (b"varsupsetneq", "\u{228b}\u{fe00}"),
+    (b"varsupsetneqq", "\u{2acc}\u{fe00}"),
+    (b"vartheta", "\u{3d1}"),
+    (b"vartriangleleft", "\u{22b2}"),
+    (b"vartriangleright", "\u{22b3}"),
+    (b"vcy", "\u{432}"),
+    (b"vdash", "\u{22a2}"),
+    (b"vee", "\u{2228}"),
+    (b"veebar", "\u{22bb}"),
+    (b"veeeq", "\u{225a}"),
+    (b"vellip", "\u{22ee}"),
+    (b"verbar", "|"),
+    (b"vert", "|"),
+    (b"vfr", "\u{1d533}"),
+    (b"vltri", "\u{22b2}"),
+    (b"vnsub", "\u{2282}\u{20d2}"),
+    (b"vnsup", "\u{2283}\u{20d2}"),
+    (b"vopf", "\u{1d567}"),
+    (b"vprop", "\u{221d}"),
+    (b"vrtri", "\u{22b3}"),
+    (b"vscr", "\u{1d4cb}"),
+    (b"vsubnE", "\u{2acb}\u{fe00}"),
+    (b"vsubne", "\u{228a}\u{fe00}"),
+    (b"vsupnE", "\u{2acc}\u{fe00}"),
+    (b"vsupne", "\u{228b}\u{fe00}"),
+    (b"vzigzag", "\u{299a}"),
+    (b"wcirc", "\u{175}"),
+    (b"wedbar", "\u{2a5f}"),
+    (b"wedge", "\u{2227}"),
+    (b"wedgeq", "\u{2259}"),
+    (b"weierp", "\u{2118}"),
+    (b"wfr", "\u{1d534}"),
+    (b"wopf", "\u{1d568}"),
+    (b"wp", "\u{2118}"),
+    (b"wr", "\u{2240}"),
+    (b"wreath", "\u{2240}"),
+    (b"wscr", "\u{1d4cc}"),
+    (b"xcap", "\u{22c2}"),
+    (b"xcirc", "\u{25ef}"),
+    (b"xcup", "\u{22c3}"),
+    (b"xdtri", "\u{25bd}"),
+    (b"xfr", "\u{1d535}"),
+    (b"xhArr", "\u{27fa}"),
+    (b"xharr", "\u{27f7}"),
+    (b"xi", "\u{3be}"),
+    (b"xlArr", "\u{27f8}"),
+    (b"xlarr", "\u{27f5}"),
+    (b"xmap", "\u{27fc}"),
+    (b"xnis", "\u{22fb}"),
+    (b"xodot", "\u{2a00}"),
+    (b"xopf", "\u{1d569}"),
+    (b"xoplus", "\u{2a01}"),
+    (b"xotime", "\u{2a02}"),
+    (b"xrArr", "\u{27f9}"),
+    (b"xrarr", "\u{27f6}"),
+    (b"xscr", "\u{1d4cd}"),
+    (b"xsqcup", "\u{2a06}"),
+    (b"xuplus", "\u{2a04}"),
+    (b"xutri", "\u{25b3}"),
+    (b"xvee", "\u{22c1}"),
+    (b"xwedge", "\u{22c0}"),
+    (b"yacute", "\u{fd}"),
+    (b"yacy", "\u{44f}"),
+    (b"ycirc", "\u{177}"),
+    (b"ycy", "\u{44b}"),
+    (b"yen", "\u{a5}"),
+    (b"yfr", "\u{1d536}"),
+    (b"yicy", "\u{457}"),
+    (b"yopf", "\u{1d56a}"),
+    (b"yscr", "\u{1d4ce}"),
+    (b"yucy", "\u{44e}"),
+    (b"yuml", "\u{ff}"),
+    (b"zacute", "\u{17a}"),
+    (b"zcaron", "\u{17e}"),
+    (b"zcy", "\u{437}"),
+    (b"zdot", "\u{17c}"),
+    (b"zeetrf", "\u{2128}"),
+    (b"zeta", "\u{3b6}"),
+    (b"zfr", "\u{1d537}"),
+    (b"zhcy", "\u{436}"),
+    (b"zigrarr", "\u{21dd}"),
+    (b"zopf", "\u{1d56b}"),
+    (b"zscr", "\u{1d4cf}"),
+    (b"zwj", "\u{200d}"),
+    (b"zwnj", "\u{200c}"),
+];
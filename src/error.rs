@@ -5,8 +5,11 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
+use std::cmp;
 use std::error::Error as StdError;
 use std::fmt;
+use std::io;
+use std::path::PathBuf;
 use std::str::Utf8Error;
 
 use quick_xml::errors::Error as XmlError;
@@ -19,15 +22,119 @@ pub enum Error {
     /// An XML parsing error.
     Xml(XmlError),
     /// The input didn't begin with an opening `<rss>` tag.
+    #[deprecated(note = "the parser returns `MissingRootElement` and `UnexpectedRoot` instead")]
     InvalidStartTag,
+    /// The input contained bytes that are not valid in its declared encoding.
+    Encoding(String),
+    /// The input declared an encoding that is not supported.
+    UnsupportedEncoding(String),
+    /// The input did not contain a root element.
+    MissingRootElement,
+    /// The root element of the input is not the one that was expected, such as an Atom `<feed>`
+    /// where an RSS `<rss>` was expected.
+    UnexpectedRoot {
+        /// The qualified name of the root element that was found.
+        found: String,
+    },
+    /// The input is a feed in another format rather than an RSS channel.
+    NotRss {
+        /// The format of the feed.
+        detected: FeedKind,
+    },
+    /// The value of an attribute could not be unescaped or decoded.
+    InvalidAttribute {
+        /// The qualified name of the element that the attribute belongs to.
+        element: String,
+        /// The qualified name of the attribute.
+        attribute: String,
+        /// The underlying error.
+        error: Box<Error>,
+    },
+    /// A byte order mark appeared after the start of the input.
+    MisplacedByteOrderMark,
     /// The end of the input was reached without finding a complete channel element.
     Eof,
+    /// An extension used a namespace prefix that was not declared when writing a channel.
+    UndeclaredNamespace(String),
+    /// A channel contained a character that XML does not allow when it was written with
+    /// `InvalidCharPolicy::Error`.
+    InvalidCharacter(char),
+    /// An item was written with a `ChannelWriter` that had already been finished.
+    WriterFinished,
+    /// An error while reading the file at the given path.
+    File(PathBuf, io::Error),
+    /// The input exceeded one of the limits set in `ParseOptions`.
+    LimitExceeded {
+        /// The limit that was exceeded.
+        kind: LimitKind,
+        /// The value of the limit.
+        limit: usize,
+    },
+    /// An XML, UTF-8, limit or attribute error at a known position in the input.
+    Element {
+        /// The qualified name of the element that was being parsed, or `None` if the error
+        /// occurred before the first element.
+        element: Option<String>,
+        /// The byte offset in the input where the error occurred.
+        position: usize,
+        /// The underlying error.
+        error: Box<Error>,
+    },
+    /// The input is not a valid JSON Feed document.
+    #[cfg(feature = "json_feed")]
+    JsonFeed(String),
+    /// The input is not a valid Atom feed.
+    #[cfg(feature = "atom")]
+    Atom(::atom_syndication::Error),
     /// An error during the web request.
     #[cfg(feature = "from_url")]
     UrlRequest(::reqwest::Error),
     /// An IO error.
-    #[cfg(feature = "from_url")]
-    Io(::std::io::Error),
+    #[cfg(any(feature = "from_url", feature = "async"))]
+    Io(io::Error),
+    /// The input looked compressed but could not be decompressed.
+    #[cfg(feature = "compression")]
+    Decompression(io::Error),
+}
+
+/// A limit on the input that is set in `ParseOptions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitKind {
+    /// The nesting depth of elements.
+    Depth,
+    /// The length of the text of an element or the value of an attribute.
+    Length,
+    /// The number of items in a channel.
+    Items,
+    /// The size in bytes of compressed input once it is decompressed.
+    DecompressedSize,
+}
+
+/// A format of feed other than RSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeedKind {
+    /// An Atom feed, whose root is a `<feed>` element in the Atom namespace.
+    Atom,
+}
+
+impl fmt::Display for FeedKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FeedKind::Atom => f.write_str("Atom"),
+        }
+    }
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            LimitKind::Depth => "nesting depth",
+            LimitKind::Length => "length",
+            LimitKind::Items => "item count",
+            LimitKind::DecompressedSize => "decompressed size",
+        };
+        f.write_str(name)
+    }
 }
 
 impl StdError for Error {
@@ -35,39 +142,282 @@ impl StdError for Error {
         match *self {
             Error::Utf8(ref err) => err.description(),
             Error::Xml(ref err) => err.description(),
+            #[allow(deprecated)]
             Error::InvalidStartTag => "the input did not begin with an rss tag",
+            Error::Encoding(_) => "the input is not valid in its declared encoding",
+            Error::UnsupportedEncoding(_) => "the input declared an unsupported encoding",
+            Error::MissingRootElement => "the input did not contain a root element",
+            Error::UnexpectedRoot { .. } => "the input has an unexpected root element",
+            Error::NotRss { .. } => "the input is not an RSS feed",
+            Error::InvalidAttribute { .. } => "the value of an attribute is not valid",
+            Error::MisplacedByteOrderMark => "a byte order mark appeared after the input started",
             Error::Eof => "reached end of input without finding a complete channel",
+            Error::UndeclaredNamespace(_) => "an extension used an undeclared namespace prefix",
+            Error::InvalidCharacter(_) => "the channel contained a character not allowed in XML",
+            Error::WriterFinished => "the channel writer has already been finished",
+            Error::File(..) => "the file could not be read",
+            Error::LimitExceeded { .. } => "the input exceeded a parse limit",
+            Error::Element { .. } => "an element of the input could not be parsed",
+            #[cfg(feature = "json_feed")]
+            Error::JsonFeed(_) => "the input is not a valid JSON Feed document",
+            #[cfg(feature = "atom")]
+            Error::Atom(_) => "the input is not a valid Atom feed",
             #[cfg(feature = "from_url")]
             Error::UrlRequest(ref err) => err.description(),
-            #[cfg(feature = "from_url")]
+            #[cfg(any(feature = "from_url", feature = "async"))]
             Error::Io(ref err) => err.description(),
+            #[cfg(feature = "compression")]
+            Error::Decompression(_) => "the input could not be decompressed",
         }
     }
 
-    fn cause(&self) -> Option<&StdError> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
             Error::Utf8(ref err) => Some(err),
             Error::Xml(ref err) => Some(err),
+            Error::File(_, ref err) => Some(err),
+            Error::InvalidAttribute { ref error, .. } => Some(&**error),
+            #[cfg(feature = "atom")]
+            Error::Atom(ref err) => Some(err),
+            // the message of the underlying error is already part of this one
+            Error::Element { ref error, .. } => error.source(),
             #[cfg(feature = "from_url")]
             Error::UrlRequest(ref err) => Some(err),
-            #[cfg(feature = "from_url")]
+            #[cfg(any(feature = "from_url", feature = "async"))]
             Error::Io(ref err) => Some(err),
+            #[cfg(feature = "compression")]
+            Error::Decompression(ref err) => Some(err),
             _ => None,
         }
     }
 }
 
+/// The message does not repeat the error that caused this one, which is returned by `source`.
+///
+/// # Examples
+///
+/// ```
+/// use std::error::Error as StdError;
+/// use rss::Channel;
+///
+/// let input = "<rss><channel><item><title>Fish &chips;</title></item></channel></rss>";
+/// let err = input.parse::<Channel>().unwrap_err();
+/// assert!(err.to_string().starts_with("the input is not valid XML in <title> at byte "));
+/// assert!(err.source().is_some());
+/// ```
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::Utf8(ref err) => err.fmt(f),
-            Error::Xml(ref err) => err.fmt(f),
+            Error::Utf8(_) => write!(f, "the input is not valid UTF-8"),
+            Error::Xml(_) => write!(f, "the input is not valid XML"),
+            #[allow(deprecated)]
             Error::InvalidStartTag => write!(f, "the input did not begin with an rss tag"),
+            Error::Encoding(ref encoding) => write!(f, "the input is not valid {}", encoding),
+            Error::UnsupportedEncoding(ref encoding) => {
+                write!(f, "the encoding {} is not supported", encoding)
+            }
+            Error::MissingRootElement => write!(f, "the input does not contain any elements"),
+            Error::UnexpectedRoot { ref found } if found == "feed" => write!(
+                f,
+                "unexpected root element <feed>, which is used by Atom feeds rather than RSS"
+            ),
+            Error::UnexpectedRoot { ref found } => {
+                write!(f, "unexpected root element <{}>", found)
+            }
+            #[cfg(feature = "atom")]
+            Error::NotRss { detected: FeedKind::Atom } => write!(
+                f,
+                "the input is an Atom feed rather than RSS, which Channel::read_any can convert"
+            ),
+            #[cfg(not(feature = "atom"))]
+            Error::NotRss { detected: FeedKind::Atom } => write!(
+                f,
+                "the input is an Atom feed rather than RSS, which Channel::read_any can convert \
+                 when the atom feature is enabled"
+            ),
+            Error::InvalidAttribute {
+                ref element,
+                ref attribute,
+                ..
+            } => write!(f, "the {} attribute of <{}> is not valid", attribute, element),
+            Error::MisplacedByteOrderMark => {
+                write!(f, "a byte order mark appeared after the start of the input")
+            }
             Error::Eof => write!(f, "reached end of input without finding a complete channel"),
+            Error::UndeclaredNamespace(ref prefix) => {
+                write!(f, "the namespace prefix {} is not declared", prefix)
+            }
+            Error::InvalidCharacter(c) => {
+                write!(f, "the character {:?} is not allowed in XML", c)
+            }
+            Error::WriterFinished => write!(f, "the channel writer has already been finished"),
+            Error::File(ref path, _) => write!(f, "failed to read {}", path.display()),
+            Error::LimitExceeded { kind, limit } => {
+                write!(f, "the {} limit of {} was exceeded", kind, limit)
+            }
+            // an attribute error already names its element
+            Error::Element {
+                position,
+                ref error,
+                ..
+            } if error.attribute().is_some() => write!(f, "{} at byte {}", error, position),
+            Error::Element {
+                element: Some(ref element),
+                position,
+                ref error,
+            } => write!(f, "{} in <{}> at byte {}", error, element, position),
+            Error::Element {
+                element: None,
+                position,
+                ref error,
+            } => write!(f, "{} at byte {}", error, position),
+            #[cfg(feature = "json_feed")]
+            Error::JsonFeed(ref message) => write!(f, "invalid JSON Feed: {}", message),
+            #[cfg(feature = "atom")]
+            Error::Atom(_) => write!(f, "the input is not a valid Atom feed"),
             #[cfg(feature = "from_url")]
-            Error::UrlRequest(ref err) => err.fmt(f),
-            #[cfg(feature = "from_url")]
-            Error::Io(ref err) => err.fmt(f),
+            Error::UrlRequest(_) => write!(f, "the request for the channel failed"),
+            #[cfg(any(feature = "from_url", feature = "async"))]
+            Error::Io(_) => write!(f, "failed to read the input"),
+            #[cfg(feature = "compression")]
+            Error::Decompression(_) => write!(f, "failed to decompress the input"),
+        }
+    }
+}
+
+impl Error {
+    /// Return the underlying error, without the element and position where it occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, Error};
+    ///
+    /// let input = "<rss><channel><item><title>Fish &chips;</title></item></channel></rss>";
+    /// let err = input.parse::<Channel>().unwrap_err();
+    /// match *err.kind() {
+    ///     Error::Xml(_) => {}
+    ///     _ => panic!("expected an XML error"),
+    /// }
+    /// ```
+    pub fn kind(&self) -> &Error {
+        match *self {
+            Error::Element { ref error, .. } => error.kind(),
+            ref err => err,
+        }
+    }
+
+    /// Return the qualified name of the element that was being parsed when the error occurred,
+    /// if it is known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = "<rss><channel><item><title>Fish &chips;</title></item></channel></rss>";
+    /// let err = input.parse::<Channel>().unwrap_err();
+    /// assert_eq!(err.element(), Some("title"));
+    ///
+    /// let position = err.position().unwrap();
+    /// assert!(err.to_string().ends_with(&format!("in <title> at byte {}", position)));
+    /// ```
+    pub fn element(&self) -> Option<&str> {
+        match *self {
+            Error::Element { ref error, .. } if error.attribute().is_some() => error.element(),
+            Error::Element { ref element, .. } => element.as_ref().map(|s| s.as_str()),
+            Error::InvalidAttribute { ref element, .. } => Some(element.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the qualified name of the attribute whose value was not valid, if the error is
+    /// about an attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"<rss><channel><item><source url="&bogus;"/></item></channel></rss>"#;
+    /// let err = input.parse::<Channel>().unwrap_err();
+    /// assert_eq!(err.attribute(), Some("url"));
+    /// assert_eq!(err.element(), Some("source"));
+    /// assert!(err.to_string().starts_with("the url attribute of <source> is not valid at byte "));
+    /// ```
+    pub fn attribute(&self) -> Option<&str> {
+        match *self.kind() {
+            Error::InvalidAttribute { ref attribute, .. } => Some(attribute.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the byte offset in the input where the error occurred, if it is known.
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            Error::Element { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Return the line and column where the error occurred, if its position is known.
+    ///
+    /// The input must be the same bytes that were parsed. Lines and columns start at 1, and
+    /// columns count characters. For input that is not UTF-8 the position refers to the input
+    /// after it was converted to UTF-8, so the result is only approximate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = "<rss>\n<channel>\n<title>Fish &chips;</title>\n</channel>\n</rss>";
+    /// let err = input.parse::<Channel>().unwrap_err();
+    /// let (line, _) = err.line_column(input.as_bytes()).unwrap();
+    /// assert_eq!(line, 3);
+    /// ```
+    pub fn line_column(&self, input: &[u8]) -> Option<(usize, usize)> {
+        // the position does not include a byte order mark
+        let input = if input.starts_with(b"\xEF\xBB\xBF") {
+            &input[3..]
+        } else {
+            input
+        };
+        let before = &input[..cmp::min(self.position()?, input.len())];
+        let start = before
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |index| index + 1);
+        let line = before[..start].iter().filter(|&&byte| byte == b'\n').count() + 1;
+        let column = String::from_utf8_lossy(&before[start..]).chars().count() + 1;
+        Some((line, column))
+    }
+
+    /// Attach the element being parsed and the current position to an XML, UTF-8, limit or
+    /// attribute error.
+    ///
+    /// Other errors, and errors that already know their element, are returned unchanged.
+    pub(crate) fn in_element(self, element: &[u8], position: usize) -> Error {
+        self.at(Some(String::from_utf8_lossy(element).into_owned()), position)
+    }
+
+    /// Attach the current position to an XML, UTF-8, limit or attribute error that occurred
+    /// before the first element.
+    pub(crate) fn at_position(self, position: usize) -> Error {
+        self.at(None, position)
+    }
+
+    fn at(self, element: Option<String>, position: usize) -> Error {
+        match self {
+            Error::Xml(_) |
+            Error::Utf8(_) |
+            Error::LimitExceeded { .. } |
+            Error::InvalidAttribute { .. } => Error::Element {
+                element: element,
+                position: position,
+                error: Box::new(self),
+            },
+            err => err,
         }
     }
 }
@@ -91,9 +441,9 @@ impl From<::reqwest::Error> for Error {
     }
 }
 
-#[cfg(feature = "from_url")]
-impl From<::std::io::Error> for Error {
-    fn from(err: ::std::io::Error) -> Error {
+#[cfg(any(feature = "from_url", feature = "async"))]
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
         Error::Io(err)
     }
 }
@@ -0,0 +1,361 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::any::Any;
+use std::collections::{HashMap, hash_map};
+use std::fmt;
+use std::sync::Arc;
+
+use error::Error;
+use extension::Extension;
+
+/// A typed value read from the elements of a custom extension namespace.
+///
+/// Values are created by an `ExtensionHandler` while a channel is parsed, and are converted back
+/// into elements by `to_extensions` when the channel is written.
+///
+/// # Examples
+///
+/// ```
+/// use rss::extension::{Extension, ExtensionBuilder};
+/// use rss::extension::custom::CustomExtension;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Rating(String);
+///
+/// impl CustomExtension for Rating {
+///     fn to_extensions(&self, prefix: &str) -> Vec<Extension> {
+///         let rating = ExtensionBuilder::default()
+///             .name(format!("{}:rating", prefix))
+///             .value(self.0.clone())
+///             .build()
+///             .unwrap();
+///         vec![rating]
+///     }
+/// }
+/// ```
+pub trait CustomExtension: CustomExtensionClone + fmt::Debug {
+    /// Convert this value into the elements that are written, using `prefix` for their
+    /// qualified names.
+    fn to_extensions(&self, prefix: &str) -> Vec<Extension>;
+}
+
+/// Cloning, comparison and downcasting of boxed custom extensions.
+///
+/// This is implemented for every `CustomExtension` that is also `Clone` and `PartialEq`.
+pub trait CustomExtensionClone {
+    /// Return a boxed copy of this value.
+    fn clone_box(&self) -> Box<dyn CustomExtension>;
+
+    /// Return whether this value is equal to another custom extension.
+    fn eq_box(&self, other: &dyn CustomExtension) -> bool;
+
+    /// Return this value as `Any` so that it can be downcast.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Return this value as a mutable `Any` so that it can be downcast.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> CustomExtensionClone for T
+where
+    T: CustomExtension + Clone + PartialEq + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CustomExtension> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn CustomExtension) -> bool {
+        other.as_any().downcast_ref::<T>().map_or(false, |other| self == other)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A parser for the elements of a custom extension namespace.
+///
+/// Handlers are registered with `ParseOptions::register_extension` and are called once for
+/// every channel and item that contains elements in their namespace. Closures with the same
+/// signature as `parse` are handlers as well.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rss::{Channel, Error, ParseOptions};
+/// use rss::extension::{Extension, ExtensionBuilder};
+/// use rss::extension::custom::{CustomExtension, ExtensionHandler};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Rating(String);
+///
+/// impl CustomExtension for Rating {
+///     fn to_extensions(&self, prefix: &str) -> Vec<Extension> {
+///         let rating = ExtensionBuilder::default()
+///             .name(format!("{}:rating", prefix))
+///             .value(self.0.clone())
+///             .build()
+///             .unwrap();
+///         vec![rating]
+///     }
+/// }
+///
+/// struct RatingHandler;
+///
+/// impl ExtensionHandler for RatingHandler {
+///     fn parse(
+///         &self,
+///         elements: &mut HashMap<String, Vec<Extension>>,
+///     ) -> Result<Option<Box<dyn CustomExtension>>, Error> {
+///         let rating = elements
+///             .remove("rating")
+///             .and_then(|mut ratings| ratings.remove(0).value().map(String::from));
+///         Ok(rating.map(|rating| Box::new(Rating(rating)) as Box<dyn CustomExtension>))
+///     }
+/// }
+///
+/// let mut options = ParseOptions::default();
+/// options.register_extension("http://example.com/rating", RatingHandler);
+///
+/// let input = r#"
+///     <rss version="2.0" xmlns:r="http://example.com/rating">
+///         <channel><r:rating>PG</r:rating></channel>
+///     </rss>
+/// "#;
+///
+/// let (channel, _) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+/// let rating = channel.custom_extensions().get::<Rating>("http://example.com/rating");
+/// assert_eq!(rating, Some(&Rating("PG".to_string())));
+/// assert!(channel.to_string().contains("<r:rating>PG</r:rating>"));
+/// ```
+pub trait ExtensionHandler: Send + Sync {
+    /// Parse the elements of the namespace found in a channel or an item.
+    ///
+    /// `elements` maps local names to the elements with that name. Elements that are left in the
+    /// map stay in the extension map of the channel or item. If `None` is returned, no value is
+    /// stored.
+    fn parse(
+        &self,
+        elements: &mut HashMap<String, Vec<Extension>>,
+    ) -> Result<Option<Box<dyn CustomExtension>>, Error>;
+}
+
+impl<F> ExtensionHandler for F
+where
+    F: Fn(&mut HashMap<String, Vec<Extension>>) -> Result<Option<Box<dyn CustomExtension>>, Error>,
+    F: Send + Sync,
+{
+    fn parse(
+        &self,
+        elements: &mut HashMap<String, Vec<Extension>>,
+    ) -> Result<Option<Box<dyn CustomExtension>>, Error> {
+        self(elements)
+    }
+}
+
+/// The handlers registered for custom extension namespaces, keyed by namespace URI.
+#[derive(Default, Clone)]
+pub struct ExtensionHandlers {
+    handlers: HashMap<String, Arc<dyn ExtensionHandler>>,
+}
+
+impl ExtensionHandlers {
+    /// Register a handler for the namespace with the given URI, replacing any existing handler.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use rss::Error;
+    /// use rss::extension::Extension;
+    /// use rss::extension::custom::{CustomExtension, ExtensionHandlers};
+    ///
+    /// fn ignore(_: &mut HashMap<String, Vec<Extension>>)
+    ///     -> Result<Option<Box<dyn CustomExtension>>, Error> {
+    ///     Ok(None)
+    /// }
+    ///
+    /// let mut handlers = ExtensionHandlers::default();
+    /// handlers.register("http://example.com/ns", ignore);
+    /// assert!(handlers.contains("http://example.com/ns"));
+    /// ```
+    pub fn register<S, H>(&mut self, namespace: S, handler: H)
+    where
+        S: Into<String>,
+        H: ExtensionHandler + 'static,
+    {
+        self.handlers.insert(namespace.into(), Arc::new(handler));
+    }
+
+    /// Return whether a handler is registered for the namespace with the given URI.
+    pub fn contains(&self, namespace: &str) -> bool {
+        self.handlers.contains_key(namespace)
+    }
+
+    /// Return whether no handlers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    pub(crate) fn get(&self, namespace: &str) -> Option<&dyn ExtensionHandler> {
+        self.handlers.get(namespace).map(|handler| &**handler)
+    }
+}
+
+impl fmt::Debug for ExtensionHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.handlers.keys()).finish()
+    }
+}
+
+impl PartialEq for ExtensionHandlers {
+    fn eq(&self, other: &ExtensionHandlers) -> bool {
+        self.handlers.len() == other.handlers.len() &&
+            self.handlers.iter().all(|(namespace, handler)| {
+                other
+                    .handlers
+                    .get(namespace)
+                    .map_or(false, |other| Arc::ptr_eq(handler, other))
+            })
+    }
+}
+
+/// The values of the custom extensions of a channel or an item, keyed by namespace URI.
+#[derive(Default)]
+pub struct CustomExtensionMap {
+    values: HashMap<String, (String, Box<dyn CustomExtension>)>,
+}
+
+impl CustomExtensionMap {
+    /// Return the value for the namespace with the given URI, if it exists and is a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::Extension;
+    /// use rss::extension::custom::{CustomExtension, CustomExtensionMap};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Empty;
+    ///
+    /// impl CustomExtension for Empty {
+    ///     fn to_extensions(&self, _: &str) -> Vec<Extension> {
+    ///         Vec::new()
+    ///     }
+    /// }
+    ///
+    /// let mut map = CustomExtensionMap::default();
+    /// map.insert("e", "http://example.com/empty", Empty);
+    /// assert_eq!(map.get::<Empty>("http://example.com/empty"), Some(&Empty));
+    /// assert_eq!(map.prefix("http://example.com/empty"), Some("e"));
+    /// ```
+    pub fn get<T: 'static>(&self, namespace: &str) -> Option<&T> {
+        self.values
+            .get(namespace)
+            .and_then(|&(_, ref value)| value.as_any().downcast_ref())
+    }
+
+    /// Return a mutable reference to the value for the namespace with the given URI, if it
+    /// exists and is a `T`.
+    pub fn get_mut<T: 'static>(&mut self, namespace: &str) -> Option<&mut T> {
+        self.values
+            .get_mut(namespace)
+            .and_then(|&mut (_, ref mut value)| value.as_any_mut().downcast_mut())
+    }
+
+    /// Return the prefix that the namespace with the given URI is written with.
+    pub fn prefix(&self, namespace: &str) -> Option<&str> {
+        self.values
+            .get(namespace)
+            .map(|&(ref prefix, _)| prefix.as_str())
+    }
+
+    /// Set the value for the namespace with the given URI, which is written with the given
+    /// prefix. The namespace is declared when the channel is written.
+    ///
+    /// The previous value for the namespace is returned.
+    pub fn insert<P, N, T>(
+        &mut self,
+        prefix: P,
+        namespace: N,
+        value: T,
+    ) -> Option<Box<dyn CustomExtension>>
+    where
+        P: Into<String>,
+        N: Into<String>,
+        T: CustomExtension + 'static,
+    {
+        self.insert_boxed(prefix.into(), namespace.into(), Box::new(value))
+    }
+
+    /// Remove the value for the namespace with the given URI.
+    pub fn remove(&mut self, namespace: &str) -> Option<Box<dyn CustomExtension>> {
+        self.values.remove(namespace).map(|(_, value)| value)
+    }
+
+    /// Return the number of namespaces with a value.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub(crate) fn insert_boxed(
+        &mut self,
+        prefix: String,
+        namespace: String,
+        value: Box<dyn CustomExtension>,
+    ) -> Option<Box<dyn CustomExtension>> {
+        self.values
+            .insert(namespace, (prefix, value))
+            .map(|(_, value)| value)
+    }
+
+    pub(crate) fn iter(&self) -> hash_map::Iter<'_, String, (String, Box<dyn CustomExtension>)> {
+        self.values.iter()
+    }
+}
+
+impl Clone for CustomExtensionMap {
+    fn clone(&self) -> CustomExtensionMap {
+        let values = self.values
+            .iter()
+            .map(|(namespace, &(ref prefix, ref value))| {
+                (namespace.clone(), (prefix.clone(), value.clone_box()))
+            })
+            .collect();
+        CustomExtensionMap { values: values }
+    }
+}
+
+impl PartialEq for CustomExtensionMap {
+    fn eq(&self, other: &CustomExtensionMap) -> bool {
+        self.values.len() == other.values.len() &&
+            self.values.iter().all(|(namespace, &(ref prefix, ref value))| {
+                other.values.get(namespace).map_or(false, |&(ref other_prefix, ref other)| {
+                    prefix == other_prefix && value.eq_box(&**other)
+                })
+            })
+    }
+}
+
+impl fmt::Debug for CustomExtensionMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.values.iter()).finish()
+    }
+}
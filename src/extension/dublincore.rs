@@ -8,51 +8,74 @@
 use std::collections::HashMap;
 use std::io::Write;
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset};
 use quick_xml::errors::Error as XmlError;
-use quick_xml::writer::Writer;
 
+#[cfg(feature = "chrono")]
+use date::parse_w3cdtf;
 use extension::Extension;
 use extension::util::remove_extension_values;
 
-use toxml::{ToXml, WriterExt};
+use toxml::{ToXml, Writer, WriterExt};
 
 /// The Dublin Core XML namespace.
 pub static NAMESPACE: &'static str = "http://purl.org/dc/elements/1.1/";
 
 /// A Dublin Core element extension.
+///
+/// Each element can be repeated, so the values are kept in the order they appear. Most elements
+/// also have a getter for their first value, such as `creator`.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct DublinCoreExtension {
     /// An entity responsible for making contributions to the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     contributors: Vec<String>,
     /// The spatial or temporal topic of the resource, the spatial applicability of the resource,
     /// or the jurisdiction under which the resource is relevant.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     coverages: Vec<String>,
     /// An entity primarily responsible for making the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     creators: Vec<String>,
     /// A point or period of time associated with an event in the lifecycle of the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     dates: Vec<String>,
     /// An account of the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     descriptions: Vec<String>,
     /// The file format, physical medium, or dimensions of the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     formats: Vec<String>,
     /// An unambiguous reference to the resource within a given context.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     identifiers: Vec<String>,
     /// A language of the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     languages: Vec<String>,
     /// An entity responsible for making the resource available.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     publishers: Vec<String>,
     /// A related resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     relations: Vec<String>,
     /// Information about rights held in and over the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     rights: Vec<String>,
     /// A related resource from which the described resource is derived.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     sources: Vec<String>,
     /// The topic of the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     subjects: Vec<String>,
     /// A name given to the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     titles: Vec<String>,
     /// The nature or genre of the resource.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     types: Vec<String>,
 }
 
@@ -75,6 +98,11 @@ impl DublinCoreExtension {
         self.contributors = contributors.into();
     }
 
+    /// Return the first contributor to the resource.
+    pub fn contributor(&self) -> Option<&str> {
+        self.contributors.first().map(|s| s.as_str())
+    }
+
     /// Return the spatial or temporal topics of the resource, the spatial applicabilities of the
     /// resource, or the jurisdictions under which the resource is relevant.
     pub fn coverages(&self) -> &[String] {
@@ -96,6 +124,11 @@ impl DublinCoreExtension {
         self.coverages = coverages.into();
     }
 
+    /// Return the first coverage of the resource.
+    pub fn coverage(&self) -> Option<&str> {
+        self.coverages.first().map(|s| s.as_str())
+    }
+
     /// Return the creators of the resource.
     pub fn creators(&self) -> &[String] {
         &self.creators
@@ -114,6 +147,11 @@ impl DublinCoreExtension {
         self.creators = creators.into();
     }
 
+    /// Return the first creator of the resource.
+    pub fn creator(&self) -> Option<&str> {
+        self.creators.first().map(|s| s.as_str())
+    }
+
     /// Return the times associated with the resource.
     pub fn dates(&self) -> &[String] {
         &self.dates
@@ -132,6 +170,34 @@ impl DublinCoreExtension {
         self.dates = dates.into();
     }
 
+    /// Return the first time associated with the resource.
+    pub fn date(&self) -> Option<&str> {
+        self.dates.first().map(|s| s.as_str())
+    }
+
+    /// Return the first time associated with the resource as a `DateTime`.
+    ///
+    /// `None` is returned if there is no date or it is not a W3C date and time, the profile of
+    /// ISO 8601 that Dublin Core recommends.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::dublincore::DublinCoreExtension;
+    ///
+    /// let mut extension = DublinCoreExtension::default();
+    /// extension.set_dates(vec!["2016-08-27T10:30:00-05:00".to_string()]);
+    ///
+    /// let date = extension.dc_date_parsed().unwrap();
+    /// assert_eq!(date.to_rfc3339(), "2016-08-27T10:30:00-05:00");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn dc_date_parsed(&self) -> Option<DateTime<FixedOffset>> {
+        self.date().and_then(parse_w3cdtf)
+    }
+
     /// Return the descriptions of the resource.
     pub fn descriptions(&self) -> &[String] {
         &self.descriptions
@@ -150,6 +216,11 @@ impl DublinCoreExtension {
         self.descriptions = descriptions.into();
     }
 
+    /// Return the first description of the resource.
+    pub fn description(&self) -> Option<&str> {
+        self.descriptions.first().map(|s| s.as_str())
+    }
+
     /// Return the file formats, physical mediums, or dimensions of the resource.
     pub fn formats(&self) -> &[String] {
         &self.formats
@@ -169,6 +240,11 @@ impl DublinCoreExtension {
         self.formats = formats.into();
     }
 
+    /// Return the first format of the resource.
+    pub fn format(&self) -> Option<&str> {
+        self.formats.first().map(|s| s.as_str())
+    }
+
     /// Return the identifiers of the resource.
     pub fn identifiers(&self) -> &[String] {
         &self.identifiers
@@ -187,6 +263,11 @@ impl DublinCoreExtension {
         self.identifiers = identifiers.into();
     }
 
+    /// Return the first identifier of the resource.
+    pub fn identifier(&self) -> Option<&str> {
+        self.identifiers.first().map(|s| s.as_str())
+    }
+
     /// Return the languages of the resource.
     pub fn languages(&self) -> &[String] {
         &self.languages
@@ -205,6 +286,11 @@ impl DublinCoreExtension {
         self.languages = languages.into();
     }
 
+    /// Return the first language of the resource.
+    pub fn language(&self) -> Option<&str> {
+        self.languages.first().map(|s| s.as_str())
+    }
+
     /// Return the publishers of the resource.
     pub fn publishers(&self) -> &[String] {
         &self.publishers
@@ -223,6 +309,11 @@ impl DublinCoreExtension {
         self.publishers = publishers.into();
     }
 
+    /// Return the first publisher of the resource.
+    pub fn publisher(&self) -> Option<&str> {
+        self.publishers.first().map(|s| s.as_str())
+    }
+
     /// Return the related resources.
     pub fn relations(&self) -> &[String] {
         &self.relations
@@ -241,6 +332,11 @@ impl DublinCoreExtension {
         self.relations = relations.into();
     }
 
+    /// Return the first related resource.
+    pub fn relation(&self) -> Option<&str> {
+        self.relations.first().map(|s| s.as_str())
+    }
+
     /// Return the information about rights held in and over the resource.
     pub fn rights(&self) -> &[String] {
         &self.rights
@@ -278,6 +374,11 @@ impl DublinCoreExtension {
         self.sources = sources.into();
     }
 
+    /// Return the first resource that the resource is derived from.
+    pub fn source(&self) -> Option<&str> {
+        self.sources.first().map(|s| s.as_str())
+    }
+
     /// Return the topics of the resource.
     pub fn subjects(&self) -> &[String] {
         &self.subjects
@@ -296,6 +397,11 @@ impl DublinCoreExtension {
         self.subjects = subjects.into();
     }
 
+    /// Return the first subject of the resource.
+    pub fn subject(&self) -> Option<&str> {
+        self.subjects.first().map(|s| s.as_str())
+    }
+
     /// Return the titles of the resource.
     pub fn titles(&self) -> &[String] {
         &self.titles
@@ -314,6 +420,11 @@ impl DublinCoreExtension {
         self.titles = titles.into();
     }
 
+    /// Return the first title of the resource.
+    pub fn title(&self) -> Option<&str> {
+        self.titles.first().map(|s| s.as_str())
+    }
+
     /// Return the natures or genres of the resource.
     pub fn types(&self) -> &[String] {
         &self.types
@@ -336,24 +447,36 @@ impl DublinCoreExtension {
 impl DublinCoreExtension {
     /// Creates a `DublinCoreExtension` using the specified `HashMap`.
     pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        DublinCoreExtension::take_from_map(&mut map)
+    }
+
+    /// Create a `DublinCoreExtension` from the recognized elements of a `HashMap`, leaving
+    /// the other elements in the map.
+    pub(crate) fn take_from_map(map: &mut HashMap<String, Vec<Extension>>) -> Self {
         let mut ext = DublinCoreExtension::default();
-        ext.contributors = remove_extension_values(&mut map, "contributor").unwrap_or_default();
-        ext.coverages = remove_extension_values(&mut map, "coverage").unwrap_or_default();
-        ext.creators = remove_extension_values(&mut map, "creator").unwrap_or_default();
-        ext.dates = remove_extension_values(&mut map, "date").unwrap_or_default();
-        ext.descriptions = remove_extension_values(&mut map, "description").unwrap_or_default();
-        ext.formats = remove_extension_values(&mut map, "format").unwrap_or_default();
-        ext.identifiers = remove_extension_values(&mut map, "identifier").unwrap_or_default();
-        ext.languages = remove_extension_values(&mut map, "language").unwrap_or_default();
-        ext.publishers = remove_extension_values(&mut map, "publisher").unwrap_or_default();
-        ext.relations = remove_extension_values(&mut map, "relation").unwrap_or_default();
-        ext.rights = remove_extension_values(&mut map, "rights").unwrap_or_default();
-        ext.sources = remove_extension_values(&mut map, "source").unwrap_or_default();
-        ext.subjects = remove_extension_values(&mut map, "subject").unwrap_or_default();
-        ext.titles = remove_extension_values(&mut map, "title").unwrap_or_default();
-        ext.types = remove_extension_values(&mut map, "type").unwrap_or_default();
+        ext.read_map(map);
         ext
     }
+
+    /// Append the recognized elements of a `HashMap` to this extension, leaving the other
+    /// elements in the map.
+    pub(crate) fn read_map(&mut self, map: &mut HashMap<String, Vec<Extension>>) {
+        self.contributors.extend(remove_extension_values(map, "contributor").unwrap_or_default());
+        self.coverages.extend(remove_extension_values(map, "coverage").unwrap_or_default());
+        self.creators.extend(remove_extension_values(map, "creator").unwrap_or_default());
+        self.dates.extend(remove_extension_values(map, "date").unwrap_or_default());
+        self.descriptions.extend(remove_extension_values(map, "description").unwrap_or_default());
+        self.formats.extend(remove_extension_values(map, "format").unwrap_or_default());
+        self.identifiers.extend(remove_extension_values(map, "identifier").unwrap_or_default());
+        self.languages.extend(remove_extension_values(map, "language").unwrap_or_default());
+        self.publishers.extend(remove_extension_values(map, "publisher").unwrap_or_default());
+        self.relations.extend(remove_extension_values(map, "relation").unwrap_or_default());
+        self.rights.extend(remove_extension_values(map, "rights").unwrap_or_default());
+        self.sources.extend(remove_extension_values(map, "source").unwrap_or_default());
+        self.subjects.extend(remove_extension_values(map, "subject").unwrap_or_default());
+        self.titles.extend(remove_extension_values(map, "title").unwrap_or_default());
+        self.types.extend(remove_extension_values(map, "type").unwrap_or_default());
+    }
 }
 
 impl ToXml for DublinCoreExtension {
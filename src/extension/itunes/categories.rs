@@ -0,0 +1,225 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+//! The category taxonomy of Apple Podcasts.
+//!
+//! Apple only lists a podcast under the categories and subcategories named here, which replaced
+//! the older list in 2019. Categories are still read and written as free-form text, so feeds
+//! that use the older names keep working.
+
+/// The Arts category.
+pub const ARTS: &'static str = "Arts";
+/// The Business category.
+pub const BUSINESS: &'static str = "Business";
+/// The Comedy category.
+pub const COMEDY: &'static str = "Comedy";
+/// The Education category.
+pub const EDUCATION: &'static str = "Education";
+/// The Fiction category.
+pub const FICTION: &'static str = "Fiction";
+/// The Government category.
+pub const GOVERNMENT: &'static str = "Government";
+/// The History category.
+pub const HISTORY: &'static str = "History";
+/// The Health & Fitness category.
+pub const HEALTH_AND_FITNESS: &'static str = "Health & Fitness";
+/// The Kids & Family category.
+pub const KIDS_AND_FAMILY: &'static str = "Kids & Family";
+/// The Leisure category.
+pub const LEISURE: &'static str = "Leisure";
+/// The Music category.
+pub const MUSIC: &'static str = "Music";
+/// The News category.
+pub const NEWS: &'static str = "News";
+/// The Religion & Spirituality category.
+pub const RELIGION_AND_SPIRITUALITY: &'static str = "Religion & Spirituality";
+/// The Science category.
+pub const SCIENCE: &'static str = "Science";
+/// The Society & Culture category.
+pub const SOCIETY_AND_CULTURE: &'static str = "Society & Culture";
+/// The Sports category.
+pub const SPORTS: &'static str = "Sports";
+/// The Technology category.
+pub const TECHNOLOGY: &'static str = "Technology";
+/// The True Crime category.
+pub const TRUE_CRIME: &'static str = "True Crime";
+/// The TV & Film category.
+pub const TV_AND_FILM: &'static str = "TV & Film";
+
+/// Every category with its subcategories, in the order Apple lists them.
+pub static CATEGORIES: &'static [(&'static str, &'static [&'static str])] = &[
+    (
+        ARTS,
+        &[
+            "Books",
+            "Design",
+            "Fashion & Beauty",
+            "Food",
+            "Performing Arts",
+            "Visual Arts",
+        ],
+    ),
+    (
+        BUSINESS,
+        &[
+            "Careers",
+            "Entrepreneurship",
+            "Investing",
+            "Management",
+            "Marketing",
+            "Non-Profit",
+        ],
+    ),
+    (COMEDY, &["Comedy Interviews", "Improv", "Stand-Up"]),
+    (
+        EDUCATION,
+        &["Courses", "How To", "Language Learning", "Self-Improvement"],
+    ),
+    (FICTION, &["Comedy Fiction", "Drama", "Science Fiction"]),
+    (GOVERNMENT, &[]),
+    (HISTORY, &[]),
+    (
+        HEALTH_AND_FITNESS,
+        &[
+            "Alternative Health",
+            "Fitness",
+            "Medicine",
+            "Mental Health",
+            "Nutrition",
+            "Sexuality",
+        ],
+    ),
+    (
+        KIDS_AND_FAMILY,
+        &[
+            "Education for Kids",
+            "Parenting",
+            "Pets & Animals",
+            "Stories for Kids",
+        ],
+    ),
+    (
+        LEISURE,
+        &[
+            "Animation & Manga",
+            "Automotive",
+            "Aviation",
+            "Crafts",
+            "Games",
+            "Hobbies",
+            "Home & Garden",
+            "Video Games",
+        ],
+    ),
+    (
+        MUSIC,
+        &["Music Commentary", "Music History", "Music Interviews"],
+    ),
+    (
+        NEWS,
+        &[
+            "Business News",
+            "Daily News",
+            "Entertainment News",
+            "News Commentary",
+            "Politics",
+            "Sports News",
+            "Tech News",
+        ],
+    ),
+    (
+        RELIGION_AND_SPIRITUALITY,
+        &[
+            "Buddhism",
+            "Christianity",
+            "Hinduism",
+            "Islam",
+            "Judaism",
+            "Religion",
+            "Spirituality",
+        ],
+    ),
+    (
+        SCIENCE,
+        &[
+            "Astronomy",
+            "Chemistry",
+            "Earth Sciences",
+            "Life Sciences",
+            "Mathematics",
+            "Natural Sciences",
+            "Nature",
+            "Physics",
+            "Social Sciences",
+        ],
+    ),
+    (
+        SOCIETY_AND_CULTURE,
+        &[
+            "Documentary",
+            "Personal Journals",
+            "Philosophy",
+            "Places & Travel",
+            "Relationships",
+        ],
+    ),
+    (
+        SPORTS,
+        &[
+            "Baseball",
+            "Basketball",
+            "Cricket",
+            "Fantasy Sports",
+            "Football",
+            "Golf",
+            "Hockey",
+            "Rugby",
+            "Running",
+            "Soccer",
+            "Swimming",
+            "Tennis",
+            "Volleyball",
+            "Wilderness",
+            "Wrestling",
+        ],
+    ),
+    (TECHNOLOGY, &[]),
+    (TRUE_CRIME, &[]),
+    (
+        TV_AND_FILM,
+        &[
+            "After Shows",
+            "Film History",
+            "Film Interviews",
+            "Film Reviews",
+            "TV Reviews",
+        ],
+    ),
+];
+
+/// Return the subcategories of a category, or `None` if it is not an Apple Podcasts category.
+///
+/// # Examples
+///
+/// ```
+/// use rss::extension::itunes::categories;
+///
+/// assert!(categories::subcategories("Arts").unwrap().contains(&"Design"));
+/// assert_eq!(categories::subcategories("Technology"), Some(&[][..]));
+/// assert_eq!(categories::subcategories("Gadgets"), None);
+/// ```
+pub fn subcategories(category: &str) -> Option<&'static [&'static str]> {
+    CATEGORIES
+        .iter()
+        .find(|&&(name, _)| name == category)
+        .map(|&(_, subcategories)| subcategories)
+}
+
+/// Return whether `subcategory` is a subcategory of `category`.
+pub fn is_subcategory(category: &str, subcategory: &str) -> bool {
+    subcategories(category).map_or(false, |subcategories| subcategories.contains(&subcategory))
+}
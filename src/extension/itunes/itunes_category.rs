@@ -9,12 +9,13 @@ use std::io::Write;
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
-use quick_xml::writer::Writer;
 
-use toxml::ToXml;
+use toxml::{ToXml, Writer, escape};
 
 /// A category for an iTunes podcast.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct ITunesCategory {
     /// The name of the category.
@@ -22,6 +23,7 @@ pub struct ITunesCategory {
     // This is contained within a Box to ensure it gets allocated on the heap to prevent an
     // infinite size.
     /// An optional subcategory for the cagetory.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     subcategory: Option<Box<ITunesCategory>>,
 }
 
@@ -92,16 +94,48 @@ impl ITunesCategory {
 
 impl ToXml for ITunesCategory {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
-        let name = b"itunes:category";
+        write_categories(writer, &[self])
+    }
+}
+
+/// Write a list of categories.
+///
+/// Adjacent categories with the same text and a subcategory are written as one element that
+/// contains all of their subcategories, which is how they are read. A category without a
+/// subcategory is written as an empty element.
+pub(crate) fn write_categories<W: Write>(
+    writer: &mut Writer<W>,
+    categories: &[&ITunesCategory],
+) -> Result<(), XmlError> {
+    let name = b"itunes:category";
+    let mut i = 0;
+
+    while i < categories.len() {
+        let category = categories[i];
         let mut element = BytesStart::borrowed(name, name.len());
-        element.push_attribute(("text", &*self.text));
-        writer.write_event(Event::Start(element))?;
+        element.push_attribute((b"text" as &[u8], &*escape(category.text.as_bytes())));
 
-        if let Some(subcategory) = self.subcategory.as_ref() {
-            subcategory.to_xml(writer)?;
+        let mut subcategories = Vec::new();
+        for other in &categories[i..] {
+            match other.subcategory {
+                Some(ref subcategory) if other.text == category.text => {
+                    subcategories.push(&**subcategory)
+                }
+                _ => break,
+            }
         }
 
+        if subcategories.is_empty() {
+            writer.write_event(Event::Empty(element))?;
+            i += 1;
+            continue;
+        }
+
+        writer.write_event(Event::Start(element))?;
+        write_categories(writer, &subcategories)?;
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
-        Ok(())
+        i += subcategories.len();
     }
+
+    Ok(())
 }
@@ -10,45 +10,59 @@ use std::io::Write;
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart};
-use quick_xml::writer::Writer;
 
 use super::{parse_categories, parse_image, parse_owner};
+use super::itunes_category::write_categories;
+use super::itunes_explicit::canonical_explicit;
 use error::Error;
 use extension::Extension;
-use extension::itunes::{ITunesCategory, ITunesOwner};
+use extension::itunes::{ITunesCategory, ITunesExplicit, ITunesOwner};
 use extension::util::remove_extension_value;
-use toxml::{ToXml, WriterExt};
+use toxml::{ToXml, Writer, WriterExt, escape};
 
 /// An iTunes channel element extension.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct ITunesChannelExtension {
     /// The author of the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     author: Option<String>,
     /// Specifies if the podcast should be prevented from appearing in the iTunes Store. A value of
     /// `Yes` indicates that the podcast should not show up in the iTunes Store. All other values
     /// are ignored.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     block: Option<String>,
     /// The iTunes categories the podcast belongs to.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     categories: Vec<ITunesCategory>,
     /// The artwork for the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     image: Option<String>,
     /// Specifies whether the podcast contains explicit content. A value of `Yes`, `Explicit`, or
     /// `True` indicates that the podcast contains explicit content. A value of `Clean`, `No`,
     /// `False` inidicates that none of the episodes contain explicit content.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     explicit: Option<String>,
     /// Specifies whether the podcast is complete and no new episodes will be posted. A value of
     /// `Yes` indicates that the podcast is complete.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     complete: Option<String>,
     /// The new URL where the podcast is located.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     new_feed_url: Option<String>,
     /// The contact information for the owner of the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     owner: Option<ITunesOwner>,
     /// A description of the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     subtitle: Option<String>,
     /// A summary of the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     summary: Option<String>,
     /// Keywords for the podcast. The string contains a comma separated list of keywords.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     keywords: Option<String>,
 }
 
@@ -125,6 +139,10 @@ impl ITunesChannelExtension {
 
     /// Return the iTunes categories that the podcast belongs to.
     ///
+    /// Nested categories are kept as subcategories. A category that contains several
+    /// subcategories is read as one category per subcategory, each with the same text, and
+    /// adjacent categories with the same text are written back as one element.
+    ///
     /// # Examples
     ///
     /// ```
@@ -232,6 +250,30 @@ impl ITunesChannelExtension {
         self.explicit = explicit.into();
     }
 
+    /// Return whether the podcast contains explicit content, if the value is a known spelling.
+    ///
+    /// The raw value is still available through `explicit`, and is written unchanged if it is
+    /// not recognized. Recognized values are written as `true` or `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::{ITunesChannelExtension, ITunesExplicit};
+    ///
+    /// let mut extension = ITunesChannelExtension::default();
+    /// extension.set_explicit("Clean".to_string());
+    /// assert_eq!(extension.explicit_parsed(), Some(ITunesExplicit::Clean));
+    ///
+    /// extension.set_explicit(ITunesExplicit::Yes);
+    /// assert_eq!(extension.explicit(), Some("true"));
+    ///
+    /// extension.set_explicit("maybe".to_string());
+    /// assert_eq!(extension.explicit_parsed(), None);
+    /// ```
+    pub fn explicit_parsed(&self) -> Option<ITunesExplicit> {
+        self.explicit.as_ref().and_then(|explicit| explicit.parse().ok())
+    }
+
     /// Return whether the podcast is complete and no new episodes will be posted.
     ///
     /// A value of `Yes` indicates that the podcast is complete.
@@ -436,20 +478,43 @@ impl ITunesChannelExtension {
 impl ITunesChannelExtension {
     /// Create an `ITunesChannelExtension` from a `HashMap`.
     pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Result<Self, Error> {
+        ITunesChannelExtension::take_from_map(&mut map)
+    }
+
+    /// Create an `ITunesChannelExtension` from the recognized elements of a `HashMap`, leaving
+    /// the other elements in the map.
+    pub(crate) fn take_from_map(map: &mut HashMap<String, Vec<Extension>>) -> Result<Self, Error> {
         let mut ext = ITunesChannelExtension::default();
-        ext.author = remove_extension_value(&mut map, "author");
-        ext.block = remove_extension_value(&mut map, "block");
-        ext.categories = parse_categories(&mut map)?;
-        ext.image = parse_image(&mut map);
-        ext.explicit = remove_extension_value(&mut map, "explicit");
-        ext.complete = remove_extension_value(&mut map, "complete");
-        ext.new_feed_url = remove_extension_value(&mut map, "new-feed-url");
-        ext.owner = parse_owner(&mut map)?;
-        ext.subtitle = remove_extension_value(&mut map, "subtitle");
-        ext.summary = remove_extension_value(&mut map, "summary");
-        ext.keywords = remove_extension_value(&mut map, "keywords");
+        ext.read_map(map)?;
         Ok(ext)
     }
+
+    /// Fill in the fields of this extension that are not yet set from the recognized elements
+    /// of a `HashMap`, leaving the other elements in the map.
+    pub(crate) fn read_map(
+        &mut self,
+        map: &mut HashMap<String, Vec<Extension>>,
+    ) -> Result<(), Error> {
+        fill(&mut self.author, remove_extension_value(map, "author"));
+        fill(&mut self.block, remove_extension_value(map, "block"));
+        self.categories.extend(parse_categories(map)?);
+        fill(&mut self.image, parse_image(map));
+        fill(&mut self.explicit, remove_extension_value(map, "explicit"));
+        fill(&mut self.complete, remove_extension_value(map, "complete"));
+        fill(&mut self.new_feed_url, remove_extension_value(map, "new-feed-url"));
+        fill(&mut self.owner, parse_owner(map)?);
+        fill(&mut self.subtitle, remove_extension_value(map, "subtitle"));
+        fill(&mut self.summary, remove_extension_value(map, "summary"));
+        fill(&mut self.keywords, remove_extension_value(map, "keywords"));
+        Ok(())
+    }
+}
+
+/// Set `field` to `value` unless it already has a value.
+fn fill<T>(field: &mut Option<T>, value: Option<T>) {
+    if field.is_none() {
+        *field = value;
+    }
 }
 
 impl ToXml for ITunesChannelExtension {
@@ -462,17 +527,18 @@ impl ToXml for ITunesChannelExtension {
             writer.write_text_element(b"itunes:block", block)?;
         }
 
-        writer.write_objects(&self.categories)?;
+        let categories = self.categories.iter().collect::<Vec<_>>();
+        write_categories(writer, &categories)?;
 
         if let Some(image) = self.image.as_ref() {
             let name = b"itunes:image";
             let mut element = BytesStart::borrowed(name, name.len());
-            element.push_attribute(("href", &**image));
+            element.push_attribute((b"href" as &[u8], &*escape(image.as_bytes())));
             writer.write_event(Event::Empty(element))?;
         }
 
         if let Some(explicit) = self.explicit.as_ref() {
-            writer.write_text_element(b"itunes:explicit", explicit)?;
+            writer.write_text_element(b"itunes:explicit", canonical_explicit(explicit))?;
         }
 
         if let Some(complete) = self.complete.as_ref() {
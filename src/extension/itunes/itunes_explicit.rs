@@ -0,0 +1,107 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Whether an iTunes podcast or episode contains explicit content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ITunesExplicit {
+    /// The content is explicit.
+    Yes,
+    /// The content is not explicit.
+    No,
+    /// The content is a clean version of explicit content.
+    Clean,
+}
+
+impl ITunesExplicit {
+    /// Return whether the content is explicit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::ITunesExplicit;
+    ///
+    /// assert!(ITunesExplicit::Yes.is_explicit());
+    /// assert!(!ITunesExplicit::Clean.is_explicit());
+    /// ```
+    pub fn is_explicit(&self) -> bool {
+        *self == ITunesExplicit::Yes
+    }
+
+    /// Return the value that is written for this variant.
+    ///
+    /// Apple only recommends `true` and `false`, so clean content is written as `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::ITunesExplicit;
+    ///
+    /// assert_eq!(ITunesExplicit::Yes.as_str(), "true");
+    /// assert_eq!(ITunesExplicit::Clean.as_str(), "false");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        if self.is_explicit() {
+            "true"
+        } else {
+            "false"
+        }
+    }
+}
+
+impl FromStr for ITunesExplicit {
+    type Err = ();
+
+    /// Parse one of the known spellings, ignoring case.
+    ///
+    /// `yes`, `true` and `explicit` are `Yes`, `no` and `false` are `No`, and `clean` is
+    /// `Clean`.
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("yes") || s.eq_ignore_ascii_case("true")
+            || s.eq_ignore_ascii_case("explicit")
+        {
+            Ok(ITunesExplicit::Yes)
+        } else if s.eq_ignore_ascii_case("no") || s.eq_ignore_ascii_case("false") {
+            Ok(ITunesExplicit::No)
+        } else if s.eq_ignore_ascii_case("clean") {
+            Ok(ITunesExplicit::Clean)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl fmt::Display for ITunesExplicit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<ITunesExplicit> for String {
+    fn from(explicit: ITunesExplicit) -> String {
+        explicit.as_str().to_string()
+    }
+}
+
+impl From<ITunesExplicit> for Option<String> {
+    fn from(explicit: ITunesExplicit) -> Option<String> {
+        Some(explicit.into())
+    }
+}
+
+/// Return the value to write for an `itunes:explicit` element.
+///
+/// Values that are not recognized are written unchanged.
+pub(crate) fn canonical_explicit(explicit: &str) -> &str {
+    explicit
+        .parse::<ITunesExplicit>()
+        .map(|explicit| explicit.as_str())
+        .unwrap_or(explicit)
+}
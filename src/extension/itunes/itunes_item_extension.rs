@@ -7,45 +7,59 @@
 
 use std::collections::HashMap;
 use std::io::Write;
+use std::time::Duration;
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
-use quick_xml::writer::Writer;
 
 use super::parse_image;
+use super::itunes_explicit::canonical_explicit;
 use extension::Extension;
+use extension::itunes::ITunesExplicit;
 use extension::util::remove_extension_value;
-use toxml::{ToXml, WriterExt};
+use toxml::{ToXml, Writer, WriterExt, escape};
 
 /// An iTunes item element extension.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct ITunesItemExtension {
     /// The author of the podcast episode.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     author: Option<String>,
     /// Specifies if the podcast episode should be prevented from appearing in the iTunes Store. A
     /// value of `Yes` indicates that the episode should not show up in the iTunes Store. All other
     /// values are ignored.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     block: Option<String>,
     /// The artwork for the podcast episode.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     image: Option<String>,
     /// The podcast episode duration in one of the following formats: HH:MM:SS, H:MM:SS, MM:SS,
     /// M:SS.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     duration: Option<String>,
     /// Specifies whether the podcast episode contains explicit content. A value of `Yes`,
     /// `Explicit`, or `True` indicates that the episode contains explicit content. A value of
     /// `Clean`, `No`, `False` inidicates that episode does not contain explicit content.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     explicit: Option<String>,
     /// Specifies whether the podcast episode contains embedded closed captioning. A value of `Yes`
     /// indicates that it does. Any other value indicates that it does not.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     closed_captioned: Option<String>,
     /// A value used to override the default sorting order for episodes.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     order: Option<String>,
     /// A description of the podcast episode.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     subtitle: Option<String>,
     /// A summary of the podcast episode.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     summary: Option<String>,
     /// Keywords for the podcast. The string contains a comma separated list of keywords.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     keywords: Option<String>,
 }
 
@@ -188,6 +202,95 @@ impl ITunesItemExtension {
         self.duration = duration.into();
     }
 
+    /// Return the duration of this podcast episode as a `Duration`.
+    ///
+    /// The HH:MM:SS, H:MM:SS, MM:SS and M:SS formats are supported, as well as plain seconds.
+    /// The seconds may contain a fractional part. `None` is returned if there is no duration or
+    /// it cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rss::extension::itunes::ITunesItemExtension;
+    ///
+    /// let mut extension = ITunesItemExtension::default();
+    /// extension.set_duration("1:02:03".to_string());
+    /// assert_eq!(extension.duration_parsed(), Some(Duration::from_secs(3723)));
+    ///
+    /// extension.set_duration("3723".to_string());
+    /// assert_eq!(extension.duration_parsed(), Some(Duration::from_secs(3723)));
+    /// ```
+    pub fn duration_parsed(&self) -> Option<Duration> {
+        self.duration.as_ref().and_then(|duration| parse_duration(duration))
+    }
+
+    /// Return the duration of this podcast episode in whole seconds.
+    ///
+    /// The same formats as `duration_parsed` are supported, and any fractional part of the
+    /// seconds is discarded. `None` is returned if there is no duration or it cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::ITunesItemExtension;
+    ///
+    /// let mut extension = ITunesItemExtension::default();
+    /// extension.set_duration("02:03".to_string());
+    /// assert_eq!(extension.duration_seconds(), Some(123));
+    ///
+    /// extension.set_duration("two minutes".to_string());
+    /// assert_eq!(extension.duration_seconds(), None);
+    /// assert_eq!(extension.duration(), Some("two minutes"));
+    /// ```
+    pub fn duration_seconds(&self) -> Option<u64> {
+        self.duration_parsed().map(|duration| duration.as_secs())
+    }
+
+    /// Set the duration of this podcast episode in seconds.
+    ///
+    /// The duration is written in the HH:MM:SS format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::ITunesItemExtension;
+    ///
+    /// let mut extension = ITunesItemExtension::default();
+    /// extension.set_duration_seconds(123);
+    /// assert_eq!(extension.duration(), Some("00:02:03"));
+    /// ```
+    pub fn set_duration_seconds(&mut self, seconds: u64) {
+        self.set_duration_from(Duration::from_secs(seconds));
+    }
+
+    /// Set the duration of this podcast episode from a `Duration`.
+    ///
+    /// The duration is rounded to the nearest second and written in the HH:MM:SS format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rss::extension::itunes::ITunesItemExtension;
+    ///
+    /// let mut extension = ITunesItemExtension::default();
+    /// extension.set_duration_from(Duration::from_secs(3723));
+    /// assert_eq!(extension.duration(), Some("01:02:03"));
+    /// ```
+    pub fn set_duration_from(&mut self, duration: Duration) {
+        let mut secs = duration.as_secs();
+        if duration.subsec_nanos() >= 500_000_000 {
+            secs += 1;
+        }
+        self.duration = Some(format!(
+            "{:02}:{:02}:{:02}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        ));
+    }
+
     /// Return whether this podcast episode contains explicit content.
     ///
     /// A value of `Yes`, `Explicit`, or `True` indicates that the episode contains explicit
@@ -228,6 +331,30 @@ impl ITunesItemExtension {
         self.explicit = explicit.into();
     }
 
+    /// Return whether this episode contains explicit content, if the value is a known spelling.
+    ///
+    /// The raw value is still available through `explicit`, and is written unchanged if it is
+    /// not recognized. Recognized values are written as `true` or `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::{ITunesItemExtension, ITunesExplicit};
+    ///
+    /// let mut extension = ITunesItemExtension::default();
+    /// extension.set_explicit("Clean".to_string());
+    /// assert_eq!(extension.explicit_parsed(), Some(ITunesExplicit::Clean));
+    ///
+    /// extension.set_explicit(ITunesExplicit::Yes);
+    /// assert_eq!(extension.explicit(), Some("true"));
+    ///
+    /// extension.set_explicit("maybe".to_string());
+    /// assert_eq!(extension.explicit_parsed(), None);
+    /// ```
+    pub fn explicit_parsed(&self) -> Option<ITunesExplicit> {
+        self.explicit.as_ref().and_then(|explicit| explicit.parse().ok())
+    }
+
     /// Return whether this podcast episode contains embedded closed captioning.
     ///
     /// A value of `Yes` indicates that it does. Any other value indicates that it does not.
@@ -400,17 +527,23 @@ impl ITunesItemExtension {
 impl ITunesItemExtension {
     /// Create an `ITunesChannelExtension` from a `HashMap`.
     pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        ITunesItemExtension::take_from_map(&mut map)
+    }
+
+    /// Create an `ITunesItemExtension` from the recognized elements of a `HashMap`, leaving the
+    /// other elements in the map.
+    pub(crate) fn take_from_map(map: &mut HashMap<String, Vec<Extension>>) -> Self {
         let mut ext = ITunesItemExtension::default();
-        ext.author = remove_extension_value(&mut map, "author");
-        ext.block = remove_extension_value(&mut map, "block");
-        ext.image = parse_image(&mut map);
-        ext.duration = remove_extension_value(&mut map, "duration");
-        ext.explicit = remove_extension_value(&mut map, "explicit");
-        ext.closed_captioned = remove_extension_value(&mut map, "isClosedCaptioned");
-        ext.order = remove_extension_value(&mut map, "order");
-        ext.subtitle = remove_extension_value(&mut map, "subtitle");
-        ext.summary = remove_extension_value(&mut map, "summary");
-        ext.keywords = remove_extension_value(&mut map, "keywords");
+        ext.author = remove_extension_value(map, "author");
+        ext.block = remove_extension_value(map, "block");
+        ext.image = parse_image(map);
+        ext.duration = remove_extension_value(map, "duration");
+        ext.explicit = remove_extension_value(map, "explicit");
+        ext.closed_captioned = remove_extension_value(map, "isClosedCaptioned");
+        ext.order = remove_extension_value(map, "order");
+        ext.subtitle = remove_extension_value(map, "subtitle");
+        ext.summary = remove_extension_value(map, "summary");
+        ext.keywords = remove_extension_value(map, "keywords");
         ext
     }
 }
@@ -428,7 +561,7 @@ impl ToXml for ITunesItemExtension {
         if let Some(image) = self.image.as_ref() {
             let name = b"itunes:image";
             let mut element = BytesStart::borrowed(name, name.len());
-            element.push_attribute(("href", &**image));
+            element.push_attribute((b"href" as &[u8], &*escape(image.as_bytes())));
             writer.write_event(Event::Start(element))?;
             writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
         }
@@ -438,7 +571,7 @@ impl ToXml for ITunesItemExtension {
         }
 
         if let Some(explicit) = self.explicit.as_ref() {
-            writer.write_text_element(b"itunes:explicit", explicit)?;
+            writer.write_text_element(b"itunes:explicit", canonical_explicit(explicit))?;
         }
 
         if let Some(closed_captioned) = self.closed_captioned.as_ref() {
@@ -465,3 +598,54 @@ impl ToXml for ITunesItemExtension {
         Ok(())
     }
 }
+
+/// Parse a duration in the HH:MM:SS, MM:SS or SS format.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let parts = value.trim().split(':').collect::<Vec<_>>();
+    if parts.len() > 3 {
+        return None;
+    }
+
+    let (last, rest) = parts.split_last()?;
+    let (secs, nanos) = parse_seconds(last)?;
+
+    let mut total = 0u64;
+    for (index, part) in rest.iter().enumerate() {
+        let value = parse_digits(part)?;
+        // minutes may exceed 59 only when there are no hours
+        if index > 0 && value >= 60 {
+            return None;
+        }
+        total = total.checked_mul(60)?.checked_add(value)?;
+    }
+
+    if !rest.is_empty() && secs >= 60 {
+        return None;
+    }
+
+    let secs = total.checked_mul(60)?.checked_add(secs)?;
+    Some(Duration::new(secs, nanos))
+}
+
+/// Parse seconds with an optional fractional part into whole seconds and nanoseconds.
+fn parse_seconds(value: &str) -> Option<(u64, u32)> {
+    let mut split = value.splitn(2, '.');
+    let secs = parse_digits(split.next()?)?;
+    let nanos = match split.next() {
+        Some(fraction) => {
+            parse_digits(fraction)?;
+            let digits = fraction.bytes().chain(::std::iter::repeat(b'0')).take(9);
+            digits.fold(0, |nanos, digit| nanos * 10 + u32::from(digit - b'0'))
+        }
+        None => 0,
+    };
+    Some((secs, nanos))
+}
+
+/// Parse a non-empty string of ASCII digits.
+fn parse_digits(value: &str) -> Option<u64> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
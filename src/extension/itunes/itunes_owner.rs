@@ -9,17 +9,20 @@ use std::io::Write;
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
-use quick_xml::writer::Writer;
 
-use toxml::{ToXml, WriterExt};
+use toxml::{ToXml, Writer, WriterExt};
 
 /// The contact information for the owner of an iTunes podcast.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct ITunesOwner {
     /// The name of the owner.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     name: Option<String>,
     /// The email of the owner.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     email: Option<String>,
 }
 
@@ -97,11 +100,11 @@ impl ToXml for ITunesOwner {
             .write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
 
         if let Some(name) = self.name.as_ref() {
-            writer.write_text_element(b"name", name)?;
+            writer.write_text_element(b"itunes:name", name)?;
         }
 
         if let Some(email) = self.email.as_ref() {
-            writer.write_text_element(b"email", email)?;
+            writer.write_text_element(b"itunes:email", email)?;
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
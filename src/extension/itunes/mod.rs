@@ -10,13 +10,17 @@ use std::collections::HashMap;
 use error::Error;
 use extension::Extension;
 
+pub mod categories;
+
 mod itunes_category;
 mod itunes_channel_extension;
+mod itunes_explicit;
 mod itunes_item_extension;
 mod itunes_owner;
 
 pub use self::itunes_category::*;
 pub use self::itunes_channel_extension::*;
+pub use self::itunes_explicit::*;
 pub use self::itunes_item_extension::*;
 pub use self::itunes_owner::*;
 
@@ -43,28 +47,39 @@ fn parse_categories(
     let mut categories = Vec::with_capacity(elements.len());
 
     for elem in &mut elements {
-        let text = elem.attrs.remove("text").unwrap_or_default();
-
-        let child = {
-            if let Some(mut child) = elem.children.remove("category").map(|mut v| v.remove(0)) {
-                let text = child.attrs.remove("text").unwrap_or_default();
-                let mut category = ITunesCategory::default();
-                category.set_text(text);
-                Some(Box::new(category))
-            } else {
-                None
-            }
-        };
-
-        let mut category = ITunesCategory::default();
-        category.set_text(text);
-        category.set_subcategory(child);
-        categories.push(category);
+        categories.extend(parse_category(elem));
     }
 
     Ok(categories)
 }
 
+/// Parse an `itunes:category` element and its nested subcategories.
+///
+/// A category that contains several subcategories is split into one category per subcategory
+/// so that every parent and child pair is kept.
+fn parse_category(elem: &mut Extension) -> Vec<ITunesCategory> {
+    let text = elem.attrs.remove("text").unwrap_or_default();
+    let children = elem.children.remove("category").unwrap_or_default();
+
+    let mut category = ITunesCategory::default();
+    category.set_text(text);
+
+    if children.is_empty() {
+        return vec![category];
+    }
+
+    let mut categories = Vec::with_capacity(children.len());
+    for mut child in children {
+        for subcategory in parse_category(&mut child) {
+            let mut category = category.clone();
+            category.set_subcategory(Box::new(subcategory));
+            categories.push(category);
+        }
+    }
+
+    categories
+}
+
 fn parse_owner(map: &mut HashMap<String, Vec<Extension>>) -> Result<Option<ITunesOwner>, Error> {
     let mut element = match map.remove("owner").map(|mut v| v.remove(0)) {
         Some(element) => element,
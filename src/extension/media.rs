@@ -0,0 +1,679 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use quick_xml::errors::Error as XmlError;
+use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
+
+use extension::Extension;
+use extension::util::remove_extension_value;
+
+use toxml::{ToXml, Writer, WriterExt, escape};
+
+/// The Media RSS XML namespace.
+pub static NAMESPACE: &'static str = "http://search.yahoo.com/mrss/";
+
+/// A Media RSS element extension.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct MediaExtension {
+    /// The media objects attached to the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    contents: Vec<MediaContent>,
+    /// The images representing the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    thumbnails: Vec<MediaThumbnail>,
+    /// The title of the media.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    title: Option<String>,
+    /// A short description of the media.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    description: Option<String>,
+    /// The people and organizations involved in creating the media.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    credits: Vec<MediaCredit>,
+    /// The groups of media objects that are different versions of the same content.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    groups: Vec<MediaGroup>,
+}
+
+impl MediaExtension {
+    /// Return the media objects attached to the item.
+    ///
+    /// Media objects inside a `media:group` are available through `groups()` instead.
+    pub fn contents(&self) -> &[MediaContent] {
+        &self.contents
+    }
+
+    /// Return a mutable slice of the media objects attached to the item.
+    pub fn contents_mut(&mut self) -> &mut [MediaContent] {
+        &mut self.contents
+    }
+
+    /// Set the media objects attached to the item.
+    pub fn set_contents<V>(&mut self, contents: V)
+    where
+        V: Into<Vec<MediaContent>>,
+    {
+        self.contents = contents.into();
+    }
+
+    /// Return the images representing the item.
+    pub fn thumbnails(&self) -> &[MediaThumbnail] {
+        &self.thumbnails
+    }
+
+    /// Return a mutable slice of the images representing the item.
+    pub fn thumbnails_mut(&mut self) -> &mut [MediaThumbnail] {
+        &mut self.thumbnails
+    }
+
+    /// Set the images representing the item.
+    pub fn set_thumbnails<V>(&mut self, thumbnails: V)
+    where
+        V: Into<Vec<MediaThumbnail>>,
+    {
+        self.thumbnails = thumbnails.into();
+    }
+
+    /// Return the title of the media.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the title of the media.
+    pub fn set_title<V>(&mut self, title: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.title = title.into();
+    }
+
+    /// Return the description of the media.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the description of the media.
+    pub fn set_description<V>(&mut self, description: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.description = description.into();
+    }
+
+    /// Return the people and organizations involved in creating the media.
+    pub fn credits(&self) -> &[MediaCredit] {
+        &self.credits
+    }
+
+    /// Return a mutable slice of the people and organizations involved in creating the media.
+    pub fn credits_mut(&mut self) -> &mut [MediaCredit] {
+        &mut self.credits
+    }
+
+    /// Set the people and organizations involved in creating the media.
+    pub fn set_credits<V>(&mut self, credits: V)
+    where
+        V: Into<Vec<MediaCredit>>,
+    {
+        self.credits = credits.into();
+    }
+
+    /// Return the groups of media objects attached to the item.
+    pub fn groups(&self) -> &[MediaGroup] {
+        &self.groups
+    }
+
+    /// Return a mutable slice of the groups of media objects attached to the item.
+    pub fn groups_mut(&mut self) -> &mut [MediaGroup] {
+        &mut self.groups
+    }
+
+    /// Set the groups of media objects attached to the item.
+    pub fn set_groups<V>(&mut self, groups: V)
+    where
+        V: Into<Vec<MediaGroup>>,
+    {
+        self.groups = groups.into();
+    }
+}
+
+impl MediaExtension {
+    /// Create a `MediaExtension` from a `HashMap`.
+    pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        MediaExtension::take_from_map(&mut map)
+    }
+
+    /// Create a `MediaExtension` from the recognized elements of a `HashMap`, leaving the other
+    /// elements in the map.
+    pub(crate) fn take_from_map(map: &mut HashMap<String, Vec<Extension>>) -> Self {
+        let groups = map.remove("group")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut group| MediaGroup::from_map(&mut group.children))
+            .collect::<Vec<_>>();
+
+        let item = MediaGroup::from_map(map);
+
+        MediaExtension {
+            contents: item.contents,
+            thumbnails: item.thumbnails,
+            title: item.title,
+            description: item.description,
+            credits: item.credits,
+            groups: groups,
+        }
+    }
+}
+
+impl ToXml for MediaExtension {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        writer.write_objects(&self.contents)?;
+        writer.write_objects(&self.thumbnails)?;
+
+        if let Some(title) = self.title.as_ref() {
+            writer.write_text_element(b"media:title", title)?;
+        }
+
+        if let Some(description) = self.description.as_ref() {
+            writer.write_text_element(b"media:description", description)?;
+        }
+
+        writer.write_objects(&self.credits)?;
+        writer.write_objects(&self.groups)?;
+        Ok(())
+    }
+}
+
+/// A group of media objects that are different versions of the same content, such as the same
+/// video at different bitrates.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct MediaGroup {
+    /// The media objects in the group.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    contents: Vec<MediaContent>,
+    /// The images representing the group.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    thumbnails: Vec<MediaThumbnail>,
+    /// The title of the group.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    title: Option<String>,
+    /// A short description of the group.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    description: Option<String>,
+    /// The people and organizations involved in creating the media in the group.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    credits: Vec<MediaCredit>,
+}
+
+impl MediaGroup {
+    /// Return the media objects in the group.
+    pub fn contents(&self) -> &[MediaContent] {
+        &self.contents
+    }
+
+    /// Return a mutable slice of the media objects in the group.
+    pub fn contents_mut(&mut self) -> &mut [MediaContent] {
+        &mut self.contents
+    }
+
+    /// Set the media objects in the group.
+    pub fn set_contents<V>(&mut self, contents: V)
+    where
+        V: Into<Vec<MediaContent>>,
+    {
+        self.contents = contents.into();
+    }
+
+    /// Return the images representing the group.
+    pub fn thumbnails(&self) -> &[MediaThumbnail] {
+        &self.thumbnails
+    }
+
+    /// Return a mutable slice of the images representing the group.
+    pub fn thumbnails_mut(&mut self) -> &mut [MediaThumbnail] {
+        &mut self.thumbnails
+    }
+
+    /// Set the images representing the group.
+    pub fn set_thumbnails<V>(&mut self, thumbnails: V)
+    where
+        V: Into<Vec<MediaThumbnail>>,
+    {
+        self.thumbnails = thumbnails.into();
+    }
+
+    /// Return the title of the group.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the title of the group.
+    pub fn set_title<V>(&mut self, title: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.title = title.into();
+    }
+
+    /// Return the description of the group.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the description of the group.
+    pub fn set_description<V>(&mut self, description: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.description = description.into();
+    }
+
+    /// Return the people and organizations involved in creating the media in the group.
+    pub fn credits(&self) -> &[MediaCredit] {
+        &self.credits
+    }
+
+    /// Return a mutable slice of the people and organizations involved in creating the media
+    /// in the group.
+    pub fn credits_mut(&mut self) -> &mut [MediaCredit] {
+        &mut self.credits
+    }
+
+    /// Set the people and organizations involved in creating the media in the group.
+    pub fn set_credits<V>(&mut self, credits: V)
+    where
+        V: Into<Vec<MediaCredit>>,
+    {
+        self.credits = credits.into();
+    }
+
+    fn from_map(map: &mut HashMap<String, Vec<Extension>>) -> Self {
+        MediaGroup {
+            contents: map.remove("content")
+                .unwrap_or_default()
+                .into_iter()
+                .map(MediaContent::from_extension)
+                .collect(),
+            thumbnails: map.remove("thumbnail")
+                .unwrap_or_default()
+                .into_iter()
+                .map(MediaThumbnail::from_extension)
+                .collect(),
+            title: remove_extension_value(map, "title"),
+            description: remove_extension_value(map, "description"),
+            credits: map.remove("credit")
+                .unwrap_or_default()
+                .into_iter()
+                .map(MediaCredit::from_extension)
+                .collect(),
+        }
+    }
+}
+
+impl ToXml for MediaGroup {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let name = b"media:group";
+        writer
+            .write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
+        writer.write_objects(&self.contents)?;
+        writer.write_objects(&self.thumbnails)?;
+
+        if let Some(title) = self.title.as_ref() {
+            writer.write_text_element(b"media:title", title)?;
+        }
+
+        if let Some(description) = self.description.as_ref() {
+            writer.write_text_element(b"media:description", description)?;
+        }
+
+        writer.write_objects(&self.credits)?;
+        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        Ok(())
+    }
+}
+
+/// A person or organization involved in creating the media.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct MediaCredit {
+    /// The name of the person or organization.
+    value: String,
+    /// The role the person or organization played, such as `producer`.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    role: Option<String>,
+    /// The URI of the scheme that defines the role.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    scheme: Option<String>,
+}
+
+impl MediaCredit {
+    /// Return the name of the person or organization.
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+
+    /// Set the name of the person or organization.
+    pub fn set_value<V>(&mut self, value: V)
+    where
+        V: Into<String>,
+    {
+        self.value = value.into();
+    }
+
+    /// Return the role the person or organization played.
+    pub fn role(&self) -> Option<&str> {
+        self.role.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the role the person or organization played.
+    pub fn set_role<V>(&mut self, role: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.role = role.into();
+    }
+
+    /// Return the URI of the scheme that defines the role.
+    pub fn scheme(&self) -> Option<&str> {
+        self.scheme.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the URI of the scheme that defines the role.
+    pub fn set_scheme<V>(&mut self, scheme: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.scheme = scheme.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        MediaCredit {
+            value: element.value.take().unwrap_or_default(),
+            role: element.attrs.remove("role"),
+            scheme: element.attrs.remove("scheme"),
+        }
+    }
+}
+
+impl ToXml for MediaCredit {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let name = b"media:credit";
+        let mut element = BytesStart::borrowed(name, name.len());
+
+        if let Some(role) = self.role.as_ref() {
+            element.push_attribute((b"role" as &[u8], &*escape(role.as_bytes())));
+        }
+
+        if let Some(scheme) = self.scheme.as_ref() {
+            element.push_attribute((b"scheme" as &[u8], &*escape(scheme.as_bytes())));
+        }
+
+        writer.write_event(Event::Start(element))?;
+        writer
+            .write_event(Event::Text(BytesText::borrowed(self.value.as_bytes())))?;
+        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        Ok(())
+    }
+}
+
+/// A media object attached to an item.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct MediaContent {
+    /// The URL of the media object.
+    url: String,
+    /// The MIME type of the media object.
+    #[cfg_attr(feature = "serialization", serde(rename = "type"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    mime_type: Option<String>,
+    /// The type of media object, such as `image`, `audio` or `video`.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    medium: Option<String>,
+    /// The width of the media object in pixels.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    width: Option<String>,
+    /// The height of the media object in pixels.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    height: Option<String>,
+    /// The duration of the media object in seconds.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    duration: Option<String>,
+    /// The bitrate of the media object in kilobits per second.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    bitrate: Option<String>,
+}
+
+impl MediaContent {
+    /// Return the URL of the media object.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Set the URL of the media object.
+    pub fn set_url<V>(&mut self, url: V)
+    where
+        V: Into<String>,
+    {
+        self.url = url.into();
+    }
+
+    /// Return the MIME type of the media object.
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the MIME type of the media object.
+    pub fn set_mime_type<V>(&mut self, mime_type: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.mime_type = mime_type.into();
+    }
+
+    /// Return the type of media object.
+    pub fn medium(&self) -> Option<&str> {
+        self.medium.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the type of media object.
+    pub fn set_medium<V>(&mut self, medium: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.medium = medium.into();
+    }
+
+    /// Return the width of the media object.
+    pub fn width(&self) -> Option<&str> {
+        self.width.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the width of the media object.
+    pub fn set_width<V>(&mut self, width: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.width = width.into();
+    }
+
+    /// Return the height of the media object.
+    pub fn height(&self) -> Option<&str> {
+        self.height.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the height of the media object.
+    pub fn set_height<V>(&mut self, height: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.height = height.into();
+    }
+
+    /// Return the duration of the media object.
+    pub fn duration(&self) -> Option<&str> {
+        self.duration.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the duration of the media object.
+    pub fn set_duration<V>(&mut self, duration: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.duration = duration.into();
+    }
+
+    /// Return the bitrate of the media object.
+    pub fn bitrate(&self) -> Option<&str> {
+        self.bitrate.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the bitrate of the media object.
+    pub fn set_bitrate<V>(&mut self, bitrate: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.bitrate = bitrate.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        MediaContent {
+            url: element.attrs.remove("url").unwrap_or_default(),
+            mime_type: element.attrs.remove("type"),
+            medium: element.attrs.remove("medium"),
+            width: element.attrs.remove("width"),
+            height: element.attrs.remove("height"),
+            duration: element.attrs.remove("duration"),
+            bitrate: element.attrs.remove("bitrate"),
+        }
+    }
+}
+
+impl ToXml for MediaContent {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let name = b"media:content";
+        let mut element = BytesStart::borrowed(name, name.len());
+
+        let attrs = [
+            (b"url" as &[u8], Some(&self.url)),
+            (b"type", self.mime_type.as_ref()),
+            (b"medium", self.medium.as_ref()),
+            (b"width", self.width.as_ref()),
+            (b"height", self.height.as_ref()),
+            (b"duration", self.duration.as_ref()),
+            (b"bitrate", self.bitrate.as_ref()),
+        ];
+
+        for &(key, value) in &attrs {
+            if let Some(value) = value {
+                element.push_attribute((key, &*escape(value.as_bytes())));
+            }
+        }
+
+        writer.write_event(Event::Empty(element))?;
+        Ok(())
+    }
+}
+
+/// An image representing an item.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct MediaThumbnail {
+    /// The URL of the image.
+    url: String,
+    /// The width of the image in pixels.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    width: Option<String>,
+    /// The height of the image in pixels.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    height: Option<String>,
+}
+
+impl MediaThumbnail {
+    /// Return the URL of the image.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Set the URL of the image.
+    pub fn set_url<V>(&mut self, url: V)
+    where
+        V: Into<String>,
+    {
+        self.url = url.into();
+    }
+
+    /// Return the width of the image.
+    pub fn width(&self) -> Option<&str> {
+        self.width.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the width of the image.
+    pub fn set_width<V>(&mut self, width: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.width = width.into();
+    }
+
+    /// Return the height of the image.
+    pub fn height(&self) -> Option<&str> {
+        self.height.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the height of the image.
+    pub fn set_height<V>(&mut self, height: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.height = height.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        MediaThumbnail {
+            url: element.attrs.remove("url").unwrap_or_default(),
+            width: element.attrs.remove("width"),
+            height: element.attrs.remove("height"),
+        }
+    }
+}
+
+impl ToXml for MediaThumbnail {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let name = b"media:thumbnail";
+        let mut element = BytesStart::borrowed(name, name.len());
+
+        let attrs = [
+            (b"url" as &[u8], Some(&self.url)),
+            (b"width", self.width.as_ref()),
+            (b"height", self.height.as_ref()),
+        ];
+
+        for &(key, value) in &attrs {
+            if let Some(value) = value {
+                element.push_attribute((key, &*escape(value.as_bytes())));
+            }
+        }
+
+        writer.write_event(Event::Empty(element))?;
+        Ok(())
+    }
+}
@@ -11,9 +11,8 @@ use std::str;
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
-use quick_xml::writer::Writer;
 
-use toxml::ToXml;
+use toxml::{ToXml, Writer, escape};
 
 /// Types and methods for
 /// [iTunes](https://help.apple.com/itc/podcasts_connect/#/itcb54353390) extensions.
@@ -22,6 +21,23 @@ pub mod itunes;
 /// Types and methods for [Dublin Core](http://dublincore.org/documents/dces/) extensions.
 pub mod dublincore;
 
+/// Types and methods for [Media RSS](http://www.rssboard.org/media-rss) extensions.
+pub mod media;
+
+/// Types and methods for [Podcasting 2.0](https://podcastindex.org/namespace/1.0) extensions.
+pub mod podcast;
+
+/// Types and methods for [Slash module](http://web.resource.org/rss/1.0/modules/slash/)
+/// extensions.
+pub mod slash;
+
+/// Types and methods for
+/// [syndication module](http://web.resource.org/rss/1.0/modules/syndication/) extensions.
+pub mod syndication;
+
+/// Types and traits for parsing extensions in other namespaces into custom types.
+pub mod custom;
+
 pub(crate) mod util;
 
 /// A map of extension namespace prefixes to local names to elements.
@@ -29,20 +45,82 @@ pub type ExtensionMap = HashMap<String, HashMap<String, Vec<Extension>>>;
 
 /// A namespaced extension such as iTunes or Dublin Core.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Extension {
     /// The qualified name of the extension element.
     name: String,
     /// The content of the extension element.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     value: Option<String>,
     /// The attributes for the extension element.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "HashMap::is_empty"))]
     attrs: HashMap<String, String>,
     /// The children of the extension element. This is a map of local names to child
     /// elements.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "HashMap::is_empty"))]
     children: HashMap<String, Vec<Extension>>,
+    /// The order that the text and children of the extension element were read in.
+    #[builder(setter(skip))]
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    order: ContentOrder,
+}
+
+/// A piece of the content of an extension element.
+#[derive(Debug, Clone)]
+enum Node {
+    /// Text with the given length in bytes, taken from the start of the remaining value.
+    Text(usize),
+    /// The next child with the given local name.
+    Child(String),
+}
+
+/// The order that the text and children of an extension element were read in.
+///
+/// The order only affects how the extension is written, so it is ignored when extensions are
+/// compared.
+#[derive(Debug, Default, Clone)]
+struct ContentOrder(Vec<Node>);
+
+impl PartialEq for ContentOrder {
+    fn eq(&self, _: &ContentOrder) -> bool {
+        true
+    }
+}
+
+/// A piece of the content of an extension element that is written.
+enum Content<'a> {
+    Text(&'a str),
+    Child(&'a Extension),
 }
 
 impl Extension {
+    /// Return a builder for an extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::Extension;
+    ///
+    /// let rating = Extension::builder()
+    ///     .name("ext:rating")
+    ///     .attr("scheme", "urn:simple")
+    ///     .value("adult".to_string())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let extension = Extension::builder()
+    ///     .name("ext:details")
+    ///     .child(rating)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(extension.children()["rating"][0].attrs()["scheme"], "urn:simple");
+    /// ```
+    pub fn builder() -> ExtensionBuilder {
+        ExtensionBuilder::default()
+    }
+
     /// Return the qualified name of this extension.
     pub fn name(&self) -> &str {
         self.name.as_str()
@@ -82,20 +160,129 @@ impl Extension {
     }
 }
 
+impl Extension {
+    /// Return the text and children of this extension in the order they are written.
+    ///
+    /// The order they were read in is used if it still matches the value and children, and
+    /// otherwise the text is followed by the children sorted by local name.
+    fn content(&self) -> Vec<Content<'_>> {
+        let value = self.value.as_ref().map_or("", |s| s.as_str());
+        if let Some(content) = self.recorded_content(value) {
+            return content;
+        }
+
+        let mut content = Vec::new();
+        if !value.is_empty() {
+            content.push(Content::Text(value));
+        }
+
+        let mut names = self.children.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            content.extend(self.children[name].iter().map(Content::Child));
+        }
+
+        content
+    }
+
+    /// Return the text and children of this extension in the order they were read in, or
+    /// `None` if the value or children have changed since.
+    fn recorded_content<'a>(&'a self, value: &'a str) -> Option<Vec<Content<'a>>> {
+        if self.order.0.is_empty() {
+            return None;
+        }
+
+        let mut content = Vec::with_capacity(self.order.0.len());
+        let mut offset = 0;
+        let mut counts = HashMap::new();
+
+        for node in &self.order.0 {
+            match *node {
+                Node::Text(len) => {
+                    content.push(Content::Text(value.get(offset..offset + len)?));
+                    offset += len;
+                }
+                Node::Child(ref name) => {
+                    let count = counts.entry(name.as_str()).or_insert(0);
+                    let child = self.children.get(name)?.get(*count)?;
+                    content.push(Content::Child(child));
+                    *count += 1;
+                }
+            }
+        }
+
+        let complete = offset == value.len() &&
+            self.children.iter().all(|(name, children)| {
+                counts.get(name.as_str()).map_or(0, |count| *count) == children.len()
+            });
+
+        if complete {
+            Some(content)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExtensionBuilder {
+    /// Add an attribute to the extension element.
+    pub fn attr<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.attrs
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Add a child element to the extension element, after the other children with the same
+    /// local name.
+    pub fn child(&mut self, child: Extension) -> &mut Self {
+        let name = match child.name.find(':') {
+            Some(position) => child.name[position + 1..].to_string(),
+            None => child.name.clone(),
+        };
+        self.children
+            .get_or_insert_with(HashMap::new)
+            .entry(name)
+            .or_insert_with(Vec::new)
+            .push(child);
+        self
+    }
+}
+
 impl ToXml for Extension {
+    /// Write this extension, with its text and children in the order they were read in.
+    ///
+    /// The attributes are written sorted by name, and an extension without text or children is
+    /// written as an empty element.
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = self.name.as_bytes();
         let mut element = BytesStart::borrowed(name, name.len());
-        element.extend_attributes(self.attrs.iter().map(|a| (a.0.as_bytes(), a.1.as_bytes())));
-        writer.write_event(Event::Start(element))?;
+        let mut attrs = self.attrs.iter().collect::<Vec<_>>();
+        attrs.sort();
+        for (name, value) in attrs {
+            element.push_attribute((name.as_bytes(), &*escape(value.as_bytes())));
+        }
 
-        if let Some(value) = self.value.as_ref() {
-            writer
-                .write_event(Event::Text(BytesText::borrowed(value.as_bytes())))?;
+        let content = self.content();
+        if content.is_empty() {
+            writer.write_event(Event::Empty(element))?;
+            return Ok(());
         }
 
-        for extension in self.children.values().flat_map(|extensions| extensions) {
-            extension.to_xml(writer)?;
+        writer.write_event(Event::Start(element))?;
+
+        for content in content {
+            match content {
+                Content::Text(text) => {
+                    writer
+                        .write_event(Event::Text(BytesText::borrowed(text.as_bytes())))?;
+                }
+                Content::Child(child) => child.to_xml(writer)?,
+            }
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
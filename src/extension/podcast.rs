@@ -0,0 +1,683 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use quick_xml::errors::Error as XmlError;
+use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
+
+use extension::Extension;
+use extension::util::{remove_extension_value, remove_first_extension};
+
+use toxml::{ToXml, Writer, WriterExt, escape};
+
+/// The Podcasting 2.0 XML namespace.
+pub static NAMESPACE: &'static str = "https://podcastindex.org/namespace/1.0";
+
+/// A Podcasting 2.0 channel element extension.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastChannelExtension {
+    /// Whether other platforms are allowed to import the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    locked: Option<PodcastLocked>,
+    /// The places where listeners can support the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    funding: Vec<PodcastFunding>,
+    /// The people involved in the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    persons: Vec<PodcastPerson>,
+    /// The globally unique identifier of the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    guid: Option<String>,
+}
+
+impl PodcastChannelExtension {
+    /// Return whether other platforms are allowed to import the podcast.
+    pub fn locked(&self) -> Option<&PodcastLocked> {
+        self.locked.as_ref()
+    }
+
+    /// Set whether other platforms are allowed to import the podcast.
+    pub fn set_locked<V>(&mut self, locked: V)
+    where
+        V: Into<Option<PodcastLocked>>,
+    {
+        self.locked = locked.into();
+    }
+
+    /// Return the places where listeners can support the podcast.
+    pub fn funding(&self) -> &[PodcastFunding] {
+        &self.funding
+    }
+
+    /// Return a mutable slice of the places where listeners can support the podcast.
+    pub fn funding_mut(&mut self) -> &mut [PodcastFunding] {
+        &mut self.funding
+    }
+
+    /// Set the places where listeners can support the podcast.
+    pub fn set_funding<V>(&mut self, funding: V)
+    where
+        V: Into<Vec<PodcastFunding>>,
+    {
+        self.funding = funding.into();
+    }
+
+    /// Return the people involved in the podcast.
+    pub fn persons(&self) -> &[PodcastPerson] {
+        &self.persons
+    }
+
+    /// Return a mutable slice of the people involved in the podcast.
+    pub fn persons_mut(&mut self) -> &mut [PodcastPerson] {
+        &mut self.persons
+    }
+
+    /// Set the people involved in the podcast.
+    pub fn set_persons<V>(&mut self, persons: V)
+    where
+        V: Into<Vec<PodcastPerson>>,
+    {
+        self.persons = persons.into();
+    }
+
+    /// Return the globally unique identifier of the podcast.
+    pub fn guid(&self) -> Option<&str> {
+        self.guid.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the globally unique identifier of the podcast.
+    pub fn set_guid<V>(&mut self, guid: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.guid = guid.into();
+    }
+}
+
+impl PodcastChannelExtension {
+    /// Create a `PodcastChannelExtension` from a `HashMap`.
+    pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        let mut ext = PodcastChannelExtension::default();
+        ext.read_map(&mut map);
+        ext
+    }
+
+    /// Add the recognized elements of a `HashMap` to this extension, leaving the other elements
+    /// in the map.
+    ///
+    /// Fields that are already set are not replaced.
+    pub(crate) fn read_map(&mut self, map: &mut HashMap<String, Vec<Extension>>) {
+        if self.locked.is_none() {
+            self.locked = remove_first_extension(map, "locked").map(PodcastLocked::from_extension);
+        }
+
+        if self.guid.is_none() {
+            self.guid = remove_extension_value(map, "guid");
+        }
+
+        let funding = map.remove("funding").unwrap_or_default();
+        self.funding
+            .extend(funding.into_iter().map(PodcastFunding::from_extension));
+
+        let persons = map.remove("person").unwrap_or_default();
+        self.persons
+            .extend(persons.into_iter().map(PodcastPerson::from_extension));
+    }
+}
+
+impl ToXml for PodcastChannelExtension {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        if let Some(locked) = self.locked.as_ref() {
+            writer.write_object(locked)?;
+        }
+
+        writer.write_objects(&self.funding)?;
+        writer.write_objects(&self.persons)?;
+
+        if let Some(guid) = self.guid.as_ref() {
+            writer.write_text_element(b"podcast:guid", guid)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A Podcasting 2.0 item element extension.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastItemExtension {
+    /// The transcripts of the episode, such as one per language.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    transcripts: Vec<PodcastTranscript>,
+    /// The chapters of the episode.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    chapters: Option<PodcastChapters>,
+    /// The people involved in the episode.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    persons: Vec<PodcastPerson>,
+}
+
+impl PodcastItemExtension {
+    /// Return the transcripts of the episode.
+    pub fn transcripts(&self) -> &[PodcastTranscript] {
+        &self.transcripts
+    }
+
+    /// Return a mutable slice of the transcripts of the episode.
+    pub fn transcripts_mut(&mut self) -> &mut [PodcastTranscript] {
+        &mut self.transcripts
+    }
+
+    /// Set the transcripts of the episode.
+    pub fn set_transcripts<V>(&mut self, transcripts: V)
+    where
+        V: Into<Vec<PodcastTranscript>>,
+    {
+        self.transcripts = transcripts.into();
+    }
+
+    /// Return the chapters of the episode.
+    ///
+    /// Only the first `podcast:chapters` element is read into the extension, and any others are
+    /// left in the extension map of the item.
+    pub fn chapters(&self) -> Option<&PodcastChapters> {
+        self.chapters.as_ref()
+    }
+
+    /// Set the chapters of the episode.
+    pub fn set_chapters<V>(&mut self, chapters: V)
+    where
+        V: Into<Option<PodcastChapters>>,
+    {
+        self.chapters = chapters.into();
+    }
+
+    /// Return the people involved in the episode.
+    pub fn persons(&self) -> &[PodcastPerson] {
+        &self.persons
+    }
+
+    /// Return a mutable slice of the people involved in the episode.
+    pub fn persons_mut(&mut self) -> &mut [PodcastPerson] {
+        &mut self.persons
+    }
+
+    /// Set the people involved in the episode.
+    pub fn set_persons<V>(&mut self, persons: V)
+    where
+        V: Into<Vec<PodcastPerson>>,
+    {
+        self.persons = persons.into();
+    }
+}
+
+impl PodcastItemExtension {
+    /// Create a `PodcastItemExtension` from a `HashMap`.
+    pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        PodcastItemExtension::take_from_map(&mut map)
+    }
+
+    /// Create a `PodcastItemExtension` from the recognized elements of a `HashMap`, leaving the
+    /// other elements in the map.
+    pub(crate) fn take_from_map(map: &mut HashMap<String, Vec<Extension>>) -> Self {
+        let mut ext = PodcastItemExtension::default();
+        ext.read_map(map);
+        ext
+    }
+
+    /// Add the recognized elements of a `HashMap` to this extension, leaving the other elements
+    /// in the map.
+    ///
+    /// Chapters that are already set are not replaced.
+    pub(crate) fn read_map(&mut self, map: &mut HashMap<String, Vec<Extension>>) {
+        let transcripts = map.remove("transcript").unwrap_or_default();
+        self.transcripts
+            .extend(transcripts.into_iter().map(PodcastTranscript::from_extension));
+
+        if self.chapters.is_none() {
+            self.chapters =
+                remove_first_extension(map, "chapters").map(PodcastChapters::from_extension);
+        }
+
+        let persons = map.remove("person").unwrap_or_default();
+        self.persons
+            .extend(persons.into_iter().map(PodcastPerson::from_extension));
+    }
+}
+
+impl ToXml for PodcastItemExtension {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        writer.write_objects(&self.transcripts)?;
+
+        if let Some(chapters) = self.chapters.as_ref() {
+            writer.write_object(chapters)?;
+        }
+
+        writer.write_objects(&self.persons)?;
+        Ok(())
+    }
+}
+
+/// Whether other platforms are allowed to import a podcast.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastLocked {
+    /// Whether the podcast is locked against being imported.
+    locked: bool,
+    /// The email address of the owner, which can be used to unlock the podcast.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    owner: Option<String>,
+}
+
+impl PodcastLocked {
+    /// Return whether the podcast is locked against being imported.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Set whether the podcast is locked against being imported.
+    pub fn set_locked<V>(&mut self, locked: V)
+    where
+        V: Into<bool>,
+    {
+        self.locked = locked.into();
+    }
+
+    /// Return the email address of the owner.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the email address of the owner.
+    pub fn set_owner<V>(&mut self, owner: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.owner = owner.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        let locked = element.value.as_ref().map_or(false, |value| {
+            value.trim().eq_ignore_ascii_case("yes")
+        });
+
+        PodcastLocked {
+            locked: locked,
+            owner: element.attrs.remove("owner"),
+        }
+    }
+}
+
+impl ToXml for PodcastLocked {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let attrs = [(b"owner" as &[u8], self.owner.as_ref())];
+        let value = if self.locked { "yes" } else { "no" };
+        write_element(writer, b"podcast:locked", &attrs, Some(value))
+    }
+}
+
+/// A place where listeners can support a podcast.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastFunding {
+    /// The URL of the page where listeners can support the podcast.
+    url: String,
+    /// A short description of the kind of support.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    value: Option<String>,
+}
+
+impl PodcastFunding {
+    /// Return the URL of the page where listeners can support the podcast.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Set the URL of the page where listeners can support the podcast.
+    pub fn set_url<V>(&mut self, url: V)
+    where
+        V: Into<String>,
+    {
+        self.url = url.into();
+    }
+
+    /// Return the description of the kind of support.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the description of the kind of support.
+    pub fn set_value<V>(&mut self, value: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.value = value.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        PodcastFunding {
+            url: element.attrs.remove("url").unwrap_or_default(),
+            value: element.value.take(),
+        }
+    }
+}
+
+impl ToXml for PodcastFunding {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let attrs = [(b"url" as &[u8], Some(&self.url))];
+        let value = self.value.as_ref().map(|s| s.as_str());
+        write_element(writer, b"podcast:funding", &attrs, value)
+    }
+}
+
+/// A person involved in a podcast or an episode.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastPerson {
+    /// The name of the person.
+    name: String,
+    /// The role the person played, such as `host` or `guest`.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    role: Option<String>,
+    /// The group of the role, such as `cast` or `writing`.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    group: Option<String>,
+    /// The URL of a picture of the person.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    img: Option<String>,
+    /// The URL of a page about the person.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    href: Option<String>,
+}
+
+impl PodcastPerson {
+    /// Return the name of the person.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of the person.
+    pub fn set_name<V>(&mut self, name: V)
+    where
+        V: Into<String>,
+    {
+        self.name = name.into();
+    }
+
+    /// Return the role the person played.
+    ///
+    /// The Podcasting 2.0 specification defines the role as `host` when it is absent.
+    pub fn role(&self) -> Option<&str> {
+        self.role.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the role the person played.
+    pub fn set_role<V>(&mut self, role: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.role = role.into();
+    }
+
+    /// Return the group of the role.
+    ///
+    /// The Podcasting 2.0 specification defines the group as `cast` when it is absent.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the group of the role.
+    pub fn set_group<V>(&mut self, group: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.group = group.into();
+    }
+
+    /// Return the URL of a picture of the person.
+    pub fn img(&self) -> Option<&str> {
+        self.img.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the URL of a picture of the person.
+    pub fn set_img<V>(&mut self, img: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.img = img.into();
+    }
+
+    /// Return the URL of a page about the person.
+    pub fn href(&self) -> Option<&str> {
+        self.href.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the URL of a page about the person.
+    pub fn set_href<V>(&mut self, href: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.href = href.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        PodcastPerson {
+            name: element.value.take().unwrap_or_default(),
+            role: element.attrs.remove("role"),
+            group: element.attrs.remove("group"),
+            img: element.attrs.remove("img"),
+            href: element.attrs.remove("href"),
+        }
+    }
+}
+
+impl ToXml for PodcastPerson {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let attrs = [
+            (b"role" as &[u8], self.role.as_ref()),
+            (b"group", self.group.as_ref()),
+            (b"img", self.img.as_ref()),
+            (b"href", self.href.as_ref()),
+        ];
+        write_element(writer, b"podcast:person", &attrs, Some(&self.name))
+    }
+}
+
+/// A transcript of an episode.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastTranscript {
+    /// The URL of the transcript.
+    url: String,
+    /// The MIME type of the transcript, such as `text/vtt`.
+    #[cfg_attr(feature = "serialization", serde(rename = "type"))]
+    mime_type: String,
+    /// The language of the transcript.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    language: Option<String>,
+    /// Set to `captions` if the transcript is meant to be used as closed captions.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    rel: Option<String>,
+}
+
+impl PodcastTranscript {
+    /// Return the URL of the transcript.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Set the URL of the transcript.
+    pub fn set_url<V>(&mut self, url: V)
+    where
+        V: Into<String>,
+    {
+        self.url = url.into();
+    }
+
+    /// Return the MIME type of the transcript.
+    pub fn mime_type(&self) -> &str {
+        self.mime_type.as_str()
+    }
+
+    /// Set the MIME type of the transcript.
+    pub fn set_mime_type<V>(&mut self, mime_type: V)
+    where
+        V: Into<String>,
+    {
+        self.mime_type = mime_type.into();
+    }
+
+    /// Return the language of the transcript.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the language of the transcript.
+    pub fn set_language<V>(&mut self, language: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.language = language.into();
+    }
+
+    /// Return the relationship of the transcript to the episode.
+    pub fn rel(&self) -> Option<&str> {
+        self.rel.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the relationship of the transcript to the episode.
+    pub fn set_rel<V>(&mut self, rel: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.rel = rel.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        PodcastTranscript {
+            url: element.attrs.remove("url").unwrap_or_default(),
+            mime_type: element.attrs.remove("type").unwrap_or_default(),
+            language: element.attrs.remove("language"),
+            rel: element.attrs.remove("rel"),
+        }
+    }
+}
+
+impl ToXml for PodcastTranscript {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let attrs = [
+            (b"url" as &[u8], Some(&self.url)),
+            (b"type", Some(&self.mime_type)),
+            (b"language", self.language.as_ref()),
+            (b"rel", self.rel.as_ref()),
+        ];
+        write_element(writer, b"podcast:transcript", &attrs, None)
+    }
+}
+
+/// The chapters of an episode.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct PodcastChapters {
+    /// The URL of the chapters file.
+    url: String,
+    /// The MIME type of the chapters file, such as `application/json+chapters`.
+    #[cfg_attr(feature = "serialization", serde(rename = "type"))]
+    mime_type: String,
+}
+
+impl PodcastChapters {
+    /// Return the URL of the chapters file.
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Set the URL of the chapters file.
+    pub fn set_url<V>(&mut self, url: V)
+    where
+        V: Into<String>,
+    {
+        self.url = url.into();
+    }
+
+    /// Return the MIME type of the chapters file.
+    pub fn mime_type(&self) -> &str {
+        self.mime_type.as_str()
+    }
+
+    /// Set the MIME type of the chapters file.
+    pub fn set_mime_type<V>(&mut self, mime_type: V)
+    where
+        V: Into<String>,
+    {
+        self.mime_type = mime_type.into();
+    }
+
+    fn from_extension(mut element: Extension) -> Self {
+        PodcastChapters {
+            url: element.attrs.remove("url").unwrap_or_default(),
+            mime_type: element.attrs.remove("type").unwrap_or_default(),
+        }
+    }
+}
+
+impl ToXml for PodcastChapters {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let attrs = [
+            (b"url" as &[u8], Some(&self.url)),
+            (b"type", Some(&self.mime_type)),
+        ];
+        write_element(writer, b"podcast:chapters", &attrs, None)
+    }
+}
+
+/// Write an element with the attributes that are present and an optional text value.
+fn write_element<W: Write>(
+    writer: &mut Writer<W>,
+    name: &[u8],
+    attrs: &[(&[u8], Option<&String>)],
+    value: Option<&str>,
+) -> Result<(), XmlError> {
+    let mut element = BytesStart::borrowed(name, name.len());
+
+    for &(key, value) in attrs {
+        if let Some(value) = value {
+            element.push_attribute((key, &*escape(value.as_bytes())));
+        }
+    }
+
+    match value {
+        Some(value) => {
+            writer.write_event(Event::Start(element))?;
+            writer
+                .write_event(Event::Text(BytesText::borrowed(value.as_bytes())))?;
+            writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        }
+        None => {
+            writer.write_event(Event::Empty(element))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use quick_xml::errors::Error as XmlError;
+
+use extension::Extension;
+use extension::util::{remove_extension_value, remove_parsed};
+use toxml::{ToXml, Writer, WriterExt};
+
+/// The Slash module XML namespace.
+pub static NAMESPACE: &'static str = "http://purl.org/rss/1.0/modules/slash/";
+
+/// A Slash module item element extension.
+///
+/// Blogs use the extension to report how many comments an item has.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct SlashExtension {
+    /// The number of comments on the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    comments: Option<u32>,
+    /// The number of comments at each threshold, separated by commas.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    hit_parade: Option<String>,
+    /// The department of the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    department: Option<String>,
+    /// The section of the site that the item belongs to.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    section: Option<String>,
+}
+
+impl SlashExtension {
+    /// Return the number of comments on the item.
+    pub fn comments(&self) -> Option<u32> {
+        self.comments
+    }
+
+    /// Set the number of comments on the item.
+    pub fn set_comments<V>(&mut self, comments: V)
+    where
+        V: Into<Option<u32>>,
+    {
+        self.comments = comments.into();
+    }
+
+    /// Return the number of comments at each threshold, separated by commas.
+    pub fn hit_parade(&self) -> Option<&str> {
+        self.hit_parade.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the number of comments at each threshold, separated by commas.
+    pub fn set_hit_parade<V>(&mut self, hit_parade: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.hit_parade = hit_parade.into();
+    }
+
+    /// Return the department of the item.
+    pub fn department(&self) -> Option<&str> {
+        self.department.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the department of the item.
+    pub fn set_department<V>(&mut self, department: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.department = department.into();
+    }
+
+    /// Return the section of the site that the item belongs to.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the section of the site that the item belongs to.
+    pub fn set_section<V>(&mut self, section: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.section = section.into();
+    }
+}
+
+impl SlashExtension {
+    /// Create a `SlashExtension` from a `HashMap`.
+    pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        SlashExtension::take_from_map(&mut map)
+    }
+
+    /// Create a `SlashExtension` from the recognized elements of a `HashMap`, leaving the other
+    /// elements in the map.
+    ///
+    /// A comment count that cannot be parsed is left in the map so that it is written back out.
+    pub(crate) fn take_from_map(map: &mut HashMap<String, Vec<Extension>>) -> Self {
+        let mut ext = SlashExtension::default();
+        ext.read_map(map);
+        ext
+    }
+
+    /// Fill in the fields of this extension that are not yet set from the recognized elements
+    /// of a `HashMap`, leaving the other elements in the map.
+    pub(crate) fn read_map(&mut self, map: &mut HashMap<String, Vec<Extension>>) {
+        if self.comments.is_none() {
+            self.comments = remove_parsed(map, "comments");
+        }
+
+        if self.hit_parade.is_none() {
+            self.hit_parade = remove_extension_value(map, "hit_parade");
+        }
+
+        if self.department.is_none() {
+            self.department = remove_extension_value(map, "department");
+        }
+
+        if self.section.is_none() {
+            self.section = remove_extension_value(map, "section");
+        }
+    }
+}
+
+impl ToXml for SlashExtension {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        if let Some(section) = self.section.as_ref() {
+            writer.write_text_element(b"slash:section", section)?;
+        }
+
+        if let Some(department) = self.department.as_ref() {
+            writer.write_text_element(b"slash:department", department)?;
+        }
+
+        if let Some(comments) = self.comments {
+            writer.write_text_element(b"slash:comments", comments.to_string())?;
+        }
+
+        if let Some(hit_parade) = self.hit_parade.as_ref() {
+            writer.write_text_element(b"slash:hit_parade", hit_parade)?;
+        }
+
+        Ok(())
+    }
+}
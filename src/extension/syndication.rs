@@ -0,0 +1,279 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset};
+use quick_xml::errors::Error as XmlError;
+
+#[cfg(feature = "chrono")]
+use date::parse_w3cdtf;
+use extension::Extension;
+use extension::util::{remove_extension_value, remove_parsed};
+use toxml::{ToXml, Writer, WriterExt};
+
+/// The syndication module XML namespace.
+pub static NAMESPACE: &'static str = "http://purl.org/rss/1.0/modules/syndication/";
+
+/// A syndication module channel element extension.
+///
+/// The extension describes how often the channel is updated, so that readers know how often to
+/// fetch it.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct SyndicationExtension {
+    /// The period over which the channel is updated.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    update_period: Option<UpdatePeriod>,
+    /// The number of times the channel is updated per period.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    update_frequency: Option<u32>,
+    /// The date and time that the update schedule starts from.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    update_base: Option<String>,
+}
+
+impl SyndicationExtension {
+    /// Return the period over which the channel is updated.
+    ///
+    /// The syndication module specifies that the period is `UpdatePeriod::Daily` when it is
+    /// absent.
+    pub fn update_period(&self) -> Option<UpdatePeriod> {
+        self.update_period
+    }
+
+    /// Set the period over which the channel is updated.
+    pub fn set_update_period<V>(&mut self, update_period: V)
+    where
+        V: Into<Option<UpdatePeriod>>,
+    {
+        self.update_period = update_period.into();
+    }
+
+    /// Return the number of times the channel is updated per period.
+    ///
+    /// The syndication module specifies that the frequency is `1` when it is absent.
+    pub fn update_frequency(&self) -> Option<u32> {
+        self.update_frequency
+    }
+
+    /// Set the number of times the channel is updated per period.
+    pub fn set_update_frequency<V>(&mut self, update_frequency: V)
+    where
+        V: Into<Option<u32>>,
+    {
+        self.update_frequency = update_frequency.into();
+    }
+
+    /// Return the date and time that the update schedule starts from.
+    pub fn update_base(&self) -> Option<&str> {
+        self.update_base.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the date and time that the update schedule starts from.
+    pub fn set_update_base<V>(&mut self, update_base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.update_base = update_base.into();
+    }
+
+    /// Return the date and time that the update schedule starts from as a `DateTime`.
+    ///
+    /// `None` is returned if there is no base or it is not a W3C date and time.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::syndication::SyndicationExtension;
+    ///
+    /// let mut extension = SyndicationExtension::default();
+    /// extension.set_update_base("2000-01-01T12:00+00:00".to_string());
+    ///
+    /// let update_base = extension.update_base_parsed().unwrap();
+    /// assert_eq!(update_base.to_rfc3339(), "2000-01-01T12:00:00+00:00");
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn update_base_parsed(&self) -> Option<DateTime<FixedOffset>> {
+        self.update_base
+            .as_ref()
+            .and_then(|update_base| parse_w3cdtf(update_base))
+    }
+
+    /// Return how long to wait between fetches of the channel.
+    ///
+    /// This is the update period divided by the update frequency, using the defaults of the
+    /// syndication module for whichever is absent. `None` is returned if neither is present or
+    /// the frequency is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rss::extension::syndication::{SyndicationExtension, UpdatePeriod};
+    ///
+    /// let mut extension = SyndicationExtension::default();
+    /// assert_eq!(extension.refresh_interval(), None);
+    ///
+    /// extension.set_update_period(UpdatePeriod::Hourly);
+    /// extension.set_update_frequency(2);
+    /// assert_eq!(extension.refresh_interval(), Some(Duration::from_secs(30 * 60)));
+    /// ```
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        if self.update_period.is_none() && self.update_frequency.is_none() {
+            return None;
+        }
+
+        let period = self.update_period.unwrap_or(UpdatePeriod::Daily);
+        match self.update_frequency.unwrap_or(1) {
+            0 => None,
+            frequency => Some(period.duration() / frequency),
+        }
+    }
+}
+
+impl SyndicationExtension {
+    /// Create a `SyndicationExtension` from a `HashMap`.
+    pub fn from_map(mut map: HashMap<String, Vec<Extension>>) -> Self {
+        let mut ext = SyndicationExtension::default();
+        ext.read_map(&mut map);
+        ext
+    }
+
+    /// Add the recognized elements of a `HashMap` to this extension, leaving the other elements
+    /// in the map.
+    ///
+    /// Fields that are already set are not replaced. A period or frequency that cannot be parsed
+    /// is left in the map so that it is written back out.
+    pub(crate) fn read_map(&mut self, map: &mut HashMap<String, Vec<Extension>>) {
+        if self.update_period.is_none() {
+            self.update_period = remove_parsed(map, "updatePeriod");
+        }
+
+        if self.update_frequency.is_none() {
+            self.update_frequency = remove_parsed(map, "updateFrequency");
+        }
+
+        if self.update_base.is_none() {
+            self.update_base = remove_extension_value(map, "updateBase");
+        }
+    }
+}
+
+impl ToXml for SyndicationExtension {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        if let Some(update_period) = self.update_period {
+            writer.write_text_element(b"sy:updatePeriod", update_period.as_str())?;
+        }
+
+        if let Some(update_frequency) = self.update_frequency {
+            writer.write_text_element(b"sy:updateFrequency", update_frequency.to_string())?;
+        }
+
+        if let Some(update_base) = self.update_base.as_ref() {
+            writer.write_text_element(b"sy:updateBase", update_base)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The period over which a channel is updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(rename_all = "lowercase"))]
+pub enum UpdatePeriod {
+    /// Every hour.
+    Hourly,
+    /// Every day.
+    Daily,
+    /// Every week.
+    Weekly,
+    /// Every month.
+    Monthly,
+    /// Every year.
+    Yearly,
+}
+
+impl UpdatePeriod {
+    /// Return the value that is written for this period.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::syndication::UpdatePeriod;
+    ///
+    /// assert_eq!(UpdatePeriod::Hourly.as_str(), "hourly");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            UpdatePeriod::Hourly => "hourly",
+            UpdatePeriod::Daily => "daily",
+            UpdatePeriod::Weekly => "weekly",
+            UpdatePeriod::Monthly => "monthly",
+            UpdatePeriod::Yearly => "yearly",
+        }
+    }
+
+    /// Return the length of this period, counting a month as 30 days and a year as 365 days.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rss::extension::syndication::UpdatePeriod;
+    ///
+    /// assert_eq!(UpdatePeriod::Daily.duration(), Duration::from_secs(24 * 60 * 60));
+    /// ```
+    pub fn duration(&self) -> Duration {
+        const DAY: u64 = 24 * 60 * 60;
+        let seconds = match *self {
+            UpdatePeriod::Hourly => 60 * 60,
+            UpdatePeriod::Daily => DAY,
+            UpdatePeriod::Weekly => 7 * DAY,
+            UpdatePeriod::Monthly => 30 * DAY,
+            UpdatePeriod::Yearly => 365 * DAY,
+        };
+        Duration::from_secs(seconds)
+    }
+}
+
+impl FromStr for UpdatePeriod {
+    type Err = ();
+
+    /// Parse one of the periods defined by the syndication module, ignoring case.
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let periods = [
+            UpdatePeriod::Hourly,
+            UpdatePeriod::Daily,
+            UpdatePeriod::Weekly,
+            UpdatePeriod::Monthly,
+            UpdatePeriod::Yearly,
+        ];
+        let s = s.trim();
+        periods
+            .iter()
+            .find(|period| s.eq_ignore_ascii_case(period.as_str()))
+            .cloned()
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for UpdatePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
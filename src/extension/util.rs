@@ -5,16 +5,28 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::BufRead;
-use std::str;
+use std::str::{self, FromStr};
 
-use quick_xml::events::Event;
-use quick_xml::events::attributes::Attributes;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 
 use error::Error;
-use extension::{Extension, ExtensionMap};
+use extension::{Extension, ExtensionMap, Node};
+use parseoptions::ParseContext;
+use util::{attr_value, decode_with_context, unescape};
+
+/// Return whether every element of the extensions with the given prefix declares the prefix
+/// itself, so that the prefix does not have to be declared on the root element.
+pub(crate) fn declares_prefix(prefix: &str, extensions: &HashMap<String, Vec<Extension>>) -> bool {
+    let key = format!("xmlns:{}", prefix);
+    extensions
+        .values()
+        .flat_map(|elements| elements)
+        .all(|element| element.attrs().contains_key(&key))
+}
 
 pub fn extension_name(element_name: &[u8]) -> Option<(&[u8], &[u8])> {
     let mut split = element_name.splitn(2, |b| *b == b':');
@@ -26,17 +38,19 @@ pub fn extension_name(element_name: &[u8]) -> Option<(&[u8], &[u8])> {
 
 pub fn parse_extension<R>(
     reader: &mut Reader<R>,
-    atts: Attributes,
+    element: &BytesStart,
     ns: &[u8],
     name: &[u8],
     extensions: &mut ExtensionMap,
+    context: &mut ParseContext,
+    depth: usize,
 ) -> Result<(), Error>
 where
     R: BufRead,
 {
     let ns = str::from_utf8(ns)?;
     let name = str::from_utf8(name)?;
-    let ext = parse_extension_element(reader, atts)?;
+    let ext = parse_extension_element(reader, element, context, depth)?;
 
     if !extensions.contains_key(ns) {
         extensions.insert(ns.to_string(), HashMap::new());
@@ -61,17 +75,23 @@ where
     Ok(())
 }
 
-fn parse_extension_element<R: BufRead>(
+/// Parse an element and its children, which is at the given depth in the document.
+pub fn parse_extension_element<R: BufRead>(
     reader: &mut Reader<R>,
-    mut atts: Attributes,
+    element: &BytesStart,
+    context: &mut ParseContext,
+    depth: usize,
 ) -> Result<Extension, Error> {
+    context.check_depth(depth)?;
+
     let mut extension = Extension::default();
     let mut buf = Vec::new();
 
-    for attr in atts.with_checks(false) {
+    for attr in element.attributes().with_checks(false) {
         if let Ok(attr) = attr {
+            context.check_length(attr.value.len())?;
             let key = str::from_utf8(attr.key)?;
-            let value = attr.unescape_and_decode_value(reader)?;
+            let value = attr_value(reader, element.name(), &attr, context)?;
             extension.attrs.insert(key.to_string(), value);
         }
     }
@@ -79,7 +99,7 @@ fn parse_extension_element<R: BufRead>(
     loop {
         match reader.read_event(&mut buf)? {
             Event::Start(element) => {
-                let ext = parse_extension_element(reader, element.attributes())?;
+                let ext = parse_extension_element(reader, &element, context, depth + 1)?;
                 let name = str::from_utf8(element.local_name())?;
 
                 if !extension.children.contains_key(name) {
@@ -92,12 +112,26 @@ fn parse_extension_element<R: BufRead>(
                 };
 
                 items.push(ext);
+                extension.order.0.push(Node::Child(name.to_string()));
             }
             Event::CData(element) => {
-                extension.value = Some(reader.decode(&element).into_owned());
+                let text = decode_with_context(reader, &element, context)?;
+                let value = extension.value.get_or_insert_with(String::new);
+                value.push_str(&text);
+                context.check_length(value.len())?;
+                extension.order.0.push(Node::Text(text.len()));
             }
             Event::Text(element) => {
-                extension.value = Some(element.unescape_and_decode(reader)?);
+                let text = decode_with_context(reader, &element, context)?;
+                let text = if text.contains('&') {
+                    Cow::Owned(unescape(&text, reader.buffer_position(), context)?)
+                } else {
+                    text
+                };
+                let value = extension.value.get_or_insert_with(String::new);
+                value.push_str(&text);
+                context.check_length(value.len())?;
+                extension.order.0.push(Node::Text(text.len()));
             }
             Event::End(element) => {
                 extension.name = reader.decode(element.name()).into_owned();
@@ -113,6 +147,47 @@ fn parse_extension_element<R: BufRead>(
     Ok(extension)
 }
 
+/// Return the first element with the given local name in the namespace with the given URI,
+/// whichever prefix the namespace was declared with.
+pub fn find_extension<'a>(
+    extensions: &'a ExtensionMap,
+    namespaces: &HashMap<String, String>,
+    namespace: &str,
+    name: &str,
+) -> Option<&'a Extension> {
+    // sorted so that the result does not depend on the order of the maps
+    let mut prefixes = namespaces
+        .iter()
+        .filter(|&(_, uri)| uri == namespace)
+        .map(|(prefix, _)| prefix)
+        .collect::<Vec<_>>();
+    prefixes.sort();
+
+    prefixes
+        .into_iter()
+        .filter_map(|prefix| extensions.get(prefix))
+        .filter_map(|map| map.get(name))
+        .filter_map(|elements| elements.first())
+        .next()
+}
+
+/// Remove the first element with the given name from the map, leaving any others in the map.
+pub fn remove_first_extension(
+    map: &mut HashMap<String, Vec<Extension>>,
+    key: &str,
+) -> Option<Extension> {
+    let (extension, empty) = match map.get_mut(key) {
+        Some(elements) if !elements.is_empty() => (elements.remove(0), elements.is_empty()),
+        _ => return None,
+    };
+
+    if empty {
+        map.remove(key);
+    }
+
+    Some(extension)
+}
+
 pub fn remove_extension_values(
     map: &mut HashMap<String, Vec<Extension>>,
     key: &str,
@@ -132,3 +207,20 @@ pub fn remove_extension_value(
         .map(|mut v| v.remove(0))
         .and_then(|ext| ext.value)
 }
+
+/// Remove the first element with the given name from the map if its value can be parsed.
+pub fn remove_parsed<T: FromStr>(
+    map: &mut HashMap<String, Vec<Extension>>,
+    key: &str,
+) -> Option<T> {
+    let value = map.get(key)
+        .and_then(|elements| elements.first())
+        .and_then(|element| element.value())
+        .and_then(|value| value.trim().parse().ok());
+
+    if value.is_some() {
+        map.remove(key);
+    }
+
+    value
+}
@@ -11,37 +11,44 @@ use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::ToXml;
-use util::element_text;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer};
+use util::{attr_values, element_text_with_context};
 
 /// Represents the GUID of an RSS item.
-#[derive(Debug, Clone, PartialEq, Builder)]
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Guid {
     /// The value of the GUID.
     value: String,
-    /// Indicates if the GUID is a permalink.
-    permalink: bool,
+    /// The value of the `isPermaLink` attribute, if it is present.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    permalink: Option<bool>,
 }
 
 impl Guid {
     /// Return whether this GUID is a permalink.
     ///
+    /// A GUID is a permalink unless the `isPermaLink` attribute is `false`.
+    ///
     /// # Examples
     ///
     /// ```
     /// use rss::Guid;
     ///
     /// let mut guid = Guid::default();
-    /// guid.set_permalink(true);
     /// assert!(guid.is_permalink());
+    ///
+    /// guid.set_permalink(false);
+    /// assert!(!guid.is_permalink());
     /// ```
     pub fn is_permalink(&self) -> bool {
-        self.permalink
+        self.permalink.unwrap_or(true)
     }
 
     /// Set whether this GUID is a permalink.
@@ -57,10 +64,68 @@ impl Guid {
     pub fn set_permalink<V>(&mut self, permalink: V)
     where
         V: Into<bool>,
+    {
+        self.permalink = Some(permalink.into())
+    }
+
+    /// Return the value of the `isPermaLink` attribute of this GUID, or `None` if the attribute
+    /// is not present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Guid;
+    ///
+    /// let mut guid = Guid::default();
+    /// assert_eq!(guid.permalink_attribute(), None);
+    ///
+    /// guid.set_permalink(true);
+    /// assert_eq!(guid.permalink_attribute(), Some(true));
+    /// ```
+    pub fn permalink_attribute(&self) -> Option<bool> {
+        self.permalink
+    }
+
+    /// Set the value of the `isPermaLink` attribute of this GUID. The attribute is not written
+    /// if this is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Guid;
+    ///
+    /// let mut guid = Guid::default();
+    /// guid.set_permalink_attribute(None);
+    /// ```
+    pub fn set_permalink_attribute<V>(&mut self, permalink: V)
+    where
+        V: Into<Option<bool>>,
     {
         self.permalink = permalink.into()
     }
 
+    /// Return the value of this GUID if it is a permalink.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Guid;
+    ///
+    /// let mut guid = Guid::default();
+    /// guid.set_value("http://example.com/1");
+    /// assert_eq!(guid.permalink_url(), Some("http://example.com/1"));
+    ///
+    /// guid.set_permalink(false);
+    /// assert_eq!(guid.permalink_url(), None);
+    /// ```
+    pub fn permalink_url(&self) -> Option<&str> {
+        if self.is_permalink() {
+            Some(self.value.as_str())
+        } else {
+            None
+        }
+    }
+
     /// Return the value of this GUID.
     ///
     /// # Examples
@@ -94,30 +159,27 @@ impl Guid {
     }
 }
 
-impl Default for Guid {
-    #[inline]
-    fn default() -> Self {
-        Guid {
-            value: Default::default(),
-            permalink: true,
-        }
+impl FromXml for Guid {
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Guid::from_xml_with_context(reader, atts, &mut ParseContext::default())
     }
 }
 
-impl FromXml for Guid {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, mut atts: Attributes) -> Result<Self, Error> {
+impl Guid {
+    /// Parse a `<guid>` element, resolving character references as the context requires.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
         let mut guid = Guid::default();
+        let mut attrs = attr_values(reader, b"guid", atts, &[b"isPermaLink"], context)?;
+        // only an explicit false makes the GUID an opaque identifier
+        guid.permalink = attrs
+            .take(b"isPermaLink")
+            .map(|value| !value.trim().eq_ignore_ascii_case("false"));
 
-        for attr in atts.with_checks(false) {
-            if let Ok(attr) = attr {
-                if attr.key == b"isPermaLink" {
-                    guid.permalink = attr.value != b"false";
-                    break;
-                }
-            }
-        }
-
-        guid.value = element_text(reader)?.unwrap_or_default();
+        guid.value = element_text_with_context(reader, context)?.unwrap_or_default();
         Ok(guid)
     }
 }
@@ -126,8 +188,9 @@ impl ToXml for Guid {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = b"guid";
         let mut element = BytesStart::borrowed(name, name.len());
-        if !self.permalink {
-            element.push_attribute(("isPermaLink", "false"));
+        if let Some(permalink) = self.permalink {
+            let value = if permalink { "true" } else { "false" };
+            element.push_attribute(("isPermaLink", value));
         }
 
         writer.write_event(Event::Start(element))?;
@@ -5,21 +5,33 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
+use std::error::Error as StdError;
+use std::fmt;
 use std::io::{BufRead, Write};
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::{ToXml, WriterExt};
+use toxml::{ToXml, Writer, WriterExt};
 use util::element_text;
 
+/// The width of an image when none is given.
+const DEFAULT_WIDTH: u32 = 88;
+/// The largest width of an image.
+const MAX_WIDTH: u32 = 144;
+/// The height of an image when none is given.
+const DEFAULT_HEIGHT: u32 = 31;
+/// The largest height of an image.
+const MAX_HEIGHT: u32 = 400;
+
 /// Represents an image in an RSS feed.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Image {
     /// The URL of the image.
@@ -29,10 +41,13 @@ pub struct Image {
     /// The URL that the image links to.
     link: String,
     /// The width of the image.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     width: Option<String>,
     /// The height of the image.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     height: Option<String>,
     /// The text for the HTML `title` attribute of the link formed around the image.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     description: Option<String>,
 }
 
@@ -137,7 +152,7 @@ impl Image {
 
     /// Return the width of this image.
     ///
-    /// If the width is `None` the default value should be considered to be `80`.
+    /// If the width is `None` the default value should be considered to be `88`.
     ///
     /// # Examples
     ///
@@ -147,6 +162,7 @@ impl Image {
     /// let mut image = Image::default();
     /// image.set_width("80".to_string());
     /// assert_eq!(image.width(), Some("80"));
+    /// ```
     pub fn width(&self) -> Option<&str> {
         self.width.as_ref().map(|s| s.as_str())
     }
@@ -160,6 +176,7 @@ impl Image {
     ///
     /// let mut image = Image::default();
     /// image.set_width("80".to_string());
+    /// ```
     pub fn set_width<V>(&mut self, width: V)
     where
         V: Into<Option<String>>,
@@ -167,6 +184,62 @@ impl Image {
         self.width = width.into();
     }
 
+    /// Return the width of this image in pixels.
+    ///
+    /// The default value of `88` is returned if there is no width, and `None` is
+    /// returned if the width is not a whole number. Use `width` to tell whether the width was
+    /// actually given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Image;
+    ///
+    /// let mut image = Image::default();
+    /// assert_eq!(image.width_px(), Some(88));
+    ///
+    /// image.set_width("20".to_string());
+    /// assert_eq!(image.width_px(), Some(20));
+    ///
+    /// image.set_width("wide".to_string());
+    /// assert_eq!(image.width_px(), None);
+    /// ```
+    pub fn width_px(&self) -> Option<u32> {
+        match self.width {
+            Some(ref width) => width.trim().parse().ok(),
+            None => Some(DEFAULT_WIDTH),
+        }
+    }
+
+    /// Set the width of this image in pixels.
+    ///
+    /// The width is left unchanged and an error is returned if it is larger than the maximum
+    /// of `144`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Image;
+    ///
+    /// let mut image = Image::default();
+    /// image.set_width_px(20).unwrap();
+    /// assert_eq!(image.width(), Some("20"));
+    ///
+    /// assert!(image.set_width_px(145).is_err());
+    /// assert_eq!(image.width(), Some("20"));
+    /// ```
+    pub fn set_width_px(&mut self, width: u32) -> Result<(), ImageSizeError> {
+        if width > MAX_WIDTH {
+            return Err(ImageSizeError {
+                dimension: "width",
+                value: width,
+                max: MAX_WIDTH,
+            });
+        }
+        self.width = Some(width.to_string());
+        Ok(())
+    }
+
     /// Return the height of this image.
     ///
     /// If the height is `None` the default value should be considered to be `31`.
@@ -203,6 +276,62 @@ impl Image {
         self.height = height.into();
     }
 
+    /// Return the height of this image in pixels.
+    ///
+    /// The default value of `31` is returned if there is no height, and `None` is
+    /// returned if the height is not a whole number. Use `height` to tell whether the height was
+    /// actually given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Image;
+    ///
+    /// let mut image = Image::default();
+    /// assert_eq!(image.height_px(), Some(31));
+    ///
+    /// image.set_height("20".to_string());
+    /// assert_eq!(image.height_px(), Some(20));
+    ///
+    /// image.set_height("wide".to_string());
+    /// assert_eq!(image.height_px(), None);
+    /// ```
+    pub fn height_px(&self) -> Option<u32> {
+        match self.height {
+            Some(ref height) => height.trim().parse().ok(),
+            None => Some(DEFAULT_HEIGHT),
+        }
+    }
+
+    /// Set the height of this image in pixels.
+    ///
+    /// The height is left unchanged and an error is returned if it is larger than the maximum
+    /// of `400`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Image;
+    ///
+    /// let mut image = Image::default();
+    /// image.set_height_px(20).unwrap();
+    /// assert_eq!(image.height(), Some("20"));
+    ///
+    /// assert!(image.set_height_px(401).is_err());
+    /// assert_eq!(image.height(), Some("20"));
+    /// ```
+    pub fn set_height_px(&mut self, height: u32) -> Result<(), ImageSizeError> {
+        if height > MAX_HEIGHT {
+            return Err(ImageSizeError {
+                dimension: "height",
+                value: height,
+                max: MAX_HEIGHT,
+            });
+        }
+        self.height = Some(height.to_string());
+        Ok(())
+    }
+
     /// Return the title for the link formed around this image.
     ///
     /// # Examples
@@ -293,3 +422,49 @@ impl ToXml for Image {
         Ok(())
     }
 }
+
+/// An error returned when an image width or height is larger than the RSS specification allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageSizeError {
+    /// The dimension that was out of range, either `width` or `height`.
+    dimension: &'static str,
+    /// The value that was rejected.
+    value: u32,
+    /// The largest value that is allowed.
+    max: u32,
+}
+
+impl ImageSizeError {
+    /// Return the dimension that was out of range, either `width` or `height`.
+    pub fn dimension(&self) -> &'static str {
+        self.dimension
+    }
+
+    /// Return the value that was rejected.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Return the largest value that is allowed.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+}
+
+impl StdError for ImageSizeError {
+    fn description(&self) -> &str {
+        "the image size is larger than the maximum"
+    }
+}
+
+impl fmt::Display for ImageSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the image {} of {} is larger than the maximum of {}",
+            self.dimension,
+            self.value,
+            self.max
+        )
+    }
+}
@@ -5,59 +5,199 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
+use std::borrow::Cow;
+#[cfg(feature = "chrono")]
+use std::fmt::Display;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::io::{BufRead, Write};
+use std::str::{self, FromStr};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, TimeZone};
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use category::Category;
+#[cfg(feature = "chrono")]
+use date::parse_rfc822;
 use enclosure::Enclosure;
+use entities::unescape_html;
 use error::Error;
-use extension::ExtensionMap;
+use extension::{Extension, ExtensionMap};
+use extension::custom::CustomExtensionMap;
 use extension::dublincore::DublinCoreExtension;
 use extension::itunes::ITunesItemExtension;
-use extension::util::{extension_name, parse_extension};
+use extension::media::MediaExtension;
+use extension::podcast::{self, PodcastItemExtension};
+use extension::slash::{self, SlashExtension};
+use extension::util::{extension_name, find_extension, parse_extension, parse_extension_element};
 use fromxml::FromXml;
 use guid::Guid;
+use link::Link;
+use parseoptions::ParseContext;
 use source::Source;
-use toxml::{ToXml, WriterExt};
-use util::element_text;
+use toxml::{ToXml, Writer, WriterExt, escape};
+use util::attr_value;
+
+/// The content module XML namespace.
+pub(crate) static CONTENT_NAMESPACE: &'static str = "http://purl.org/rss/1.0/modules/content/";
+
+/// The number of characters of the content that `Item::summary` keeps.
+const SUMMARY_LENGTH: usize = 200;
+
+/// The names of the elements of an item that are matched ignoring case, if parsing is
+/// case-insensitive.
+static ELEMENTS: &'static [&'static [u8]] = &[
+    b"title",
+    b"link",
+    b"description",
+    b"author",
+    b"category",
+    b"comments",
+    b"enclosure",
+    b"guid",
+    b"pubDate",
+    b"source",
+    b"content:encoded",
+];
 
 /// Represents an item in an RSS feed.
+///
+/// An item that was read is written with its children in the order they were read in, followed
+/// by any that were added since. The elements of the iTunes, Dublin Core, Media RSS and
+/// Podcasting 2.0 extensions and of each custom extension are written together, in place of the
+/// first of them.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Item {
     /// The title of the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     title: Option<String>,
     /// The URL of the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     link: Option<String>,
+    /// The links of the item other than its URL.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    links: Vec<Link>,
     /// The item synopsis.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     description: Option<String>,
     /// The email address of author of the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     author: Option<String>,
     /// The categories the item belongs to.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
     categories: Vec<Category>,
     /// The URL for the comments page of the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     comments: Option<String>,
     /// The description of a media object that is attached to the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     enclosure: Option<Enclosure>,
     /// A unique identifier for the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     guid: Option<Guid>,
     /// The date the item was published as an RFC822 timestamp.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     pub_date: Option<String>,
     /// The RSS channel the item came from.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     source: Option<Source>,
     /// The HTML contents of the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     content: Option<String>,
     /// The extensions for the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "HashMap::is_empty"))]
     extensions: ExtensionMap,
     /// The iTunes extension for the item.
+    #[cfg_attr(feature = "serialization", serde(rename = "itunes"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     itunes_ext: Option<ITunesItemExtension>,
     /// The Dublin Core extension for the item.
+    #[cfg_attr(feature = "serialization", serde(rename = "dublinCore"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     dublin_core_ext: Option<DublinCoreExtension>,
+    /// The Media RSS extension for the item.
+    #[cfg_attr(feature = "serialization", serde(rename = "media"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    media_ext: Option<MediaExtension>,
+    /// The Podcasting 2.0 extension for the item.
+    #[cfg_attr(feature = "serialization", serde(rename = "podcast"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    podcast_ext: Option<PodcastItemExtension>,
+    /// The Slash module extension for the item.
+    #[cfg_attr(feature = "serialization", serde(rename = "slash"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    slash_ext: Option<SlashExtension>,
+    /// The values of the custom extensions for the item, keyed by namespace URI.
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    custom_extensions: CustomExtensionMap,
+    /// The elements of the item that are not otherwise supported.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Vec::is_empty"))]
+    unknown_elements: Vec<Extension>,
+    /// The base URI for the relative URLs in the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    base: Option<String>,
+    /// The order that the children of the item were read in.
+    #[builder(setter(skip))]
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    order: ChildOrder,
+}
+
+/// A child element of an item, used to record the order that the children were read in.
+#[derive(Debug, Clone, PartialEq)]
+enum Child {
+    Title,
+    Link,
+    /// A link other than the URL of the item.
+    OtherLink,
+    Description,
+    Author,
+    Category,
+    Comments,
+    Enclosure,
+    Guid,
+    PubDate,
+    Source,
+    Content,
+    /// A namespaced element, with its qualified name.
+    Extension(String),
+    Unknown,
+}
+
+/// The order that the children of an item were read in.
+///
+/// The order only affects how the item is written, so it is ignored when items are compared.
+#[derive(Debug, Default, Clone)]
+struct ChildOrder(Vec<Child>);
+
+impl PartialEq for ChildOrder {
+    fn eq(&self, _: &ChildOrder) -> bool {
+        true
+    }
+}
+
+/// The children of an item that have been written so far.
+#[derive(Default)]
+struct Written<'a> {
+    /// The text and object fields that have been written.
+    fields: Vec<Child>,
+    /// The number of links other than the URL that have been written.
+    links: usize,
+    /// The number of categories that have been written.
+    categories: usize,
+    /// The number of unknown elements that have been written.
+    unknown_elements: usize,
+    /// The number of elements of each name in the extension map that have been written.
+    extensions: HashMap<(&'a str, &'a str), usize>,
+    /// The prefixes of the extension structs and custom extensions that have been written.
+    prefixes: HashSet<&'a str>,
 }
 
 impl Item {
@@ -125,6 +265,53 @@ impl Item {
         self.link = link.into();
     }
 
+    /// Return the links of this item other than its URL, in the order they were read in.
+    ///
+    /// The first `<link>` element without a `rel` attribute, or with `rel="alternate"`, is the
+    /// URL returned by `link()` and the others are returned here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let input = r#"
+    ///     <item>
+    ///         <link>http://example.com/post</link>
+    ///         <link rel="related" href="http://example.com/other"/>
+    ///     </item>
+    /// "#;
+    /// let item = input.parse::<Item>().unwrap();
+    /// assert_eq!(item.link(), Some("http://example.com/post"));
+    /// assert_eq!(item.links()[0].href(), "http://example.com/other");
+    /// assert_eq!(item.links()[0].rel(), Some("related"));
+    /// ```
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    /// Return a mutable reference to the links of this item other than its URL.
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
+    /// Set the links of this item other than its URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Item, Link};
+    ///
+    /// let mut item = Item::default();
+    /// item.set_links(vec![Link::default()]);
+    /// ```
+    pub fn set_links<V>(&mut self, links: V)
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.links = links.into();
+    }
+
     /// Return the description of this item.
     ///
     /// # Examples
@@ -204,8 +391,8 @@ impl Item {
         &self.categories
     }
 
-    /// Return a mutable slice of the categories that this item belongs to.
-    pub fn categories_mut(&mut self) -> &mut [Category] {
+    /// Return a mutable reference to the categories that this item belongs to.
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
         &mut self.categories
     }
 
@@ -226,6 +413,42 @@ impl Item {
         self.categories = categories.into();
     }
 
+    /// Return the names of the categories that this item belongs to, without surrounding
+    /// whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{CategoryBuilder, Item};
+    ///
+    /// let mut item = Item::default();
+    /// item.set_categories(vec![
+    ///     CategoryBuilder::default().name(" Rust ").build().unwrap(),
+    ///     CategoryBuilder::default().name("XML").build().unwrap(),
+    /// ]);
+    /// assert_eq!(item.category_values().collect::<Vec<_>>(), vec!["Rust", "XML"]);
+    /// ```
+    pub fn category_values(&self) -> impl Iterator<Item = &str> {
+        self.categories.iter().map(|category| category.name().trim())
+    }
+
+    /// Return whether this item belongs to a category with the given name, ignoring case and
+    /// surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{CategoryBuilder, Item};
+    ///
+    /// let mut item = Item::default();
+    /// item.set_categories(vec![CategoryBuilder::default().name(" Rust ").build().unwrap()]);
+    /// assert!(item.has_category("rust"));
+    /// assert!(!item.has_category("python"));
+    /// ```
+    pub fn has_category(&self, name: &str) -> bool {
+        self.categories.iter().any(|category| category.matches(name))
+    }
+
     /// Return the URL for comments about this item.
     ///
     /// # Examples
@@ -273,6 +496,24 @@ impl Item {
         self.enclosure.as_ref()
     }
 
+    /// Return a mutable reference to the enclosure information for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Enclosure, Item};
+    ///
+    /// let mut item = Item::default();
+    /// item.set_enclosure(Enclosure::default());
+    /// if let Some(enclosure) = item.enclosure_mut() {
+    ///     enclosure.set_url("http://example.com/audio.mp3");
+    /// }
+    /// assert_eq!(item.enclosure().unwrap().url(), "http://example.com/audio.mp3");
+    /// ```
+    pub fn enclosure_mut(&mut self) -> Option<&mut Enclosure> {
+        self.enclosure.as_mut()
+    }
+
     /// Set the enclosure information for this item.
     ///
     /// # Examples
@@ -305,6 +546,11 @@ impl Item {
         self.guid.as_ref()
     }
 
+    /// Return a mutable reference to the GUID for this item.
+    pub fn guid_mut(&mut self) -> Option<&mut Guid> {
+        self.guid.as_mut()
+    }
+
     /// Set the GUID for this item.
     ///
     /// # Examples
@@ -355,6 +601,56 @@ impl Item {
         self.pub_date = pub_date.into();
     }
 
+    /// Return the publication date of this item as a `DateTime`.
+    ///
+    /// Common deviations from RFC822 found in real-world feeds, such as single-digit days or
+    /// `UT` and `UTC` zones, are tolerated. `None` is returned if the date is missing or cannot
+    /// be parsed.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_pub_date("Sun, 1 Jan 2017 12:00:00 UT".to_string());
+    /// assert_eq!(item.pub_date_parsed().unwrap().timestamp(), 1483272000);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn pub_date_parsed(&self) -> Option<DateTime<FixedOffset>> {
+        self.pub_date.as_ref().and_then(|s| parse_rfc822(s))
+    }
+
+    /// Set the publication date of this item from a `DateTime`.
+    ///
+    /// **Note**: This requires enabling the `chrono` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate chrono;
+    /// # extern crate rss;
+    ///
+    /// use chrono::{TimeZone, Utc};
+    /// use rss::Item;
+    ///
+    /// # fn main() {
+    /// let mut item = Item::default();
+    /// item.set_pub_date_from(Utc.ymd(2017, 1, 1).and_hms(12, 0, 0));
+    /// assert_eq!(item.pub_date(), Some("Sun, 1 Jan 2017 12:00:00 +0000"));
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn set_pub_date_from<Tz>(&mut self, pub_date: DateTime<Tz>)
+    where
+        Tz: TimeZone,
+        Tz::Offset: Display,
+    {
+        self.pub_date = Some(pub_date.to_rfc2822());
+    }
+
     /// Return the source URL for this item.
     ///
     /// # Examples
@@ -370,6 +666,11 @@ impl Item {
         self.source.as_ref()
     }
 
+    /// Return a mutable reference to the source URL for this item.
+    pub fn source_mut(&mut self) -> Option<&mut Source> {
+        self.source.as_mut()
+    }
+
     /// Set the source URL for this item.
     ///
     /// # Examples
@@ -389,6 +690,9 @@ impl Item {
 
     /// Return the content of this item.
     ///
+    /// This is the full HTML body of the item from the `<content:encoded>` element of the
+    /// [content](http://purl.org/rss/1.0/modules/content/) module.
+    ///
     /// # Examples
     ///
     /// ```
@@ -404,6 +708,8 @@ impl Item {
 
     /// Set the content of this item.
     ///
+    /// The content is written in a CDATA section so that HTML does not need to be escaped.
+    ///
     /// # Examples
     ///
     /// ```
@@ -435,6 +741,11 @@ impl Item {
         self.itunes_ext.as_ref()
     }
 
+    /// Return a mutable reference to the iTunes extension for this item.
+    pub fn itunes_ext_mut(&mut self) -> Option<&mut ITunesItemExtension> {
+        self.itunes_ext.as_mut()
+    }
+
     /// Set the iTunes extension for this item.
     ///
     /// # Examples
@@ -469,6 +780,11 @@ impl Item {
         self.dublin_core_ext.as_ref()
     }
 
+    /// Return a mutable reference to the Dublin Core extension for this item.
+    pub fn dublin_core_ext_mut(&mut self) -> Option<&mut DublinCoreExtension> {
+        self.dublin_core_ext.as_mut()
+    }
+
     /// Set the Dublin Core extension for this item.
     ///
     /// # Examples
@@ -487,6 +803,171 @@ impl Item {
         self.dublin_core_ext = dublin_core_ext.into();
     }
 
+    /// Return the Media RSS extension for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::media::MediaExtension;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_media_ext(MediaExtension::default());
+    /// assert!(item.media_ext().is_some());
+    /// ```
+    pub fn media_ext(&self) -> Option<&MediaExtension> {
+        self.media_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the Media RSS extension for this item.
+    pub fn media_ext_mut(&mut self) -> Option<&mut MediaExtension> {
+        self.media_ext.as_mut()
+    }
+
+    /// Set the Media RSS extension for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::media::MediaExtension;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_media_ext(MediaExtension::default());
+    /// ```
+    pub fn set_media_ext<V>(&mut self, media_ext: V)
+    where
+        V: Into<Option<MediaExtension>>,
+    {
+        self.media_ext = media_ext.into();
+    }
+
+    /// Return the Podcasting 2.0 extension for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::podcast::PodcastItemExtension;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_podcast_ext(PodcastItemExtension::default());
+    /// assert!(item.podcast_ext().is_some());
+    /// ```
+    pub fn podcast_ext(&self) -> Option<&PodcastItemExtension> {
+        self.podcast_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the Podcasting 2.0 extension for this item.
+    pub fn podcast_ext_mut(&mut self) -> Option<&mut PodcastItemExtension> {
+        self.podcast_ext.as_mut()
+    }
+
+    /// Set the Podcasting 2.0 extension for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::podcast::PodcastItemExtension;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_podcast_ext(PodcastItemExtension::default());
+    /// ```
+    pub fn set_podcast_ext<V>(&mut self, podcast_ext: V)
+    where
+        V: Into<Option<PodcastItemExtension>>,
+    {
+        self.podcast_ext = podcast_ext.into();
+    }
+
+    /// Return the Slash module extension for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::slash::SlashExtension;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_slash_ext(SlashExtension::default());
+    /// assert!(item.slash_ext().is_some());
+    /// ```
+    pub fn slash_ext(&self) -> Option<&SlashExtension> {
+        self.slash_ext.as_ref()
+    }
+
+    /// Return a mutable reference to the Slash module extension for this item.
+    pub fn slash_ext_mut(&mut self) -> Option<&mut SlashExtension> {
+        self.slash_ext.as_mut()
+    }
+
+    /// Set the Slash module extension for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::slash::SlashExtension;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_slash_ext(SlashExtension::default());
+    /// ```
+    pub fn set_slash_ext<V>(&mut self, slash_ext: V)
+    where
+        V: Into<Option<SlashExtension>>,
+    {
+        self.slash_ext = slash_ext.into();
+    }
+
+    /// Return the values of the custom extensions for this item.
+    ///
+    /// Values are added by the handlers registered with `ParseOptions::register_extension`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let item = Item::default();
+    /// assert!(item.custom_extensions().is_empty());
+    /// ```
+    pub fn custom_extensions(&self) -> &CustomExtensionMap {
+        &self.custom_extensions
+    }
+
+    /// Return a mutable reference to the values of the custom extensions for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.custom_extensions_mut().remove("http://example.com/ns");
+    /// ```
+    pub fn custom_extensions_mut(&mut self) -> &mut CustomExtensionMap {
+        &mut self.custom_extensions
+    }
+
+    /// Set the values of the custom extensions for this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::custom::CustomExtensionMap;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_custom_extensions(CustomExtensionMap::default());
+    /// ```
+    pub fn set_custom_extensions<V>(&mut self, custom_extensions: V)
+    where
+        V: Into<CustomExtensionMap>,
+    {
+        self.custom_extensions = custom_extensions.into();
+    }
+
     /// Return the extensions for this item.
     ///
     /// # Examples
@@ -516,6 +997,11 @@ impl Item {
         &self.extensions
     }
 
+    /// Return a mutable reference to the extensions for this item.
+    pub fn extensions_mut(&mut self) -> &mut ExtensionMap {
+        &mut self.extensions
+    }
+
     /// Set the extensions for this item.
     ///
     /// # Examples
@@ -533,54 +1019,439 @@ impl Item {
     {
         self.extensions = extensions.into();
     }
+
+    /// Return the first extension element of this item with the given local name in the
+    /// namespace with the given URI.
+    ///
+    /// The namespace is resolved through the given namespaces, which are those of the channel
+    /// that the item belongs to, so the element is found whichever prefix the feed declared the
+    /// namespace with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let input = r#"
+    /// <rss version="2.0" xmlns:ex="http://example.com/ns">
+    ///   <channel>
+    ///     <item><ex:note>First</ex:note><ex:note>Second</ex:note></item>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let channel = input.parse::<Channel>().unwrap();
+    /// let item = &channel.items()[0];
+    /// let note = item.extension(channel.namespaces(), "http://example.com/ns", "note");
+    /// assert_eq!(note.and_then(|note| note.value()), Some("First"));
+    /// ```
+    pub fn extension(
+        &self,
+        namespaces: &HashMap<String, String>,
+        namespace: &str,
+        name: &str,
+    ) -> Option<&Extension> {
+        find_extension(&self.extensions, namespaces, namespace, name)
+    }
+
+    /// Return the elements of this item that are not otherwise supported.
+    ///
+    /// Unprefixed child elements that are not part of the RSS specification are kept here so
+    /// that they are written back out when the item is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    /// use rss::extension::Extension;
+    ///
+    /// let mut element = Extension::default();
+    /// element.set_name("rank");
+    /// element.set_value("5".to_string());
+    ///
+    /// let mut item = Item::default();
+    /// item.set_unknown_elements(vec![element]);
+    /// assert_eq!(item.unknown_elements()[0].name(), "rank");
+    /// ```
+    pub fn unknown_elements(&self) -> &[Extension] {
+        &self.unknown_elements
+    }
+
+    /// Set the elements of this item that are not otherwise supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_unknown_elements(Vec::new());
+    /// ```
+    pub fn set_unknown_elements<V>(&mut self, unknown_elements: V)
+    where
+        V: Into<Vec<Extension>>,
+    {
+        self.unknown_elements = unknown_elements.into();
+    }
+
+    /// Return the base URI for the relative URLs in this item.
+    ///
+    /// This is the value of the `xml:base` attribute of the `<item>` element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_base("http://example.com/posts/".to_string());
+    /// assert_eq!(item.base(), Some("http://example.com/posts/"));
+    /// ```
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the base URI for the relative URLs in this item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_base("http://example.com/posts/".to_string());
+    /// ```
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into();
+    }
+
+    /// Return a copy of this item with only the elements that RSS 0.92 has.
+    pub(crate) fn downlevel(&self) -> Item {
+        let categories = self.categories
+            .iter()
+            .map(|category| {
+                let mut downlevel = Category::default();
+                downlevel.set_name(category.name());
+                downlevel
+            })
+            .collect();
+
+        Item {
+            title: self.title.clone(),
+            link: self.link.clone(),
+            description: self.description.clone(),
+            categories: categories,
+            enclosure: self.enclosure.clone(),
+            source: self.source.clone(),
+            ..Item::default()
+        }
+    }
+
+    /// Return the key that identifies this item when duplicate items are removed.
+    ///
+    /// The key is the GUID of the item if it has one, otherwise its link, and otherwise its
+    /// title and publication date. `None` is returned if the item has none of these.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{DedupKey, Guid, Item};
+    ///
+    /// let mut item = Item::default();
+    /// assert_eq!(item.dedup_key(), None);
+    ///
+    /// item.set_title("Title".to_string());
+    /// assert_eq!(item.dedup_key(), Some(DedupKey::TitleDate(Some("Title"), None)));
+    ///
+    /// item.set_link("http://example.com".to_string());
+    /// assert_eq!(item.dedup_key(), Some(DedupKey::Link("http://example.com")));
+    ///
+    /// let mut guid = Guid::default();
+    /// guid.set_value("1");
+    /// item.set_guid(guid);
+    /// assert_eq!(item.dedup_key(), Some(DedupKey::Guid("1")));
+    /// ```
+    pub fn dedup_key(&self) -> Option<DedupKey<'_>> {
+        if let Some(guid) = self.guid.as_ref().filter(|guid| !guid.value().is_empty()) {
+            return Some(DedupKey::Guid(guid.value()));
+        }
+
+        if let Some(link) = self.link.as_ref().filter(|link| !link.is_empty()) {
+            return Some(DedupKey::Link(link));
+        }
+
+        if self.title.is_none() && self.pub_date.is_none() {
+            return None;
+        }
+
+        Some(DedupKey::TitleDate(self.title(), self.pub_date()))
+    }
+
+    /// Return the body of this item, which is its content if it has any and otherwise its
+    /// description.
+    ///
+    /// A content or description that is empty or only whitespace is treated as absent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_description("Description".to_string());
+    /// item.set_content(" ".to_string());
+    /// assert_eq!(item.body(), Some("Description"));
+    ///
+    /// item.set_content("<p>Content</p>".to_string());
+    /// assert_eq!(item.body(), Some("<p>Content</p>"));
+    /// ```
+    pub fn body(&self) -> Option<&str> {
+        non_blank(self.content()).or_else(|| non_blank(self.description()))
+    }
+
+    /// Return a summary of this item, which is its description if it has any and otherwise the
+    /// first 200 characters of its content as plain text.
+    ///
+    /// See `summary_with_limit` for how the content is rendered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_content("<p>Fish &amp; chips</p>".to_string());
+    /// assert_eq!(item.summary().unwrap(), "Fish & chips");
+    ///
+    /// item.set_description("Description".to_string());
+    /// assert_eq!(item.summary().unwrap(), "Description");
+    /// ```
+    pub fn summary(&self) -> Option<Cow<'_, str>> {
+        self.summary_with_limit(SUMMARY_LENGTH)
+    }
+
+    /// Return a summary of this item, which is its description if it has any and otherwise at
+    /// most `limit` characters of its content as plain text.
+    ///
+    /// The content is made plain text for previews without parsing the HTML: tags are replaced
+    /// by spaces, character references are resolved and runs of whitespace are collapsed into a
+    /// single space. Content that is cut short ends with the last word that fits, followed by
+    /// `…`. The description is returned as it is.
+    ///
+    /// A content or description that is empty or only whitespace is treated as absent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_content("<p>The quick brown fox</p>\n<p>jumps</p>".to_string());
+    /// assert_eq!(item.summary_with_limit(15).unwrap(), "The quick brown\u{2026}");
+    /// assert_eq!(item.summary_with_limit(100).unwrap(), "The quick brown fox jumps");
+    /// ```
+    pub fn summary_with_limit(&self, limit: usize) -> Option<Cow<'_, str>> {
+        if let Some(description) = non_blank(self.description()) {
+            return Some(Cow::Borrowed(description));
+        }
+
+        let text = plain_text(non_blank(self.content())?, limit);
+        if text.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(text))
+        }
+    }
 }
 
-impl FromXml for Item {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, _: Attributes) -> Result<Self, Error> {
-        let mut item = Item::default();
+/// Return the value if it has any characters other than whitespace.
+fn non_blank(value: Option<&str>) -> Option<&str> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+/// Render HTML as plain text of at most `limit` characters, followed by `…` if it is cut short.
+fn plain_text(html: &str, limit: usize) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                stripped.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if in_tag => {}
+            c => stripped.push(c),
+        }
+    }
+
+    let unescaped = unescape_html(stripped.as_bytes(), &mut Vec::new());
+    let unescaped = String::from_utf8_lossy(&unescaped);
+
+    let mut text = String::new();
+    let mut length = 0;
+    for word in unescaped.split_whitespace() {
+        let separator = if text.is_empty() { 0 } else { 1 };
+        let word_length = word.chars().count();
+        if length + separator + word_length > limit {
+            // a single word longer than the limit is cut in the middle
+            if text.is_empty() {
+                text.extend(word.chars().take(limit));
+            }
+            text.push('\u{2026}');
+            break;
+        }
+
+        if separator == 1 {
+            text.push(' ');
+        }
+        text.push_str(word);
+        length += separator + word_length;
+    }
+    text
+}
+
+/// The key that identifies an item when duplicate items are removed.
+///
+/// See `Item::dedup_key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DedupKey<'a> {
+    /// The value of the GUID of the item.
+    Guid(&'a str),
+    /// The link of an item without a GUID.
+    Link(&'a str),
+    /// The title and publication date of an item without a GUID or a link.
+    TitleDate(Option<&'a str>, Option<&'a str>),
+}
+
+impl FromStr for Item {
+    type Err = Error;
+
+    /// Parse a standalone `<item>` element.
+    ///
+    /// Namespaces used by extensions are declared on the `<item>` element itself.
+    /// `Error::UnexpectedRoot` is returned if the input does not begin with an `<item>`
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// # fn main() { run().unwrap() }
+    /// # fn run() -> Result<(), rss::Error> {
+    /// let input = r#"
+    ///     <item xmlns:dc="http://purl.org/dc/elements/1.1/">
+    ///         <title>Title</title>
+    ///         <dc:creator>Creator</dc:creator>
+    ///     </item>
+    /// "#;
+    ///
+    /// let item = input.parse::<Item>()?;
+    /// assert_eq!(item.title(), Some("Title"));
+    /// assert_eq!(item.dublin_core_ext().unwrap().creators(), &["Creator".to_string()]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Item, Error> {
+        let mut reader = Reader::from_reader(s.as_bytes());
+        reader.trim_text(true).expand_empty_elements(true);
+        let mut context = ParseContext::default();
         let mut buf = Vec::new();
 
         loop {
-            match reader.read_event(&mut buf)? {
+            match reader.read_event(&mut buf).map_err(|err| {
+                Error::from(err).at_position(reader.buffer_position())
+            })? {
                 Event::Start(element) => {
-                    match element.name() {
-                        b"category" => {
-                            let category = Category::from_xml(reader, element.attributes())?;
-                            item.categories.push(category);
-                        }
-                        b"guid" => {
-                            let guid = Guid::from_xml(reader, element.attributes())?;
-                            item.guid = Some(guid);
-                        }
-                        b"enclosure" => {
-                            let enclosure = Enclosure::from_xml(reader, element.attributes())?;
-                            item.enclosure = Some(enclosure);
-                        }
-                        b"source" => {
-                            let source = Source::from_xml(reader, element.attributes())?;
-                            item.source = Some(source);
-                        }
-                        b"title" => item.title = element_text(reader)?,
-                        b"link" => item.link = element_text(reader)?,
-                        b"description" => item.description = element_text(reader)?,
-                        b"author" => item.author = element_text(reader)?,
-                        b"comments" => item.comments = element_text(reader)?,
-                        b"pubDate" => item.pub_date = element_text(reader)?,
-                        b"content:encoded" => item.content = element_text(reader)?,
-                        n => {
-                            if let Some((ns, name)) = extension_name(n) {
-                                parse_extension(
-                                    reader,
-                                    element.attributes(),
-                                    ns,
-                                    name,
-                                    &mut item.extensions,
-                                )?;
-                            } else {
-                                reader.read_to_end(n, &mut Vec::new())?;
+                    if element.name() != b"item" {
+                        let found = String::from_utf8_lossy(element.name()).into_owned();
+                        return Err(Error::UnexpectedRoot { found: found });
+                    }
+
+                    for attr in element.attributes().with_checks(false) {
+                        if let Ok(attr) = attr {
+                            if attr.key.starts_with(b"xmlns:") {
+                                let prefix = str::from_utf8(&attr.key[6..])?.to_string();
+                                let namespace = attr_value(&reader, b"item", &attr, &mut context)?;
+                                context.add_namespace(prefix, namespace);
                             }
                         }
                     }
+
+                    return Item::from_xml_with_context(
+                        &mut reader,
+                        element.attributes(),
+                        &mut context,
+                    );
+                }
+                Event::Eof => return Err(Error::MissingRootElement),
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    /// Write the item as a standalone `<item>` element.
+    ///
+    /// No namespaces are declared for the extensions of the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Item;
+    ///
+    /// let mut item = Item::default();
+    /// item.set_title("Title".to_string());
+    /// assert_eq!(item.to_string(), "<item><title>Title</title></item>");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut writer = Writer::new(Vec::new());
+        self.to_xml(&mut writer).map_err(|_| fmt::Error)?;
+        let output = writer.into_inner();
+        f.write_str(str::from_utf8(&output).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl FromXml for Item {
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Item::from_xml_with_context(reader, atts, &mut ParseContext::default())
+    }
+}
+
+impl Item {
+    /// Parse an `<item>` element, reporting recoverable problems to the context.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        mut atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
+        let mut item = Item::default();
+        let mut buf = context.take_buffer();
+        let mut about = None;
+
+        for attr in atts.with_checks(false) {
+            if let Ok(attr) = attr {
+                match attr.key {
+                    b"rdf:about" => about = Some(attr_value(reader, b"item", &attr, context)?),
+                    b"xml:base" => item.base = Some(attr_value(reader, b"item", &attr, context)?),
+                    _ => {}
+                }
+            }
+        }
+
+        loop {
+            match reader.read_event(&mut buf).map_err(|err| {
+                Error::from(err).in_element(b"item", reader.buffer_position())
+            })? {
+                Event::Start(element) => {
+                    item.read_child(reader, &element, context)
+                        .map_err(|err| err.in_element(element.name(), reader.buffer_position()))?;
                 }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
@@ -589,86 +1460,380 @@ impl FromXml for Item {
             buf.clear();
         }
 
-        if !item.extensions.is_empty() {
-            if let Some(map) = item.extensions.remove("itunes") {
-                item.itunes_ext = Some(ITunesItemExtension::from_map(map));
+        context.return_buffer(buf);
+        context.read_custom_extensions(&mut item.extensions, &mut item.custom_extensions)?;
+
+        // the Podcasting 2.0 and Slash module extensions are only extracted if their namespaces
+        // were declared, from every prefix that they were declared with
+        for prefix in context.namespace_prefixes(podcast::NAMESPACE) {
+            if let Some(map) = item.extensions.get_mut(prefix) {
+                item.podcast_ext
+                    .get_or_insert_with(PodcastItemExtension::default)
+                    .read_map(map);
+            }
+        }
+
+        for prefix in context.namespace_prefixes(slash::NAMESPACE) {
+            if let Some(map) = item.extensions.get_mut(prefix) {
+                item.slash_ext
+                    .get_or_insert_with(SlashExtension::default)
+                    .read_map(map);
+            }
+        }
+
+        item.extract_extensions();
+
+        // RSS 1.0 identifies each item by its `rdf:about` URI
+        if let Some(about) = about {
+            if item.guid.is_none() {
+                let mut guid = Guid::default();
+                guid.set_value(about.as_str());
+                guid.set_permalink(false);
+                item.guid = Some(guid);
             }
 
-            if let Some(map) = item.extensions.remove("dc") {
-                item.dublin_core_ext = Some(DublinCoreExtension::from_map(map));
+            if item.link.is_none() {
+                item.link = Some(about);
             }
         }
 
         Ok(item)
     }
+
+    /// Parse a child element of `<item>` into this item.
+    fn read_child<R: BufRead>(
+        &mut self,
+        reader: &mut Reader<R>,
+        element: &BytesStart,
+        context: &mut ParseContext,
+    ) -> Result<(), Error> {
+        context.check_length(element.len())?;
+
+        let name = element.name();
+        let mut child = match context.canonical_name(name, ELEMENTS) {
+            b"title" => Child::Title,
+            b"link" => Child::Link,
+            b"description" => Child::Description,
+            b"author" => Child::Author,
+            b"category" => Child::Category,
+            b"comments" => Child::Comments,
+            b"enclosure" => Child::Enclosure,
+            b"guid" => Child::Guid,
+            b"pubDate" => Child::PubDate,
+            b"source" => Child::Source,
+            b"content:encoded" => Child::Content,
+            n if extension_name(n).is_some() => Child::Extension(str::from_utf8(n)?.to_string()),
+            _ => Child::Unknown,
+        };
+
+        match context.canonical_name(name, ELEMENTS) {
+            b"category" => {
+                let position = reader.buffer_position();
+                let attributes = context.normalize_attributes(position, element, &[b"domain"]);
+                let category =
+                    Category::from_xml_with_context(reader, attributes.attributes(), context)?;
+                self.categories.push(category);
+            }
+            b"guid" => {
+                if !context.skip_duplicate(reader, name, self.guid.is_some())? {
+                    let keys = [b"isPermaLink" as &[u8]];
+                    let position = reader.buffer_position();
+                    let attributes = context.normalize_attributes(position, element, &keys);
+                    let guid =
+                        Guid::from_xml_with_context(reader, attributes.attributes(), context)?;
+                    self.guid = Some(guid);
+                }
+            }
+            b"enclosure" => {
+                let present = self.enclosure.is_some();
+                if !context.skip_duplicate(reader, name, present)? {
+                    let keys = [b"url" as &[u8], b"length", b"type"];
+                    let position = reader.buffer_position();
+                    let attributes = context.normalize_attributes(position, element, &keys);
+                    let enclosure =
+                        Enclosure::from_xml_with_context(reader, attributes.attributes(), context)?;
+                    self.enclosure = Some(enclosure);
+                }
+            }
+            b"source" => {
+                if !context.skip_duplicate(reader, name, self.source.is_some())? {
+                    let position = reader.buffer_position();
+                    let attributes = context.normalize_attributes(position, element, &[b"url"]);
+                    let source =
+                        Source::from_xml_with_context(reader, attributes.attributes(), context)?;
+                    self.source = Some(source);
+                }
+            }
+            b"link" => {
+                let keys = [b"href" as &[u8], b"rel", b"type"];
+                let position = reader.buffer_position();
+                let attributes = context.normalize_attributes(position, element, &keys);
+                let link = Link::from_xml_with_context(reader, attributes.attributes(), context)?;
+                if self.link.is_none() && link.is_alternate() {
+                    if !link.href().is_empty() {
+                        self.link = Some(link.href().to_string());
+                    }
+                } else {
+                    self.links.push(link);
+                    child = Child::OtherLink;
+                }
+            }
+            b"title" => context.read_text(reader, name, &mut self.title)?,
+            b"description" => context.read_text(reader, name, &mut self.description)?,
+            b"author" => context.read_text(reader, name, &mut self.author)?,
+            b"comments" => context.read_text(reader, name, &mut self.comments)?,
+            b"pubDate" => context.read_text(reader, name, &mut self.pub_date)?,
+            b"content:encoded" => context.read_text(reader, name, &mut self.content)?,
+            n => {
+                if let Some((ns, name)) = extension_name(n) {
+                    let extensions = &mut self.extensions;
+                    parse_extension(reader, element, ns, name, extensions, context, 4)?;
+                } else {
+                    let element = parse_extension_element(reader, element, context, 4)?;
+                    self.unknown_elements.push(element);
+                }
+            }
+        }
+
+        self.order.0.push(child);
+        Ok(())
+    }
+}
+
+impl Item {
+    /// Move the iTunes, Dublin Core and Media RSS elements out of the extension map and into
+    /// their dedicated extension structs.
+    ///
+    /// Elements that the extension structs do not support stay in the extension map.
+    pub(crate) fn extract_extensions(&mut self) {
+        if self.extensions.is_empty() {
+            return;
+        }
+
+        if let Some(map) = self.extensions.get_mut("itunes") {
+            self.itunes_ext = Some(ITunesItemExtension::take_from_map(map));
+        }
+
+        if let Some(map) = self.extensions.get_mut("dc") {
+            self.dublin_core_ext = Some(DublinCoreExtension::take_from_map(map));
+        }
+
+        if let Some(map) = self.extensions.get_mut("media") {
+            self.media_ext = Some(MediaExtension::take_from_map(map));
+        }
+
+        self.extensions.retain(|_, map| !map.is_empty());
+    }
 }
 
 impl ToXml for Item {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = b"item";
+        let mut element = BytesStart::borrowed(name, name.len());
+        if let Some(base) = self.base.as_ref() {
+            element.push_attribute((b"xml:base" as &[u8], &*escape(base.as_bytes())));
+        }
+
+        writer.write_event(Event::Start(element))?;
+
+        // the children that were read are written in the same order, followed by the rest
+        let mut written = Written::default();
+        for child in &self.order.0 {
+            match *child {
+                Child::Extension(ref qualified) => {
+                    self.write_extension(writer, qualified, &mut written)?
+                }
+                ref child => self.write_child(writer, child, &mut written)?,
+            }
+        }
 
-        writer
-            .write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
+        for child in &[Child::Title, Child::Link] {
+            self.write_child(writer, child, &mut written)?;
+        }
 
-        if let Some(title) = self.title.as_ref() {
-            writer.write_text_element(b"title", title)?;
+        while written.links < self.links.len() {
+            self.write_child(writer, &Child::OtherLink, &mut written)?;
         }
 
-        if let Some(link) = self.link.as_ref() {
-            writer.write_text_element(b"link", link)?;
+        for child in &[Child::Description, Child::Author] {
+            self.write_child(writer, child, &mut written)?;
         }
 
-        if let Some(description) = self.description.as_ref() {
-            writer.write_text_element(b"description", description)?;
+        while written.categories < self.categories.len() {
+            self.write_child(writer, &Child::Category, &mut written)?;
         }
 
-        if let Some(author) = self.author.as_ref() {
-            writer.write_text_element(b"author", author)?;
+        let fields = [
+            Child::Comments,
+            Child::Enclosure,
+            Child::Guid,
+            Child::PubDate,
+            Child::Source,
+            Child::Content,
+        ];
+        for child in &fields {
+            self.write_child(writer, child, &mut written)?;
         }
 
-        writer.write_objects(&self.categories)?;
+        for (prefix, map) in &self.extensions {
+            for (name, extensions) in map {
+                let count = written.extensions.get(&(prefix.as_str(), name.as_str())).cloned();
+                writer.write_objects(&extensions[count.unwrap_or(0)..])?;
+            }
+        }
 
-        if let Some(comments) = self.comments.as_ref() {
-            writer.write_text_element(b"comments", comments)?;
+        for (_, &(ref prefix, ref value)) in self.custom_extensions.iter() {
+            if !written.prefixes.contains(prefix.as_str()) {
+                writer.write_objects(&value.to_extensions(prefix))?;
+            }
         }
 
-        if let Some(enclosure) = self.enclosure.as_ref() {
-            writer.write_object(enclosure)?;
+        for prefix in &["itunes", "dc", "media", "podcast", "slash"] {
+            if !written.prefixes.contains(prefix) {
+                self.write_extension_struct(writer, prefix)?;
+            }
         }
 
-        if let Some(guid) = self.guid.as_ref() {
-            writer.write_object(guid)?;
+        writer.write_objects(&self.unknown_elements[written.unknown_elements..])?;
+
+        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        Ok(())
+    }
+}
+
+impl Item {
+    /// Write the next child of the given kind that has not been written yet, if there is one.
+    fn write_child<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        child: &Child,
+        written: &mut Written,
+    ) -> Result<(), XmlError> {
+        match *child {
+            Child::OtherLink => {
+                if let Some(link) = self.links.get(written.links) {
+                    writer.write_object(link)?;
+                    written.links += 1;
+                }
+                return Ok(());
+            }
+            Child::Category => {
+                if let Some(category) = self.categories.get(written.categories) {
+                    writer.write_object(category)?;
+                    written.categories += 1;
+                }
+                return Ok(());
+            }
+            Child::Unknown => {
+                if let Some(element) = self.unknown_elements.get(written.unknown_elements) {
+                    writer.write_object(element)?;
+                    written.unknown_elements += 1;
+                }
+                return Ok(());
+            }
+            _ => {}
         }
 
-        if let Some(pub_date) = self.pub_date.as_ref() {
-            writer.write_text_element(b"pubDate", pub_date)?;
+        if written.fields.contains(child) {
+            return Ok(());
         }
+        written.fields.push(child.clone());
 
-        if let Some(source) = self.source.as_ref() {
-            writer.write_object(source)?;
+        match *child {
+            Child::Title => if let Some(title) = self.title.as_ref() {
+                writer.write_text_element(b"title", title)?;
+            },
+            Child::Link => if let Some(link) = self.link.as_ref() {
+                writer.write_text_element(b"link", link)?;
+            },
+            Child::Description => if let Some(description) = self.description.as_ref() {
+                writer.write_text_element(b"description", description)?;
+            },
+            Child::Author => if let Some(author) = self.author.as_ref() {
+                writer.write_text_element(b"author", author)?;
+            },
+            Child::Comments => if let Some(comments) = self.comments.as_ref() {
+                writer.write_text_element(b"comments", comments)?;
+            },
+            Child::Enclosure => if let Some(enclosure) = self.enclosure.as_ref() {
+                writer.write_object(enclosure)?;
+            },
+            Child::Guid => if let Some(guid) = self.guid.as_ref() {
+                writer.write_object(guid)?;
+            },
+            Child::PubDate => if let Some(pub_date) = self.pub_date.as_ref() {
+                writer.write_text_element(b"pubDate", pub_date)?;
+            },
+            Child::Source => if let Some(source) = self.source.as_ref() {
+                writer.write_object(source)?;
+            },
+            Child::Content => if let Some(content) = self.content.as_ref() {
+                writer.write_cdata_element(b"content:encoded", content)?;
+            },
+            _ => {}
         }
 
-        if let Some(content) = self.content.as_ref() {
-            writer.write_cdata_element(b"content:encoded", content)?;
+        Ok(())
+    }
+
+    /// Write the next element with the given qualified name from the extension map.
+    ///
+    /// The extension structs and custom extensions are written as a whole in place of the first
+    /// element with their prefix that is not left in the extension map.
+    fn write_extension<'a, W: Write>(
+        &'a self,
+        writer: &mut Writer<W>,
+        qualified: &'a str,
+        written: &mut Written<'a>,
+    ) -> Result<(), XmlError> {
+        let position = qualified.find(':').unwrap_or(0);
+        let (prefix, name) = (&qualified[..position], &qualified[position + 1..]);
+
+        if let Some(extensions) = self.extensions.get(prefix).and_then(|map| map.get(name)) {
+            let count = written.extensions.entry((prefix, name)).or_insert(0);
+            if let Some(extension) = extensions.get(*count) {
+                *count += 1;
+                return writer.write_object(extension);
+            }
         }
 
-        for map in self.extensions.values() {
-            for extensions in map.values() {
-                for extension in extensions {
-                    extension.to_xml(writer)?;
+        if written.prefixes.insert(prefix) {
+            for (_, &(ref custom, ref value)) in self.custom_extensions.iter() {
+                if custom == prefix {
+                    writer.write_objects(&value.to_extensions(custom))?;
                 }
             }
+            self.write_extension_struct(writer, prefix)?;
         }
 
-        if let Some(ext) = self.itunes_ext.as_ref() {
-            ext.to_xml(writer)?;
-        }
+        Ok(())
+    }
 
-        if let Some(ext) = self.dublin_core_ext.as_ref() {
-            ext.to_xml(writer)?;
+    /// Write the extension struct with the given prefix, if it is present.
+    fn write_extension_struct<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        prefix: &str,
+    ) -> Result<(), XmlError> {
+        match prefix {
+            "itunes" => if let Some(ext) = self.itunes_ext.as_ref() {
+                ext.to_xml(writer)?;
+            },
+            "dc" => if let Some(ext) = self.dublin_core_ext.as_ref() {
+                ext.to_xml(writer)?;
+            },
+            "media" => if let Some(ext) = self.media_ext.as_ref() {
+                ext.to_xml(writer)?;
+            },
+            "podcast" => if let Some(ext) = self.podcast_ext.as_ref() {
+                ext.to_xml(writer)?;
+            },
+            "slash" => if let Some(ext) = self.slash_ext.as_ref() {
+                ext.to_xml(writer)?;
+            },
+            _ => {}
         }
 
-        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
         Ok(())
     }
 }
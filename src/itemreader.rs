@@ -0,0 +1,497 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str;
+
+use quick_xml::events::{Event, BytesStart};
+use quick_xml::reader::Reader;
+
+use encoding_rs::Encoding;
+
+use channel::Channel;
+use encoding::DecodingReader;
+use error::{Error, FeedKind};
+use fromxml::FromXml;
+use image::Image;
+use item::Item;
+use parseoptions::{ParseContext, ParseOptions, ParseWarning};
+use stylesheet::Stylesheet;
+use textinput::TextInput;
+use util::{attr_value, xml_base};
+
+/// The Atom XML namespace.
+pub(crate) static ATOM_NAMESPACE: &'static str = "http://www.w3.org/2005/Atom";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Root,
+    Channel,
+    Done,
+}
+
+/// A reader that parses the items of an RSS channel one at a time.
+///
+/// The channel metadata found before the first item is parsed when the reader is created and is
+/// available through `channel()`. Items are then parsed lazily as the iterator is advanced, so
+/// only a single item is held in memory at a time and iteration can be stopped at any point
+/// without reading the rest of the input.
+///
+/// If an item fails to parse, the error is returned and the rest of that item is skipped so that
+/// iteration can continue with the next one. Errors outside of an item end the iteration.
+///
+/// # Examples
+///
+/// ```
+/// use rss::ItemReader;
+///
+/// let input = r#"
+/// <rss version="2.0">
+///   <channel>
+///     <title>Title</title>
+///     <item><title>First</title></item>
+///     <item><title>Second</title></item>
+///   </channel>
+/// </rss>
+/// "#;
+///
+/// let mut reader = ItemReader::new(input.as_bytes()).unwrap();
+/// assert_eq!(reader.channel().title(), "Title");
+///
+/// let item = reader.next().unwrap().unwrap();
+/// assert_eq!(item.title(), Some("First"));
+/// ```
+pub struct ItemReader<R: BufRead> {
+    reader: Reader<DecodingReader<R>>,
+    channel: Channel,
+    state: State,
+    found_channel: bool,
+    rdf: bool,
+    at_item: bool,
+    item_start: BytesStart<'static>,
+    buf: Vec<u8>,
+    skip_buf: Vec<u8>,
+    context: ParseContext,
+    items: usize,
+}
+
+impl<R: BufRead> ItemReader<R> {
+    /// Create a new item reader, parsing the channel metadata up to the first item.
+    pub fn new(reader: R) -> Result<ItemReader<R>, Error> {
+        ItemReader::with_context(reader, ParseContext::default())
+    }
+
+    /// Create a new item reader using the provided options.
+    ///
+    /// When parsing is lenient, items that fail to parse are skipped instead of being returned
+    /// as errors. The problems found so far are available through `warnings()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{ItemReader, ParseOptionsBuilder};
+    ///
+    /// let input = r#"
+    /// <rss version="2.0">
+    ///   <channel>
+    ///     <item><title>First</title><title>Again</title></item>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let options = ParseOptionsBuilder::default().lenient(true).build().unwrap();
+    /// let mut reader = ItemReader::with_options(input.as_bytes(), &options).unwrap();
+    ///
+    /// let item = reader.next().unwrap().unwrap();
+    /// assert_eq!(item.title(), Some("First"));
+    /// assert_eq!(reader.warnings().len(), 1);
+    /// ```
+    pub fn with_options(reader: R, options: &ParseOptions) -> Result<ItemReader<R>, Error> {
+        ItemReader::with_context(reader, ParseContext::new(options))
+    }
+
+    pub(crate) fn with_context(
+        reader: R,
+        mut context: ParseContext,
+    ) -> Result<ItemReader<R>, Error> {
+        let mut reader = Reader::from_reader(DecodingReader::new(reader));
+        reader.trim_text(true).expand_empty_elements(true);
+        let mut namespaces = HashMap::new();
+        let mut buf = context.buffer();
+        let rdf;
+        let mut stylesheets = Vec::new();
+
+        // find opening element
+        loop {
+            buf.clear();
+            let event = reader.read_event(&mut buf);
+            let position = reader.buffer_position();
+            match event.map_err(|err| Error::from(err).at_position(position))? {
+                Event::Start(element) => {
+                    let name = context.canonical_name(element.name(), &[b"rss", b"rdf:RDF"]);
+                    if name == b"rss" || name == b"rdf:RDF" {
+                        rdf = name == b"rdf:RDF";
+                        for attr in element.attributes().with_checks(false) {
+                            let attr = match attr {
+                                Ok(attr) => attr,
+                                Err(err) => {
+                                    if context.is_lenient() {
+                                        context.warn(
+                                            reader.buffer_position(),
+                                            format!("unparsable attribute skipped: {}", err),
+                                        );
+                                    }
+                                    continue;
+                                }
+                            };
+
+                            if !attr.key.starts_with(b"xmlns:") {
+                                continue;
+                            }
+
+                            let key = str::from_utf8(&attr.key[6..])?.to_string();
+                            let value = attr_value(&reader, element.name(), &attr, &mut context)?;
+                            context.add_namespace(key.clone(), value.clone());
+                            namespaces.insert(key, value);
+                        }
+
+                        break;
+                    } else if let Some(kind) = feed_kind(&element) {
+                        return Err(Error::NotRss { detected: kind });
+                    } else {
+                        let found = String::from_utf8_lossy(element.name()).into_owned();
+                        return Err(Error::UnexpectedRoot { found: found });
+                    }
+                }
+                Event::PI(ref pi) => {
+                    if let Some(stylesheet) = Stylesheet::from_pi(&reader, pi, &mut context)? {
+                        stylesheets.push(stylesheet);
+                    }
+                }
+                Event::Decl(decl) => {
+                    if let Some(label) = decl.encoding() {
+                        let label = label?;
+                        if Encoding::for_label(&label).is_none() {
+                            let label = String::from_utf8_lossy(&label).into_owned();
+                            return Err(Error::UnsupportedEncoding(label));
+                        }
+                    }
+                }
+                // a byte order mark is only skipped at the very start of the input
+                Event::Text(ref text) if text.starts_with(b"\xEF\xBB\xBF") => {
+                    if !context.is_lenient() {
+                        return Err(Error::MisplacedByteOrderMark);
+                    }
+                    context.warn(
+                        reader.buffer_position(),
+                        "byte order mark after the start of the input skipped",
+                    );
+                }
+                Event::Eof => return Err(Error::MissingRootElement),
+                _ => continue,
+            }
+        }
+
+        buf.clear();
+
+        let mut channel = Channel::default();
+        channel.set_namespaces(namespaces);
+        channel.remove_implied_namespaces();
+        channel.set_stylesheets(stylesheets);
+
+        let mut item_reader = ItemReader {
+            reader: reader,
+            channel: channel,
+            state: State::Root,
+            found_channel: false,
+            rdf: rdf,
+            at_item: false,
+            item_start: BytesStart::borrowed(b"item", 4),
+            buf: buf,
+            skip_buf: context.buffer(),
+            context: context,
+            items: 0,
+        };
+
+        item_reader.at_item = item_reader.advance()?;
+        item_reader.channel.extract_all_extensions(&item_reader.context)?;
+
+        Ok(item_reader)
+    }
+
+    /// Return the channel metadata that has been read so far.
+    ///
+    /// The returned channel never contains any items. Channel elements that appear after the
+    /// first item are added to it as they are reached during iteration.
+    pub fn channel(&self) -> &Channel {
+        &self.channel
+    }
+
+    /// Return the warnings reported so far.
+    ///
+    /// Warnings are only reported when the reader was created with lenient parse options.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        self.context.warnings()
+    }
+
+    /// Consume this reader, returning the channel metadata that has been read so far.
+    pub fn into_channel(self) -> Channel {
+        self.channel
+    }
+
+    /// Consume this reader, returning the channel metadata and the warnings reported so far.
+    pub(crate) fn into_parts(self) -> (Channel, Vec<ParseWarning>) {
+        (self.channel, self.context.into_warnings())
+    }
+
+    /// Returns `true` if reading has stopped, either because the end of the feed was reached or
+    /// because of an error that could not be recovered from.
+    pub(crate) fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+
+    /// Skip the remaining items without parsing them, returning how many there were.
+    ///
+    /// The channel elements that appear between and after the items are still read.
+    pub(crate) fn skip_items(&mut self) -> Result<usize, Error> {
+        let mut count = 0;
+
+        loop {
+            if !self.at_item && !self.advance()? {
+                return Ok(count);
+            }
+
+            self.at_item = false;
+            self.buf.clear();
+            count += 1;
+
+            let result = self.reader
+                .read_to_end(self.item_start.name(), &mut self.skip_buf);
+            if let Err(err) = result {
+                let position = self.reader.buffer_position();
+                return Err(Error::from(err).in_element(b"item", position));
+            }
+        }
+    }
+
+    /// Skip the remainder of an item that failed to parse.
+    fn skip_item(&mut self) -> Result<(), Error> {
+        let mut depth = 0;
+
+        loop {
+            self.skip_buf.clear();
+            let event = self.reader.read_event(&mut self.skip_buf);
+            let position = self.reader.buffer_position();
+            match event.map_err(|err| Error::from(err).in_element(b"item", position))? {
+                Event::Start(_) => depth += 1,
+                Event::End(element) => {
+                    let name = self.context.canonical_name(element.name(), &[b"item"]);
+                    if depth <= 0 && name == b"item" {
+                        return Ok(());
+                    }
+                    depth -= 1;
+                }
+                Event::Eof => return Err(Error::Eof),
+                _ => {}
+            }
+        }
+    }
+
+    /// Read until the start of the next item. Returns `false` once the end of the feed is reached.
+    fn advance(&mut self) -> Result<bool, Error> {
+        loop {
+            match self.state {
+                State::Root => {
+                    let event = self.reader.read_event(&mut self.buf);
+                    let position = self.reader.buffer_position();
+                    match event.map_err(|err| Error::from(err).in_element(b"rss", position))? {
+                        Event::Start(element) => {
+                            let names = [b"channel" as &[u8], b"item", b"image", b"textinput"];
+                            match self.context.canonical_name(element.name(), &names) {
+                                b"channel" => {
+                                    self.state = State::Channel;
+                                    self.found_channel = true;
+                                    let attributes = element.attributes();
+                                    let context = &mut self.context;
+                                    let base =
+                                        xml_base(&self.reader, b"channel", attributes, context)
+                                            .map_err(|err| err.in_element(b"channel", position))?;
+                                    self.channel.set_base(base);
+                                }
+                                b"item" => {
+                                    self.item_start = element.into_owned();
+                                    return Ok(true);
+                                }
+                                b"image" => {
+                                    let attributes = element.attributes();
+                                    match Image::from_xml(&mut self.reader, attributes) {
+                                        Ok(image) => self.channel.set_image(image),
+                                        Err(err) => {
+                                            let position = self.reader.buffer_position();
+                                            return Err(err.in_element(b"image", position));
+                                        }
+                                    }
+                                }
+                                b"textinput" => {
+                                    let attributes = element.attributes();
+                                    match TextInput::from_xml(&mut self.reader, attributes) {
+                                        Ok(text_input) => self.channel.set_text_input(text_input),
+                                        Err(err) => {
+                                            let position = self.reader.buffer_position();
+                                            return Err(err.in_element(b"textinput", position));
+                                        }
+                                    }
+                                }
+                                name => {
+                                    let result = self.reader.read_to_end(name, &mut self.skip_buf);
+                                    if let Err(err) = result {
+                                        let position = self.reader.buffer_position();
+                                        return Err(Error::from(err).in_element(name, position));
+                                    }
+                                }
+                            }
+                        }
+                        Event::End(_) | Event::Eof => {
+                            self.state = State::Done;
+                            if !self.found_channel {
+                                return Err(Error::Eof);
+                            }
+                            self.channel.extract_all_extensions(&self.context)?;
+                            return Ok(false);
+                        }
+                        _ => {}
+                    }
+                }
+                State::Channel => {
+                    let event = self.reader.read_event(&mut self.buf);
+                    let position = self.reader.buffer_position();
+                    match event.map_err(|err| Error::from(err).in_element(b"channel", position))? {
+                        Event::Start(element) => {
+                            let name = self.context.canonical_name(element.name(), &[b"item"]);
+                            if name == b"item" {
+                                self.item_start = element.into_owned();
+                                return Ok(true);
+                            }
+
+                            // the items of an RSS 1.0 channel only list the items that follow it
+                            let result = if self.rdf && name == b"items" {
+                                self.reader
+                                    .read_to_end(name, &mut self.skip_buf)
+                                    .map_err(Error::from)
+                            } else {
+                                self.channel.read_child(
+                                    &mut self.reader,
+                                    &element,
+                                    &mut self.skip_buf,
+                                    &mut self.context,
+                                )
+                            };
+                            if let Err(err) = result {
+                                let position = self.reader.buffer_position();
+                                return Err(err.in_element(element.name(), position));
+                            }
+                        }
+                        Event::End(_) => self.state = State::Root,
+                        Event::Eof => return Err(Error::Eof),
+                        _ => {}
+                    }
+                }
+                State::Done => return Ok(false),
+            }
+
+            self.buf.clear();
+        }
+    }
+
+    /// Read the next item, stopping after it even when it fails to parse and is skipped, in
+    /// which case `Ok(None)` is returned.
+    pub(crate) fn next_or_skipped(&mut self) -> Option<Result<Option<Item>, Error>> {
+        if !self.at_item {
+            match self.advance() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => {
+                    self.state = State::Done;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        self.at_item = false;
+        self.buf.clear();
+
+        self.items += 1;
+        if let Err(err) = self.context.check_items(self.items) {
+            self.state = State::Done;
+            return Some(Err(err));
+        }
+
+        let position = self.reader.buffer_position();
+        let result = Item::from_xml_with_context(
+            &mut self.reader,
+            self.item_start.attributes(),
+            &mut self.context,
+        );
+
+        match result {
+            Ok(item) => Some(Ok(Some(item))),
+            Err(err) => {
+                if self.skip_item().is_err() {
+                    self.state = State::Done;
+                    return Some(Err(err));
+                }
+
+                if !self.context.is_lenient() {
+                    return Some(Err(err));
+                }
+
+                self.context
+                    .warn(position, format!("malformed item skipped: {}", err));
+                Some(Ok(None))
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ItemReader<R> {
+    type Item = Result<Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_or_skipped() {
+                Some(Ok(Some(item))) => return Some(Ok(item)),
+                Some(Ok(None)) => {}
+                Some(Err(err)) => return Some(Err(err)),
+                None => return None,
+            }
+        }
+    }
+}
+
+/// Return the format of the feed whose root is the given element, if it is not RSS.
+pub(crate) fn feed_kind(root: &BytesStart) -> Option<FeedKind> {
+    let name = root.name();
+    let (declaration, local_name) = match name.iter().position(|&b| b == b':') {
+        Some(index) => ([b"xmlns:", &name[..index]].concat(), &name[index + 1..]),
+        None => (b"xmlns".to_vec(), name),
+    };
+
+    if local_name != b"feed" {
+        return None;
+    }
+
+    let atom = root.attributes()
+        .with_checks(false)
+        .filter_map(|attr| attr.ok())
+        .any(|attr| attr.key == &declaration[..] && attr.value == ATOM_NAMESPACE.as_bytes());
+    if atom {
+        Some(FeedKind::Atom)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,304 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use chrono::DateTime;
+use serde_json::{self, Map, Value};
+
+use category::Category;
+use channel::Channel;
+use date::parse_rfc822;
+use enclosure::Enclosure;
+use error::Error;
+use guid::Guid;
+use item::Item;
+
+/// The JSON Feed version that is written.
+static VERSION: &'static str = "https://jsonfeed.org/version/1.1";
+
+impl Channel {
+    /// Convert this channel into a [JSON Feed 1.1](https://jsonfeed.org/version/1.1) document.
+    ///
+    /// The elements of the channel correspond to the members of the feed as follows:
+    ///
+    /// | RSS                        | JSON Feed        |
+    /// |----------------------------|------------------|
+    /// | `title`                    | `title`          |
+    /// | `link`                     | `home_page_url`  |
+    /// | `atom:link` (`rel="self"`) | `feed_url`       |
+    /// | `description`              | `description`    |
+    /// | `language`                 | `language`       |
+    /// | `managingEditor`           | `authors`        |
+    /// | `item`                     | `items`          |
+    ///
+    /// The elements of each item correspond to the members of the feed item as follows:
+    ///
+    /// | RSS                               | JSON Feed        |
+    /// |-----------------------------------|------------------|
+    /// | `guid`, or `link`                 | `id`             |
+    /// | `link`                            | `url`            |
+    /// | `title`                           | `title`          |
+    /// | `content:encoded`, or `description` | `content_html` |
+    /// | `description` if there is content | `summary`        |
+    /// | `pubDate`                         | `date_published` |
+    /// | `author`                          | `authors`        |
+    /// | `category`                        | `tags`           |
+    /// | `enclosure`                       | `attachments`    |
+    ///
+    /// Publication dates are converted to RFC 3339 and are left out if they cannot be parsed.
+    ///
+    /// **Note**: This requires enabling the `json_feed` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ChannelBuilder;
+    ///
+    /// let channel = ChannelBuilder::default()
+    ///     .title("Title")
+    ///     .link("http://example.com/")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let json = channel.to_json_feed();
+    /// assert!(json.contains(r#""home_page_url":"http://example.com/""#));
+    /// ```
+    pub fn to_json_feed(&self) -> String {
+        let mut feed = Map::new();
+        feed.insert("version".to_string(), VERSION.into());
+        feed.insert("title".to_string(), self.title().into());
+
+        if !self.link().is_empty() {
+            feed.insert("home_page_url".to_string(), self.link().into());
+        }
+
+        if let Some(feed_url) = self.atom_link_self() {
+            feed.insert("feed_url".to_string(), feed_url.into());
+        }
+
+        if !self.description().is_empty() {
+            feed.insert("description".to_string(), self.description().into());
+        }
+
+        if let Some(language) = self.language() {
+            feed.insert("language".to_string(), language.into());
+        }
+
+        if let Some(managing_editor) = self.managing_editor() {
+            feed.insert("authors".to_string(), authors(managing_editor));
+        }
+
+        let items = self.items()
+            .iter()
+            .map(json_feed_item)
+            .collect::<Vec<_>>();
+        feed.insert("items".to_string(), Value::Array(items));
+
+        Value::Object(feed).to_string()
+    }
+
+    /// Attempt to read a channel from a [JSON Feed](https://jsonfeed.org/version/1.1) document.
+    ///
+    /// This is the reverse of `to_json_feed`. Both version 1 and version 1.1 documents are
+    /// accepted. The first author and the first attachment are used, `content_text` is used if
+    /// there is no `content_html`, and a `feed_url` becomes an `atom:link` with the `self`
+    /// relationship. Item ids become GUIDs that are permalinks only if they are the same as the
+    /// item URL.
+    ///
+    /// **Note**: This requires enabling the `json_feed` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Channel;
+    ///
+    /// let json = r#"{
+    ///     "version": "https://jsonfeed.org/version/1.1",
+    ///     "title": "Title",
+    ///     "home_page_url": "http://example.com/",
+    ///     "items": [{"id": "1", "content_html": "<p>Hello</p>"}]
+    /// }"#;
+    ///
+    /// let channel = Channel::from_json_feed(json).unwrap();
+    /// assert_eq!(channel.title(), "Title");
+    /// assert_eq!(channel.link(), "http://example.com/");
+    /// assert_eq!(channel.items()[0].description(), Some("<p>Hello</p>"));
+    /// ```
+    pub fn from_json_feed(json: &str) -> Result<Channel, Error> {
+        let feed = serde_json::from_str::<Value>(json)
+            .map_err(|err| Error::JsonFeed(err.to_string()))?;
+
+        let is_json_feed = feed.get("version")
+            .and_then(Value::as_str)
+            .map_or(false, |version| version.starts_with("https://jsonfeed.org/version/"));
+        if !is_json_feed {
+            return Err(Error::JsonFeed("missing JSON Feed version".to_string()));
+        }
+
+        let mut channel = Channel::default();
+        channel.set_title(string(&feed, "title").unwrap_or_default());
+        channel.set_link(string(&feed, "home_page_url").unwrap_or_default());
+        channel.set_description(string(&feed, "description").unwrap_or_default());
+        channel.set_language(string(&feed, "language"));
+        channel.set_managing_editor(author(&feed));
+        channel.set_atom_link_self(string(&feed, "feed_url"));
+
+        let items = match feed.get("items") {
+            Some(&Value::Array(ref items)) => items.iter().map(item).collect(),
+            Some(_) => return Err(Error::JsonFeed("items is not an array".to_string())),
+            None => Vec::new(),
+        };
+        channel.set_items(items);
+
+        Ok(channel)
+    }
+}
+
+fn json_feed_item(item: &Item) -> Value {
+    let mut object = Map::new();
+
+    let id = item.guid()
+        .map(|guid| guid.value())
+        .or_else(|| item.link())
+        .unwrap_or_default();
+    object.insert("id".to_string(), id.into());
+
+    if let Some(link) = item.link() {
+        object.insert("url".to_string(), link.into());
+    }
+
+    if let Some(title) = item.title() {
+        object.insert("title".to_string(), title.into());
+    }
+
+    match (item.content(), item.description()) {
+        (Some(content), description) => {
+            object.insert("content_html".to_string(), content.into());
+            if let Some(description) = description {
+                object.insert("summary".to_string(), description.into());
+            }
+        }
+        (None, Some(description)) => {
+            object.insert("content_html".to_string(), description.into());
+        }
+        (None, None) => {}
+    }
+
+    if let Some(date) = item.pub_date().and_then(parse_rfc822) {
+        object.insert("date_published".to_string(), date.to_rfc3339().into());
+    }
+
+    if let Some(author) = item.author() {
+        object.insert("authors".to_string(), authors(author));
+    }
+
+    if !item.categories().is_empty() {
+        let tags = item.categories()
+            .iter()
+            .map(|category| category.name().into())
+            .collect::<Vec<Value>>();
+        object.insert("tags".to_string(), Value::Array(tags));
+    }
+
+    if let Some(enclosure) = item.enclosure() {
+        let mut attachment = Map::new();
+        attachment.insert("url".to_string(), enclosure.url().into());
+        attachment.insert("mime_type".to_string(), enclosure.mime_type().into());
+
+        if let Ok(length) = enclosure.length().parse::<u64>() {
+            attachment.insert("size_in_bytes".to_string(), length.into());
+        }
+
+        object.insert(
+            "attachments".to_string(),
+            Value::Array(vec![Value::Object(attachment)]),
+        );
+    }
+
+    Value::Object(object)
+}
+
+fn authors(name: &str) -> Value {
+    let mut author = Map::new();
+    author.insert("name".to_string(), name.into());
+    Value::Array(vec![Value::Object(author)])
+}
+
+/// Return the string value of a member of a JSON object.
+fn string(object: &Value, key: &str) -> Option<String> {
+    object.get(key).and_then(Value::as_str).map(|s| s.to_string())
+}
+
+/// Return the name of the first author, using `author` from version 1 if there are no `authors`.
+fn author(object: &Value) -> Option<String> {
+    object
+        .get("authors")
+        .and_then(|authors| authors.get(0))
+        .or_else(|| object.get("author"))
+        .and_then(|author| string(author, "name"))
+}
+
+fn item(object: &Value) -> Item {
+    let mut item = Item::default();
+    item.set_title(string(object, "title"));
+    item.set_link(string(object, "url"));
+    item.set_author(author(object));
+
+    let content = string(object, "content_html").or_else(|| string(object, "content_text"));
+    match string(object, "summary") {
+        Some(summary) => {
+            item.set_description(summary);
+            item.set_content(content);
+        }
+        None => item.set_description(content),
+    }
+
+    // ids are strings, but version 1 allowed numbers as well
+    let id = match object.get("id") {
+        Some(&Value::String(ref id)) => Some(id.clone()),
+        Some(&Value::Number(ref id)) => Some(id.to_string()),
+        _ => None,
+    };
+    if let Some(id) = id {
+        let mut guid = Guid::default();
+        guid.set_permalink(item.link() == Some(id.as_str()));
+        guid.set_value(id);
+        item.set_guid(guid);
+    }
+
+    if let Some(date) = string(object, "date_published") {
+        let date = match DateTime::parse_from_rfc3339(&date) {
+            Ok(parsed) => parsed.to_rfc2822(),
+            Err(_) => date,
+        };
+        item.set_pub_date(date);
+    }
+
+    if let Some(tags) = object.get("tags").and_then(Value::as_array) {
+        let categories = tags.iter()
+            .filter_map(Value::as_str)
+            .map(|tag| {
+                let mut category = Category::default();
+                category.set_name(tag);
+                category
+            })
+            .collect::<Vec<_>>();
+        item.set_categories(categories);
+    }
+
+    let attachment = object.get("attachments").and_then(|attachments| attachments.get(0));
+    if let Some(attachment) = attachment {
+        let mut enclosure = Enclosure::default();
+        enclosure.set_url(string(attachment, "url").unwrap_or_default());
+        enclosure.set_mime_type(string(attachment, "mime_type").unwrap_or_default());
+        if let Some(size) = attachment.get("size_in_bytes").and_then(Value::as_u64) {
+            enclosure.set_length(size.to_string());
+        }
+        item.set_enclosure(enclosure);
+    }
+
+    item
+}
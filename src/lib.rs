@@ -25,6 +25,35 @@
 //! let channel = Channel::read_from(BufReader::new(file)).unwrap();
 //! ```
 //!
+//! Files, byte slices and strings can also be read directly.
+//!
+//! ```rust,no_run
+//! use rss::Channel;
+//!
+//! let channel = Channel::from_file("example.xml").unwrap();
+//! let channel = Channel::read_from_slice(b"<rss><channel></channel></rss>").unwrap();
+//! let channel = "<rss><channel></channel></rss>".parse::<Channel>().unwrap();
+//! ```
+//!
+//! ## Item by Item
+//!
+//! Large feeds can be read one item at a time with an `ItemReader`. The channel metadata is
+//! parsed up front and items are only parsed as the reader is advanced.
+//!
+//! ```rust,no_run
+//! use std::fs::File;
+//! use std::io::BufReader;
+//! use rss::ItemReader;
+//!
+//! let file = File::open("example.xml").unwrap();
+//! let reader = ItemReader::new(BufReader::new(file)).unwrap();
+//! println!("{}", reader.channel().title());
+//!
+//! for item in reader.take(10) {
+//!     let item = item.unwrap();
+//! }
+//! ```
+//!
 //! ## From a URL
 //!
 //! A channel can also be read from a URL.
@@ -37,13 +66,38 @@
 //! let channel = Channel::from_url("http://example.com/feed.xml").unwrap();
 //! ```
 //!
+//! ## From an Asynchronous Reader
+//!
+//! A channel can be read from any object that implements the `futures_io::AsyncBufRead` trait.
+//!
+//! **Note**: This requires enabling the `async` feature.
+//!
+//! ```ignore
+//! use rss::Channel;
+//!
+//! let channel = Channel::read_from_async(reader).await.unwrap();
+//! ```
+//!
+//! ## From a Compressed Reader
+//!
+//! A channel can be read from input that may be compressed with gzip or zlib, as some servers
+//! send it regardless of the request. The compression is detected from the first bytes of the
+//! input, and input that is not compressed is read as it is. The decompressed size is limited by
+//! `ParseOptions::max_decompressed_size`.
+//!
+//! **Note**: This requires enabling the `compression` feature.
+//!
+//! ```ignore
+//! use rss::Channel;
+//!
+//! let channel = Channel::read_from_maybe_compressed(reader).unwrap();
+//! ```
+//!
 //! # Writing
 //!
 //! A channel can be written to any object that implements the `Write` trait or converted to an
 //! XML string using the `ToString` trait.
 //!
-//! **Note**: Writing a channel does not perform any escaping of XML entities.
-//!
 //! ```rust
 //! use rss::Channel;
 //!
@@ -52,6 +106,18 @@
 //! let string = channel.to_string(); // convert the channel to a string
 //! ```
 //!
+//! Large feeds can be written one item at a time with a `ChannelWriter`, which writes the channel
+//! metadata up front.
+//!
+//! ```rust
+//! use rss::{Channel, ChannelWriter, Item};
+//!
+//! let channel = Channel::default();
+//! let mut writer = ChannelWriter::new(::std::io::sink(), &channel).unwrap();
+//! writer.write_item(&Item::default()).unwrap();
+//! writer.finish().unwrap();
+//! ```
+//!
 //! # Creation
 //!
 //! Builder methods are provided to assist in the creation of channels.
@@ -79,37 +145,140 @@
 //! use rss::validation::Validate;
 //!
 //! let channel = Channel::default();
-//! channel.validate().unwrap();
+//! if let Err(err) = channel.validate() {
+//!     // e.g. "title: Channel title is empty"
+//!     println!("{}", err);
+//! }
+//!
+//! // report every problem instead of only the first one
+//! if let Err(errors) = channel.validate_all() {
+//!     for err in errors {
+//!         println!("{}", err);
+//!     }
+//! }
+//! ```
+//!
+//! ## Atom
+//!
+//! A channel can be converted to and from an
+//! [atom_syndication](https://crates.io/crates/atom_syndication) feed. `Channel::read_any`
+//! reads either an RSS channel or an Atom feed, converting the feed into a channel.
+//!
+//! **Note**: This requires enabling the `atom` feature.
+//!
+//! ```rust,ignore
+//! let feed = channel.to_atom();
+//! let channel = Channel::from(feed);
+//!
+//! let channel = Channel::read_any(reader).unwrap();
+//! ```
+//!
+//! ## JSON Feed
+//!
+//! A channel can be converted to and read from a [JSON Feed 1.1](https://jsonfeed.org/version/1.1)
+//! document. Version 1 documents can be read as well.
+//!
+//! **Note**: This requires enabling the `json_feed` feature.
+//!
+//! ```rust,ignore
+//! let json = channel.to_json_feed();
+//! let channel = Channel::from_json_feed(&json).unwrap();
+//! ```
+//!
+//! ## Serialization
+//!
+//! The types of the crate implement `Serialize` and `Deserialize` from
+//! [serde](https://serde.rs). The shape of the serialized data is part of the public API of the
+//! crate and does not follow changes to the representation of the types:
+//!
+//! * Fields are named after the RSS element or attribute in camel case, for example `pubDate`,
+//!   `webMaster`, `textInput` and `type`.
+//! * The namespaced extensions are the fields `itunes`, `dublinCore`, `media`, `podcast`,
+//!   `slash` and `syndication`.
+//! * Fields without a value and empty lists and maps are left out.
+//! * Missing fields take their default value, and unknown fields are ignored.
+//! * The values of custom extensions and the order of the elements of an item are not
+//!   serialized.
+//!
+//! **Note**: This requires enabling the `serialization` feature.
+//!
+//! ```rust,ignore
+//! let json = serde_json::to_string(&channel).unwrap();
+//! let channel: Channel = serde_json::from_str(&json).unwrap();
+//! ```
+//!
+//! ## Relative URLs
+//!
+//! Relative item links, enclosure URLs and source URLs can be resolved against the link of the
+//! channel.
+//!
+//! **Note**: This requires enabling the `resolve_urls` feature.
+//!
+//! ```rust,ignore
+//! channel.resolve_relative_urls();
 //! ```
 
 #[macro_use]
 extern crate derive_builder;
 
 extern crate quick_xml;
+extern crate encoding_rs;
 
-#[cfg(feature = "validation")]
+#[cfg(feature = "chrono")]
 extern crate chrono;
-#[cfg(feature = "validation")]
+#[cfg(feature = "url")]
 extern crate url;
 #[cfg(feature = "validation")]
 extern crate mime;
 
 #[cfg(feature = "from_url")]
 extern crate reqwest;
+#[cfg(feature = "atom")]
+extern crate atom_syndication;
+#[cfg(feature = "json_feed")]
+extern crate serde_json;
+#[cfg(feature = "async")]
+extern crate futures_io;
+#[cfg(feature = "compression")]
+extern crate flate2;
+#[cfg(feature = "serialization")]
+#[macro_use]
+extern crate serde;
 
 mod channel;
+mod channelwriter;
 mod category;
 mod cloud;
 mod enclosure;
 mod guid;
 mod image;
 mod item;
+mod itemreader;
+mod link;
+mod parseoptions;
 mod source;
+mod stylesheet;
 mod textinput;
+mod writeoptions;
 
+#[cfg(feature = "chrono")]
+mod date;
+mod diff;
+mod encoding;
+mod entities;
 mod error;
 mod fromxml;
 mod toxml;
+#[cfg(feature = "atom")]
+mod atom;
+#[cfg(feature = "json_feed")]
+mod jsonfeed;
+#[cfg(feature = "async")]
+mod asyncread;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "resolve_urls")]
+mod resolve;
 mod util;
 
 /// Types and methods for namespaced extensions.
@@ -119,14 +288,24 @@ pub mod extension;
 #[cfg(feature = "validation")]
 pub mod validation;
 
-pub use channel::{Channel, ChannelBuilder};
+pub use channel::{Channel, ChannelBuilder, MergeStrategy, SortOrder};
+pub use channelwriter::ChannelWriter;
 pub use category::{Category, CategoryBuilder};
-pub use cloud::{Cloud, CloudBuilder};
+pub use cloud::{Cloud, CloudBuilder, CloudProtocol};
 pub use enclosure::{Enclosure, EnclosureBuilder};
 pub use guid::{Guid, GuidBuilder};
-pub use image::{Image, ImageBuilder};
-pub use item::{Item, ItemBuilder};
+pub use image::{Image, ImageBuilder, ImageSizeError};
+pub use item::{DedupKey, Item, ItemBuilder};
+pub use itemreader::ItemReader;
+pub use link::{Link, LinkBuilder};
+pub use parseoptions::{ParseOptions, ParseOptionsBuilder, ParseWarning};
 pub use source::{Source, SourceBuilder};
+pub use stylesheet::{Stylesheet, StylesheetBuilder};
 pub use textinput::{TextInput, TextInputBuilder};
+pub use writeoptions::{InvalidCharPolicy, Version, WriteOptions, WriteOptionsBuilder};
+
+pub use diff::ChannelDiff;
+pub use error::{Error, FeedKind, LimitKind};
 
-pub use error::Error;
+#[cfg(feature = "async")]
+pub use asyncread::{ReadFromAsync, ReadFromAsyncWithOptions};
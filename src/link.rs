@@ -0,0 +1,227 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::io::{BufRead, Write};
+
+use quick_xml::errors::Error as XmlError;
+use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
+use quick_xml::events::attributes::Attributes;
+use quick_xml::reader::Reader;
+
+use error::Error;
+use extension::Extension;
+use fromxml::FromXml;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer, escape};
+use util::{attr_values, element_text_with_context};
+
+/// Represents a `<link>` element of an item, with the `rel` and `type` attributes that Atom
+/// links use.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct Link {
+    /// The URL of the link.
+    href: String,
+    /// The relationship of the linked resource to the item.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    rel: Option<String>,
+    /// The MIME type of the linked resource.
+    #[cfg_attr(feature = "serialization", serde(rename = "type"))]
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
+    mime_type: Option<String>,
+}
+
+impl Link {
+    /// Return the URL of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/related");
+    /// assert_eq!(link.href(), "http://example.com/related");
+    /// ```
+    pub fn href(&self) -> &str {
+        self.href.as_str()
+    }
+
+    /// Set the URL of this link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/related");
+    /// ```
+    pub fn set_href<V>(&mut self, href: V)
+    where
+        V: Into<String>,
+    {
+        self.href = href.into();
+    }
+
+    /// Return the relationship of the linked resource to the item.
+    ///
+    /// A link without a relationship is an `alternate` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("related".to_string());
+    /// assert_eq!(link.rel(), Some("related"));
+    /// ```
+    pub fn rel(&self) -> Option<&str> {
+        self.rel.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the relationship of the linked resource to the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("related".to_string());
+    /// ```
+    pub fn set_rel<V>(&mut self, rel: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.rel = rel.into();
+    }
+
+    /// Return the MIME type of the linked resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_mime_type("text/html".to_string());
+    /// assert_eq!(link.mime_type(), Some("text/html"));
+    /// ```
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the MIME type of the linked resource.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_mime_type("text/html".to_string());
+    /// ```
+    pub fn set_mime_type<V>(&mut self, mime_type: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.mime_type = mime_type.into();
+    }
+
+    /// Return whether this is an `alternate` link, which has no relationship or the
+    /// relationship `alternate`.
+    pub(crate) fn is_alternate(&self) -> bool {
+        self.rel.as_ref().map_or(true, |rel| rel == "alternate")
+    }
+
+    /// Create a link from an `<atom:link>` extension element.
+    ///
+    /// `None` is returned if the element has content or attributes other than `href`, `rel` and
+    /// `type`, so that it is kept as an extension and written back out unchanged.
+    pub(crate) fn from_atom_extension(extension: &Extension) -> Option<Link> {
+        let known = ["href", "rel", "type"];
+        if extension.value().is_some() || !extension.children().is_empty() ||
+            extension.attrs().keys().any(|key| !known.contains(&key.as_str()))
+        {
+            return None;
+        }
+
+        let attrs = extension.attrs();
+        Some(Link {
+            href: attrs.get("href").cloned().unwrap_or_default(),
+            rel: attrs.get("rel").cloned(),
+            mime_type: attrs.get("type").cloned(),
+        })
+    }
+
+    /// Write the link as an `<atom:link>` element with the given prefix for the Atom namespace.
+    pub(crate) fn write_atom<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        prefix: &str,
+    ) -> Result<(), XmlError> {
+        let name = format!("{}:link", prefix);
+        let mut element = BytesStart::borrowed(name.as_bytes(), name.len());
+        element.push_attribute((b"href" as &[u8], &*escape(self.href.as_bytes())));
+        if let Some(ref rel) = self.rel {
+            element.push_attribute((b"rel" as &[u8], &*escape(rel.as_bytes())));
+        }
+        if let Some(ref mime_type) = self.mime_type {
+            element.push_attribute((b"type" as &[u8], &*escape(mime_type.as_bytes())));
+        }
+        writer.write_event(Event::Empty(element))?;
+        Ok(())
+    }
+}
+
+impl FromXml for Link {
+    /// Parse a link, whose URL is either the text of the element or its `href` attribute.
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Link::from_xml_with_context(reader, atts, &mut ParseContext::default())
+    }
+}
+
+impl Link {
+    /// Parse a `<link>` element, decoding its attributes and text as the context requires.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
+        let mut link = Link::default();
+        let keys = [b"href" as &[u8], b"rel", b"type"];
+        let mut attrs = attr_values(reader, b"link", atts, &keys, context)?;
+        link.rel = attrs.take(b"rel");
+        link.mime_type = attrs.take(b"type");
+
+        let text = element_text_with_context(reader, context)?;
+        link.href = attrs.take(b"href").or(text).unwrap_or_default();
+        Ok(link)
+    }
+}
+
+impl ToXml for Link {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let name = b"link";
+        let mut element = BytesStart::borrowed(name, name.len());
+        if let Some(ref rel) = self.rel {
+            element.push_attribute((b"rel" as &[u8], &*escape(rel.as_bytes())));
+        }
+        if let Some(ref mime_type) = self.mime_type {
+            element.push_attribute((b"type" as &[u8], &*escape(mime_type.as_bytes())));
+        }
+        writer.write_event(Event::Start(element))?;
+        writer
+            .write_event(Event::Text(BytesText::borrowed(self.href.as_bytes())))?;
+        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        Ok(())
+    }
+}
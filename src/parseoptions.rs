@@ -0,0 +1,698 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+
+use quick_xml::events::BytesStart;
+use quick_xml::reader::Reader;
+
+use entities::unescape_html;
+use error::{Error, LimitKind};
+use extension::ExtensionMap;
+use extension::custom::{CustomExtensionMap, ExtensionHandler, ExtensionHandlers};
+use util::element_text_with_context;
+
+/// The default maximum nesting depth of elements.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// The default maximum length of the text of an element or the value of an attribute.
+const DEFAULT_MAX_LENGTH: usize = 16 * 1024 * 1024;
+
+/// The default maximum number of items in a channel.
+const DEFAULT_MAX_ITEMS: usize = 100_000;
+
+/// The default maximum size of compressed input once it is decompressed.
+const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Options that control how a channel is parsed.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into), default)]
+pub struct ParseOptions {
+    /// Whether recoverable problems are reported as warnings instead of aborting the parse.
+    lenient: bool,
+    /// The handlers for custom extension namespaces.
+    extension_handlers: ExtensionHandlers,
+    /// The initial capacity of the buffers that XML events are read into.
+    buffer_capacity: Option<usize>,
+    /// The maximum nesting depth of elements.
+    max_depth: usize,
+    /// The maximum length in bytes of the text of an element or the value of an attribute.
+    max_length: usize,
+    /// The maximum number of items in a channel.
+    max_items: usize,
+    /// The maximum size in bytes of compressed input once it is decompressed.
+    max_decompressed_size: usize,
+    /// Whether element names and attribute keys are matched ignoring ASCII case.
+    case_insensitive: bool,
+    /// Whether HTML named character references are resolved, or `None` to resolve them when
+    /// parsing is lenient.
+    html_entities: Option<bool>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            lenient: false,
+            extension_handlers: ExtensionHandlers::default(),
+            buffer_capacity: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_length: DEFAULT_MAX_LENGTH,
+            max_items: DEFAULT_MAX_ITEMS,
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            case_insensitive: false,
+            html_entities: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Return whether parsing is lenient.
+    ///
+    /// When parsing is lenient, duplicate elements are ignored, text that cannot be unescaped is
+    /// kept as is, text that is not valid in the declared encoding has the invalid bytes
+    /// replaced with U+FFFD, and items that fail to parse are skipped. Each of these problems is reported
+    /// as a `ParseWarning`. HTML named character references such as `&nbsp;` are resolved unless
+    /// `set_html_entities` disables them. Otherwise the channel is parsed exactly as with
+    /// `Channel::read_from`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_lenient(true);
+    /// assert!(options.lenient());
+    /// ```
+    pub fn lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Set whether parsing is lenient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_lenient(true);
+    /// ```
+    pub fn set_lenient<V>(&mut self, lenient: V)
+    where
+        V: Into<bool>,
+    {
+        self.lenient = lenient.into();
+    }
+
+    /// Return the initial capacity of the buffers that XML events are read into.
+    ///
+    /// The buffers are reused for every event and grow as needed, so a capacity large enough
+    /// for the biggest elements of a feed avoids reallocating them while parsing. A value of
+    /// `None` starts with empty buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_buffer_capacity(64 * 1024);
+    /// assert_eq!(options.buffer_capacity(), Some(64 * 1024));
+    /// ```
+    pub fn buffer_capacity(&self) -> Option<usize> {
+        self.buffer_capacity
+    }
+
+    /// Set the initial capacity of the buffers that XML events are read into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_buffer_capacity(None);
+    /// ```
+    pub fn set_buffer_capacity<V>(&mut self, buffer_capacity: V)
+    where
+        V: Into<Option<usize>>,
+    {
+        self.buffer_capacity = buffer_capacity.into();
+    }
+
+    /// Return the maximum nesting depth of elements.
+    ///
+    /// The root element has a depth of 1. Deeper elements, which can only appear in extensions,
+    /// abort the parse with `Error::LimitExceeded`. The default is 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_depth(32);
+    /// assert_eq!(options.max_depth(), 32);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Set the maximum nesting depth of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_depth(32);
+    /// ```
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Return the maximum length in bytes of the text of an element or the value of an
+    /// attribute.
+    ///
+    /// Longer text or attributes abort the parse with `Error::LimitExceeded`. The default is
+    /// 16 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_length(1024 * 1024);
+    /// assert_eq!(options.max_length(), 1024 * 1024);
+    /// ```
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Set the maximum length in bytes of the text of an element or the value of an attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_length(1024 * 1024);
+    /// ```
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length;
+    }
+
+    /// Return the maximum number of items in a channel.
+    ///
+    /// Reading more items aborts the parse with `Error::LimitExceeded`. The default is 100,000.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_items(500);
+    /// assert_eq!(options.max_items(), 500);
+    /// ```
+    pub fn max_items(&self) -> usize {
+        self.max_items
+    }
+
+    /// Set the maximum number of items in a channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_items(500);
+    /// ```
+    pub fn set_max_items(&mut self, max_items: usize) {
+        self.max_items = max_items;
+    }
+
+    /// Return the maximum size in bytes of compressed input once it is decompressed.
+    ///
+    /// Input that `Channel::read_from_maybe_compressed_with_options` decompresses to more bytes
+    /// aborts the parse with `Error::LimitExceeded`, so that a small payload that expands
+    /// enormously cannot use up the memory. Input that is not compressed is not limited. The
+    /// default is 64 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_decompressed_size(8 * 1024 * 1024);
+    /// assert_eq!(options.max_decompressed_size(), 8 * 1024 * 1024);
+    /// ```
+    pub fn max_decompressed_size(&self) -> usize {
+        self.max_decompressed_size
+    }
+
+    /// Set the maximum size in bytes of compressed input once it is decompressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_max_decompressed_size(8 * 1024 * 1024);
+    /// ```
+    pub fn set_max_decompressed_size(&mut self, max_decompressed_size: usize) {
+        self.max_decompressed_size = max_decompressed_size;
+    }
+
+    /// Return whether element names and attribute keys are matched ignoring ASCII case.
+    ///
+    /// When this is enabled, elements such as `<PubDate>` and attributes such as `ispermalink`
+    /// are read as if they had been written with the standard case. This applies to the
+    /// elements of the channel and its items, and to the attributes of those elements. Names
+    /// are always written with the standard case. The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, ParseOptionsBuilder};
+    ///
+    /// let input = r#"
+    /// <rss version="2.0">
+    ///   <channel>
+    ///     <Title>Title</Title>
+    ///     <item><GUID IsPermaLink="false">1</GUID></item>
+    ///   </channel>
+    /// </rss>
+    /// "#;
+    ///
+    /// let options = ParseOptionsBuilder::default().case_insensitive(true).build().unwrap();
+    /// let (channel, _) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    ///
+    /// assert_eq!(channel.title(), "Title");
+    /// let guid = channel.items()[0].guid().unwrap();
+    /// assert_eq!(guid.value(), "1");
+    /// assert!(!guid.is_permalink());
+    /// ```
+    pub fn case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Set whether element names and attribute keys are matched ignoring ASCII case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_case_insensitive(true);
+    /// ```
+    pub fn set_case_insensitive<V>(&mut self, case_insensitive: V)
+    where
+        V: Into<bool>,
+    {
+        self.case_insensitive = case_insensitive.into();
+    }
+
+    /// Return whether HTML named character references such as `&nbsp;` and `&mdash;` are
+    /// resolved in text and attribute values.
+    ///
+    /// XML only defines `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`, so other named references
+    /// are errors unless they are resolved. A reference that is still unknown, or an `&` that does
+    /// not start a reference, is kept as it is and reported as a `ParseWarning`. A value of
+    /// `None`, the default, resolves them when parsing is lenient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Channel, ParseOptionsBuilder};
+    ///
+    /// let input = "<rss><channel><title>Fish&nbsp;&amp; Chips&hellip;</title></channel></rss>";
+    /// assert!(input.parse::<Channel>().is_err());
+    ///
+    /// let options = ParseOptionsBuilder::default().html_entities(true).build().unwrap();
+    /// let (channel, _) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    /// assert_eq!(channel.title(), "Fish\u{a0}& Chips\u{2026}");
+    /// ```
+    pub fn html_entities(&self) -> Option<bool> {
+        self.html_entities
+    }
+
+    /// Set whether HTML named character references are resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.set_html_entities(false);
+    /// assert_eq!(options.html_entities(), Some(false));
+    /// ```
+    pub fn set_html_entities<V>(&mut self, html_entities: V)
+    where
+        V: Into<Option<bool>>,
+    {
+        self.html_entities = html_entities.into();
+    }
+
+    /// Return the handlers registered for custom extension namespaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ParseOptions;
+    ///
+    /// let options = ParseOptions::default();
+    /// assert!(options.extension_handlers().is_empty());
+    /// ```
+    pub fn extension_handlers(&self) -> &ExtensionHandlers {
+        &self.extension_handlers
+    }
+
+    /// Register a handler for the extension namespace with the given URI.
+    ///
+    /// The elements of the namespace in each channel and item are passed to the handler, and
+    /// the value it returns is stored in the `custom_extensions` of the channel or item. The
+    /// value writes itself back out when the channel is written. A handler registered for the
+    /// iTunes, Dublin Core or Media RSS namespace is used instead of the built-in extension.
+    ///
+    /// See `ExtensionHandler` for a complete example.
+    pub fn register_extension<S, H>(&mut self, namespace: S, handler: H)
+    where
+        S: Into<String>,
+        H: ExtensionHandler + 'static,
+    {
+        self.extension_handlers.register(namespace, handler);
+    }
+}
+
+/// A recoverable problem found while parsing a channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    /// The byte offset in the input where the problem was found.
+    position: usize,
+    /// A description of the problem.
+    message: String,
+}
+
+impl ParseWarning {
+    /// Return the byte offset in the input where the problem was found.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return a description of the problem.
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+/// The state shared by the parsers while reading a channel.
+#[derive(Debug)]
+pub(crate) struct ParseContext {
+    lenient: bool,
+    warnings: Vec<ParseWarning>,
+    handlers: ExtensionHandlers,
+    namespaces: HashMap<String, String>,
+    buffer_capacity: usize,
+    buffers: Vec<Vec<u8>>,
+    max_depth: usize,
+    max_length: usize,
+    max_items: usize,
+    case_insensitive: bool,
+    html_entities: bool,
+}
+
+impl Default for ParseContext {
+    fn default() -> ParseContext {
+        ParseContext::new(&ParseOptions::default())
+    }
+}
+
+impl ParseContext {
+    pub fn new(options: &ParseOptions) -> ParseContext {
+        ParseContext {
+            lenient: options.lenient,
+            warnings: Vec::new(),
+            handlers: options.extension_handlers.clone(),
+            namespaces: HashMap::new(),
+            buffer_capacity: options.buffer_capacity.unwrap_or(0),
+            buffers: Vec::new(),
+            max_depth: options.max_depth,
+            max_length: options.max_length,
+            max_items: options.max_items,
+            case_insensitive: options.case_insensitive,
+            html_entities: options.html_entities.unwrap_or(options.lenient),
+        }
+    }
+
+    /// Create a buffer for reading XML events with the configured capacity.
+    pub fn buffer(&self) -> Vec<u8> {
+        Vec::with_capacity(self.buffer_capacity)
+    }
+
+    /// Take a buffer that was returned with `return_buffer`, or create one if there is none.
+    ///
+    /// Reusing the buffers avoids allocating new ones for every element that is read.
+    pub fn take_buffer(&mut self) -> Vec<u8> {
+        match self.buffers.pop() {
+            Some(buf) => buf,
+            None => self.buffer(),
+        }
+    }
+
+    /// Return a buffer so that it can be reused by `take_buffer`.
+    pub fn return_buffer(&mut self, mut buf: Vec<u8>) {
+        buf.clear();
+        self.buffers.push(buf);
+    }
+
+    /// Return an error if an element at the given depth is nested too deeply.
+    pub fn check_depth(&self, depth: usize) -> Result<(), Error> {
+        check_limit(LimitKind::Depth, depth, self.max_depth)
+    }
+
+    /// Return an error if text or an attribute value of the given length is too long.
+    pub fn check_length(&self, length: usize) -> Result<(), Error> {
+        check_limit(LimitKind::Length, length, self.max_length)
+    }
+
+    /// Return an error if a channel with the given number of items has too many.
+    pub fn check_items(&self, items: usize) -> Result<(), Error> {
+        check_limit(LimitKind::Items, items, self.max_items)
+    }
+
+    /// Return the name in `names` that matches `name`, ignoring ASCII case if parsing is
+    /// case-insensitive.
+    ///
+    /// `name` is returned unchanged if it matches exactly or there is no match.
+    pub fn canonical_name<'a>(&self, name: &'a [u8], names: &[&'static [u8]]) -> &'a [u8] {
+        if !self.case_insensitive || names.contains(&name) {
+            return name;
+        }
+
+        names
+            .iter()
+            .find(|candidate| candidate.eq_ignore_ascii_case(name))
+            .map_or(name, |candidate| *candidate)
+    }
+
+    /// Return the element with its attribute keys replaced by the matching keys in `keys`, if
+    /// parsing is case-insensitive.
+    ///
+    /// In lenient mode, a malformed attribute, which the parser of the element skips, is
+    /// reported as a warning at `position`.
+    pub fn normalize_attributes<'a, 'b>(
+        &mut self,
+        position: usize,
+        element: &'b BytesStart<'a>,
+        keys: &[&'static [u8]],
+    ) -> Cow<'b, BytesStart<'a>> {
+        if self.lenient {
+            let mut attributes = element.attributes();
+            let error = attributes.with_checks(false).filter_map(|attr| attr.err()).next();
+            if let Some(err) = error {
+                let name = String::from_utf8_lossy(element.name()).into_owned();
+                self.warn(position, format!("malformed attribute of <{}> skipped: {}", name, err));
+            }
+        }
+
+        if !self.case_insensitive {
+            return Cow::Borrowed(element);
+        }
+
+        let mut attributes = Vec::new();
+        let mut changed = false;
+
+        for attr in element.attributes().with_checks(false) {
+            let attr = match attr {
+                Ok(attr) => attr,
+                // the parser reports the malformed attribute
+                Err(_) => return Cow::Borrowed(element),
+            };
+            let key = self.canonical_name(attr.key, keys);
+            changed |= key != attr.key;
+            attributes.push((key, attr.value));
+        }
+
+        if !changed {
+            return Cow::Borrowed(element);
+        }
+
+        let name = element.name();
+        let mut normalized = BytesStart::owned(name.to_vec(), name.len());
+        normalized.extend_attributes(attributes);
+        Cow::Owned(normalized)
+    }
+
+    /// Return whether HTML named character references are resolved.
+    pub fn html_entities(&self) -> bool {
+        self.html_entities
+    }
+
+    /// Unescape text or an attribute value, resolving HTML named character references and
+    /// reporting the references that are kept as warnings at `position`.
+    pub fn unescape_html<'a>(&mut self, position: usize, raw: &'a [u8]) -> Cow<'a, [u8]> {
+        let mut unknown = Vec::new();
+        let text = unescape_html(raw, &mut unknown);
+        for reference in unknown {
+            self.warn(position, format!("unknown character reference kept: {}", reference));
+        }
+        text
+    }
+
+    /// Record a namespace declared on the root element.
+    pub fn add_namespace(&mut self, prefix: String, namespace: String) {
+        self.namespaces.insert(prefix, namespace);
+    }
+
+    /// Return the prefixes that the namespace with the given URI was declared with, in sorted
+    /// order so that elements are extracted in the same order on every run.
+    pub fn namespace_prefixes(&self, namespace: &str) -> Vec<&str> {
+        let mut prefixes = self.namespaces
+            .iter()
+            .filter(|&(_, uri)| uri == namespace)
+            .map(|(prefix, _)| prefix.as_str())
+            .collect::<Vec<_>>();
+        prefixes.sort();
+        prefixes
+    }
+
+    /// Move the elements of namespaces with a registered handler out of `extensions` and
+    /// store the values that the handlers return.
+    pub fn read_custom_extensions(
+        &self,
+        extensions: &mut ExtensionMap,
+        custom_extensions: &mut CustomExtensionMap,
+    ) -> Result<(), Error> {
+        if self.handlers.is_empty() || extensions.is_empty() {
+            return Ok(());
+        }
+
+        for (prefix, namespace) in &self.namespaces {
+            let handler = match self.handlers.get(namespace) {
+                Some(handler) => handler,
+                None => continue,
+            };
+
+            if let Some(mut map) = extensions.remove(prefix) {
+                if let Some(value) = handler.parse(&mut map)? {
+                    custom_extensions.insert_boxed(prefix.clone(), namespace.clone(), value);
+                }
+                if !map.is_empty() {
+                    extensions.insert(prefix.clone(), map);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    pub fn warn<S: Into<String>>(&mut self, position: usize, message: S) {
+        self.warnings.push(ParseWarning {
+            position: position,
+            message: message.into(),
+        });
+    }
+
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    pub fn into_warnings(self) -> Vec<ParseWarning> {
+        self.warnings
+    }
+
+    /// Skip an element that has already been read, if parsing is lenient.
+    ///
+    /// Returns `true` if the element was skipped.
+    pub fn skip_duplicate<R: BufRead>(
+        &mut self,
+        reader: &mut Reader<R>,
+        name: &[u8],
+        present: bool,
+    ) -> Result<bool, Error> {
+        if !self.lenient || !present {
+            return Ok(false);
+        }
+
+        let position = reader.buffer_position();
+        reader.read_to_end(name, &mut Vec::new())?;
+        self.warn(
+            position,
+            format!("duplicate <{}> element ignored", reader.decode(name)),
+        );
+        Ok(true)
+    }
+
+    /// Read the text of an element into an optional field, ignoring duplicates if parsing is
+    /// lenient.
+    pub fn read_text<R: BufRead>(
+        &mut self,
+        reader: &mut Reader<R>,
+        name: &[u8],
+        field: &mut Option<String>,
+    ) -> Result<(), Error> {
+        if !self.skip_duplicate(reader, name, field.is_some())? {
+            *field = element_text_with_context(reader, self)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_limit(kind: LimitKind, value: usize, limit: usize) -> Result<(), Error> {
+    if value > limit {
+        Err(Error::LimitExceeded {
+            kind: kind,
+            limit: limit,
+        })
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use url::{ParseError, Url};
+
+use channel::Channel;
+
+impl Channel {
+    /// Resolve the relative URLs in this channel against the nearest base URI.
+    ///
+    /// The links, enclosure URLs and source URLs of the items are rewritten. URLs that are
+    /// already absolute and values that are not URLs at all are left untouched.
+    ///
+    /// The URLs of an item are resolved against its `xml:base`, the `xml:base` of the channel
+    /// or the channel's link, whichever is nearest. A relative `xml:base` is itself resolved
+    /// against the next base out. The URLs of an item are left unchanged if none of these is an
+    /// absolute URL.
+    ///
+    /// **Note**: This requires enabling the `resolve_urls` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{ChannelBuilder, EnclosureBuilder, ItemBuilder};
+    ///
+    /// let item = ItemBuilder::default()
+    ///     .link("posts/1".to_string())
+    ///     .enclosure(EnclosureBuilder::default().url("/audio/1.mp3").build().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut channel = ChannelBuilder::default()
+    ///     .link("http://example.com/blog/")
+    ///     .items(vec![item])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// channel.resolve_relative_urls();
+    ///
+    /// let item = &channel.items()[0];
+    /// assert_eq!(item.link(), Some("http://example.com/blog/posts/1"));
+    /// assert_eq!(item.enclosure().unwrap().url(), "http://example.com/audio/1.mp3");
+    /// ```
+    pub fn resolve_relative_urls(&mut self) {
+        let link = Url::parse(self.link()).ok();
+        let channel_base = match self.base() {
+            Some(base) => resolve_base(link.as_ref(), base),
+            None => link,
+        };
+
+        for item in self.items_mut() {
+            let base = match item.base() {
+                Some(base) => resolve_base(channel_base.as_ref(), base),
+                None => channel_base.clone(),
+            };
+            let base = match base {
+                Some(base) => base,
+                None => continue,
+            };
+
+            if let Some(link) = item.link().and_then(|link| resolve(&base, link)) {
+                item.set_link(link);
+            }
+
+            for link in item.links_mut() {
+                if let Some(href) = resolve(&base, link.href()) {
+                    link.set_href(href);
+                }
+            }
+
+            let enclosure = item.enclosure().and_then(|enclosure| {
+                resolve(&base, enclosure.url()).map(|url| {
+                    let mut enclosure = enclosure.clone();
+                    enclosure.set_url(url);
+                    enclosure
+                })
+            });
+            if enclosure.is_some() {
+                item.set_enclosure(enclosure);
+            }
+
+            let source = item.source().and_then(|source| {
+                resolve(&base, source.url()).map(|url| {
+                    let mut source = source.clone();
+                    source.set_url(url);
+                    source
+                })
+            });
+            if source.is_some() {
+                item.set_source(source);
+            }
+        }
+    }
+}
+
+/// Return the base URI given by an `xml:base` value, resolving it against `parent` if it is
+/// relative.
+///
+/// A value that is not a URL is ignored in favour of `parent`.
+fn resolve_base(parent: Option<&Url>, base: &str) -> Option<Url> {
+    match Url::parse(base) {
+        Ok(base) => Some(base),
+        Err(ParseError::RelativeUrlWithoutBase) => parent.and_then(|parent| parent.join(base).ok()),
+        Err(_) => parent.cloned(),
+    }
+}
+
+/// Resolve `value` against `base` if it is a relative URL.
+fn resolve(base: &Url, value: &str) -> Option<String> {
+    match Url::parse(value) {
+        Err(ParseError::RelativeUrlWithoutBase) => base.join(value).ok().map(Url::into_string),
+        _ => None,
+    }
+}
@@ -11,20 +11,23 @@ use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::ToXml;
-use util::element_text;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer, escape};
+use util::{attr_values, element_text_with_context};
 
 /// Represents the source of an RSS item.
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct Source {
     /// The URL of the source.
     url: String,
     /// The title of the source.
+    #[cfg_attr(feature = "serialization", serde(skip_serializing_if = "Option::is_none"))]
     title: Option<String>,
 }
 
@@ -95,19 +98,22 @@ impl Source {
 }
 
 impl FromXml for Source {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, mut atts: Attributes) -> Result<Self, Error> {
-        let mut source = Source::default();
-
-        for attr in atts.with_checks(false) {
-            if let Ok(attr) = attr {
-                if attr.key == b"url" {
-                    source.url = attr.unescape_and_decode_value(reader)?;
-                    break;
-                }
-            }
-        }
+    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes) -> Result<Self, Error> {
+        Source::from_xml_with_context(reader, atts, &mut ParseContext::default())
+    }
+}
 
-        source.title = element_text(reader)?;
+impl Source {
+    /// Parse a `<source>` element, resolving character references as the context requires.
+    pub(crate) fn from_xml_with_context<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes,
+        context: &mut ParseContext,
+    ) -> Result<Self, Error> {
+        let mut source = Source::default();
+        let mut attrs = attr_values(reader, b"source", atts, &[b"url"], context)?;
+        source.url = attrs.take(b"url").unwrap_or_default();
+        source.title = element_text_with_context(reader, context)?;
         Ok(source)
     }
 }
@@ -116,7 +122,7 @@ impl ToXml for Source {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = b"source";
         let mut element = BytesStart::borrowed(name, name.len());
-        element.push_attribute(("url", &*self.url));
+        element.push_attribute((b"url" as &[u8], &*escape(self.url.as_bytes())));
 
         writer.write_event(Event::Start(element))?;
 
@@ -0,0 +1,155 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+use std::io::{BufRead, Write};
+
+use quick_xml::errors::Error as XmlError;
+use quick_xml::events::{Event, BytesStart, BytesText};
+use quick_xml::reader::Reader;
+
+use error::Error;
+use parseoptions::ParseContext;
+use toxml::{ToXml, Writer, escape};
+use util::attr_value;
+
+/// The target of a stylesheet processing instruction.
+const TARGET: &'static [u8] = b"xml-stylesheet";
+
+/// Represents an `xml-stylesheet` processing instruction before the root element of a feed.
+///
+/// Browsers use the stylesheet to display a feed that is opened directly.
+#[derive(Debug, Default, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct Stylesheet {
+    /// The URL of the stylesheet.
+    href: String,
+    /// The MIME type of the stylesheet.
+    #[cfg_attr(feature = "serialization", serde(rename = "type"))]
+    media_type: String,
+}
+
+impl Stylesheet {
+    /// Return the URL of this stylesheet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Stylesheet;
+    ///
+    /// let mut stylesheet = Stylesheet::default();
+    /// stylesheet.set_href("feed.xsl");
+    /// assert_eq!(stylesheet.href(), "feed.xsl");
+    /// ```
+    pub fn href(&self) -> &str {
+        self.href.as_str()
+    }
+
+    /// Set the URL of this stylesheet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Stylesheet;
+    ///
+    /// let mut stylesheet = Stylesheet::default();
+    /// stylesheet.set_href("feed.xsl");
+    /// ```
+    pub fn set_href<V>(&mut self, href: V)
+    where
+        V: Into<String>,
+    {
+        self.href = href.into();
+    }
+
+    /// Return the MIME type of this stylesheet.
+    ///
+    /// An empty type is not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Stylesheet;
+    ///
+    /// let mut stylesheet = Stylesheet::default();
+    /// stylesheet.set_media_type("text/xsl");
+    /// assert_eq!(stylesheet.media_type(), "text/xsl");
+    /// ```
+    pub fn media_type(&self) -> &str {
+        self.media_type.as_str()
+    }
+
+    /// Set the MIME type of this stylesheet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Stylesheet;
+    ///
+    /// let mut stylesheet = Stylesheet::default();
+    /// stylesheet.set_media_type("text/xsl");
+    /// ```
+    pub fn set_media_type<V>(&mut self, media_type: V)
+    where
+        V: Into<String>,
+    {
+        self.media_type = media_type.into();
+    }
+}
+
+impl Stylesheet {
+    /// Parse the content of a processing instruction, returning `None` if it is not an
+    /// `xml-stylesheet` instruction.
+    pub(crate) fn from_pi<R: BufRead>(
+        reader: &Reader<R>,
+        content: &[u8],
+        context: &mut ParseContext,
+    ) -> Result<Option<Stylesheet>, Error> {
+        if !content.starts_with(TARGET) {
+            return Ok(None);
+        }
+
+        let rest = &content[TARGET.len()..];
+        if !rest.is_empty() && !rest[0].is_ascii_whitespace() {
+            return Ok(None);
+        }
+
+        let element = BytesStart::borrowed(content, TARGET.len());
+        let mut stylesheet = Stylesheet::default();
+        for attr in element.attributes().with_checks(false) {
+            if let Ok(attr) = attr {
+                match attr.key {
+                    b"href" => stylesheet.href = attr_value(reader, TARGET, &attr, context)?,
+                    b"type" => {
+                        stylesheet.media_type = attr_value(reader, TARGET, &attr, context)?
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Some(stylesheet))
+    }
+}
+
+impl ToXml for Stylesheet {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let mut content = TARGET.to_vec();
+        if !self.media_type.is_empty() {
+            content.extend_from_slice(b" type=\"");
+            content.extend_from_slice(&escape(self.media_type.as_bytes()));
+            content.push(b'"');
+        }
+        content.extend_from_slice(b" href=\"");
+        content.extend_from_slice(&escape(self.href.as_bytes()));
+        content.push(b'"');
+
+        writer.write_event(Event::PI(BytesText::borrowed(&content)))?;
+        Ok(())
+    }
+}
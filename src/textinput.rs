@@ -11,16 +11,38 @@ use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::reader::Reader;
-use quick_xml::writer::Writer;
 
 use error::Error;
 use fromxml::FromXml;
-use toxml::{ToXml, WriterExt};
+use toxml::{ToXml, Writer, WriterExt};
 use util::element_text;
 
 /// Represents a text input for an RSS channel.
+///
+/// A text input requires all four of its elements, so `TextInputBuilder::build` returns an error
+/// if any of them is missing or empty.
+///
+/// # Examples
+///
+/// ```
+/// use rss::TextInputBuilder;
+///
+/// let text_input = TextInputBuilder::default()
+///     .title("Search")
+///     .description("Search the archives")
+///     .name("q")
+///     .link("http://example.com/search")
+///     .build()
+///     .unwrap();
+/// assert_eq!(text_input.name(), "q");
+///
+/// let result = TextInputBuilder::default().title("Search").build();
+/// assert_eq!(result.unwrap_err(), "the text input description is missing");
+/// ```
 #[derive(Debug, Default, Clone, PartialEq, Builder)]
-#[builder(setter(into), default)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(default, rename_all = "camelCase"))]
+#[builder(setter(into), default, build_fn(validate = "Self::validate"))]
 pub struct TextInput {
     /// The label of the Submit button for the text input.
     title: String,
@@ -162,6 +184,26 @@ impl TextInput {
     }
 }
 
+impl TextInputBuilder {
+    /// Check that every element of the text input has been given a value.
+    fn validate(&self) -> Result<(), String> {
+        let fields = [
+            ("title", &self.title),
+            ("description", &self.description),
+            ("name", &self.name),
+            ("link", &self.link),
+        ];
+
+        for &(name, value) in &fields {
+            if value.as_ref().map_or(true, |value| value.is_empty()) {
+                return Err(format!("the text input {} is missing", name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl FromXml for TextInput {
     fn from_xml<R: BufRead>(reader: &mut Reader<R>, _: Attributes) -> Result<Self, Error> {
         let mut text_input = TextInput::default();
@@ -193,6 +235,8 @@ impl FromXml for TextInput {
 }
 
 impl ToXml for TextInput {
+    /// Write the text input, always using the `textInput` name and the element order of the
+    /// specification.
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = b"textInput";
 
@@ -5,11 +5,118 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
-use std::io::Write;
+use std::borrow::Cow;
+use std::io::{self, Write};
 
 use quick_xml::errors::Error as XmlError;
 use quick_xml::events::{Event, BytesStart, BytesEnd, BytesText};
-use quick_xml::writer::Writer;
+use quick_xml::writer::Writer as XmlWriter;
+
+use writeoptions::{InvalidCharPolicy, WriteOptions};
+
+/// A writer of XML events that indents the elements and writes the text of some item fields as
+/// CDATA sections, as set by `WriteOptions`.
+pub struct Writer<W: Write> {
+    inner: XmlWriter<W>,
+    indent: Option<(u8, usize)>,
+    cdata_fields: Vec<Vec<u8>>,
+    /// The names of the open elements, which are only kept when there are CDATA fields.
+    open: Vec<Vec<u8>>,
+    depth: usize,
+    started: bool,
+    after_start: bool,
+    /// The depth of the element that directly contains text, if any. No whitespace is added
+    /// inside it, so the content of leaf elements and mixed content is written as it is.
+    inline: Option<usize>,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(inner: W) -> Self {
+        Writer {
+            inner: XmlWriter::new(inner),
+            indent: None,
+            cdata_fields: Vec::new(),
+            open: Vec::new(),
+            depth: 0,
+            started: false,
+            after_start: false,
+            inline: None,
+        }
+    }
+
+    pub fn with_options(inner: W, options: &WriteOptions) -> Self {
+        let mut writer = Writer::new(inner);
+        writer.indent = options.indent();
+        writer.cdata_fields = options
+            .cdata_fields()
+            .iter()
+            .map(|name| name.as_bytes().to_vec())
+            .collect();
+        writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+
+    pub fn write_event<'a, E: AsRef<Event<'a>>>(&mut self, event: E) -> Result<usize, XmlError> {
+        let event = event.as_ref();
+        let newline = match *event {
+            Event::Text(ref text) if !text.is_empty() && self.in_cdata_field() => {
+                return write_cdata(self, text);
+            }
+            Event::Text(_) | Event::CData(_) => {
+                if self.inline.is_none() {
+                    self.inline = Some(self.depth);
+                }
+                false
+            }
+            Event::End(_) => {
+                // the channel writer closes elements that were opened by another writer
+                self.depth = self.depth.saturating_sub(1);
+                self.open.pop();
+                self.inline.is_none() && !self.after_start
+            }
+            _ => self.inline.is_none() && self.started,
+        };
+
+        let mut len = 0;
+        if let (true, Some((indent_char, indent_size))) = (newline, self.indent) {
+            len += self.inner.write(b"\n")?;
+            len += self.inner.write(&vec![indent_char; self.depth * indent_size])?;
+        }
+        len += self.inner.write_event(event)?;
+
+        match *event {
+            Event::Start(ref element) => {
+                self.depth += 1;
+                if !self.cdata_fields.is_empty() {
+                    self.open.push(element.name().to_vec());
+                }
+            }
+            Event::End(_) => {
+                if self.inline.map_or(false, |inline| self.depth < inline) {
+                    self.inline = None;
+                }
+            }
+            _ => {}
+        }
+
+        self.after_start = match *event {
+            Event::Start(_) => true,
+            _ => false,
+        };
+        self.started = true;
+        Ok(len)
+    }
+
+    /// Return whether the current element is one of the CDATA fields of an item.
+    fn in_cdata_field(&self) -> bool {
+        let len = self.open.len();
+        len >= 2 && self.open[len - 2] == b"item" &&
+            self.cdata_fields.contains(&self.open[len - 1])
+    }
+}
 
 pub trait ToXml {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError>;
@@ -81,7 +188,7 @@ impl<W: Write> WriterExt for Writer<W> {
     {
         let name = name.as_ref();
         self.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
-        self.write_event(Event::CData(BytesText::borrowed(text.as_ref())))?;
+        write_cdata(self, text.as_ref())?;
         self.write_event(Event::End(BytesEnd::borrowed(name)))?;
         Ok(())
     }
@@ -106,3 +213,138 @@ impl<W: Write> WriterExt for Writer<W> {
         Ok(())
     }
 }
+
+/// Escape the characters that are not allowed to appear verbatim in an attribute value.
+pub fn escape(raw: &[u8]) -> Cow<'_, [u8]> {
+    let mut escaped: Option<Vec<u8>> = None;
+
+    for (index, byte) in raw.iter().enumerate() {
+        let entity: &[u8] = match *byte {
+            b'<' => b"&lt;",
+            b'>' => b"&gt;",
+            b'&' => b"&amp;",
+            b'"' => b"&quot;",
+            b'\'' => b"&apos;",
+            _ => {
+                if let Some(escaped) = escaped.as_mut() {
+                    escaped.push(*byte);
+                }
+                continue;
+            }
+        };
+
+        escaped
+            .get_or_insert_with(|| raw[..index].to_vec())
+            .extend_from_slice(entity);
+    }
+
+    match escaped {
+        Some(escaped) => Cow::Owned(escaped),
+        None => Cow::Borrowed(raw),
+    }
+}
+
+/// Write the text of an element, as a CDATA section if it starts or ends with whitespace that
+/// would otherwise be trimmed when the element is read.
+pub fn write_untrimmed_text<W: Write>(writer: &mut Writer<W>, text: &[u8]) -> Result<(), XmlError> {
+    let padded = match (text.first(), text.last()) {
+        (Some(first), Some(last)) => is_whitespace(&[*first]) || is_whitespace(&[*last]),
+        _ => false,
+    };
+
+    if padded {
+        write_cdata(writer, text)?;
+    } else {
+        writer.write_event(Event::Text(BytesText::borrowed(text)))?;
+    }
+    Ok(())
+}
+
+fn write_cdata<W: Write>(writer: &mut Writer<W>, mut text: &[u8]) -> Result<usize, XmlError> {
+    let mut len = 0;
+    // a CDATA section cannot contain its own terminator so it is split across two sections
+    while let Some(index) = find(text, b"]]>") {
+        len += writer.write_event(Event::CData(BytesText::borrowed(&text[..index + 2])))?;
+        text = &text[index + 2..];
+    }
+    len += writer.write_event(Event::CData(BytesText::borrowed(text)))?;
+    Ok(len)
+}
+
+/// A writer that strips, references or rejects the control characters that XML does not allow.
+///
+/// The characters are single bytes in UTF-8, so they are handled however the output is split
+/// into writes.
+pub struct CharFilter<W> {
+    inner: W,
+    policy: InvalidCharPolicy,
+    invalid: Option<char>,
+}
+
+impl<W: Write> CharFilter<W> {
+    pub fn new(inner: W, policy: InvalidCharPolicy) -> Self {
+        CharFilter {
+            inner: inner,
+            policy: policy,
+            invalid: None,
+        }
+    }
+
+    /// Return the character that made a write fail with `InvalidCharPolicy::Error`, if any.
+    pub fn invalid(&self) -> Option<char> {
+        self.invalid
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CharFilter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while let Some(index) = rest.iter().position(|&byte| is_invalid(byte)) {
+            self.inner.write_all(&rest[..index])?;
+            match self.policy {
+                InvalidCharPolicy::Strip => {}
+                InvalidCharPolicy::Reference => {
+                    write!(self.inner, "&#x{:X};", rest[index])?;
+                }
+                InvalidCharPolicy::Error => {
+                    self.invalid = Some(rest[index] as char);
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "character not allowed in XML",
+                    ));
+                }
+            }
+            rest = &rest[index + 1..];
+        }
+
+        self.inner.write_all(rest)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Return whether a byte is a control character that XML does not allow.
+fn is_invalid(byte: u8) -> bool {
+    match byte {
+        b'\t' | b'\n' | b'\r' => false,
+        byte => byte < 0x20,
+    }
+}
+
+fn is_whitespace(text: &[u8]) -> bool {
+    text.iter().all(|byte| match *byte {
+        b' ' | b'\t' | b'\r' | b'\n' => true,
+        _ => false,
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
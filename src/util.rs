@@ -5,30 +5,228 @@
 // This program is free software; you can redistribute it and/or modify
 // it under the terms of the MIT License and/or Apache 2.0 License.
 
+use std::borrow::Cow;
 use std::io::BufRead;
 
-use quick_xml::events::Event;
+use quick_xml::events::{BytesText, Event};
+use quick_xml::events::attributes::{Attribute, Attributes};
 use quick_xml::reader::Reader;
 
+use encoding::parsed_encoding;
 use error::Error;
+use parseoptions::ParseContext;
 
-pub fn element_text<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<String>, Error> {
-    let mut content: Option<String> = None;
+/// Decode bytes from the encoding declared by the input.
+///
+/// Malformed sequences are replaced with U+FFFD if parsing is lenient.
+pub fn decode_with_context<'a, R: BufRead>(
+    reader: &Reader<R>,
+    bytes: &'a [u8],
+    context: &mut ParseContext,
+) -> Result<Cow<'a, str>, Error> {
+    let encoding = parsed_encoding(reader.encoding());
+    let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+
+    if had_errors {
+        if !context.is_lenient() {
+            return Err(Error::Encoding(encoding.name().to_string()));
+        }
+        context.warn(
+            reader.buffer_position(),
+            format!("invalid {} replaced", encoding.name()),
+        );
+    }
+
+    Ok(text)
+}
+
+/// Resolve the character references in text that has been decoded from the input encoding.
+///
+/// The text must be decoded first, or the characters that the references stand for would be
+/// decoded a second time as if they were in the input encoding.
+pub fn unescape(text: &str, position: usize, context: &mut ParseContext) -> Result<String, Error> {
+    let bytes = if context.html_entities() {
+        context.unescape_html(position, text.as_bytes()).into_owned()
+    } else {
+        BytesText::borrowed(text.as_bytes()).unescaped()?.into_owned()
+    };
+
+    // a reference to a surrogate is resolved to bytes that are not UTF-8
+    String::from_utf8(bytes).map_err(|err| Error::Utf8(err.utf8_error()))
+}
+
+/// Decode and unescape the value of an attribute of the given element.
+pub fn attr_value<R: BufRead>(
+    reader: &Reader<R>,
+    element: &[u8],
+    attr: &Attribute,
+    context: &mut ParseContext,
+) -> Result<String, Error> {
+    let value = match decode_with_context(reader, attr.value, context) {
+        Ok(ref value) if value.contains('&') => {
+            unescape(value, reader.buffer_position(), context)
+        }
+        Ok(value) => Ok(value.into_owned()),
+        Err(err) => Err(err),
+    };
+
+    value.map_err(|err| Error::InvalidAttribute {
+        element: String::from_utf8_lossy(element).into_owned(),
+        attribute: String::from_utf8_lossy(attr.key).into_owned(),
+        error: Box::new(err),
+    })
+}
+
+/// The values of the attributes of an element that a parser looks for.
+pub struct AttrValues {
+    values: Vec<(&'static [u8], Option<String>)>,
+}
+
+impl AttrValues {
+    /// Take the value of the attribute with the given name, if the element has one.
+    pub fn take(&mut self, name: &[u8]) -> Option<String> {
+        self.values
+            .iter_mut()
+            .find(|&&mut (key, _)| key == name)
+            .and_then(|&mut (_, ref mut value)| value.take())
+    }
+}
+
+/// Decode and unescape the values of the attributes of the given element that have one of the
+/// given names.
+///
+/// An attribute matches a name by its local name, whatever its namespace prefix, except for
+/// namespace declarations and `xml:` attributes. An attribute without a prefix takes precedence
+/// over prefixed ones, and otherwise the first of several matching attributes is used. Malformed
+/// attributes are skipped, which `ParseContext::normalize_attributes` reports in lenient mode.
+pub fn attr_values<R: BufRead>(
+    reader: &Reader<R>,
+    element: &[u8],
+    mut atts: Attributes,
+    names: &[&'static [u8]],
+    context: &mut ParseContext,
+) -> Result<AttrValues, Error> {
+    let mut values = names.iter().map(|name| (*name, None)).collect::<Vec<_>>();
+    let mut prefixed = vec![false; names.len()];
+
+    for attr in atts.with_checks(false) {
+        let attr = match attr {
+            Ok(attr) => attr,
+            Err(_) => continue,
+        };
+
+        let (local_name, has_prefix) = match attr.key.iter().position(|b| *b == b':') {
+            Some(_) if attr.key.starts_with(b"xmlns:") || attr.key.starts_with(b"xml:") => {
+                continue;
+            }
+            Some(index) => (&attr.key[index + 1..], true),
+            None => (attr.key, false),
+        };
+
+        let index = match names.iter().position(|name| *name == local_name) {
+            Some(index) => index,
+            None => continue,
+        };
+
+        if values[index].1.is_none() || (prefixed[index] && !has_prefix) {
+            values[index].1 = Some(attr_value(reader, element, &attr, context)?);
+            prefixed[index] = has_prefix;
+        }
+    }
+
+    Ok(AttrValues { values: values })
+}
+
+/// Skip the rest of the element whose start has just been read.
+pub fn skip_element<R: BufRead>(reader: &mut Reader<R>) -> Result<(), Error> {
     let mut buf = Vec::new();
-    let mut skip_buf = Vec::new();
+    let mut depth = 0;
 
     loop {
         match reader.read_event(&mut buf)? {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                if depth == 0 {
+                    return Ok(());
+                }
+                depth -= 1;
+            }
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+}
+
+/// Return the value of the `xml:base` attribute of an element.
+pub fn xml_base<R: BufRead>(
+    reader: &Reader<R>,
+    element: &[u8],
+    mut atts: Attributes,
+    context: &mut ParseContext,
+) -> Result<Option<String>, Error> {
+    for attr in atts.with_checks(false) {
+        if let Ok(attr) = attr {
+            if attr.key == b"xml:base" {
+                return attr_value(reader, element, &attr, context).map(Some);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn element_text<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<String>, Error> {
+    element_text_with_context(reader, &mut ParseContext::default())
+}
+
+pub fn element_text_with_context<R: BufRead>(
+    reader: &mut Reader<R>,
+    context: &mut ParseContext,
+) -> Result<Option<String>, Error> {
+    let mut buf = context.take_buffer();
+    let content = read_element_text(reader, &mut buf, context)?;
+    context.return_buffer(buf);
+    Ok(content)
+}
+
+fn read_element_text<R: BufRead>(
+    reader: &mut Reader<R>,
+    buf: &mut Vec<u8>,
+    context: &mut ParseContext,
+) -> Result<Option<String>, Error> {
+    let mut content: Option<String> = None;
+
+    loop {
+        match reader.read_event(buf)? {
             Event::Start(element) => {
-                reader.read_to_end(element.name(), &mut skip_buf)?;
+                reader.read_to_end(element.name(), &mut Vec::new())?;
             }
             Event::CData(element) => {
-                let text = reader.decode(&*element).into_owned();
-                content = Some(text);
+                let text = decode_with_context(reader, &*element, context)?;
+                push_text(&mut content, &text, context)?;
             }
             Event::Text(element) => {
-                let text = element.unescape_and_decode(reader)?;
-                content = Some(text);
+                let text = decode_with_context(reader, &*element, context)?;
+                // most text has nothing to unescape, so the decoded text is used as it is
+                let unescaped = if text.contains('&') {
+                    let position = reader.buffer_position();
+                    match unescape(&text, position, context) {
+                        Ok(unescaped) => Some(unescaped),
+                        Err(err) => {
+                            if !context.is_lenient() {
+                                return Err(err);
+                            }
+                            context.warn(position, format!("text kept unescaped: {}", err));
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let text = unescaped.as_ref().map_or(&*text, |unescaped| unescaped.as_str());
+                push_text(&mut content, text, context)?;
             }
             Event::End(_) | Event::Eof => break,
             _ => {}
@@ -38,3 +236,17 @@ pub fn element_text<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<String>
 
     Ok(content)
 }
+
+/// Append text to the content of an element, checking that it is not too long.
+fn push_text(
+    content: &mut Option<String>,
+    text: &str,
+    context: &ParseContext,
+) -> Result<(), Error> {
+    match *content {
+        Some(ref mut content) => content.push_str(text),
+        None => *content = Some(text.to_string()),
+    }
+
+    context.check_length(content.as_ref().map_or(0, |content| content.len()))
+}
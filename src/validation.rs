@@ -17,7 +17,9 @@ use mime::FromStrError as MimeParseError;
 use url::ParseError as UrlParseError;
 use url::Url;
 
-use {Category, Channel, Cloud, Enclosure, Image, Item, Source, TextInput};
+use extension::itunes::{ITunesCategory, categories};
+use {Category, Channel, ChannelBuilder, Cloud, Enclosure, EnclosureBuilder, Image, Item,
+     ItemBuilder, Source, TextInput};
 
 #[derive(Debug)]
 /// Errors that occur during validation.
@@ -32,6 +34,67 @@ pub enum ValidationError {
     MimeParsing(MimeParseError),
     /// A different validation error.
     Validation(String),
+    /// An error in a specific element of the channel.
+    Element {
+        /// The path to the element that failed validation, such as `enclosure.length`.
+        element: String,
+        /// The index of the item containing the element, if the element belongs to an item.
+        item: Option<usize>,
+        /// The error for the element.
+        error: Box<ValidationError>,
+    },
+}
+
+impl ValidationError {
+    /// Return the path to the element that failed validation, if known.
+    pub fn element(&self) -> Option<&str> {
+        match *self {
+            ValidationError::Element { ref element, .. } => Some(element.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the index of the item that failed validation, if any.
+    pub fn item(&self) -> Option<usize> {
+        match *self {
+            ValidationError::Element { item, .. } => item,
+            _ => None,
+        }
+    }
+
+    fn in_element(self, name: &str) -> Self {
+        match self {
+            ValidationError::Element {
+                element,
+                item,
+                error,
+            } => ValidationError::Element {
+                element: format!("{}.{}", name, element),
+                item: item,
+                error: error,
+            },
+            error => ValidationError::Element {
+                element: name.to_string(),
+                item: None,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    fn in_item(self, index: usize) -> Self {
+        match self {
+            ValidationError::Element { element, error, .. } => ValidationError::Element {
+                element: element,
+                item: Some(index),
+                error: error,
+            },
+            error => ValidationError::Element {
+                element: "item".to_string(),
+                item: Some(index),
+                error: Box::new(error),
+            },
+        }
+    }
 }
 
 impl StdError for ValidationError {
@@ -42,6 +105,7 @@ impl StdError for ValidationError {
             ValidationError::UrlParsing(ref err) => err.description(),
             ValidationError::MimeParsing(_) => "Unable to parse MIME type",
             ValidationError::Validation(ref s) => s.as_str(),
+            ValidationError::Element { .. } => "an element failed validation",
         }
     }
 
@@ -50,6 +114,7 @@ impl StdError for ValidationError {
             ValidationError::DateParsing(ref err) => Some(err),
             ValidationError::IntParsing(ref err) => Some(err),
             ValidationError::UrlParsing(ref err) => Some(err),
+            ValidationError::Element { ref error, .. } => Some(&**error),
             _ => None,
         }
     }
@@ -63,6 +128,16 @@ impl fmt::Display for ValidationError {
             ValidationError::UrlParsing(ref err) => err.fmt(f),
             ValidationError::MimeParsing(_) => write!(f, "Unable to parse MIME type"),
             ValidationError::Validation(ref s) => write!(f, "{}", s),
+            ValidationError::Element {
+                ref element,
+                item: Some(item),
+                ref error,
+            } => write!(f, "item {} {}: {}", item, element, error),
+            ValidationError::Element {
+                ref element,
+                item: None,
+                ref error,
+            } => write!(f, "{}: {}", element, error),
         }
     }
 }
@@ -91,58 +166,212 @@ impl From<MimeParseError> for ValidationError {
     }
 }
 
+#[derive(Debug)]
+/// Errors that occur when building a value with `validated_build`.
+pub enum BuilderError {
+    /// A field required by the RSS specification was not set. This contains the name of the
+    /// field's element.
+    MissingField(&'static str),
+    /// A field was set to a value that failed validation.
+    InvalidValue {
+        /// The path to the element of the field, such as `enclosure.length`.
+        field: String,
+        /// The validation error for the value.
+        error: ValidationError,
+    },
+}
+
+impl BuilderError {
+    /// Return the name of the field that caused the error.
+    pub fn field(&self) -> &str {
+        match *self {
+            BuilderError::MissingField(field) => field,
+            BuilderError::InvalidValue { ref field, .. } => field.as_str(),
+        }
+    }
+}
+
+impl StdError for BuilderError {
+    fn description(&self) -> &str {
+        match *self {
+            BuilderError::MissingField(_) => "a required field is missing",
+            BuilderError::InvalidValue { .. } => "a field has an invalid value",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            BuilderError::InvalidValue { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BuilderError::MissingField(field) => write!(f, "missing required field: {}", field),
+            BuilderError::InvalidValue { ref error, .. } => write!(f, "invalid value: {}", error),
+        }
+    }
+}
+
+impl From<ValidationError> for BuilderError {
+    fn from(err: ValidationError) -> Self {
+        BuilderError::InvalidValue {
+            field: err.element().unwrap_or_default().to_string(),
+            error: err,
+        }
+    }
+}
+
+impl From<BuilderError> for ValidationError {
+    fn from(err: BuilderError) -> Self {
+        match err {
+            BuilderError::MissingField(field) => {
+                ValidationError::Validation(err.to_string()).in_element(field)
+            }
+            BuilderError::InvalidValue { error, .. } => error,
+        }
+    }
+}
+
+/// Return an error for the first of the required fields that is empty.
+fn require(fields: &[(&'static str, &str)]) -> Result<(), BuilderError> {
+    match fields.iter().find(|&&(_, value)| value.is_empty()) {
+        Some(&(name, _)) => Err(BuilderError::MissingField(name)),
+        None => Ok(()),
+    }
+}
+
 /// A trait to support data validation.
 pub trait Validate {
     /// Validate the data against the RSS specification.
     fn validate(&self) -> Result<(), ValidationError>;
+
+    /// Validate the data against the RSS specification, returning every error that was found
+    /// instead of only the first one.
+    fn validate_all(&self) -> Result<(), Vec<ValidationError>> {
+        self.validate().map_err(|err| vec![err])
+    }
+}
+
+trait ElementContext<T> {
+    /// Record the name of the element that produced an error.
+    fn element(self, name: &str) -> Result<T, ValidationError>;
+}
+
+impl<T, E: Into<ValidationError>> ElementContext<T> for Result<T, E> {
+    fn element(self, name: &str) -> Result<T, ValidationError> {
+        self.map_err(|err| err.into().in_element(name))
+    }
 }
 
 macro_rules! validate {
     ($e:expr, $msg:expr) => ({
-        if !($e) {
-            return Err(ValidationError::Validation(String::from($msg)));
+        if $e {
+            Ok(())
+        } else {
+            Err(ValidationError::Validation(String::from($msg)))
         }
     })
 }
 
+/// Collects the errors found while validating.
+#[derive(Default)]
+struct Errors(Vec<ValidationError>);
+
+impl Errors {
+    /// Record the error of a check, if any. Returns the value of a successful check.
+    fn check<T>(&mut self, result: Result<T, ValidationError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.0.push(err);
+                None
+            }
+        }
+    }
+
+    fn into_result(self) -> Result<(), Vec<ValidationError>> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(self.0)
+        }
+    }
+}
+
+fn first_error(result: Result<(), Vec<ValidationError>>) -> Result<(), ValidationError> {
+    result.map_err(|errors| errors.into_iter().next().unwrap())
+}
+
 impl Validate for Channel {
     fn validate(&self) -> Result<(), ValidationError> {
-        Url::parse(self.link())?;
+        first_error(self.validate_all())
+    }
+
+    fn validate_all(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Errors::default();
+
+        errors.check(
+            validate!(!self.title().is_empty(), "Channel title is empty").element("title"),
+        );
+
+        if self.link().is_empty() {
+            errors.check(validate!(false, "Channel link is empty").element("link"));
+        } else {
+            errors.check(Url::parse(self.link()).element("link"));
+        }
+
+        errors.check(
+            validate!(
+                !self.description().is_empty(),
+                "Channel description is empty"
+            ).element("description"),
+        );
 
         for category in self.categories() {
-            category.validate()?;
+            errors.check(category.validate().element("category"));
         }
 
         if let Some(cloud) = self.cloud() {
-            cloud.validate()?;
+            errors.check(cloud.validate().element("cloud"));
         }
 
         if let Some(docs) = self.docs() {
-            Url::parse(docs)?;
+            errors.check(Url::parse(docs).element("docs"));
         }
 
         if let Some(image) = self.image() {
-            image.validate()?;
+            errors.check(image.validate().element("image"));
         }
 
-        for item in self.items() {
-            item.validate()?;
+        for (index, item) in self.items().iter().enumerate() {
+            if let Err(item_errors) = item.validate_all() {
+                errors
+                    .0
+                    .extend(item_errors.into_iter().map(|err| err.in_item(index)));
+            }
         }
 
         if let Some(last_build_date) = self.last_build_date() {
-            DateTime::parse_from_rfc2822(last_build_date)?;
+            errors.check(DateTime::parse_from_rfc2822(last_build_date).element("lastBuildDate"));
         }
 
         if let Some(pub_date) = self.pub_date() {
-            DateTime::parse_from_rfc2822(pub_date)?;
+            errors.check(DateTime::parse_from_rfc2822(pub_date).element("pubDate"));
         }
 
         for hour in self.skip_hours() {
-            let hour = hour.parse::<i64>()?;
-            validate!(
-                hour >= 0 && hour <= 23,
-                "Channel skip hour is not between 0 and 23"
-            );
+            if let Some(hour) = errors.check(hour.parse::<i64>().element("skipHours")) {
+                errors.check(
+                    validate!(
+                        hour >= 0 && hour <= 23,
+                        "Channel skip hour is not between 0 and 23"
+                    ).element("skipHours"),
+                );
+            }
         }
 
         let valid_days = {
@@ -158,29 +387,34 @@ impl Validate for Channel {
         };
 
         for day in self.skip_days() {
-            validate!(
-                valid_days.contains(day.as_str()),
-                format!("Unknown skip day: {}", day)
+            errors.check(
+                validate!(
+                    valid_days.contains(day.as_str()),
+                    format!("Unknown skip day: {}", day)
+                ).element("skipDays"),
             );
         }
 
         if let Some(text_input) = self.text_input() {
-            text_input.validate()?;
+            errors.check(text_input.validate().element("textInput"));
         }
 
         if let Some(ttl) = self.ttl() {
-            let ttl = ttl.parse::<i64>()?;
-            validate!(ttl > 0, "Channel TTL is not greather than 0");
+            if let Some(ttl) = errors.check(ttl.parse::<i64>().element("ttl")) {
+                errors.check(
+                    validate!(ttl > 0, "Channel TTL is not greather than 0").element("ttl"),
+                );
+            }
         }
 
-        Ok(())
+        errors.into_result()
     }
 }
 
 impl Validate for Category {
     fn validate(&self) -> Result<(), ValidationError> {
         if let Some(domain) = self.domain() {
-            Url::parse(domain)?;
+            Url::parse(domain).element("domain")?;
         }
         Ok(())
     }
@@ -188,54 +422,60 @@ impl Validate for Category {
 
 impl Validate for Cloud {
     fn validate(&self) -> Result<(), ValidationError> {
-        let port = self.port().parse::<i64>()?;
-        validate!(port > 0, "Cloud port must be greather than 0");
-        Url::parse(self.domain())?;
+        let port = self.port().parse::<i64>().element("port")?;
+        validate!(port > 0, "Cloud port must be greather than 0").element("port")?;
+        Url::parse(self.domain()).element("domain")?;
         validate!(
             self.protocol() == "xml-rpc" || self.protocol() == "soap" ||
                 self.protocol() == "http-post",
             format!("Unknown cloud protocol: {}", self.protocol())
-        );
+        ).element("protocol")?;
         Ok(())
     }
 }
 
 impl Validate for Enclosure {
     fn validate(&self) -> Result<(), ValidationError> {
-        Url::parse(self.url())?;
-        self.mime_type().parse::<Mime>()?;
-        let length = self.length().parse::<i64>()?;
-        validate!(length > 0, "Enclosure length is not greather than 0");
+        validate!(!self.url().is_empty(), "Enclosure url is empty").element("url")?;
+        Url::parse(self.url()).element("url")?;
+        validate!(!self.mime_type().is_empty(), "Enclosure type is empty").element("type")?;
+        self.mime_type().parse::<Mime>().element("type")?;
+        let length = self.length().parse::<i64>().element("length")?;
+        validate!(length >= 0, "Enclosure length is negative").element("length")?;
         Ok(())
     }
 }
 
 impl Validate for TextInput {
     fn validate(&self) -> Result<(), ValidationError> {
-        Url::parse(self.link())?;
+        validate!(!self.title().is_empty(), "TextInput title is empty").element("title")?;
+        validate!(!self.description().is_empty(), "TextInput description is empty")
+            .element("description")?;
+        validate!(!self.name().is_empty(), "TextInput name is empty").element("name")?;
+        Url::parse(self.link()).element("link")?;
         Ok(())
     }
 }
 
 impl Validate for Image {
     fn validate(&self) -> Result<(), ValidationError> {
-        Url::parse(self.link())?;
-        Url::parse(self.url())?;
+        Url::parse(self.link()).element("link")?;
+        Url::parse(self.url()).element("url")?;
 
         if let Some(width) = self.width() {
-            let width = width.parse::<i64>()?;
+            let width = width.parse::<i64>().element("width")?;
             validate!(
                 width >= 0 && width <= 144,
                 "Image width is not between 0 and 144"
-            );
+            ).element("width")?;
         }
 
         if let Some(height) = self.height() {
-            let height = height.parse::<i64>()?;
+            let height = height.parse::<i64>().element("height")?;
             validate!(
-                height >= 0 && height <= 144,
-                "Image height is not between 0 and 144"
-            );
+                height >= 0 && height <= 400,
+                "Image height is not between 0 and 400"
+            ).element("height")?;
         }
 
         Ok(())
@@ -244,33 +484,285 @@ impl Validate for Image {
 
 impl Validate for Item {
     fn validate(&self) -> Result<(), ValidationError> {
+        first_error(self.validate_all())
+    }
+
+    fn validate_all(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Errors::default();
+
+        errors.check(validate!(
+            self.title().is_some() || self.description().is_some(),
+            "Item has neither a title nor a description"
+        ));
+
         if let Some(link) = self.link() {
-            Url::parse(link)?;
+            errors.check(Url::parse(link).element("link"));
         }
 
         if let Some(comments) = self.comments() {
-            Url::parse(comments)?;
+            errors.check(Url::parse(comments).element("comments"));
         }
 
         if let Some(enclosure) = self.enclosure() {
-            enclosure.validate()?;
+            errors.check(enclosure.validate().element("enclosure"));
+        }
+
+        if let Some(guid) = self.guid() {
+            if guid.is_permalink() {
+                errors.check(Url::parse(guid.value()).element("guid"));
+            }
         }
 
         if let Some(pub_date) = self.pub_date() {
-            DateTime::parse_from_rfc2822(pub_date)?;
+            errors.check(DateTime::parse_from_rfc2822(pub_date).element("pubDate"));
         }
 
         if let Some(source) = self.source() {
-            source.validate()?;
+            errors.check(source.validate().element("source"));
         }
 
-        Ok(())
+        errors.into_result()
     }
 }
 
 impl Validate for Source {
     fn validate(&self) -> Result<(), ValidationError> {
-        Url::parse(self.url())?;
+        Url::parse(self.url()).element("url")?;
         Ok(())
     }
 }
+
+impl ITunesCategory {
+    /// Validate this category against the category taxonomy of Apple Podcasts.
+    ///
+    /// The text must be an Apple category and the subcategory, if any, must be one of its
+    /// subcategories. See the `categories` module for the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::extension::itunes::ITunesCategory;
+    ///
+    /// let mut category = ITunesCategory::default();
+    /// category.set_text("Arts");
+    /// assert!(category.validate_against_apple_list().is_ok());
+    ///
+    /// let mut subcategory = ITunesCategory::default();
+    /// subcategory.set_text("Gadgets");
+    /// category.set_subcategory(Box::new(subcategory));
+    /// assert!(category.validate_against_apple_list().is_err());
+    /// ```
+    pub fn validate_against_apple_list(&self) -> Result<(), ValidationError> {
+        validate!(
+            categories::subcategories(self.text()).is_some(),
+            format!("Unknown iTunes category: {}", self.text())
+        )?;
+
+        if let Some(subcategory) = self.subcategory() {
+            validate!(
+                categories::is_subcategory(self.text(), subcategory.text()),
+                format!(
+                    "Unknown iTunes subcategory of {}: {}",
+                    self.text(),
+                    subcategory.text()
+                )
+            ).element("category")?;
+            validate!(
+                subcategory.subcategory().is_none(),
+                "iTunes categories have at most one level of subcategories"
+            ).element("category")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ChannelBuilder {
+    /// Build a `Channel`, validating it against the RSS specification.
+    ///
+    /// Use `build` to construct a channel that is intentionally incomplete.
+    ///
+    /// This checks the same things as `validated_build`, but a missing required field is
+    /// reported as a `ValidationError` for its element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ChannelBuilder;
+    ///
+    /// let result = ChannelBuilder::default()
+    ///     .title("Title")
+    ///     .link("http://example.com/")
+    ///     .description("Description")
+    ///     .build_validated();
+    /// assert!(result.is_ok());
+    ///
+    /// let err = ChannelBuilder::default().title("Title").build_validated().unwrap_err();
+    /// assert_eq!(err.element(), Some("link"));
+    /// ```
+    pub fn build_validated(&self) -> Result<Channel, ValidationError> {
+        self.validated_build().map_err(ValidationError::from)
+    }
+
+    /// Build a `Channel`, distinguishing required fields that are missing from fields with
+    /// invalid values.
+    ///
+    /// The title, link and description are required. The other fields, including the items,
+    /// are checked with `Validate::validate`; the index of an invalid item is available from
+    /// the `ValidationError`. Use `build_validated` to get every error as a `ValidationError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ChannelBuilder;
+    /// use rss::validation::BuilderError;
+    ///
+    /// let err = ChannelBuilder::default().title("Title").validated_build().unwrap_err();
+    /// match err {
+    ///     BuilderError::MissingField(field) => assert_eq!(field, "link"),
+    ///     _ => panic!("expected a missing field"),
+    /// }
+    ///
+    /// let err = ChannelBuilder::default()
+    ///     .title("Title")
+    ///     .link("not a url")
+    ///     .description("Description")
+    ///     .validated_build()
+    ///     .unwrap_err();
+    /// assert_eq!(err.field(), "link");
+    /// ```
+    pub fn validated_build(&self) -> Result<Channel, BuilderError> {
+        let channel = self.build().map_err(ValidationError::Validation)?;
+        require(&[
+            ("title", channel.title()),
+            ("link", channel.link()),
+            ("description", channel.description()),
+        ])?;
+        channel.validate()?;
+        Ok(channel)
+    }
+}
+
+impl ItemBuilder {
+    /// Build an `Item`, validating it against the RSS specification.
+    ///
+    /// Use `build` to construct an item that is intentionally incomplete.
+    ///
+    /// This is `validated_build` with a missing title and description reported as a
+    /// `ValidationError` for the `title` element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ItemBuilder;
+    ///
+    /// let result = ItemBuilder::default().title("Title".to_string()).build_validated();
+    /// assert!(result.is_ok());
+    ///
+    /// let result = ItemBuilder::default().build_validated();
+    /// assert!(result.is_err());
+    /// ```
+    pub fn build_validated(&self) -> Result<Item, ValidationError> {
+        self.validated_build().map_err(ValidationError::from)
+    }
+
+    /// Build an `Item`, distinguishing required fields that are missing from fields with
+    /// invalid values.
+    ///
+    /// An item requires a title or a description; if neither is set the missing field is
+    /// reported as `title`. `build_validated` makes the same checks but returns a
+    /// `ValidationError`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::ItemBuilder;
+    ///
+    /// let err = ItemBuilder::default()
+    ///     .title("Title".to_string())
+    ///     .link("/relative".to_string())
+    ///     .validated_build()
+    ///     .unwrap_err();
+    /// assert_eq!(err.field(), "link");
+    /// ```
+    pub fn validated_build(&self) -> Result<Item, BuilderError> {
+        let item = self.build().map_err(ValidationError::Validation)?;
+        if item.title().is_none() && item.description().is_none() {
+            return Err(BuilderError::MissingField("title"));
+        }
+        item.validate()?;
+        Ok(item)
+    }
+}
+
+impl EnclosureBuilder {
+    /// Build an `Enclosure`, validating it against the RSS specification.
+    ///
+    /// Use `build` to construct an enclosure that is intentionally incomplete.
+    ///
+    /// Unlike `validated_build`, an empty URL, type or length is a `ValidationError` for that
+    /// element rather than a `BuilderError::MissingField`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::EnclosureBuilder;
+    ///
+    /// let result = EnclosureBuilder::default()
+    ///     .url("http://example.com/audio.mp3")
+    ///     .length("1024")
+    ///     .mime_type("audio/mpeg")
+    ///     .build_validated();
+    /// assert!(result.is_ok());
+    ///
+    /// let err = EnclosureBuilder::default()
+    ///     .url("http://example.com/audio.mp3")
+    ///     .build_validated()
+    ///     .unwrap_err();
+    /// assert_eq!(err.element(), Some("type"));
+    /// ```
+    pub fn build_validated(&self) -> Result<Enclosure, ValidationError> {
+        self.validated_build().map_err(ValidationError::from)
+    }
+
+    /// Build an `Enclosure`, distinguishing required fields that are missing from fields with
+    /// invalid values.
+    ///
+    /// The URL, type and length are required, and a missing one is reported as
+    /// `BuilderError::MissingField` instead of the `ValidationError` of `build_validated`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::EnclosureBuilder;
+    /// use rss::validation::BuilderError;
+    ///
+    /// let err = EnclosureBuilder::default()
+    ///     .url("http://example.com/audio.mp3")
+    ///     .length("1024")
+    ///     .validated_build()
+    ///     .unwrap_err();
+    /// match err {
+    ///     BuilderError::MissingField(field) => assert_eq!(field, "type"),
+    ///     _ => panic!("expected a missing field"),
+    /// }
+    ///
+    /// let err = EnclosureBuilder::default()
+    ///     .url("http://example.com/audio.mp3")
+    ///     .length("large")
+    ///     .mime_type("audio/mpeg")
+    ///     .validated_build()
+    ///     .unwrap_err();
+    /// assert_eq!(err.field(), "length");
+    /// ```
+    pub fn validated_build(&self) -> Result<Enclosure, BuilderError> {
+        let enclosure = self.build().map_err(ValidationError::Validation)?;
+        require(&[
+            ("url", enclosure.url()),
+            ("type", enclosure.mime_type()),
+            ("length", enclosure.length()),
+        ])?;
+        enclosure.validate()?;
+        Ok(enclosure)
+    }
+}
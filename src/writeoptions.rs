@@ -0,0 +1,228 @@
+// This file is part of rss.
+//
+// Copyright © 2015-2017 The rust-syndication Developers
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the MIT License and/or Apache 2.0 License.
+
+/// Options that control how a channel is written as XML.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(setter(into), default)]
+pub struct WriteOptions {
+    /// The character and the number of times it is repeated for each level of indentation.
+    indent: Option<(u8, usize)>,
+    /// The qualified names of the item fields whose text is written as CDATA sections.
+    cdata_fields: Vec<String>,
+    /// The version of RSS that the channel is written as.
+    version: Version,
+    /// What is done with characters that XML does not allow.
+    invalid_char_policy: InvalidCharPolicy,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            indent: None,
+            cdata_fields: Vec::new(),
+            version: Version::Rss2_0,
+            invalid_char_policy: InvalidCharPolicy::Strip,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Return the indentation used when writing.
+    ///
+    /// A value of `None` writes the channel without any whitespace between elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::WriteOptions;
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_indent((b' ', 2));
+    /// assert_eq!(options.indent(), Some((b' ', 2)));
+    /// ```
+    pub fn indent(&self) -> Option<(u8, usize)> {
+        self.indent
+    }
+
+    /// Set the indentation used when writing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::WriteOptions;
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_indent((b'\t', 1));
+    /// ```
+    pub fn set_indent<V>(&mut self, indent: V)
+    where
+        V: Into<Option<(u8, usize)>>,
+    {
+        self.indent = indent.into();
+    }
+
+    /// Return the qualified names of the item fields whose text is written as CDATA sections
+    /// instead of escaped text.
+    ///
+    /// The names only match elements directly inside an `item`, so `description` is the
+    /// description of each item and not that of the channel, its image or its text input. The
+    /// `content:encoded` element of an item is always written as CDATA. Text that contains `]]>`
+    /// is split across several CDATA sections. By default there are no such fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::WriteOptions;
+    ///
+    /// let options = WriteOptions::default();
+    /// assert!(options.cdata_fields().is_empty());
+    /// ```
+    pub fn cdata_fields(&self) -> &[String] {
+        &self.cdata_fields
+    }
+
+    /// Set the qualified names of the item fields whose text is written as CDATA sections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::WriteOptions;
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_cdata_fields(vec!["description".to_string(), "itunes:summary".to_string()]);
+    /// ```
+    pub fn set_cdata_fields<V>(&mut self, cdata_fields: V)
+    where
+        V: Into<Vec<String>>,
+    {
+        self.cdata_fields = cdata_fields.into();
+    }
+
+    /// Return the version of RSS that the channel is written as.
+    ///
+    /// The default is `Version::Rss2_0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Version, WriteOptions};
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_version(Version::Rss0_92);
+    /// assert_eq!(options.version(), Version::Rss0_92);
+    /// ```
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Set the version of RSS that the channel is written as.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{Version, WriteOptions};
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_version(Version::Rss0_92);
+    /// ```
+    pub fn set_version(&mut self, version: Version) {
+        self.version = version;
+    }
+
+    /// Return what is done with the control characters that XML does not allow, which are
+    /// those below U+0020 other than tab, line feed and carriage return.
+    ///
+    /// The default is `InvalidCharPolicy::Strip`, the only policy that always writes
+    /// well-formed XML 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{InvalidCharPolicy, WriteOptions};
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_invalid_char_policy(InvalidCharPolicy::Error);
+    /// assert_eq!(options.invalid_char_policy(), InvalidCharPolicy::Error);
+    /// ```
+    pub fn invalid_char_policy(&self) -> InvalidCharPolicy {
+        self.invalid_char_policy
+    }
+
+    /// Set what is done with the control characters that XML does not allow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::{InvalidCharPolicy, WriteOptions};
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.set_invalid_char_policy(InvalidCharPolicy::Reference);
+    /// ```
+    pub fn set_invalid_char_policy(&mut self, invalid_char_policy: InvalidCharPolicy) {
+        self.invalid_char_policy = invalid_char_policy;
+    }
+}
+
+/// A version of RSS that a channel can be written as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// RSS 2.0, which can represent every part of a channel.
+    Rss2_0,
+    /// RSS 0.92, which does not have the elements introduced by RSS 2.0 or any extensions.
+    ///
+    /// The channel's categories, generator, TTL, extensions and unknown elements are left out,
+    /// as are the authors, comments, GUIDs, publication dates, content, extensions and unknown
+    /// elements of its items and the domains of their categories. `xml:base` attributes are
+    /// also left out.
+    Rss0_92,
+}
+
+impl Version {
+    /// Return the value of the `version` attribute for this version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rss::Version;
+    ///
+    /// assert_eq!(Version::Rss0_92.as_str(), "0.92");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Version::Rss2_0 => "2.0",
+            Version::Rss0_92 => "0.92",
+        }
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Version::Rss2_0
+    }
+}
+
+/// What is done with the control characters that XML does not allow when a channel is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvalidCharPolicy {
+    /// The characters are left out. This is the only policy that always writes well-formed
+    /// XML 1.0.
+    Strip,
+    /// The characters are written as numeric character references such as `&#x8;`.
+    ///
+    /// The output is not well-formed XML 1.0, which does not allow references to these
+    /// characters either. Only XML 1.1 parsers accept them, and a reference in a CDATA section
+    /// is read as literal text.
+    Reference,
+    /// Writing fails with `Error::InvalidCharacter`.
+    Error,
+}
+
+impl Default for InvalidCharPolicy {
+    fn default() -> Self {
+        InvalidCharPolicy::Strip
+    }
+}
@@ -0,0 +1,366 @@
+#![cfg(feature = "async")]
+
+extern crate futures_io;
+extern crate quick_xml;
+extern crate rss;
+
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use futures_io::{AsyncBufRead, AsyncRead};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use rss::{Channel, Error, LimitKind, ParseOptionsBuilder};
+
+/// A reader that returns at most `chunk` bytes at a time and is pending before every read.
+struct ThrottledReader<'a> {
+    input: &'a [u8],
+    chunk: usize,
+    ready: bool,
+    polls: usize,
+}
+
+impl<'a> ThrottledReader<'a> {
+    fn new(input: &'a [u8], chunk: usize) -> Self {
+        ThrottledReader {
+            input: input,
+            chunk: chunk,
+            ready: false,
+            polls: 0,
+        }
+    }
+}
+
+impl<'a> AsyncRead for ThrottledReader<'a> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let len = {
+            let chunk = match self.as_mut().poll_fill_buf(cx) {
+                Poll::Ready(Ok(chunk)) => chunk,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+            let len = chunk.len().min(buf.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            len
+        };
+        self.consume(len);
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl<'a> AsyncBufRead for ThrottledReader<'a> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        this.polls += 1;
+
+        if !this.ready {
+            this.ready = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let len = this.chunk.min(this.input.len());
+        Poll::Ready(Ok(&this.input[..len]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.input = &this.input[amt..];
+        this.ready = false;
+    }
+}
+
+/// A reader that returns its input and is then pending forever instead of reaching the end.
+struct UnfinishedReader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> AsyncRead for UnfinishedReader<'a> {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context, _: &mut [u8]) -> Poll<io::Result<usize>> {
+        unimplemented!()
+    }
+}
+
+impl<'a> AsyncBufRead for UnfinishedReader<'a> {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        if this.input.is_empty() {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(&this.input[..this.input.len().min(100)]))
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.input = &this.input[amt..];
+    }
+}
+
+/// A reader that always fails.
+struct FailingReader;
+
+impl AsyncRead for FailingReader {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context, _: &mut [u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, "failed")))
+    }
+}
+
+impl AsyncBufRead for FailingReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<&[u8]>> {
+        Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, "failed")))
+    }
+
+    fn consume(self: Pin<&mut Self>, _: usize) {}
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+/// Poll a future at most `limit` times, returning its output if it completed.
+fn poll_at_most<F: Future + Unpin>(mut future: F, limit: usize) -> Option<F::Output> {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    for _ in 0..limit {
+        if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+            return Some(output);
+        }
+    }
+    None
+}
+
+/// Poll a future to completion, returning its output and the number of times it was pending.
+fn block_on<F: Future + Unpin>(mut future: F) -> (F::Output, usize) {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut pending = 0;
+
+    loop {
+        match Pin::new(&mut future).poll(&mut cx) {
+            Poll::Ready(output) => return (output, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+/// Return whether an element named `name` inside `parents` is an item.
+fn is_item(parents: &[Vec<u8>], name: &[u8]) -> bool {
+    name == b"item" && match parents.len() {
+        1 => true,
+        2 => parents[1] == b"channel",
+        _ => false,
+    }
+}
+
+/// Return the positions where quick-xml finds the end of an item or of the root element, or
+/// `None` if it cannot read the input.
+fn element_ends(input: &[u8]) -> Option<Vec<usize>> {
+    let mut reader = Reader::from_reader(input);
+    let mut buf = Vec::new();
+    let mut parents = Vec::new();
+    let mut ends = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(ref element)) => parents.push(element.name().to_vec()),
+            Ok(Event::Empty(ref element)) => {
+                if parents.is_empty() || is_item(&parents, element.name()) {
+                    ends.push(reader.buffer_position());
+                }
+            }
+            Ok(Event::End(_)) => {
+                let name = parents.pop().unwrap_or_default();
+                if parents.is_empty() || is_item(&parents, &name) {
+                    ends.push(reader.buffer_position());
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+
+        if parents.is_empty() && !ends.is_empty() {
+            return Some(ends);
+        }
+        buf.clear();
+    }
+}
+
+/// Check that the input is parsed up to each end that quick-xml finds and no further.
+fn assert_parsed_up_to_ends(input: &[u8], name: &str) {
+    let expected = match Channel::read_from(input) {
+        Ok(channel) => channel,
+        Err(_) => return,
+    };
+    let ends = match element_ends(input) {
+        Some(ends) => ends,
+        None => return,
+    };
+    let root_end = *ends.last().unwrap();
+
+    for &end in &ends {
+        for &cut in &[end - 1, end] {
+            let reader = UnfinishedReader { input: &input[..cut] };
+            match poll_at_most(Channel::read_from_async(reader), cut + 1) {
+                Some(Ok(ref channel)) if cut == root_end => {
+                    assert_eq!(channel, &expected, "{} cut at {}", name, cut)
+                }
+                None if cut != root_end => {}
+                result => panic!("{} cut at {}: {:?}", name, cut, result),
+            }
+        }
+    }
+}
+
+#[test]
+fn read_from_async() {
+    let input = include_str!("data/rss2sample.xml");
+    let mut reader = ThrottledReader::new(input.as_bytes(), 64);
+
+    let (channel, pending) = block_on(Channel::read_from_async(&mut reader));
+    let channel = channel.expect("failed to parse xml");
+
+    assert_eq!(channel, Channel::read_from(input.as_bytes()).unwrap());
+    assert!(pending >= input.len() / 64);
+    assert!(reader.polls > pending);
+}
+
+#[test]
+fn read_from_async_invalid() {
+    let reader = ThrottledReader::new(b"<feed></feed>", 4);
+    match block_on(Channel::read_from_async(reader)).0 {
+        Err(Error::UnexpectedRoot { ref found }) if found == "feed" => {}
+        result => panic!("expected an unexpected root error, got {:?}", result),
+    }
+}
+
+#[test]
+fn read_from_async_io_error() {
+    match block_on(Channel::read_from_async(FailingReader)).0 {
+        Err(Error::Io(_)) => {}
+        result => panic!("expected an io error, got {:?}", result),
+    }
+}
+
+#[test]
+fn read_from_async_fixtures() {
+    for entry in fs::read_dir("tests/data").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(true, |extension| extension != "xml") {
+            continue;
+        }
+
+        let input = fs::read(&path).unwrap();
+        let expected = Channel::read_from(&input[..]);
+        for &chunk in &[1, 7, 4096] {
+            let reader = ThrottledReader::new(&input, chunk);
+            let (channel, _) = block_on(Channel::read_from_async(reader));
+            match (&channel, &expected) {
+                (&Ok(ref channel), &Ok(ref expected)) => {
+                    assert_eq!(channel, expected, "{:?} in chunks of {}", path, chunk)
+                }
+                (&Err(ref err), &Err(ref expected)) => assert_eq!(
+                    err.to_string(),
+                    expected.to_string(),
+                    "{:?} in chunks of {}",
+                    path,
+                    chunk
+                ),
+                _ => panic!("{:?} in chunks of {}: {:?}", path, chunk, channel),
+            }
+        }
+    }
+}
+
+#[test]
+fn read_from_async_element_ends() {
+    for entry in fs::read_dir("tests/data").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(true, |extension| extension != "xml") {
+            continue;
+        }
+
+        let input = fs::read(&path).unwrap();
+        assert_parsed_up_to_ends(&input, &path.display().to_string());
+    }
+
+    let input = "<!DOCTYPE rss [<!ENTITY title \"<item>\">]>\
+                 <rss version=\"2.0\"><extra><item></item></extra><channel><title>Title</title>\
+                 <item title=\"</item>\"><description><![CDATA[</item>]]></description></item>\
+                 <item><!-- <item> --></item><item/><image><item></item></image>\
+                 </channel></rss>";
+    assert_parsed_up_to_ends(input.as_bytes(), "markup");
+}
+
+#[test]
+fn read_from_async_with_options() {
+    let input = include_str!("data/malformed_items.xml");
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .html_entities(false)
+        .build()
+        .unwrap();
+    let reader = ThrottledReader::new(input.as_bytes(), 64);
+    let future = Channel::read_from_async_with_options(reader, &options);
+    let (channel, warnings) = block_on(future).0.expect("failed to parse xml");
+    let expected = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(channel, expected.0);
+    assert_eq!(channel.items().len(), 4);
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings, expected.1);
+
+    let input = include_str!("data/rss2sample.xml");
+    let options = ParseOptionsBuilder::default().max_items(2usize).build().unwrap();
+    let reader = ThrottledReader::new(input.as_bytes(), 64);
+    let err = block_on(Channel::read_from_async_with_options(reader, &options))
+        .0
+        .unwrap_err();
+    match *err.kind() {
+        Error::LimitExceeded { kind: LimitKind::Items, limit: 2 } => {}
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn read_from_async_incremental() {
+    let mut input = String::from("<rss version=\"2.0\"><channel><title>Title</title>");
+    for i in 0..1000 {
+        input.push_str(&format!(
+            "<item><title>Item {}</title><description><![CDATA[<p>a > b</p>]]></description>\
+             <!-- </item> --></item>",
+            i
+        ));
+    }
+    input.push_str("</channel></rss>");
+
+    // the channel is complete once the root element has ended, before the end of the input
+    let reader = UnfinishedReader { input: input.as_bytes() };
+    let channel = poll_at_most(Channel::read_from_async(reader), input.len())
+        .expect("the channel was not read before the end of the input")
+        .expect("failed to parse xml");
+    assert_eq!(channel, Channel::read_from(input.as_bytes()).unwrap());
+    assert_eq!(channel.items().len(), 1000);
+
+    // an item that fails to parse is reported as soon as it has been received
+    let end = input.find("</channel>").unwrap();
+    let input = input.replace("<title>Item 1</title>", "<title>Item 1</titel>");
+    let reader = UnfinishedReader { input: input[..end].as_bytes() };
+    match poll_at_most(Channel::read_from_async(reader), input.len()) {
+        Some(Err(Error::Element { .. })) => {}
+        result => panic!("expected an element error, got {:?}", result),
+    }
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "atom")]
+
+extern crate atom_syndication;
+extern crate rss;
+
+use std::convert::TryFrom;
+
+use rss::{Channel, Error, Item};
+
+#[test]
+fn to_atom() {
+    let input = include_str!("data/rss2sample.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let feed = channel.to_atom();
+
+    assert_eq!(feed.title(), "Liftoff News");
+    assert_eq!(feed.id(), "http://liftoff.msfc.nasa.gov/");
+    assert_eq!(feed.subtitle(), Some("Liftoff to Space Exploration."));
+    assert_eq!(feed.updated(), "2003-06-10T09:41:01+00:00");
+    assert_eq!(feed.links().len(), 1);
+    assert_eq!(feed.links()[0].rel(), "alternate");
+    assert_eq!(feed.links()[0].href(), "http://liftoff.msfc.nasa.gov/");
+    assert_eq!(feed.generator().map(|g| g.value()), Some("Weblog Editor 2.0"));
+    assert_eq!(feed.authors()[0].name(), "editor@example.com");
+    assert_eq!(feed.entries().len(), channel.items().len());
+
+    let entry = &feed.entries()[0];
+    let item = &channel.items()[0];
+    assert_eq!(entry.title(), item.title().unwrap());
+    assert_eq!(entry.id(), item.guid().unwrap().value());
+    assert_eq!(entry.summary(), item.description());
+    assert_eq!(entry.published(), Some("2003-06-03T09:39:21+00:00"));
+    assert_eq!(entry.updated(), "2003-06-03T09:39:21+00:00");
+    assert_eq!(entry.links()[0].href(), item.link().unwrap());
+}
+
+#[test]
+fn to_atom_enclosure() {
+    let input = include_str!("data/enclosure.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let entry = channel.items()[0].to_atom();
+    let enclosure = channel.items()[0].enclosure().unwrap();
+
+    let link = entry
+        .links()
+        .iter()
+        .find(|link| link.rel() == "enclosure")
+        .expect("missing enclosure link");
+    assert_eq!(link.href(), enclosure.url());
+    assert_eq!(link.mime_type(), Some(enclosure.mime_type()));
+    assert_eq!(link.length(), Some(enclosure.length()));
+}
+
+#[test]
+fn to_atom_unparsable_date() {
+    let mut channel = Channel::default();
+    channel.set_last_build_date("yesterday".to_string());
+    assert_eq!(channel.to_atom().updated(), "yesterday");
+}
+
+#[test]
+fn read_any() {
+    let input = include_str!("data/atom.xml");
+    let feed = input.parse::<atom_syndication::Feed>().expect("failed to parse atom");
+    let channel = Channel::read_any(input.as_bytes()).expect("failed to read feed");
+    assert_eq!(channel, Channel::from_atom(&feed));
+
+    let input = include_str!("data/rss2sample.xml");
+    let channel = Channel::read_any(input.as_bytes()).expect("failed to read channel");
+    assert_eq!(channel, input.parse::<Channel>().unwrap());
+
+    let input = r#"<atom:feed xmlns:atom="http://www.w3.org/2005/Atom"><atom:title>"#;
+    match Channel::read_any(input.as_bytes()) {
+        Err(Error::Atom(_)) => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    match Channel::read_any(&b"<html></html>"[..]) {
+        Err(Error::UnexpectedRoot { ref found }) if found == "html" => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn from_atom() {
+    let input = include_str!("data/atom.xml");
+    let feed = input.parse::<atom_syndication::Feed>().expect("failed to parse atom");
+    let channel = Channel::from(feed.clone());
+
+    assert_eq!(channel.title(), "Example Feed");
+    assert_eq!(channel.link(), "http://example.org/");
+    assert_eq!(channel.description(), "A subtitle.");
+    assert_eq!(channel.copyright(), Some("Copyright"));
+    assert_eq!(channel.generator(), Some("Generator"));
+    assert_eq!(channel.last_build_date(), Some("Sat, 13 Dec 2003 18:30:02 +0000"));
+    assert_eq!(channel.managing_editor(), Some("johndoe@example.com (John Doe)"));
+    assert_eq!(channel.image().map(|image| image.url()), Some("http://example.org/logo.png"));
+    assert_eq!(channel.categories()[0].name(), "news");
+    assert_eq!(channel.categories()[0].domain(), Some("http://example.org/categories"));
+    assert_eq!(channel.itunes_ext().and_then(|ext| ext.author()), Some("John Doe"));
+    assert!(channel.extensions().get("itunes").is_none());
+    assert_eq!(
+        channel.extensions()["ext"]["rating"][0].value(),
+        Some("5")
+    );
+    assert_eq!(channel.items().len(), 2);
+
+    let item = &channel.items()[0];
+    assert_eq!(item.title(), Some("Atom-Powered Robots Run Amok"));
+    assert_eq!(item.link(), Some("http://example.org/2003/12/13/atom03"));
+    assert_eq!(item.description(), Some("Some text."));
+    assert_eq!(item.content(), Some("<p>Some content.</p>"));
+    assert_eq!(item.pub_date(), Some("Sat, 13 Dec 2003 18:30:02 +0000"));
+    assert_eq!(item.itunes_ext().and_then(|ext| ext.duration()), Some("10:00"));
+
+    let guid = item.guid().unwrap();
+    assert_eq!(guid.value(), "urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a");
+    assert!(!guid.is_permalink());
+
+    let enclosure = item.enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.org/audio/ph34r_my_podcast.mp3");
+    assert_eq!(enclosure.length(), "1337");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+
+    let item = &channel.items()[1];
+    assert_eq!(item.pub_date(), Some("Sun, 14 Dec 2003 08:00:00 +0100"));
+    assert!(item.guid().unwrap().is_permalink());
+
+    assert_eq!(Channel::try_from(feed).unwrap(), channel);
+    assert_eq!(Item::from(channel.items()[1].to_atom()).link(), Some("http://example.org/2"));
+}
+
+#[test]
+fn atom_round_trip() {
+    let input = include_str!("data/rss2sample.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let feed = atom_syndication::Feed::from(channel.clone());
+    let converted = Channel::from(feed);
+
+    assert_eq!(converted.title(), channel.title());
+    assert_eq!(converted.link(), channel.link());
+    assert_eq!(converted.description(), channel.description());
+    assert_eq!(converted.items().len(), channel.items().len());
+
+    for (converted, item) in converted.items().iter().zip(channel.items()) {
+        assert_eq!(converted.title(), item.title());
+        assert_eq!(converted.link(), item.link());
+        assert_eq!(converted.description(), item.description());
+        assert_eq!(
+            converted.guid().map(|guid| guid.value()),
+            item.guid().map(|guid| guid.value())
+        );
+    }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "compression")]
+
+extern crate flate2;
+extern crate rss;
+
+use std::io::{BufReader, Write};
+
+use flate2::Compression;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use rss::{Channel, Error, LimitKind, ParseOptionsBuilder};
+
+fn gzip(input: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(input).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn zlib(input: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(input).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn read_compressed() {
+    let input = include_str!("data/rss2sample.xml");
+    let expected = input.parse::<Channel>().expect("failed to parse xml");
+
+    let channel = Channel::read_from_maybe_compressed(&gzip(input.as_bytes())[..])
+        .expect("failed to parse gzip");
+    assert_eq!(channel, expected);
+
+    let channel = Channel::read_from_maybe_compressed(&zlib(input.as_bytes())[..])
+        .expect("failed to parse zlib");
+    assert_eq!(channel, expected);
+
+    // input that is not compressed is read as it is
+    let channel = Channel::read_from_maybe_compressed(input.as_bytes())
+        .expect("failed to parse xml");
+    assert_eq!(channel, expected);
+
+    let with_bom = [&b"\xEF\xBB\xBF"[..], input.as_bytes()].concat();
+    let channel = Channel::read_from_maybe_compressed(&with_bom[..]).expect("failed to parse xml");
+    assert_eq!(channel, expected);
+}
+
+#[test]
+fn read_compressed_one_byte_at_a_time() {
+    let input = include_str!("data/rss2sample.xml");
+    let expected = input.parse::<Channel>().expect("failed to parse xml");
+
+    for compressed in &[gzip(input.as_bytes()), zlib(input.as_bytes()), input.as_bytes().to_vec()] {
+        let reader = BufReader::with_capacity(1, &compressed[..]);
+        let channel = Channel::read_from_maybe_compressed(reader).expect("failed to parse");
+        assert_eq!(channel, expected);
+    }
+}
+
+#[test]
+fn read_compressed_errors() {
+    // a gzip header followed by garbage
+    let input = b"\x1f\x8b\x08\x00garbage";
+    match Channel::read_from_maybe_compressed(&input[..]) {
+        Err(Error::Decompression(_)) => {}
+        result => panic!("expected a decompression error, got {:?}", result),
+    }
+
+    // a truncated stream
+    let compressed = gzip(include_bytes!("data/rss2sample.xml"));
+    let truncated = &compressed[..compressed.len() / 2];
+    match Channel::read_from_maybe_compressed(truncated) {
+        Err(Error::Decompression(_)) => {}
+        result => panic!("expected a decompression error, got {:?}", result),
+    }
+
+    // input too short to be compressed is parsed as XML
+    assert!(Channel::read_from_maybe_compressed(&b"x"[..]).is_err());
+    assert!(Channel::read_from_maybe_compressed(&b""[..]).is_err());
+}
+
+#[test]
+fn read_compressed_limit() {
+    // a channel padded with whitespace compresses by far more than 100:1
+    let padding = vec![b' '; 1024 * 1024];
+    let input = [
+        &b"<rss version=\"2.0\"><channel><title>Title</title>"[..],
+        &padding,
+        &b"</channel></rss>"[..],
+    ].concat();
+    let compressed = gzip(&input);
+    assert!(compressed.len() * 100 < input.len());
+
+    let limit = 64 * 1024usize;
+    let options = ParseOptionsBuilder::default()
+        .max_decompressed_size(limit)
+        .build()
+        .unwrap();
+    match Channel::read_from_maybe_compressed_with_options(&compressed[..], &options) {
+        Err(Error::LimitExceeded {
+            kind: LimitKind::DecompressedSize,
+            limit,
+        }) if limit == options.max_decompressed_size() => {}
+        result => panic!("expected a limit error, got {:?}", result),
+    }
+
+    // the limit only applies to compressed input
+    let (channel, _) = Channel::read_from_maybe_compressed_with_options(&input[..], &options)
+        .expect("failed to parse xml");
+    assert_eq!(channel.title(), "Title");
+
+    let options = ParseOptionsBuilder::default()
+        .max_decompressed_size(input.len())
+        .build()
+        .unwrap();
+    let (channel, _) = Channel::read_from_maybe_compressed_with_options(&compressed[..], &options)
+        .expect("failed to parse gzip");
+    assert_eq!(channel.title(), "Title");
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "json_feed")]
+
+extern crate rss;
+extern crate serde_json;
+
+use rss::{Channel, Error};
+use serde_json::Value;
+
+#[test]
+fn to_json_feed() {
+    let input = include_str!("data/jsonfeed.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let json = serde_json::from_str::<Value>(&channel.to_json_feed()).unwrap();
+
+    assert_eq!(json["version"], "https://jsonfeed.org/version/1.1");
+    assert_eq!(json["title"], "Title");
+    assert_eq!(json["home_page_url"], "http://example.com/");
+    assert_eq!(json["feed_url"], "http://example.com/feed.xml");
+    assert_eq!(json["description"], "Description");
+
+    let first = &json["items"][0];
+    assert_eq!(first["id"], "first");
+    assert_eq!(first["url"], "http://example.com/first");
+    assert_eq!(first["title"], "First \"quoted\"");
+    assert_eq!(first["content_html"], "<p>Body</p>");
+    assert_eq!(first["summary"], "Summary");
+    assert_eq!(first["date_published"], "2017-01-01T12:00:00+01:00");
+    assert_eq!(first["attachments"][0]["url"], "http://example.com/first.mp3");
+    assert_eq!(first["attachments"][0]["mime_type"], "audio/mpeg");
+    assert_eq!(first["attachments"][0]["size_in_bytes"], 1234);
+
+    let second = &json["items"][1];
+    assert_eq!(second["id"], "http://example.com/second");
+    assert_eq!(second["content_html"], "Second");
+    assert!(second.get("summary").is_none());
+    assert!(second.get("title").is_none());
+    assert!(second.get("date_published").is_none());
+}
+
+#[test]
+fn to_json_feed_empty() {
+    let json = serde_json::from_str::<Value>(&Channel::default().to_json_feed()).unwrap();
+    assert_eq!(json["title"], "");
+    assert!(json.get("home_page_url").is_none());
+    assert!(json.get("feed_url").is_none());
+    assert_eq!(json["items"], Value::Array(Vec::new()));
+}
+
+#[test]
+fn from_json_feed() {
+    let input = include_str!("data/jsonfeed.json");
+    let channel = Channel::from_json_feed(input).expect("failed to parse json");
+
+    assert_eq!(channel.title(), "Title");
+    assert_eq!(channel.link(), "http://example.com/");
+    assert_eq!(channel.description(), "Description");
+    assert_eq!(channel.language(), Some("en-US"));
+    assert_eq!(channel.managing_editor(), Some("Editor"));
+    let link = &channel.atom_links()[0];
+    assert_eq!(link.href(), "http://example.com/feed.json");
+    assert_eq!(link.rel(), Some("self"));
+    assert!(channel.to_string().contains(r#"xmlns:atom="http://www.w3.org/2005/Atom""#));
+
+    let first = &channel.items()[0];
+    assert_eq!(first.guid().map(|guid| guid.value()), Some("first"));
+    assert_eq!(first.guid().map(|guid| guid.is_permalink()), Some(false));
+    assert_eq!(first.link(), Some("http://example.com/first"));
+    assert_eq!(first.title(), Some("First"));
+    assert_eq!(first.description(), Some("Summary"));
+    assert_eq!(first.content(), Some("<p>Body</p>"));
+    assert_eq!(first.pub_date(), Some("Sun, 1 Jan 2017 12:00:00 +0100"));
+    assert_eq!(first.author(), Some("Author"));
+    let tags = first.categories().iter().map(|c| c.name()).collect::<Vec<_>>();
+    assert_eq!(tags, vec!["one", "two"]);
+    let enclosure = first.enclosure().expect("enclosure missing");
+    assert_eq!(enclosure.url(), "http://example.com/first.mp3");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+    assert_eq!(enclosure.length(), "1234");
+
+    let second = &channel.items()[1];
+    assert_eq!(second.guid().map(|guid| guid.is_permalink()), Some(true));
+    assert_eq!(second.description(), Some("Second"));
+    assert_eq!(second.content(), None);
+    assert_eq!(second.author(), Some("Old Author"));
+    assert_eq!(second.pub_date(), Some("not a date"));
+    assert!(second.enclosure().is_none());
+}
+
+#[test]
+fn json_feed_round_trip() {
+    let input = include_str!("data/jsonfeed.json");
+    let channel = Channel::from_json_feed(input).expect("failed to parse json");
+    let json = serde_json::from_str::<Value>(&channel.to_json_feed()).unwrap();
+
+    assert_eq!(json["feed_url"], "http://example.com/feed.json");
+    assert_eq!(json["language"], "en-US");
+    assert_eq!(json["authors"][0]["name"], "Editor");
+    assert_eq!(json["items"][0]["date_published"], "2017-01-01T12:00:00+01:00");
+    assert_eq!(json["items"][0]["authors"][0]["name"], "Author");
+    assert_eq!(json["items"][0]["tags"][1], "two");
+    assert_eq!(json["items"][0]["attachments"][0]["size_in_bytes"], 1234);
+    assert_eq!(json["items"][1]["id"], "http://example.com/second");
+
+    // dates that cannot be parsed are not written
+    let mut expected = channel.clone();
+    expected.items_mut()[1].set_pub_date(None);
+    assert_eq!(Channel::from_json_feed(&channel.to_json_feed()).unwrap(), expected);
+}
+
+#[test]
+fn from_json_feed_invalid() {
+    match Channel::from_json_feed("not json") {
+        Err(Error::JsonFeed(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match Channel::from_json_feed(r#"{"title": "Title"}"#) {
+        Err(Error::JsonFeed(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match Channel::from_json_feed(r#"{"version": "https://jsonfeed.org/version/1", "items": 1}"#) {
+        Err(Error::JsonFeed(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
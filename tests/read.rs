@@ -1,10 +1,18 @@
 extern crate rss;
+#[cfg(feature = "chrono")]
+extern crate chrono;
 
 use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::time::Duration;
 
-use rss::Channel;
+use rss::{Category, Channel, CloudProtocol, DedupKey, Error, FeedKind, Item, ItemReader,
+          LimitKind, MergeStrategy, ParseOptions, ParseOptionsBuilder, SortOrder};
 use rss::extension::Extension;
+use rss::extension::custom::CustomExtension;
 use rss::extension::dublincore::DublinCoreExtension;
+use rss::extension::itunes::ITunesExplicit;
+use rss::extension::syndication::UpdatePeriod;
 
 fn get_extension_values<'a>(
     map: &'a HashMap<String, Vec<Extension>>,
@@ -15,6 +23,19 @@ fn get_extension_values<'a>(
     })
 }
 
+#[test]
+fn read_from_file() {
+    let channel = Channel::from_file("tests/data/rss2sample.xml").expect("failed to read file");
+    let input = include_bytes!("data/rss2sample.xml");
+    assert_eq!(channel, Channel::from_bytes(input).unwrap());
+    assert_eq!(channel, Channel::read_from(&input[..]).unwrap());
+
+    match Channel::from_file("tests/data/missing.xml") {
+        Err(Error::File(ref path, _)) => assert_eq!(path.to_str(), Some("tests/data/missing.xml")),
+        result => panic!("expected a file error, got {:?}", result),
+    }
+}
+
 #[test]
 fn read_rss090() {
     let input = include_str!("data/rss090.xml");
@@ -189,6 +210,39 @@ fn read_rss1() {
     );
 }
 
+#[test]
+fn read_rdf() {
+    let input = include_str!("data/rdf.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    assert_eq!(channel.title(), "Title");
+    assert_eq!(channel.link(), "http://example.com/");
+
+    let dc = channel.dublin_core_ext().unwrap();
+    assert_eq!(dc.dates(), &["2017-01-01T12:00:00Z".to_string()]);
+    assert_eq!(dc.creators(), &["Channel Creator".to_string()]);
+
+    assert_eq!(channel.items().len(), 2);
+
+    let item = channel.items().get(0).unwrap();
+    assert_eq!(item.title(), Some("Item 1"));
+    assert_eq!(item.link(), Some("http://example.com/1"));
+    let guid = item.guid().unwrap();
+    assert_eq!(guid.value(), "http://example.com/1");
+    assert!(!guid.is_permalink());
+
+    let dc = item.dublin_core_ext().unwrap();
+    assert_eq!(dc.dates(), &["2017-01-02T12:00:00Z".to_string()]);
+    assert_eq!(dc.creators(), &["Item Creator".to_string()]);
+
+    let item = channel.items().get(1).unwrap();
+    assert_eq!(item.link(), Some("http://example.com/2?ref=rss"));
+    assert_eq!(item.guid().map(|guid| guid.value()), Some("http://example.com/2"));
+
+    assert!(channel.unknown_elements().is_empty());
+    assert!(channel.namespaces().contains_key("rdf"));
+}
+
 #[test]
 fn read_channel() {
     let input = include_str!("data/channel.xml");
@@ -211,8 +265,11 @@ fn read_channel() {
         Some("http://blogs.law.harvard.edu/tech/rss")
     );
     assert_eq!(channel.ttl(), Some("60"));
+    assert_eq!(channel.ttl_duration(), Some(Duration::from_secs(3600)));
+    assert_eq!(channel.ttl_minutes(), Some(60));
     assert_eq!(channel.skip_hours().get(0).unwrap().as_str(), "6");
     assert_eq!(channel.skip_hours().get(1).unwrap().as_str(), "8");
+    assert_eq!(channel.skip_hours_parsed(), vec![6, 8]);
     assert_eq!(channel.skip_days().get(0).unwrap().as_str(), "Tuesday");
     assert_eq!(channel.skip_days().get(1).unwrap().as_str(), "Thursday");
 }
@@ -251,6 +308,40 @@ fn read_content() {
         channel.items().get(0).unwrap().content(),
         Some("An example <a href=\"http://example.com/\">link</a>.")
     );
+    assert_eq!(
+        channel.items().get(1).unwrap().content(),
+        Some("An escaped <a href=\"http://example.com/\">link</a>.")
+    );
+}
+
+#[test]
+fn read_body_and_summary() {
+    let input = include_str!("data/body_summary.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let items = channel.items();
+
+    // both present
+    assert_eq!(items[0].body(), Some("<p>The full <b>story</b>.</p>"));
+    assert_eq!(items[0].summary().unwrap(), "A short summary");
+
+    // only content, in a CDATA section
+    assert!(items[1].body().unwrap().contains("<h1>Headline</h1>"));
+    // a non-breaking space is whitespace too
+    assert_eq!(items[1].summary().unwrap(), "Headline Caf\u{e9} & bar opening next week");
+    assert_eq!(items[1].summary_with_limit(20).unwrap(), "Headline Caf\u{e9} & bar\u{2026}");
+    assert_eq!(items[1].summary_with_limit(5).unwrap(), "Headl\u{2026}");
+
+    // blank content is treated as absent
+    assert_eq!(items[2].body(), Some("<p>Escaped &lt;html&gt;</p>"));
+    assert_eq!(items[2].summary().unwrap(), "<p>Escaped &lt;html&gt;</p>");
+
+    // neither present
+    assert_eq!(items[3].body(), None);
+    assert_eq!(items[3].summary(), None);
+
+    // content without any text
+    assert!(items[4].body().is_some());
+    assert_eq!(items[4].summary(), None);
 }
 
 #[test]
@@ -326,6 +417,19 @@ fn read_guid() {
             .map(|v| v.value(),),
         Some("def")
     );
+
+    let guids = channel
+        .items()
+        .iter()
+        .map(|item| item.guid().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(guids[0].permalink_attribute(), Some(false));
+    assert_eq!(guids[0].permalink_url(), None);
+    assert_eq!(guids[1].permalink_attribute(), None);
+    assert_eq!(guids[1].permalink_url(), Some("def"));
+    assert_eq!(guids[2].permalink_attribute(), Some(true));
+    assert!(guids[2].is_permalink());
+    assert_eq!(guids[2].permalink_url(), Some("http://example.com/ghi"));
 }
 
 #[test]
@@ -363,6 +467,14 @@ fn read_enclosure() {
             .map(|v| { v.mime_type() },),
         Some("audio/mpeg")
     );
+    assert_eq!(
+        channel
+            .items()
+            .get(0)
+            .and_then(|item| item.enclosure())
+            .and_then(|enclosure| enclosure.length_bytes()),
+        Some(4992349)
+    );
 }
 
 #[test]
@@ -426,6 +538,68 @@ fn read_category() {
     );
 }
 
+#[test]
+fn read_category_domain() {
+    let input = include_str!("data/category_domain.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let categories = channel.items()[0]
+        .categories()
+        .iter()
+        .map(|category| (category.domain(), category.name()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        categories,
+        vec![
+            (Some("http://example.com/tags?a=1&b=2"), "Science & Nature"),
+            (None, "Space"),
+            (Some(""), "Astronomy"),
+            (None, " Padded "),
+            (Some("http://example.com/other"), "Science & Nature"),
+        ]
+    );
+}
+
+#[test]
+fn read_category_matching() {
+    let input = include_str!("data/category_matching.xml");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let first = &channel.items()[0];
+    assert_eq!(first.category_values().collect::<Vec<_>>(), vec!["Rust", "XML", "rust"]);
+    assert!(first.has_category("RUST"));
+    assert!(first.has_category(" xml"));
+    assert!(!first.has_category("Go"));
+
+    let titles = channel
+        .items_with_category("rust")
+        .map(|item| item.title().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, vec!["First", "Third"]);
+    assert_eq!(channel.items_with_category("Python").count(), 0);
+
+    channel.categories_dedup();
+    let categories = channel
+        .categories()
+        .iter()
+        .map(|category| (category.domain(), category.name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        categories,
+        vec![
+            (None, "Programming"),
+            (Some("http://example.com/tags"), "Programming"),
+        ]
+    );
+
+    let names = channel
+        .items()
+        .iter()
+        .map(|item| item.category_values().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![vec!["Rust", "XML"], vec!["Go"], vec!["RUST"]]);
+}
+
 #[test]
 fn read_image() {
     let input = include_str!("data/image.xml");
@@ -436,9 +610,64 @@ fn read_image() {
     assert_eq!(channel.image().unwrap().link(), "http://example.org/link");
     assert_eq!(channel.image().unwrap().width(), Some("100"));
     assert_eq!(channel.image().unwrap().height(), Some("200"));
+    assert_eq!(channel.image().unwrap().width_px(), Some(100));
+    assert_eq!(channel.image().unwrap().height_px(), Some(200));
     assert_eq!(channel.image().unwrap().description(), Some("Description"));
 }
 
+#[test]
+fn read_malformed_numbers() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <ttl>soon</ttl>
+                <image><url>http://example.com/logo.png</url><width>wide</width></image>
+                <item>
+                    <enclosure url="http://example.com/a.mp3" length="-1" type="audio/mpeg"/>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let channel = Channel::from_bytes(input.as_bytes()).expect("failed to parse xml");
+
+    assert_eq!(channel.ttl(), Some("soon"));
+    assert_eq!(channel.ttl_minutes(), None);
+
+    let image = channel.image().unwrap();
+    assert_eq!(image.width(), Some("wide"));
+    assert_eq!(image.width_px(), None);
+    assert_eq!(image.height_px(), Some(31));
+
+    let enclosure = channel.items()[0].enclosure().unwrap();
+    assert_eq!(enclosure.length(), "-1");
+    assert_eq!(enclosure.length_bytes(), None);
+}
+
+#[test]
+fn read_enclosure_mime_types() {
+    let input = include_str!("data/enclosure_mime.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let mut enclosures = channel
+        .items()
+        .iter()
+        .map(|item| item.enclosure().unwrap().clone())
+        .collect::<Vec<_>>();
+
+    let valid = enclosures.iter().map(|e| e.is_valid_mime()).collect::<Vec<_>>();
+    assert_eq!(valid, vec![true, false, false, false, false]);
+
+    for enclosure in &mut enclosures {
+        enclosure.normalize_mime();
+    }
+    let mime_types = enclosures.iter().map(|e| e.mime_type()).collect::<Vec<_>>();
+    assert_eq!(
+        mime_types,
+        vec!["audio/mpeg", "audio/mpeg", "audio/x-m4a", "video/mp4", "application/pdf"]
+    );
+    let valid = enclosures.iter().map(|e| e.is_valid_mime()).collect::<Vec<_>>();
+    assert_eq!(valid, vec![true, true, true, true, false]);
+}
+
 #[test]
 fn read_mixed_content() {
     let input = include_str!("data/mixed_content.xml");
@@ -458,6 +687,34 @@ fn read_cloud() {
     assert_eq!(cloud.path(), "/rpc");
     assert_eq!(cloud.register_procedure(), "notify");
     assert_eq!(cloud.protocol(), "xml-rpc");
+    assert_eq!(cloud.port_u16(), Some(80));
+    assert_eq!(cloud.protocol_parsed(), Some(CloudProtocol::XmlRpc));
+}
+
+#[test]
+fn read_cloud_unknown_protocol() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <cloud domain="example.com" port="99999" path="/" registerProcedure=""
+                    protocol="carrier-pigeon"/>
+            </channel>
+        </rss>
+    "#;
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let cloud = channel.cloud().expect("cloud missing");
+
+    assert_eq!(cloud.protocol(), "carrier-pigeon");
+    assert_eq!(cloud.protocol_parsed(), None);
+    assert_eq!(cloud.port(), "99999");
+    assert_eq!(cloud.port_u16(), None);
+    assert_eq!(" SOAP ".parse(), Ok(CloudProtocol::Soap));
+
+    let options = ParseOptionsBuilder::default().lenient(true).build().unwrap();
+    let (lenient, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(lenient.cloud(), channel.cloud());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message(), "cloud port is not a port number: 99999");
 }
 
 #[test]
@@ -470,6 +727,123 @@ fn read_textinput() {
     assert_eq!(text_input.name(), "Name");
     assert_eq!(text_input.link(), "http://example.com/");
     assert_eq!(text_input.description(), "Description");
+
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <textinput>
+                    <link>http://example.com/</link>
+                    <name>Name</name>
+                    <description>Description</description>
+                    <title>Title</title>
+                </textinput>
+            </channel>
+        </rss>
+    "#;
+    let lowercase = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(lowercase.text_input(), channel.text_input());
+    assert!(lowercase.unknown_elements().is_empty());
+}
+
+#[test]
+fn read_unknown_elements() {
+    let input = include_str!("data/unknown.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let rank = &channel.unknown_elements()[0];
+    assert_eq!(rank.name(), "rank");
+    assert_eq!(rank.value(), Some("5"));
+    assert_eq!(rank.attrs().get("type").map(|s| s.as_str()), Some("popularity"));
+
+    let item = channel.items().get(0).unwrap();
+    assert_eq!(item.unknown_elements().len(), 1);
+
+    let location = &item.unknown_elements()[0];
+    assert_eq!(location.name(), "location");
+    assert_eq!(location.attrs().get("precision").map(|s| s.as_str()), Some("city"));
+    assert_eq!(
+        location.children().get("name").map(|v| v[0].value()),
+        Some(Some("Somewhere"))
+    );
+    assert_eq!(
+        location
+            .children()
+            .get("point")
+            .and_then(|v| v[0].attrs().get("lat"))
+            .map(|s| s.as_str()),
+        Some("1.5")
+    );
+}
+
+#[test]
+fn read_unknown_extensions() {
+    let input = include_str!("data/unknown.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let itunes = channel.itunes_ext().unwrap();
+    assert_eq!(itunes.author(), Some("Author"));
+    assert_eq!(itunes.block(), Some("yes"));
+    assert_eq!(
+        channel
+            .extensions()
+            .get("itunes")
+            .and_then(|map| get_extension_values(map, "type")),
+        Some(vec!["episodic"])
+    );
+
+    let item = channel.items().get(0).unwrap();
+    assert_eq!(item.itunes_ext().and_then(|ext| ext.duration()), Some("10:00"));
+    assert!(item.media_ext().is_some());
+    assert_eq!(
+        item.extensions()
+            .get("itunes")
+            .and_then(|map| get_extension_values(map, "episode")),
+        Some(vec!["3"])
+    );
+    assert_eq!(
+        item.extensions()
+            .get("georss")
+            .and_then(|map| get_extension_values(map, "point")),
+        Some(vec!["1.5 2.5"])
+    );
+    assert_eq!(
+        item.extensions()
+            .get("media")
+            .and_then(|map| get_extension_values(map, "keywords")),
+        Some(vec!["one,&two"])
+    );
+    assert!(item.extensions().get("dc").is_none());
+}
+
+#[test]
+fn read_extension_by_namespace() {
+    for input in &[
+        include_str!("data/extension.xml"),
+        include_str!("data/extension_prefix.xml"),
+    ] {
+        let channel = input.parse::<Channel>().expect("failed to parse xml");
+        let namespaces = channel.namespaces();
+        let item = &channel.items()[0];
+
+        let value = |name| {
+            item.extension(namespaces, "http://example.com/", name)
+                .and_then(|ext| ext.value())
+        };
+        assert_eq!(value("creator"), Some("Creator Name"));
+        assert_eq!(value("contributor"), Some("Contributor 1"));
+        assert_eq!(value("missing"), None);
+        assert!(item.extension(namespaces, "http://example.org/", "creator").is_none());
+    }
+
+    let input = include_str!("data/extension_prefix.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(
+        channel
+            .extension("http://example.com/", "rating")
+            .and_then(|ext| ext.value()),
+        Some("5")
+    );
+    assert!(channel.extension("http://example.com/", "creator").is_none());
 }
 
 #[test]
@@ -481,6 +855,7 @@ fn read_extension() {
         channel.namespaces().get("ext").unwrap(),
         "http://example.com/"
     );
+    // the Dublin Core declaration is left to the writer
     assert_eq!(channel.namespaces().len(), 1);
 
     assert_eq!(
@@ -591,6 +966,10 @@ fn read_itunes() {
         Some("http://example.com/image.jpg")
     );
     assert_eq!(channel.itunes_ext().unwrap().explicit(), Some("no"));
+    assert_eq!(
+        channel.itunes_ext().unwrap().explicit_parsed(),
+        Some(ITunesExplicit::No)
+    );
     assert_eq!(channel.itunes_ext().unwrap().complete(), Some("yes"));
     assert_eq!(
         channel.itunes_ext().unwrap().new_feed_url(),
@@ -722,6 +1101,65 @@ fn read_itunes() {
     );
 }
 
+#[test]
+fn read_itunes_duration() {
+    let mut ext = rss::extension::itunes::ITunesItemExtension::default();
+    let cases = vec![
+        ("01:02:03", Some(Duration::from_secs(3723))),
+        ("1:02:03", Some(Duration::from_secs(3723))),
+        ("62:03", Some(Duration::from_secs(3723))),
+        ("2:03", Some(Duration::from_secs(123))),
+        ("00:00:07", Some(Duration::from_secs(7))),
+        ("3723", Some(Duration::from_secs(3723))),
+        (" 45 ", Some(Duration::from_secs(45))),
+        ("120:00:00", Some(Duration::from_secs(432_000))),
+        ("1:02:03.5", Some(Duration::new(3723, 500_000_000))),
+        ("12.25", Some(Duration::new(12, 250_000_000))),
+        ("1:60:00", None),
+        ("1:61", None),
+        ("1:2:3:4", None),
+        ("-1:00", None),
+        ("1:xx", None),
+        ("1.2.3", None),
+        (":30", None),
+        ("", None),
+    ];
+
+    for (input, expected) in cases {
+        ext.set_duration(input.to_string());
+        assert_eq!(ext.duration_parsed(), expected, "{}", input);
+    }
+
+    ext.set_duration(None);
+    assert_eq!(ext.duration_parsed(), None);
+    assert_eq!(ext.duration_seconds(), None);
+
+    let cases = vec![
+        ("1:02:03", Some(3723)),
+        ("02:03", Some(123)),
+        ("3723", Some(3723)),
+        ("1:02:03.9", Some(3723)),
+        ("1h", None),
+        ("-5", None),
+        ("18446744073709551616", None),
+        ("5124095576030432:00:00", None),
+    ];
+
+    for (input, expected) in cases {
+        ext.set_duration(input.to_string());
+        assert_eq!(ext.duration_seconds(), expected, "{}", input);
+        assert_eq!(ext.duration(), Some(input));
+    }
+
+    ext.set_duration_seconds(59);
+    assert_eq!(ext.duration(), Some("00:00:59"));
+
+    ext.set_duration_from(Duration::new(3723, 600_000_000));
+    assert_eq!(ext.duration(), Some("01:02:04"));
+    ext.set_duration_from(Duration::from_secs(432_000));
+    assert_eq!(ext.duration(), Some("120:00:00"));
+}
+
 #[test]
 fn read_dublincore() {
     let input = include_str!("data/dublincore.xml");
@@ -735,6 +1173,7 @@ fn read_dublincore() {
                 .collect::<Vec<_>>(),
             vec!["Contributor 1", "Contributor 2"]
         );
+        assert_eq!(dc.contributor(), Some("Contributor 1"));
         assert_eq!(
             dc.coverages()
                 .iter()
@@ -746,6 +1185,8 @@ fn read_dublincore() {
             dc.creators().iter().map(|s| s.as_str()).collect::<Vec<_>>(),
             vec!["Creator"]
         );
+        assert_eq!(dc.creator(), Some("Creator"));
+        assert_eq!(dc.date(), Some("2016-08-27"));
         assert_eq!(
             dc.dates().iter().map(|s| s.as_str()).collect::<Vec<_>>(),
             vec!["2016-08-27"]
@@ -828,6 +1269,92 @@ fn read_dublincore() {
     );
 }
 
+#[test]
+fn read_dublincore_repeated() {
+    let input = include_str!("data/dublincore_repeated.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let dc = channel.items()[0].dublin_core_ext().expect("dc extension missing");
+
+    assert_eq!(
+        dc.creators(),
+        &["Ada Lovelace", "Charles Babbage", "Mary Somerville"]
+    );
+    assert_eq!(dc.creator(), Some("Ada Lovelace"));
+    assert_eq!(dc.contributors(), &["Luigi Menabrea", "Augustus De Morgan"]);
+    assert_eq!(dc.subjects(), &["Mathematics", "Computing"]);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn read_dublincore_date_parsed() {
+    let input = include_str!("data/dublincore_repeated.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let dc = channel.items()[0].dublin_core_ext().expect("dc extension missing");
+    let date = dc.dc_date_parsed().expect("failed to parse date");
+    assert_eq!(date.to_rfc3339(), "1843-09-01T12:00:00+01:00");
+
+    let input = include_str!("data/dublincore.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let dc = channel.dublin_core_ext().expect("dc extension missing");
+    let date = dc.dc_date_parsed().expect("failed to parse date");
+    assert_eq!(date.to_rfc3339(), "2016-08-27T00:00:00+00:00");
+}
+
+#[test]
+fn read_media() {
+    let input = include_str!("data/media.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.namespaces().get("media").is_none());
+
+    let media = channel.items()[0].media_ext().expect("missing media extension");
+    assert_eq!(media.contents().len(), 1);
+    let content = &media.contents()[0];
+    assert_eq!(content.url(), "http://example.com/video.mp4");
+    assert_eq!(content.mime_type(), Some("video/mp4"));
+    assert_eq!(content.medium(), Some("video"));
+    assert_eq!(content.width(), Some("640"));
+    assert_eq!(content.height(), Some("480"));
+    assert_eq!(content.duration(), Some("120"));
+    assert_eq!(media.thumbnails().len(), 1);
+    let thumbnail = &media.thumbnails()[0];
+    assert_eq!(thumbnail.url(), "http://example.com/thumb.jpg");
+    assert_eq!(thumbnail.width(), Some("75"));
+    assert_eq!(thumbnail.height(), Some("50"));
+    assert_eq!(media.title(), Some("Media Title"));
+    assert_eq!(media.description(), Some("Media Description"));
+    assert_eq!(media.credits().len(), 1);
+    assert_eq!(media.credits()[0].value(), "Jane Doe");
+    assert_eq!(media.credits()[0].role(), Some("producer"));
+    assert_eq!(media.credits()[0].scheme(), Some("urn:ebu"));
+    assert!(media.groups().is_empty());
+    assert!(channel.items()[0].extensions().is_empty());
+
+    let media = channel.items()[1].media_ext().expect("missing media extension");
+    assert!(media.contents().is_empty());
+    assert_eq!(media.thumbnails()[0].url(), "http://example.com/item.jpg");
+    assert_eq!(media.title(), None);
+    assert_eq!(media.groups().len(), 1);
+
+    let group = &media.groups()[0];
+    assert_eq!(
+        group
+            .contents()
+            .iter()
+            .map(|content| (content.url(), content.bitrate()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("http://example.com/low.mp4", Some("400")),
+            ("http://example.com/high.mp4", Some("4000")),
+        ]
+    );
+    assert_eq!(group.thumbnails()[0].url(), "http://example.com/group.jpg");
+    assert_eq!(group.thumbnails()[0].width(), None);
+    assert_eq!(group.title(), Some("Group Title"));
+    assert_eq!(group.description(), None);
+    assert_eq!(group.credits()[0].value(), "John Doe");
+    assert_eq!(group.credits()[0].role(), None);
+}
+
 #[test]
 fn read_escaped() {
     let input = r#"
@@ -843,3 +1370,1566 @@ fn read_escaped() {
     let parsed_channel = output.parse::<Channel>().unwrap();
     assert_eq!(channel, parsed_channel);
 }
+
+#[test]
+fn read_items_iterator() {
+    let input = include_str!("data/interleaved.xml");
+    let mut reader = ItemReader::new(input.as_bytes()).expect("failed to parse xml");
+
+    assert_eq!(reader.channel().title(), "Title");
+    assert_eq!(reader.channel().link(), "http://example.com/");
+
+    let titles = reader
+        .by_ref()
+        .map(|item| item.expect("failed to parse item").title().map(String::from))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        titles,
+        vec![
+            Some("Item 1".to_string()),
+            Some("Item 2".to_string()),
+            Some("Item 3".to_string()),
+        ]
+    );
+
+    assert!(reader.next().is_none());
+    assert_eq!(reader.channel().description(), "Description");
+    assert!(reader.channel().items().is_empty());
+}
+
+#[test]
+fn read_items_iterator_early_exit() {
+    let input = include_str!("data/interleaved.xml");
+    let reader = ItemReader::new(input.as_bytes()).expect("failed to parse xml");
+
+    let items = reader.take(1).collect::<Result<Vec<_>, _>>().expect("failed to parse item");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title(), Some("Item 1"));
+}
+
+#[test]
+fn read_items_iterator_rss1() {
+    let input = include_str!("data/rss1.xml");
+    let reader = ItemReader::new(input.as_bytes()).expect("failed to parse xml");
+
+    assert_eq!(reader.channel().title(), "XML.com");
+
+    let items = reader.collect::<Result<Vec<_>, _>>().expect("failed to parse item");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].title(), Some("Putting RDF to Work"));
+}
+
+#[test]
+fn read_items_iterator_with_options() {
+    let input = include_str!("data/malformed_items.xml");
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .html_entities(false)
+        .build()
+        .unwrap();
+    let mut reader =
+        ItemReader::with_options(input.as_bytes(), &options).expect("failed to parse xml");
+
+    let item = reader.next().unwrap().expect("failed to parse item");
+    assert_eq!(item.title(), Some("First"));
+    assert!(reader.warnings().is_empty());
+
+    let titles = reader
+        .map(|item| item.expect("failed to parse item").title().map(String::from))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        titles,
+        vec![
+            Some("Broken".to_string()),
+            Some("Second".to_string()),
+            Some("Third".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn read_interleaved() {
+    let input = include_str!("data/interleaved.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    assert_eq!(channel.description(), "Description");
+    assert_eq!(channel.items().len(), 3);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn read_dates() {
+    use chrono::{TimeZone, Utc};
+
+    let input = include_str!("data/channel.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    assert_eq!(
+        channel.pub_date_parsed().map(|date| date.to_rfc3339()),
+        Some("2016-08-27T00:00:00+00:00".to_string())
+    );
+    assert_eq!(
+        channel.last_build_date_parsed().map(|date| date.to_rfc3339()),
+        Some("2016-08-27T09:00:00+00:00".to_string())
+    );
+    assert_eq!(
+        channel.skip_days_parsed(),
+        vec![chrono::Weekday::Tue, chrono::Weekday::Thu]
+    );
+
+    let tuesday = Utc.ymd(2016, 8, 30).and_hms(12, 0, 0);
+    let wednesday = Utc.ymd(2016, 8, 31).and_hms(12, 0, 0);
+    assert!(channel.should_skip(&tuesday));
+    assert!(!channel.should_skip(&wednesday));
+    assert!(channel.should_skip(&Utc.ymd(2016, 8, 31).and_hms(8, 59, 59)));
+    assert!(!channel.should_skip(&Utc.ymd(2016, 8, 31).and_hms(9, 0, 0)));
+
+    let mut item = Item::default();
+    let dates = [
+        ("Sat, 1 Jul 2017 07:05:09 GMT", "2017-07-01T07:05:09+00:00"),
+        ("Mon, 1 Jul 2017 07:05:09 GMT", "2017-07-01T07:05:09+00:00"),
+        ("Sat, 01 Jul 2017 7:05:09 UT", "2017-07-01T07:05:09+00:00"),
+        ("Sat, 1 Jul 2017 07:05:09 UTC", "2017-07-01T07:05:09+00:00"),
+        ("Sat, 1 Jul 2017 07:05:09 +0000", "2017-07-01T07:05:09+00:00"),
+        ("Sat, 1 July 2017 07:05:09 +02:00", "2017-07-01T07:05:09+02:00"),
+        ("Sat, 1 Jul 2017 07:05:09 EST", "2017-07-01T07:05:09-05:00"),
+        ("Sat, 1 Jul 17 07:05:09 GMT", "2017-07-01T07:05:09+00:00"),
+        ("Sat, 1 Jul 99 07:05 GMT", "1999-07-01T07:05:00+00:00"),
+        ("Sat, 1 Jul 2017 07:05:09 A", "2017-07-01T07:05:09+00:00"),
+        ("Sat, 1 Jul 2017 07:05 m", "2017-07-01T07:05:00+00:00"),
+        ("Sat, 1 Jul 2017 07:05:09.250 Z", "2017-07-01T07:05:09+00:00"),
+        ("Samstag, 1 Jul 2017 07:05:09 GMT", "2017-07-01T07:05:09+00:00"),
+        ("Sáb 1 Jul 2017 07:05:09 GMT", "2017-07-01T07:05:09+00:00"),
+        ("1 Jul 2017 07:05:09", "2017-07-01T07:05:09+00:00"),
+    ];
+
+    for &(input, expected) in &dates {
+        item.set_pub_date(input.to_string());
+        assert_eq!(
+            item.pub_date_parsed().map(|date| date.to_rfc3339()),
+            Some(expected.to_string()),
+            "{}",
+            input
+        );
+    }
+
+    let invalid = [
+        "",
+        "garbage",
+        ",",
+        "Sat, 32 Jul 2017 07:05:09 GMT",
+        "Sat, 1 Jul 2017 25:00:00 GMT",
+        "Sat, 1 Jul 2017 07:05:09 J",
+        "Sat, 1 Jul 2017 07:05:09 +5",
+        "Sat, 1 Jül 2017 07:05:09 GMT",
+        "Sat, 1 Jul -17 07:05:09 GMT",
+        "Sat, 1 Jul 2017 07::09 GMT",
+    ];
+
+    for input in &invalid {
+        item.set_pub_date(input.to_string());
+        assert_eq!(item.pub_date_parsed(), None, "{}", input);
+    }
+}
+
+#[test]
+fn read_lenient() {
+    let input = include_str!("data/malformed_items.xml");
+    assert!(input.parse::<Channel>().is_err());
+
+    let (channel, errors) = Channel::read_from_lenient(input.as_bytes()).unwrap();
+    let titles = channel
+        .items()
+        .iter()
+        .map(|item| item.title())
+        .collect::<Vec<_>>();
+
+    assert_eq!(titles, vec![Some("First"), Some("Second"), Some("Third")]);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(channel.title(), "Title");
+    assert_eq!(channel.language(), Some("en"));
+}
+
+#[test]
+fn read_lenient_truncated() {
+    let input = "<rss><channel><item><title>Title</title></item><item><title>&</title>";
+    assert!(Channel::read_from_lenient(input.as_bytes()).is_err());
+}
+
+#[test]
+fn read_with_options() {
+    let input = include_str!("data/malformed_items.xml");
+
+    let options = ParseOptions::default();
+    assert!(Channel::read_from_with_options(input.as_bytes(), &options).is_err());
+
+    let input = include_str!("data/rss2sample.xml");
+    let (channel, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(channel, input.parse::<Channel>().unwrap());
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn read_with_options_lenient() {
+    let input = include_str!("data/malformed_items.xml");
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .html_entities(false)
+        .build()
+        .unwrap();
+
+    let (channel, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    let titles = channel
+        .items()
+        .iter()
+        .map(|item| item.title())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        titles,
+        vec![Some("First"), Some("Broken"), Some("Second"), Some("Third")]
+    );
+    assert_eq!(channel.items()[1].description(), Some("Fish & Chips"));
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0].message().starts_with("text kept unescaped"));
+    assert!(warnings[1].message().starts_with("malformed item skipped"));
+    assert!(warnings[0].position() < warnings[1].position());
+    assert_eq!(&input[..warnings[1].position()].rsplit('<').next(), &Some("item>"));
+}
+
+#[test]
+fn read_attributes() {
+    let input = include_str!("data/attributes.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let cloud = channel.cloud().unwrap();
+    assert_eq!(cloud.domain(), "rpc.example.com");
+    assert_eq!(cloud.port(), "80");
+    assert_eq!(cloud.path(), "/RPC2");
+    assert_eq!(cloud.register_procedure(), "notify");
+    assert_eq!(cloud.protocol(), "xml-rpc");
+    assert_eq!(channel.categories()[0].domain(), Some("http://example.com/first"));
+
+    let item = &channel.items()[0];
+    let source = item.source().unwrap();
+    assert_eq!(source.url(), "http://example.com/source");
+    assert_eq!(source.title(), Some("Source"));
+
+    let enclosure = item.enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.com/a.mp3");
+    assert_eq!(enclosure.length(), "200");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+
+    assert_eq!(item.guid().unwrap().permalink_attribute(), Some(false));
+    assert_eq!(item.links()[0].href(), "http://example.com/related");
+    assert_eq!(item.links()[0].rel(), Some("related"));
+    assert_eq!(item.categories()[0].domain(), Some("http://example.com/prefixed"));
+}
+
+#[test]
+fn read_attributes_malformed() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item>
+                    <enclosure url="http://example.com/a.mp3" type="audio/mpeg" length=200 />
+                </item>
+            </channel>
+        </rss>
+    "#;
+
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let enclosure = channel.items()[0].enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.com/a.mp3");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+    assert_eq!(enclosure.length(), "");
+
+    let options = ParseOptionsBuilder::default().lenient(true).build().unwrap();
+    let (lenient, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(lenient, channel);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message().starts_with("malformed attribute of <enclosure> skipped"));
+}
+
+#[test]
+fn read_html_entities() {
+    let input = include_str!("data/html_entities.xml");
+    assert!(input.parse::<Channel>().is_err());
+
+    let lenient = ParseOptionsBuilder::default().lenient(true).build().unwrap();
+    let html = ParseOptionsBuilder::default().html_entities(true).build().unwrap();
+    for options in &[lenient, html] {
+        let (channel, warnings) = Channel::read_from_with_options(input.as_bytes(), options)
+            .expect("failed to parse xml");
+
+        assert_eq!(channel.title(), "News\u{a0}\u{2014} Today");
+        assert_eq!(channel.description(), "Caf\u{e9} \u{2026} \u{e9} \u{e9} \u{1f600} <b>");
+
+        let item = &channel.items()[0];
+        assert_eq!(item.title(), Some("Fish & Chips &notanentity; &copy"));
+        let source = item.source().unwrap();
+        assert_eq!(source.url(), "http://example.com/?a=1&b=2\u{a0}");
+        assert_eq!(source.title(), Some("Source \u{2013} Name"));
+
+        let note = item.extension(channel.namespaces(), "http://example.com/ns", "note").unwrap();
+        assert_eq!(note.value(), Some("\u{2122}"));
+        assert_eq!(note.attrs().get("label").map(|s| s.as_str()), Some("\u{ab}A\u{bb}"));
+        let tag = item.extension(channel.namespaces(), "http://example.com/ns", "tag").unwrap();
+        assert_eq!(tag.value(), Some("&unknown; \u{ae}"));
+        assert_eq!(tag.attrs().get("kind").map(|s| s.as_str()), Some("&bogus;"));
+
+        let messages = warnings.iter().map(|w| w.message()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "unknown character reference kept: &notanentity;",
+                "unknown character reference kept: &",
+                "unknown character reference kept: &",
+                "unknown character reference kept: &bogus;",
+                "unknown character reference kept: &unknown;",
+            ]
+        );
+    }
+
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .html_entities(false)
+        .build()
+        .unwrap();
+    let (channel, _) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(channel.title(), "News&nbsp;&mdash; Today");
+}
+
+#[test]
+fn read_numeric_character_references() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <title>&#233;&#xE9;&#xe9;&#x1F600;&#169;&#8212;</title>
+                <item><title>&#38;&#60;&#x3E;</title></item>
+            </channel>
+        </rss>
+    "#;
+
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(channel.title(), "\u{e9}\u{e9}\u{e9}\u{1f600}\u{a9}\u{2014}");
+    assert_eq!(channel.items()[0].title(), Some("&<>"));
+
+    let options = ParseOptionsBuilder::default().html_entities(true).build().unwrap();
+    let (html, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(html, channel);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn read_with_options_case_insensitive() {
+    let input = r#"
+        <RSS version="2.0">
+            <Channel>
+                <TITLE>Title</TITLE>
+                <lastbuilddate>Sun, 01 Jan 2017 12:00:00 GMT</lastbuilddate>
+                <Cloud Domain="example.com" PORT="80" path="/rpc" registerprocedure="notify"
+                    protocol="xml-rpc"/>
+                <SkipHours><Hour>1</Hour></SkipHours>
+                <Item>
+                    <PubDate>Sun, 01 Jan 2017 12:00:00 GMT</PubDate>
+                    <GUID ISPERMALINK="false">1</GUID>
+                    <Enclosure URL="http://example.com/a.mp3" Length="1" Type="audio/mpeg"/>
+                </Item>
+                <item><Title>Second</Title></item>
+            </Channel>
+        </RSS>
+    "#;
+    let options = ParseOptionsBuilder::default()
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+
+    let (channel, _) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(channel.title(), "Title");
+    assert_eq!(channel.last_build_date(), Some("Sun, 01 Jan 2017 12:00:00 GMT"));
+    assert_eq!(channel.skip_hours(), &["1".to_string()]);
+    assert!(channel.unknown_elements().is_empty());
+
+    let cloud = channel.cloud().expect("cloud missing");
+    assert_eq!(cloud.domain(), "example.com");
+    assert_eq!(cloud.port(), "80");
+    assert_eq!(cloud.register_procedure(), "notify");
+
+    assert_eq!(channel.items().len(), 2);
+    let item = &channel.items()[0];
+    assert_eq!(item.pub_date(), Some("Sun, 01 Jan 2017 12:00:00 GMT"));
+    let guid = item.guid().expect("guid missing");
+    assert_eq!(guid.value(), "1");
+    assert!(!guid.is_permalink());
+    let enclosure = item.enclosure().expect("enclosure missing");
+    assert_eq!(enclosure.url(), "http://example.com/a.mp3");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+    assert_eq!(channel.items()[1].title(), Some("Second"));
+
+    let strict = input.replace("RSS", "rss").replace("Channel", "channel");
+    let (channel, count) =
+        Channel::read_metadata_from(strict.as_bytes()).unwrap();
+    assert_eq!(channel.title(), "");
+    assert_eq!(count, 1);
+    assert!(input.parse::<Channel>().is_err());
+}
+
+#[test]
+fn read_with_options_duplicates() {
+    let input = r#"
+<rss version="2.0">
+    <channel>
+        <title>First</title>
+        <title>Second</title>
+        <item>
+            <title>First</title>
+            <guid>first</guid>
+            <title>Second</title>
+            <guid>second</guid>
+        </item>
+    </channel>
+</rss>"#;
+
+    let channel = input.parse::<Channel>().unwrap();
+    assert_eq!(channel.title(), "Second");
+    assert_eq!(channel.items()[0].title(), Some("Second"));
+
+    let mut options = ParseOptions::default();
+    options.set_lenient(true);
+    let (channel, warnings) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+    assert_eq!(channel.title(), "First");
+    assert_eq!(channel.items()[0].title(), Some("First"));
+    assert_eq!(channel.items()[0].guid().map(|guid| guid.value()), Some("first"));
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|warning| warning.message())
+            .collect::<Vec<_>>(),
+        vec![
+            "duplicate <title> element ignored",
+            "duplicate <title> element ignored",
+            "duplicate <guid> element ignored",
+        ]
+    );
+}
+
+#[test]
+fn read_latin1() {
+    let input = include_bytes!("data/latin1.xml");
+    let channel = Channel::read_from(&input[..]).expect("failed to parse xml");
+
+    assert_eq!(channel.title(), "Café");
+    assert_eq!(channel.description(), "Crème brûlée");
+
+    let item = channel.items().get(0).unwrap();
+    assert_eq!(item.title(), Some("À la carte"));
+    assert_eq!(item.categories()[0].name(), "Français");
+    assert_eq!(item.categories()[0].domain(), Some("http://example.com/é"));
+}
+
+#[test]
+fn read_leading_content() {
+    let inputs: Vec<&[u8]> = vec![
+        include_bytes!("data/bom.xml"),
+        include_bytes!("data/leading_whitespace.xml"),
+        include_bytes!("data/leading_comment.xml"),
+    ];
+
+    for input in inputs {
+        let channel = Channel::read_from(input).expect("failed to parse xml");
+        assert_eq!(channel.title(), "Title");
+        assert_eq!(channel.description(), "Description");
+    }
+}
+
+#[test]
+fn read_misplaced_bom() {
+    let input = include_bytes!("data/misplaced_bom.xml");
+    match Channel::read_from(&input[..]) {
+        Err(Error::MisplacedByteOrderMark) => {}
+        result => panic!("expected a misplaced byte order mark error, got {:?}", result),
+    }
+
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .build()
+        .unwrap();
+    let (channel, warnings) = Channel::read_from_with_options(&input[..], &options).unwrap();
+    assert_eq!(channel.title(), "Title");
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn read_utf16() {
+    let inputs: Vec<&[u8]> = vec![
+        include_bytes!("data/utf16le.xml"),
+        include_bytes!("data/utf16be.xml"),
+    ];
+
+    for input in inputs {
+        let channel = Channel::read_from(input).expect("failed to parse xml");
+        assert_eq!(channel.title(), "Café");
+
+        let item = channel.items().get(0).unwrap();
+        assert_eq!(item.title(), Some("Ünïcödé"));
+        assert_eq!(item.categories()[0].name(), "Français");
+        assert_eq!(item.categories()[0].domain(), Some("http://example.com/é"));
+    }
+}
+
+#[test]
+fn read_windows1252() {
+    let input = include_bytes!("data/windows1252.xml");
+    let channel = Channel::read_from(&input[..]).expect("failed to parse xml");
+
+    assert_eq!(channel.title(), "\u{201C}Quoted\u{201D} \u{20AC}5");
+    assert_eq!(channel.items()[0].title(), Some("Ünïcödé"));
+}
+
+#[test]
+fn read_latin1_references() {
+    let input = include_bytes!("data/latin1_references.xml");
+    let channel = Channel::read_from(&input[..]).expect("failed to parse xml");
+    assert_eq!(channel.title(), "Caf\u{e9} \u{2014} \u{201c}Cr\u{e8}me\u{201d}");
+    assert_eq!(channel.description(), "Fish & Chips");
+
+    let item = &channel.items()[0];
+    assert_eq!(item.title(), Some("\u{c0} la carte"));
+    let enclosure = item.enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.com/caf\u{e9}/cr\u{e8}me.mp3");
+
+    let input = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\
+                  <rss version=\"2.0\"><channel><title>\xe0&nbsp;la</title>\
+                  <item><enclosure url=\"http://example.com/?a&nbsp;\xe9\"/></item>\
+                  </channel></rss>";
+    let options = ParseOptionsBuilder::default().lenient(true).build().unwrap();
+    let (channel, _) = Channel::read_from_with_options(&input[..], &options).unwrap();
+    assert_eq!(channel.title(), "\u{e0}\u{a0}la");
+    let enclosure = channel.items()[0].enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.com/?a\u{a0}\u{e9}");
+}
+
+#[test]
+fn read_unsupported_encoding() {
+    let input = include_bytes!("data/unsupported_encoding.xml");
+    match Channel::read_from(&input[..]) {
+        Err(Error::UnsupportedEncoding(ref encoding)) => assert_eq!(encoding, "x-klingon"),
+        result => panic!("expected an unsupported encoding error, got {:?}", result),
+    }
+}
+
+#[test]
+fn read_invalid_encoding() {
+    let input = include_bytes!("data/invalid_utf8.xml");
+    match Channel::read_from(&input[..]) {
+        Err(Error::Encoding(ref encoding)) => assert_eq!(encoding, "UTF-8"),
+        result => panic!("expected an encoding error, got {:?}", result),
+    }
+
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .build()
+        .unwrap();
+    let (channel, warnings) = Channel::read_from_with_options(&input[..], &options).unwrap();
+    assert_eq!(channel.title(), "Caf\u{FFFD}");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message(), "invalid UTF-8 replaced");
+}
+
+#[test]
+fn read_invalid_encoding_attribute() {
+    let input = include_bytes!("data/invalid_utf8_attribute.xml");
+    assert!(Channel::read_from(&input[..]).is_err());
+
+    let options = ParseOptionsBuilder::default()
+        .lenient(true)
+        .build()
+        .unwrap();
+    let (channel, warnings) = Channel::read_from_with_options(&input[..], &options).unwrap();
+    let enclosure = channel.items()[0].enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.com/caf\u{FFFD}.mp3");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].message(), "invalid UTF-8 replaced");
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rating(String);
+
+impl CustomExtension for Rating {
+    fn to_extensions(&self, prefix: &str) -> Vec<Extension> {
+        let mut rating = Extension::default();
+        rating.set_name(format!("{}:rating", prefix));
+        rating.set_value(self.0.clone());
+        vec![rating]
+    }
+}
+
+fn parse_rating(
+    elements: &mut HashMap<String, Vec<Extension>>,
+) -> Result<Option<Box<dyn CustomExtension>>, Error> {
+    let rating = elements
+        .remove("rating")
+        .and_then(|mut ratings| ratings.remove(0).value().map(String::from));
+    Ok(rating.map(|rating| Box::new(Rating(rating)) as Box<dyn CustomExtension>))
+}
+
+#[test]
+fn read_custom_extension() {
+    let input = include_str!("data/custom.xml");
+    let mut options = ParseOptions::default();
+    options.register_extension("http://example.com/vendor", parse_rating);
+    let (channel, _) = Channel::read_from_with_options(input.as_bytes(), &options).unwrap();
+
+    let namespace = "http://example.com/vendor";
+    assert_eq!(
+        channel.custom_extensions().get::<Rating>(namespace),
+        Some(&Rating("PG".to_string()))
+    );
+    assert_eq!(channel.custom_extensions().prefix(namespace), Some("v"));
+    assert_eq!(channel.custom_extensions().get::<String>(namespace), None);
+    assert!(channel.extensions()["v"].contains_key("unused"));
+    assert!(!channel.extensions()["v"].contains_key("rating"));
+    assert!(channel.extensions().contains_key("other"));
+
+    assert_eq!(
+        channel.items()[0].custom_extensions().get::<Rating>(namespace),
+        Some(&Rating("R".to_string()))
+    );
+    assert!(channel.items()[0].extensions().is_empty());
+    assert!(channel.items()[1].custom_extensions().is_empty());
+
+    // without a handler the elements stay in the extension map
+    let channel = input.parse::<Channel>().unwrap();
+    assert!(channel.custom_extensions().is_empty());
+    assert!(channel.extensions()["v"].contains_key("rating"));
+}
+
+#[test]
+fn read_itunes_categories() {
+    let input = include_str!("data/itunes_categories.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let categories = channel.itunes_ext().unwrap().categories();
+
+    let paths = categories
+        .iter()
+        .map(|category| {
+            let mut path = vec![category.text()];
+            let mut subcategory = category.subcategory();
+            while let Some(category) = subcategory {
+                path.push(category.text());
+                subcategory = category.subcategory();
+            }
+            path
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        vec![
+            vec!["Technology", "Gadgets"],
+            vec!["Technology", "Podcasting"],
+            vec!["Arts", "Design", "Typography"],
+            vec!["Society & Culture"],
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn read_dedup_by_guid() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item><title>1</title><guid>a</guid><pubDate>not a date</pubDate></item>
+                <item><title>2</title></item>
+                <item>
+                    <title>3</title><guid>a</guid><pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+                <item><title>4</title></item>
+                <item><title>5</title><guid>b</guid></item>
+                <item>
+                    <title>6</title><guid>a</guid><pubDate>Sat, 31 Dec 2016 12:00:00 GMT</pubDate>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let channel = input.parse::<Channel>().unwrap();
+    let titles = |channel: &Channel| {
+        channel
+            .items()
+            .iter()
+            .map(|item| item.title().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let mut first = channel.clone();
+    first.dedup_by_guid();
+    assert_eq!(titles(&first), vec!["1", "2", "4", "5"]);
+
+    let mut latest = channel.clone();
+    latest.dedup_by_guid_latest();
+    assert_eq!(titles(&latest), vec!["2", "3", "4", "5"]);
+}
+
+#[test]
+fn read_podcast() {
+    let input = include_str!("data/podcast.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let ext = channel.podcast_ext().expect("missing podcast extension");
+
+    let locked = ext.locked().unwrap();
+    assert!(locked.is_locked());
+    assert_eq!(locked.owner(), Some("podcastowner@example.com"));
+    assert_eq!(ext.funding().len(), 2);
+    assert_eq!(ext.funding()[0].url(), "https://www.example.com/donations");
+    assert_eq!(ext.funding()[0].value(), Some("Support the show!"));
+    assert_eq!(ext.persons().len(), 1);
+    assert_eq!(ext.persons()[0].name(), "John Smith");
+    assert_eq!(ext.persons()[0].role(), None);
+    assert_eq!(ext.persons()[0].img(), Some("http://example.com/images/johnsmith.jpg"));
+    assert_eq!(ext.guid(), Some("917393e3-1b1e-5cef-ace4-edaa54e1f810"));
+    // unsupported elements are kept
+    assert!(channel.extensions()["podcast"].contains_key("medium"));
+    assert!(channel.namespaces().get("podcast").is_none());
+    assert!(channel.itunes_ext().is_some());
+
+    let ext = channel.items()[0].podcast_ext().expect("missing podcast extension");
+    assert_eq!(ext.persons().len(), 3);
+    assert_eq!(ext.persons()[1].group(), Some("writing"));
+    assert_eq!(ext.persons()[2].role(), Some("host"));
+    let chapters = ext.chapters().unwrap();
+    assert_eq!(chapters.url(), "https://example.com/ep3_chapters.json");
+    assert_eq!(chapters.mime_type(), "application/json");
+    assert_eq!(ext.transcripts().len(), 2);
+    assert_eq!(ext.transcripts()[0].mime_type(), "text/plain");
+    assert_eq!(ext.transcripts()[0].language(), None);
+    assert_eq!(ext.transcripts()[1].language(), Some("es"));
+    assert_eq!(ext.transcripts()[1].rel(), Some("captions"));
+    assert!(channel.items()[0].extensions().is_empty());
+
+    assert!(channel.items()[1].podcast_ext().is_none());
+
+    // the elements are only recognized if the namespace is declared
+    let input = input.replace(r#"xmlns:podcast="https://podcastindex.org/namespace/1.0""#, "");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.podcast_ext().is_none());
+    assert!(channel.items()[0].podcast_ext().is_none());
+    assert!(channel.extensions()["podcast"].contains_key("locked"));
+}
+
+#[test]
+fn read_podcast_repeated_chapters() {
+    let chapters = r#"<podcast:chapters url="https://example.com/ep3_chapters.json" "#;
+    let extra = r#"<podcast:chapters url="https://example.com/ep3_chapters.xml" type="text/xml"/>"#;
+    let input = include_str!("data/podcast.xml")
+        .replacen(chapters, &format!("{}{}", extra, chapters), 1);
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let item = &channel.items()[0];
+    let chapters = item.podcast_ext().unwrap().chapters().unwrap();
+    assert_eq!(chapters.url(), "https://example.com/ep3_chapters.xml");
+    let others = &item.extensions()["podcast"]["chapters"];
+    assert_eq!(others.len(), 1);
+    assert_eq!(others[0].attrs()["url"], "https://example.com/ep3_chapters.json");
+
+    let output = channel.to_string();
+    assert_eq!(output.matches("<podcast:chapters ").count(), 2);
+    assert_eq!(output.parse::<Channel>().unwrap(), channel);
+}
+
+#[test]
+fn read_and_modify() {
+    let input = include_str!("data/podcast.xml");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    channel.items_mut().retain(|item| item.enclosure().is_some());
+    assert_eq!(channel.items().len(), 1);
+
+    {
+        let item = &mut channel.items_mut()[0];
+        item.enclosure_mut().unwrap().set_length("1");
+        item.podcast_ext_mut().unwrap().transcripts_mut()[0].set_language("en".to_string());
+        item.categories_mut().push(Category::default());
+    }
+    channel.podcast_ext_mut().unwrap().persons_mut()[0].set_role("host".to_string());
+    channel.extensions_mut().remove("podcast");
+    assert!(channel.image_mut().is_none());
+
+    let item = &channel.items()[0];
+    assert_eq!(item.enclosure().unwrap().length(), "1");
+    assert_eq!(item.podcast_ext().unwrap().transcripts()[0].language(), Some("en"));
+    assert_eq!(item.categories().len(), 1);
+    assert_eq!(channel.podcast_ext().unwrap().persons()[0].role(), Some("host"));
+    assert!(channel.extensions().get("podcast").is_none());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn read_sort_by_date() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item><title>1</title><pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate></item>
+                <item><title>2</title></item>
+                <item><title>3</title><pubDate>Mon, 02 Jan 2017 12:00:00 GMT</pubDate></item>
+                <item><title>4</title><pubDate>not a date</pubDate></item>
+                <item><title>5</title><pubDate>Sun, 01 Jan 2017 13:00:00 +0100</pubDate></item>
+            </channel>
+        </rss>
+    "#;
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    let titles = |channel: &Channel| {
+        channel
+            .items()
+            .iter()
+            .map(|item| item.title().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    channel.sort_by_date(true);
+    assert_eq!(titles(&channel), vec!["3", "1", "5", "2", "4"]);
+
+    channel.sort_by_date(false);
+    assert_eq!(titles(&channel), vec!["1", "5", "3", "2", "4"]);
+
+    channel.sort_items_by_date(SortOrder::Descending);
+    assert_eq!(titles(&channel), vec!["3", "1", "5", "2", "4"]);
+
+    channel.sort_items_by_date(SortOrder::Ascending);
+    assert_eq!(titles(&channel), vec!["1", "5", "3", "2", "4"]);
+
+    channel.sort_items_by_date(SortOrder::Descending);
+    channel.truncate_items(2);
+    assert_eq!(titles(&channel), vec!["3", "1"]);
+    channel.truncate_items(5);
+    assert_eq!(titles(&channel), vec!["3", "1"]);
+}
+
+#[test]
+fn read_retain_items() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item><title>1</title></item>
+                <item><title>2</title><category>old</category></item>
+                <item><title>3</title></item>
+                <item><title>4</title><category>old</category></item>
+            </channel>
+        </rss>
+    "#;
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    let titles = |channel: &Channel| {
+        channel
+            .items()
+            .iter()
+            .map(|item| item.title().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    channel.retain_items(|item| item.categories().is_empty());
+    assert_eq!(titles(&channel), vec!["1", "3"]);
+
+    let removed = channel.remove_item(0).expect("item missing");
+    assert_eq!(removed.title(), Some("1"));
+    assert_eq!(titles(&channel), vec!["3"]);
+    assert_eq!(channel.remove_item(1), None);
+    assert_eq!(titles(&channel), vec!["3"]);
+}
+
+#[test]
+fn read_merge() {
+    let first = r#"
+        <rss version="2.0" xmlns:ex="http://example.com/one">
+            <channel>
+                <title>First</title>
+                <description>The first feed</description>
+                <item><title>1</title><ex:tag>one</ex:tag></item>
+            </channel>
+        </rss>
+    "#;
+    let second = r#"
+        <rss version="2.0" xmlns:ex="http://example.com/two" xmlns:other="http://example.com/other">
+            <channel>
+                <title>Second</title>
+                <description>The second feed</description>
+                <language>en</language>
+                <item><title>2</title><other:tag>two</other:tag></item>
+                <item><link>http://example.com/3</link></item>
+            </channel>
+        </rss>
+    "#;
+    let first = first.parse::<Channel>().expect("failed to parse xml");
+    let second = second.parse::<Channel>().expect("failed to parse xml");
+
+    let mut channel = first.clone();
+    channel.merge_prefixed(second.clone(), MergeStrategy::KeepAll);
+    assert_eq!(channel.title(), "First");
+    assert_eq!(channel.description(), "The first feed");
+    assert_eq!(channel.language(), None);
+    assert_eq!(channel.namespaces()["ex"], "http://example.com/one");
+    assert_eq!(channel.namespaces()["other"], "http://example.com/other");
+    let titles = channel.items().iter().map(|item| item.title()).collect::<Vec<_>>();
+    assert_eq!(titles, vec![Some("1"), Some("Second: 2"), Some("Second")]);
+    assert!(channel.to_string().contains("<other:tag>two</other:tag>"));
+
+    let channel = vec![first, second].into_iter().collect::<Channel>();
+    assert_eq!(channel.title(), "First");
+    let titles = channel.items().iter().map(|item| item.title()).collect::<Vec<_>>();
+    assert_eq!(titles, vec![Some("1"), Some("2"), None]);
+}
+
+#[test]
+fn read_with_buffer_capacity() {
+    let input = include_str!("data/rss2sample.xml");
+    let expected = input.parse::<Channel>().expect("failed to parse xml");
+
+    let options = ParseOptionsBuilder::default()
+        .buffer_capacity(4096)
+        .build()
+        .unwrap();
+    let (channel, warnings) = Channel::read_from_with_options(input.as_bytes(), &options)
+        .expect("failed to parse xml");
+    assert_eq!(channel, expected);
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn read_merge_dedup() {
+    let first = r#"
+        <rss version="2.0">
+            <channel>
+                <title>First</title>
+                <item>
+                    <guid>1</guid>
+                    <title>1a</title>
+                    <pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+                <item><link>http://example.com/2</link><title>2a</title></item>
+                <item><title>3a</title><pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate></item>
+                <item><description>No key</description></item>
+            </channel>
+        </rss>
+    "#;
+    let second = r#"
+        <rss version="2.0">
+            <channel>
+                <title>Second</title>
+                <item>
+                    <guid>1</guid>
+                    <title>1b</title>
+                    <pubDate>Mon, 02 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+                <item><link>http://example.com/2</link><title>2b</title></item>
+                <item><title>3a</title><pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate></item>
+                <item><title>3a</title><pubDate>Mon, 02 Jan 2017 12:00:00 GMT</pubDate></item>
+                <item><description>No key</description></item>
+            </channel>
+        </rss>
+    "#;
+    let first = first.parse::<Channel>().expect("failed to parse xml");
+    let second = second.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(first.items()[0].dedup_key(), Some(DedupKey::Guid("1")));
+    assert_eq!(first.items()[3].dedup_key(), None);
+
+    let titles = |channel: &Channel| {
+        channel
+            .items()
+            .iter()
+            .map(|item| item.title().unwrap_or("-").to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let mut channel = first.clone();
+    channel.merge(second.clone(), MergeStrategy::KeepFirst);
+    assert_eq!(channel.title(), "First");
+    assert_eq!(titles(&channel), vec!["1a", "2a", "3a", "-", "3a", "-"]);
+
+    let mut channel = first.clone();
+    channel.merge(second.clone(), MergeStrategy::KeepAll);
+    assert_eq!(channel.items().len(), 9);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn read_merge_keep_newest() {
+    let first = r#"
+        <rss version="2.0">
+            <channel>
+                <item>
+                    <guid>1</guid>
+                    <title>1a</title>
+                    <pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+                <item>
+                    <guid>2</guid>
+                    <title>2a</title>
+                    <pubDate>Mon, 02 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let second = r#"
+        <rss version="2.0">
+            <channel>
+                <item>
+                    <guid>2</guid>
+                    <title>2b</title>
+                    <pubDate>Sun, 01 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+                <item>
+                    <guid>1</guid>
+                    <title>1b</title>
+                    <pubDate>Mon, 02 Jan 2017 12:00:00 GMT</pubDate>
+                </item>
+                <item><guid>1</guid><title>1c</title><pubDate>not a date</pubDate></item>
+            </channel>
+        </rss>
+    "#;
+    let mut channel = first.parse::<Channel>().expect("failed to parse xml");
+    let second = second.parse::<Channel>().expect("failed to parse xml");
+    channel.merge(second, MergeStrategy::KeepNewest);
+
+    let titles = channel.items().iter().map(|item| item.title().unwrap()).collect::<Vec<_>>();
+    assert_eq!(titles, vec!["2a", "1b"]);
+}
+
+#[test]
+fn read_diff() {
+    let previous = r#"
+        <rss version="2.0">
+            <channel>
+                <item><guid>1</guid><title>One</title></item>
+                <item><guid>2</guid><title>Two</title></item>
+                <item>
+                    <guid isPermaLink="false">episode</guid>
+                    <link>http://example.com/3</link>
+                    <enclosure url="http://example.com/3.mp3" length="1" type="audio/mpeg"/>
+                </item>
+                <item>
+                    <guid isPermaLink="false">episode</guid>
+                    <link>http://example.com/4</link>
+                </item>
+                <item><description>Only a description</description></item>
+            </channel>
+        </rss>
+    "#;
+    let current = r#"
+        <rss version="2.0">
+            <channel>
+                <item><guid>5</guid><title>Five</title></item>
+                <item><description>Only a description</description></item>
+                <item>
+                    <guid isPermaLink="false">episode</guid>
+                    <link>http://example.com/3</link>
+                    <enclosure url="http://example.com/3b.mp3" length="1" type="audio/mpeg"/>
+                </item>
+                <item><guid>2</guid><title>Two</title></item>
+                <item><guid>1</guid><title>One</title><description>Updated</description></item>
+            </channel>
+        </rss>
+    "#;
+    let previous = previous.parse::<Channel>().expect("failed to parse xml");
+    let current = current.parse::<Channel>().expect("failed to parse xml");
+
+    let diff = current.diff(&previous);
+    assert_eq!(diff.added().len(), 1);
+    assert_eq!(diff.added()[0].title(), Some("Five"));
+    assert_eq!(diff.removed().len(), 1);
+    assert_eq!(diff.removed()[0].link(), Some("http://example.com/4"));
+    assert_eq!(diff.changed().len(), 2);
+    assert_eq!(diff.changed()[0].0.link(), Some("http://example.com/3"));
+    assert_eq!(diff.changed()[0].1.enclosure().unwrap().url(), "http://example.com/3b.mp3");
+    assert_eq!(diff.changed()[1].0.description(), None);
+    assert_eq!(diff.changed()[1].1.description(), Some("Updated"));
+
+    assert!(current.diff(&current).is_empty());
+    let diff = previous.diff(&current);
+    assert_eq!(diff.added()[0].link(), Some("http://example.com/4"));
+}
+
+#[test]
+fn read_guid_permalink_attribute() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item><guid>http://example.com/1</guid></item>
+                <item><guid isPermaLink="true">http://example.com/2</guid></item>
+                <item><guid isPermaLink="false">3</guid></item>
+                <item><guid isPermaLink=" FALSE ">4</guid></item>
+            </channel>
+        </rss>
+    "#;
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let guids = channel
+        .items()
+        .iter()
+        .map(|item| item.guid().unwrap())
+        .map(|guid| (guid.is_permalink(), guid.permalink_attribute()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        guids,
+        vec![
+            (true, None),
+            (true, Some(true)),
+            (false, Some(false)),
+            (false, Some(false)),
+        ]
+    );
+
+    let output = channel.to_string();
+    assert!(output.contains("<guid>http://example.com/1</guid>"));
+    assert!(output.contains(r#"<guid isPermaLink="true">http://example.com/2</guid>"#));
+    assert!(output.contains(r#"<guid isPermaLink="false">4</guid>"#));
+}
+
+#[test]
+fn read_error_context() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item><title>First</title></item>
+                <item>
+                    <enclosure url="http://example.com/a&b.mp3" length="1" type="audio/mpeg"/>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let err = input.parse::<Channel>().unwrap_err();
+    assert_eq!(err.element(), Some("enclosure"));
+    assert_eq!(err.attribute(), Some("url"));
+    let position = err.position().unwrap();
+    assert!(input[..position].contains("<enclosure"));
+    assert!(!input[..position].contains("</item>\n            </channel>"));
+    assert_eq!(
+        err.to_string(),
+        format!("the url attribute of <enclosure> is not valid at byte {}", position)
+    );
+    match *err.kind() {
+        Error::InvalidAttribute { ref error, .. } => match **error {
+            Error::Xml(ref xml) => {
+                // each error in the chain is the source of the one before it
+                let source = err.source().expect("missing source");
+                assert_eq!(source.to_string(), "the input is not valid XML");
+                let source = source.source().expect("missing source");
+                assert_eq!(source.to_string(), xml.to_string());
+                assert!(!err.to_string().contains(&xml.to_string()));
+            }
+            ref err => panic!("unexpected error: {:?}", err),
+        },
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+
+    let (line, column) = err.line_column(input.as_bytes()).unwrap();
+    assert_eq!(line, 6);
+    assert!(column > 1);
+
+    // errors before the first element only know their position
+    let input = "<?xml version=\"1.0\"?>\n<!-- comment";
+    let err = input.parse::<Channel>().unwrap_err();
+    assert_eq!(err.element(), None);
+    assert!(err.position().is_some());
+    assert_eq!(err.line_column(input.as_bytes()).map(|(line, _)| line), Some(2));
+
+    // errors that are not about the XML itself are returned unchanged
+    let err = "<rss><channel><item>".parse::<Channel>().unwrap_err();
+    assert!(err.element().is_none());
+    match err {
+        Error::Eof => {}
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn read_root_errors() {
+    for input in &["", "<?xml version=\"1.0\"?>", "<!-- comment -->"] {
+        match input.parse::<Channel>() {
+            Err(Error::MissingRootElement) => {}
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    let input = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Title</title></feed>"#;
+    let err = input.parse::<Channel>().unwrap_err();
+    match err {
+        Error::NotRss { detected: FeedKind::Atom } => {}
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+    assert!(err.to_string().contains("Channel::read_any"));
+
+    let input = r#"<a:feed xmlns:a="http://www.w3.org/2005/Atom"></a:feed>"#;
+    match input.parse::<Channel>() {
+        Err(Error::NotRss { detected: FeedKind::Atom }) => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    // a <feed> outside of the Atom namespace is only named
+    let err = "<feed><title>Title</title></feed>".parse::<Channel>().unwrap_err();
+    match err {
+        Error::UnexpectedRoot { ref found } if found == "feed" => {}
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+    assert!(err.to_string().contains("Atom"));
+
+    match "<html><body/></html>".parse::<Channel>() {
+        Err(Error::UnexpectedRoot { ref found }) if found == "html" => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    match "".parse::<Item>() {
+        Err(Error::MissingRootElement) => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn read_root_errors_deprecated() {
+    // the error that was returned before the root element errors existed can still be matched
+    match "<html/>".parse::<Channel>() {
+        Err(Error::InvalidStartTag) => panic!("the deprecated error is no longer returned"),
+        Err(_) => {}
+        Ok(channel) => panic!("unexpected channel: {:?}", channel),
+    }
+}
+
+#[test]
+fn read_limits() {
+    // deeply nested extensions fail at the default depth limit
+    let input = format!(
+        "<rss><channel><ext:a>{}{}</ext:a></channel></rss>",
+        "<ext:a>".repeat(10000),
+        "</ext:a>".repeat(10000)
+    );
+    let err = input.parse::<Channel>().unwrap_err();
+    match *err.kind() {
+        Error::LimitExceeded { kind: LimitKind::Depth, limit: 128 } => {}
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+
+    let input = include_str!("data/rss2sample.xml");
+
+    let options = ParseOptionsBuilder::default()
+        .max_items(2usize)
+        .build()
+        .unwrap();
+    let err = Channel::read_from_with_options(input.as_bytes(), &options).unwrap_err();
+    match *err.kind() {
+        Error::LimitExceeded { kind: LimitKind::Items, limit: 2 } => {}
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+
+    let mut reader = ItemReader::with_options(input.as_bytes(), &options).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+
+    let options = ParseOptionsBuilder::default()
+        .max_length(16usize)
+        .build()
+        .unwrap();
+    let err = Channel::read_from_with_options(input.as_bytes(), &options).unwrap_err();
+    match *err.kind() {
+        Error::LimitExceeded { kind: LimitKind::Length, limit: 16 } => {}
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn read_from_slice() {
+    let input = include_str!("data/rss2sample.xml");
+    let channel = Channel::read_from_slice(input.as_bytes()).expect("failed to parse xml");
+    assert_eq!(channel, input.parse::<Channel>().expect("failed to parse xml"));
+    assert_eq!(channel.title(), "Liftoff News");
+}
+
+#[test]
+fn read_metadata_from() {
+    let input = include_str!("data/rss2sample.xml");
+    let (channel, count) =
+        Channel::read_metadata_from(input.as_bytes()).expect("failed to parse xml");
+    assert_eq!(channel.title(), "Liftoff News");
+    assert_eq!(channel.link(), "http://liftoff.msfc.nasa.gov/");
+    assert_eq!(channel.last_build_date(), Some("Tue, 10 Jun 2003 09:41:01 GMT"));
+    assert!(channel.items().is_empty());
+    assert_eq!(count, 4);
+
+    let mut expected = input.parse::<Channel>().expect("failed to parse xml");
+    expected.set_items(Vec::new());
+    assert_eq!(channel, expected);
+
+    let input = include_str!("data/interleaved.xml");
+    let (channel, count) =
+        Channel::read_metadata_from(input.as_bytes()).expect("failed to parse xml");
+    let expected = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(count, expected.items().len());
+    assert_eq!(channel.title(), expected.title());
+    assert_eq!(channel.description(), expected.description());
+
+    let input = "<rss><channel><item><title>Title</item></channel></rss>";
+    assert!(Channel::read_metadata_from(input.as_bytes()).is_err());
+}
+
+#[test]
+fn read_xml_base() {
+    let input = r#"
+        <rss version="2.0">
+            <channel xml:base="http://example.com/">
+                <item xml:base="posts/"><title>Title</title></item>
+                <item><title>Title</title></item>
+            </channel>
+        </rss>
+    "#;
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(channel.base(), Some("http://example.com/"));
+    assert_eq!(channel.items()[0].base(), Some("posts/"));
+    assert_eq!(channel.items()[1].base(), None);
+
+    let item = r#"<item xml:base="http://example.com/"><title>Title</title></item>"#;
+    let item = item.parse::<Item>().expect("failed to parse xml");
+    assert_eq!(item.base(), Some("http://example.com/"));
+}
+
+#[test]
+fn read_stylesheets() {
+    let input = include_str!("data/stylesheet.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let stylesheets = channel.stylesheets();
+    assert_eq!(stylesheets.len(), 2);
+    assert_eq!(stylesheets[0].href(), "feed.xsl");
+    assert_eq!(stylesheets[0].media_type(), "text/xsl");
+    assert_eq!(stylesheets[1].href(), "feed.css&v=2");
+    assert_eq!(stylesheets[1].media_type(), "text/css");
+}
+
+#[test]
+fn read_item_links() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <item>
+                    <link rel="related" type="text/html" href="http://example.com/related"/>
+                    <link>http://example.com/post</link>
+                    <link rel="alternate" type="application/pdf">http://example.com/post.pdf</link>
+                    <link>http://example.com/again</link>
+                </item>
+                <item>
+                    <link rel="alternate" href="http://example.com/atom"/>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let item = &channel.items()[0];
+    assert_eq!(item.link(), Some("http://example.com/post"));
+    let links = item.links()
+        .iter()
+        .map(|link| (link.href(), link.rel(), link.mime_type()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        links,
+        vec![
+            ("http://example.com/related", Some("related"), Some("text/html")),
+            ("http://example.com/post.pdf", Some("alternate"), Some("application/pdf")),
+            ("http://example.com/again", None, None),
+        ]
+    );
+
+    let item = &channel.items()[1];
+    assert_eq!(item.link(), Some("http://example.com/atom"));
+    assert!(item.links().is_empty());
+}
+
+#[test]
+fn read_item_from_str() {
+    let input = r#"
+        <?xml version="1.0"?>
+        <item xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <title>Title</title>
+            <enclosure url="http://example.com/a.mp3" length="1" type="audio/mpeg"/>
+            <itunes:author>Author</itunes:author>
+        </item>
+    "#;
+    let item = input.parse::<Item>().expect("failed to parse xml");
+    assert_eq!(item.title(), Some("Title"));
+    assert_eq!(item.enclosure().unwrap().url(), "http://example.com/a.mp3");
+    assert_eq!(item.itunes_ext().unwrap().author(), Some("Author"));
+
+    let channel = include_str!("data/rss2sample.xml").parse::<Channel>().unwrap();
+    let item = &channel.items()[0];
+    assert_eq!(item.to_string().parse::<Item>().unwrap(), *item);
+
+    match "<channel></channel>".parse::<Item>() {
+        Err(Error::UnexpectedRoot { ref found }) if found == "channel" => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+    match "<item><title>Title</title>".parse::<Item>() {
+        Err(Error::Eof) => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn read_atom_links() {
+    let input = include_str!("data/atom_links.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let links = channel.atom_links();
+    let rels = links.iter().map(|link| link.rel().unwrap()).collect::<Vec<_>>();
+    assert_eq!(rels, vec!["self", "first", "prev", "next", "last", "payment"]);
+    assert_eq!(links[0].href(), "http://example.com/feed?page=2");
+    assert_eq!(links[0].mime_type(), Some("application/rss+xml"));
+
+    assert_eq!(channel.first_page_url(), Some("http://example.com/feed?page=1"));
+    assert_eq!(channel.prev_page_url(), Some("http://example.com/feed?page=1"));
+    assert_eq!(channel.next_page_url(), Some("http://example.com/feed?page=3"));
+    assert_eq!(channel.last_page_url(), Some("http://example.com/feed?page=9"));
+
+    // a link with attributes that a `Link` does not have is kept as an extension
+    let extensions = &channel.extensions()["atom"]["link"];
+    assert_eq!(extensions.len(), 1);
+    assert_eq!(extensions[0].attrs()["hreflang"], "fr");
+
+    // the links are only recognized in the Atom namespace
+    let input = input.replace("http://www.w3.org/2005/Atom", "http://example.com/ns");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.atom_links().is_empty());
+    assert_eq!(channel.extensions()["atom"]["link"].len(), 7);
+}
+
+#[test]
+fn read_websub_links() {
+    let input = include_str!("data/websub.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(
+        channel.hubs(),
+        vec!["https://pubsubhubbub.appspot.com/", "https://websub.example.com/hub"]
+    );
+    assert_eq!(channel.atom_link_self(), Some("http://example.com/feed.xml"));
+    assert_eq!(channel.atom_links().len(), 3);
+}
+
+#[test]
+fn read_atom_link_self() {
+    // the Atom namespace is recognized whatever prefix it is bound to
+    let input = include_str!("data/atom_self.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(channel.atom_link_self(), Some("http://example.com/feed.xml"));
+    assert_eq!(channel.next_page_url(), Some("http://example.com/feed.xml?page=2"));
+    assert_eq!(channel.atom_links().len(), 2);
+    assert!(channel.extensions().is_empty());
+}
+
+#[test]
+fn read_syndication() {
+    let input = include_str!("data/syndication.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let ext = channel.syndication_ext().expect("missing syndication extension");
+
+    assert_eq!(ext.update_period(), Some(UpdatePeriod::Hourly));
+    assert_eq!(ext.update_frequency(), Some(4));
+    assert_eq!(ext.update_base(), Some("2000-01-01T12:00+00:00"));
+    assert_eq!(ext.refresh_interval(), Some(Duration::from_secs(15 * 60)));
+    assert_eq!(" Weekly ".parse::<UpdatePeriod>(), Ok(UpdatePeriod::Weekly));
+    assert_eq!(UpdatePeriod::Monthly.to_string(), "monthly");
+    assert!(channel.extensions().is_empty());
+    assert!(channel.namespaces().is_empty());
+
+    // the namespace is recognized whatever its prefix, and unknown values are kept
+    let input = input
+        .replace("xmlns:sy=", "xmlns:syn=")
+        .replace("sy:", "syn:")
+        .replace(">hourly<", ">fortnightly<");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let ext = channel.syndication_ext().expect("missing syndication extension");
+    assert_eq!(ext.update_period(), None);
+    assert_eq!(ext.update_frequency(), Some(4));
+    assert_eq!(
+        get_extension_values(&channel.extensions()["syn"], "updatePeriod"),
+        Some(vec!["fortnightly"])
+    );
+
+    // the elements of every prefix that the namespace is declared with are recognized
+    let namespace = r#"xmlns:syn="http://purl.org/rss/1.0/modules/syndication/""#;
+    let input = include_str!("data/syndication.xml")
+        .replace("xmlns:sy=", &format!("{} xmlns:sy=", namespace))
+        .replace("sy:updateFrequency", "syn:updateFrequency");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let ext = channel.syndication_ext().expect("missing syndication extension");
+    assert_eq!(ext.update_period(), Some(UpdatePeriod::Hourly));
+    assert_eq!(ext.update_frequency(), Some(4));
+    assert!(channel.extensions().is_empty());
+
+    // the elements are only recognized if the namespace is declared
+    let input = include_str!("data/syndication.xml")
+        .replace(r#"xmlns:sy="http://purl.org/rss/1.0/modules/syndication/""#, "");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.syndication_ext().is_none());
+}
+
+#[test]
+fn read_slash() {
+    let input = include_str!("data/slash.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.namespaces().is_empty());
+
+    let item = &channel.items()[0];
+    let ext = item.slash_ext().expect("missing slash extension");
+    assert_eq!(ext.comments(), Some(42));
+    assert_eq!(ext.hit_parade(), Some("42,40,31,12,5,2,0"));
+    assert_eq!(ext.department(), Some("some-department"));
+    assert_eq!(ext.section(), Some("articles"));
+    assert!(item.extensions().is_empty());
+
+    // a comment count that is not a number is kept as an extension
+    let item = &channel.items()[1];
+    assert_eq!(item.slash_ext().unwrap().comments(), None);
+    assert_eq!(
+        get_extension_values(&item.extensions()["slash"], "comments"),
+        Some(vec!["many"])
+    );
+
+    // the elements of every prefix that the namespace is declared with are recognized
+    let namespace = r#"xmlns:sl="http://purl.org/rss/1.0/modules/slash/""#;
+    let input = include_str!("data/slash.xml")
+        .replace("xmlns:slash=", &format!("{} xmlns:slash=", namespace))
+        .replace("slash:section", "sl:section");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let item = &channel.items()[0];
+    let ext = item.slash_ext().expect("missing slash extension");
+    assert_eq!(ext.comments(), Some(42));
+    assert_eq!(ext.section(), Some("articles"));
+    assert!(item.extensions().is_empty());
+
+    // the elements are only recognized if the namespace is declared
+    let input = include_str!("data/slash.xml")
+        .replace(r#"xmlns:slash="http://purl.org/rss/1.0/modules/slash/""#, "");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.items()[0].slash_ext().is_none());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn read_syndication_update_base() {
+    let input = include_str!("data/syndication.xml");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    let ext = channel.syndication_ext_mut().unwrap();
+    let base = ext.update_base_parsed().unwrap();
+    assert_eq!(base.to_rfc3339(), "2000-01-01T12:00:00+00:00");
+
+    for &(input, expected) in &[
+        ("2017-06-05", Some("2017-06-05T00:00:00+00:00")),
+        ("2017", Some("2017-01-01T00:00:00+00:00")),
+        ("2017-06-05T10:30:15.25Z", Some("2017-06-05T10:30:15+00:00")),
+        ("2017-06-05T10:30-05:00", Some("2017-06-05T10:30:00-05:00")),
+        ("2017-06-05T10:30", None),
+        ("June 5th", None),
+    ] {
+        ext.set_update_base(input.to_string());
+        assert_eq!(
+            ext.update_base_parsed().map(|date| date.to_rfc3339()),
+            expected.map(|date| date.to_string())
+        );
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "resolve_urls")]
+
+extern crate rss;
+
+use rss::Channel;
+
+#[test]
+fn resolve_relative_urls() {
+    let input = include_str!("data/relative.xml");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    channel.resolve_relative_urls();
+
+    let items = channel.items();
+    assert_eq!(items[0].link(), Some("http://example.com/blog/posts/1"));
+    assert_eq!(
+        items[0].enclosure().map(|enclosure| enclosure.url()),
+        Some("http://example.com/audio/1.mp3")
+    );
+    assert_eq!(
+        items[0].source().map(|source| source.url()),
+        Some("http://example.com/feed.xml")
+    );
+
+    assert_eq!(items[1].link(), Some("https://example.org/posts/2"));
+    assert_eq!(
+        items[1].enclosure().map(|enclosure| enclosure.url()),
+        Some("https://cdn.example.org/2.mp3")
+    );
+
+    assert_eq!(items[2].link(), Some("http://[invalid"));
+}
+
+#[test]
+fn resolve_relative_urls_xml_base() {
+    let input = r#"
+        <rss version="2.0">
+            <channel xml:base="http://example.com/blog/">
+                <link>http://example.org/</link>
+                <item>
+                    <link>posts/1</link>
+                    <link rel="related" href="related/1"/>
+                </item>
+                <item xml:base="archive/">
+                    <link>posts/2</link>
+                </item>
+                <item xml:base="https://cdn.example.com/">
+                    <link>posts/3</link>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    channel.resolve_relative_urls();
+
+    let links = channel
+        .items()
+        .iter()
+        .map(|item| item.link().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        links,
+        vec![
+            "http://example.com/blog/posts/1",
+            "http://example.com/blog/archive/posts/2",
+            "https://cdn.example.com/posts/3",
+        ]
+    );
+    assert_eq!(
+        channel.items()[0].links()[0].href(),
+        "http://example.com/blog/related/1"
+    );
+
+    let input = input.replace("http://example.com/blog/", "blog/");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    channel.resolve_relative_urls();
+    assert_eq!(channel.items()[0].link(), Some("http://example.org/blog/posts/1"));
+}
+
+#[test]
+fn resolve_relative_urls_without_base() {
+    let input = include_str!("data/relative.xml").replace("http://example.com/blog/", "blog");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    let expected = channel.clone();
+    channel.resolve_relative_urls();
+
+    assert_eq!(channel, expected);
+}
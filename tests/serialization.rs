@@ -0,0 +1,90 @@
+#![cfg(feature = "serialization")]
+
+extern crate rss;
+extern crate serde_json;
+
+use rss::{Channel, ChannelBuilder, ItemBuilder};
+
+fn round_trip(input: &str) {
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let json = serde_json::to_value(&channel).expect("failed to serialize");
+    let parsed = serde_json::from_value::<Channel>(json.clone()).expect("failed to deserialize");
+    assert_eq!(channel, parsed);
+
+    let expected = channel.to_string().parse::<Channel>().expect("failed to parse written xml");
+    let written = parsed.to_string().parse::<Channel>().expect("failed to parse written xml");
+    assert_eq!(expected, written);
+}
+
+#[test]
+fn serialization_round_trip() {
+    round_trip(include_str!("data/rss2sample.xml"));
+    round_trip(include_str!("data/channel.xml"));
+    round_trip(include_str!("data/item.xml"));
+    round_trip(include_str!("data/category.xml"));
+    round_trip(include_str!("data/cloud.xml"));
+    round_trip(include_str!("data/enclosure.xml"));
+    round_trip(include_str!("data/guid.xml"));
+    round_trip(include_str!("data/image.xml"));
+    round_trip(include_str!("data/source.xml"));
+    round_trip(include_str!("data/textinput.xml"));
+    round_trip(include_str!("data/stylesheet.xml"));
+    round_trip(include_str!("data/extension.xml"));
+    round_trip(include_str!("data/itunes.xml"));
+    round_trip(include_str!("data/dublincore.xml"));
+    round_trip(include_str!("data/syndication.xml"));
+    round_trip(include_str!("data/podcast.xml"));
+    round_trip(include_str!("data/media.xml"));
+    round_trip(include_str!("data/slash.xml"));
+}
+
+#[test]
+fn serialization_field_names() {
+    let input = include_str!("data/itunes.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let value = serde_json::to_value(&channel).expect("failed to serialize");
+
+    assert!(value["itunes"].is_object());
+    assert!(value.get("itunes_ext").is_none());
+    assert!(value.get("itunesExt").is_none());
+    assert!(value.get("customExtensions").is_none());
+
+    let channel = ChannelBuilder::default()
+        .title("Title")
+        .webmaster("webmaster@example.com".to_string())
+        .items(vec![ItemBuilder::default()
+            .title("Item".to_string())
+            .pub_date("Sun, 13 Mar 2011 19:40:00 GMT".to_string())
+            .build()
+            .unwrap()])
+        .build()
+        .unwrap();
+    let value = serde_json::to_value(&channel).expect("failed to serialize");
+
+    assert_eq!(value["title"], "Title");
+    assert_eq!(value["webMaster"], "webmaster@example.com");
+    assert_eq!(value["items"][0]["title"], "Item");
+    assert_eq!(value["items"][0]["pubDate"], "Sun, 13 Mar 2011 19:40:00 GMT");
+    assert!(value.get("language").is_none());
+    assert!(value.get("categories").is_none());
+    assert!(value["items"][0].get("enclosure").is_none());
+}
+
+#[test]
+fn deserialization_defaults() {
+    let json = r#"{
+        "title": "Title",
+        "unknown": [1, 2, 3],
+        "items": [{"title": "Item", "enclosure": {"url": "http://example.com/a.mp3",
+            "length": "100", "type": "audio/mpeg", "unknown": true}}]
+    }"#;
+    let channel = serde_json::from_str::<Channel>(json).expect("failed to deserialize");
+
+    assert_eq!(channel.title(), "Title");
+    assert_eq!(channel.link(), "");
+    assert_eq!(channel.language(), None);
+    assert_eq!(channel.items().len(), 1);
+    let enclosure = channel.items()[0].enclosure().unwrap();
+    assert_eq!(enclosure.url(), "http://example.com/a.mp3");
+    assert_eq!(enclosure.mime_type(), "audio/mpeg");
+}
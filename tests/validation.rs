@@ -0,0 +1,316 @@
+#![cfg(feature = "validation")]
+
+extern crate rss;
+
+use rss::{Channel, ChannelBuilder, EnclosureBuilder, GuidBuilder, ImageBuilder, ItemBuilder,
+          TextInput};
+use rss::extension::itunes::{ITunesCategoryBuilder, categories};
+use rss::validation::{BuilderError, Validate};
+
+fn channel() -> ChannelBuilder {
+    let mut builder = ChannelBuilder::default();
+    builder
+        .title("Title")
+        .link("http://example.com/")
+        .description("Description");
+    builder
+}
+
+#[test]
+fn validate_sample() {
+    let input = include_str!("data/rss2sample.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    channel.validate().unwrap();
+}
+
+#[test]
+fn validate_required_channel_elements() {
+    let err = Channel::default().validate().unwrap_err();
+    assert_eq!(err.element(), Some("title"));
+    assert_eq!(err.item(), None);
+
+    let err = channel().link("example.com").build().unwrap().validate().unwrap_err();
+    assert_eq!(err.element(), Some("link"));
+
+    let err = channel().description("").build().unwrap().validate().unwrap_err();
+    assert_eq!(err.element(), Some("description"));
+}
+
+#[test]
+fn validate_item_index() {
+    let good = ItemBuilder::default()
+        .title("Title".to_string())
+        .build()
+        .unwrap();
+    let empty = ItemBuilder::default().build().unwrap();
+    let channel = channel().items(vec![good, empty]).build().unwrap();
+
+    let err = channel.validate().unwrap_err();
+    assert_eq!(err.element(), Some("item"));
+    assert_eq!(err.item(), Some(1));
+}
+
+#[test]
+fn validate_enclosure() {
+    let enclosure = EnclosureBuilder::default()
+        .url("http://example.com/audio.mp3")
+        .mime_type("audio/mpeg")
+        .length("0")
+        .build()
+        .unwrap();
+    let item = ItemBuilder::default()
+        .title("Title".to_string())
+        .enclosure(enclosure.clone())
+        .build()
+        .unwrap();
+    channel().items(vec![item.clone()]).build().unwrap().validate().unwrap();
+
+    let mut bad = enclosure.clone();
+    bad.set_length("-1");
+    let mut bad_item = item.clone();
+    bad_item.set_enclosure(bad);
+    let err = channel()
+        .items(vec![item.clone(), item.clone(), bad_item])
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.element(), Some("enclosure.length"));
+    assert_eq!(err.item(), Some(2));
+    assert_eq!(
+        err.to_string(),
+        "item 2 enclosure.length: Enclosure length is negative"
+    );
+
+    let mut bad = enclosure;
+    bad.set_url("/audio.mp3");
+    let mut bad_item = item;
+    bad_item.set_enclosure(bad);
+    let err = channel()
+        .items(vec![bad_item])
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.element(), Some("enclosure.url"));
+    assert_eq!(err.item(), Some(0));
+}
+
+#[test]
+fn validate_channel_elements() {
+    let err = channel().ttl("sixty".to_string()).build().unwrap().validate().unwrap_err();
+    assert_eq!(err.element(), Some("ttl"));
+
+    let err = channel()
+        .skip_hours(vec!["24".to_string()])
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.element(), Some("skipHours"));
+
+    let image = ImageBuilder::default()
+        .url("http://example.com/image.png")
+        .link("http://example.com/")
+        .width("144".to_string())
+        .height("400".to_string())
+        .build()
+        .unwrap();
+    channel().image(image.clone()).build().unwrap().validate().unwrap();
+
+    let mut bad = image;
+    bad.set_height("401".to_string());
+    let err = channel().image(bad).build().unwrap().validate().unwrap_err();
+    assert_eq!(err.element(), Some("image.height"));
+    assert_eq!(err.item(), None);
+
+    let mut text_input = TextInput::default();
+    text_input.set_title("Search");
+    text_input.set_description("Search the archives");
+    text_input.set_link("http://example.com/search");
+    let err = channel().text_input(text_input).build().unwrap().validate().unwrap_err();
+    assert_eq!(err.element(), Some("textInput.name"));
+}
+
+#[test]
+fn validate_guid_permalink() {
+    let mut guid = GuidBuilder::default().value("not a url").build().unwrap();
+    let item = ItemBuilder::default()
+        .title("Title".to_string())
+        .guid(guid.clone())
+        .build()
+        .unwrap();
+    let err = channel().items(vec![item]).build().unwrap().validate().unwrap_err();
+    assert_eq!(err.element(), Some("guid"));
+    assert_eq!(err.item(), Some(0));
+
+    guid.set_permalink(false);
+    let item = ItemBuilder::default()
+        .title("Title".to_string())
+        .guid(guid)
+        .build()
+        .unwrap();
+    channel().items(vec![item]).build().unwrap().validate().unwrap();
+}
+
+#[test]
+fn validate_all() {
+    let input = include_str!("data/rss2sample.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    channel.validate_all().unwrap();
+
+    let enclosure = EnclosureBuilder::default()
+        .length("many")
+        .build()
+        .unwrap();
+    let items = vec![
+        ItemBuilder::default().build().unwrap(),
+        ItemBuilder::default()
+            .title("Title".to_string())
+            .enclosure(enclosure)
+            .build()
+            .unwrap(),
+    ];
+    let errors = Channel::default()
+        .validate_all()
+        .unwrap_err()
+        .into_iter()
+        .map(|err| err.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            "title: Channel title is empty",
+            "link: Channel link is empty",
+            "description: Channel description is empty",
+        ]
+    );
+
+    let errors = ChannelBuilder::default()
+        .items(items)
+        .build()
+        .unwrap()
+        .validate_all()
+        .unwrap_err();
+    let elements = errors
+        .iter()
+        .map(|err| (err.item(), err.element()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        elements,
+        vec![
+            (None, Some("title")),
+            (None, Some("link")),
+            (None, Some("description")),
+            (Some(0), Some("item")),
+            (Some(1), Some("enclosure.url")),
+        ]
+    );
+}
+
+#[test]
+fn build_validated() {
+    let built = channel().build_validated().unwrap();
+    assert_eq!(built.title(), "Title");
+
+    let err = channel().ttl("0".to_string()).build_validated().unwrap_err();
+    assert_eq!(err.element(), Some("ttl"));
+
+    let item = ItemBuilder::default()
+        .description("Description".to_string())
+        .build_validated()
+        .unwrap();
+    assert_eq!(item.description(), Some("Description"));
+
+    let err = EnclosureBuilder::default()
+        .url("http://example.com/audio.mp3")
+        .mime_type("audio/mpeg")
+        .length("")
+        .build_validated()
+        .unwrap_err();
+    assert_eq!(err.element(), Some("length"));
+
+    // build still allows partial values
+    assert!(EnclosureBuilder::default().build().is_ok());
+}
+
+#[test]
+fn validated_build() {
+    let built = channel().validated_build().unwrap();
+    assert_eq!(built.title(), "Title");
+
+    match channel().description("").validated_build() {
+        Err(BuilderError::MissingField("description")) => {}
+        result => panic!("expected a missing description, got {:?}", result),
+    }
+
+    let item = ItemBuilder::default()
+        .title("Title".to_string())
+        .pub_date("yesterday".to_string())
+        .build()
+        .unwrap();
+    match channel().items(vec![item]).validated_build() {
+        Err(BuilderError::InvalidValue { ref field, ref error }) => {
+            assert_eq!(field, "pubDate");
+            assert_eq!(error.item(), Some(0));
+        }
+        result => panic!("expected an invalid pubDate, got {:?}", result),
+    }
+
+    match ItemBuilder::default().validated_build() {
+        Err(BuilderError::MissingField("title")) => {}
+        result => panic!("expected a missing title, got {:?}", result),
+    }
+
+    match EnclosureBuilder::default().mime_type("audio/mpeg").validated_build() {
+        Err(BuilderError::MissingField("url")) => {}
+        result => panic!("expected a missing url, got {:?}", result),
+    }
+
+    let err = EnclosureBuilder::default()
+        .url("http://example.com/audio.mp3")
+        .length("1024")
+        .mime_type("audio")
+        .validated_build()
+        .unwrap_err();
+    assert_eq!(err.field(), "type");
+    assert_eq!(err.to_string(), "invalid value: type: Unable to parse MIME type");
+}
+
+#[test]
+fn validate_itunes_categories() {
+    let input = include_str!("data/itunes_categories.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let categories = channel.itunes_ext().unwrap().categories();
+
+    // Gadgets and Podcasting were removed from the list in 2019.
+    assert!(categories[0].validate_against_apple_list().is_err());
+    assert!(categories[1].validate_against_apple_list().is_err());
+    let err = categories[2].validate_against_apple_list().unwrap_err();
+    assert_eq!(err.element(), Some("category"));
+    categories[3].validate_against_apple_list().unwrap();
+
+    let category = ITunesCategoryBuilder::default()
+        .text(categories::TECHNOLOGY)
+        .build()
+        .unwrap();
+    category.validate_against_apple_list().unwrap();
+
+    let category = ITunesCategoryBuilder::default()
+        .text("Tech")
+        .build()
+        .unwrap();
+    let err = category.validate_against_apple_list().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown iTunes category: Tech");
+
+    let subcategory = ITunesCategoryBuilder::default()
+        .text("Design")
+        .build()
+        .unwrap();
+    let category = ITunesCategoryBuilder::default()
+        .text(categories::ARTS)
+        .subcategory(Box::new(subcategory))
+        .build()
+        .unwrap();
+    category.validate_against_apple_list().unwrap();
+}
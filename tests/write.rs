@@ -1,6 +1,13 @@
+extern crate quick_xml;
 extern crate rss;
 
-use rss::{Channel, ChannelBuilder, ItemBuilder, extension};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+
+use rss::{Category, Channel, ChannelBuilder, ChannelWriter, CloudBuilder, EnclosureBuilder, Error,
+          Guid, Image, InvalidCharPolicy, Item, ItemBuilder, LinkBuilder, ParseOptions, Source,
+          Version, WriteOptions, WriteOptionsBuilder, extension};
+use rss::extension::custom::CustomExtension;
 use std::collections::HashMap;
 
 macro_rules! test_write {
@@ -11,6 +18,41 @@ macro_rules! test_write {
     })
 }
 
+/// Return the names of the children of each item, in the order they appear.
+fn item_children(xml: &[u8]) -> Vec<Vec<String>> {
+    let mut reader = Reader::from_reader(xml);
+    reader.trim_text(true).expand_empty_elements(true);
+
+    let mut buf = Vec::new();
+    let mut items: Vec<Vec<String>> = Vec::new();
+    let mut depth = 0;
+    let mut item_depth = None;
+    loop {
+        match reader.read_event(&mut buf).expect("failed to parse xml") {
+            Event::Start(element) => {
+                depth += 1;
+                if item_depth == Some(depth - 1) {
+                    let name = String::from_utf8(element.name().to_vec()).unwrap();
+                    items.last_mut().unwrap().push(name);
+                } else if item_depth.is_none() && element.name() == b"item" {
+                    item_depth = Some(depth);
+                    items.push(Vec::new());
+                }
+            }
+            Event::End(_) => {
+                if item_depth == Some(depth) {
+                    item_depth = None;
+                }
+                depth -= 1;
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    items
+}
+
 #[test]
 fn write_channel() {
     let input = include_str!("data/channel.xml");
@@ -18,6 +60,109 @@ fn write_channel() {
     test_write!(channel);
 }
 
+#[test]
+fn write_rdf() {
+    let input = include_str!("data/rdf.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    test_write!(channel);
+
+    let output = channel.to_string();
+    assert!(output.starts_with("<rss version=\"2.0\""));
+    assert!(!output.contains("<items>"));
+}
+
+#[test]
+fn write_xml_base() {
+    let item = ItemBuilder::default()
+        .base("posts/?a=1&b=2".to_string())
+        .build()
+        .unwrap();
+    let channel = ChannelBuilder::default()
+        .base("http://example.com/".to_string())
+        .items(vec![item])
+        .build()
+        .unwrap();
+
+    let output = channel.to_string();
+    assert!(output.contains("<channel xml:base=\"http://example.com/\">"));
+    assert!(output.contains("<item xml:base=\"posts/?a=1&amp;b=2\">"));
+    test_write!(channel);
+}
+
+#[test]
+fn write_stylesheets() {
+    let input = include_str!("data/stylesheet.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.to_string();
+    assert!(output.starts_with(
+        "<?xml-stylesheet type=\"text/xsl\" href=\"feed.xsl\"?>\
+         <?xml-stylesheet type=\"text/css\" href=\"feed.css&amp;v=2\"?><rss"
+    ));
+    test_write!(channel);
+
+    let output = Channel::default()
+        .write_with_stylesheet(Vec::new(), "feed.xsl", "text/xsl")
+        .unwrap();
+    let channel = Channel::read_from(&output[..]).unwrap();
+    assert_eq!(channel.stylesheets()[0].href(), "feed.xsl");
+    assert_eq!(channel.stylesheets()[0].media_type(), "text/xsl");
+
+    let output = channel.pretty_write_to(Vec::new(), b' ', 2).unwrap();
+    assert!(String::from_utf8(output).unwrap().starts_with(
+        "<?xml-stylesheet type=\"text/xsl\" href=\"feed.xsl\"?>\n<rss"
+    ));
+}
+
+#[test]
+fn write_item_order() {
+    let inputs = [
+        include_str!("data/item_order.xml"),
+        include_str!("data/rss2sample.xml"),
+        include_str!("data/item.xml"),
+        include_str!("data/category.xml"),
+        include_str!("data/dublincore.xml"),
+    ];
+
+    for input in &inputs {
+        let channel = input.parse::<Channel>().expect("failed to parse xml");
+        let output = channel.to_string();
+        assert_eq!(item_children(output.as_bytes()), item_children(input.as_bytes()));
+        test_write!(channel);
+    }
+
+    let item = ItemBuilder::default()
+        .guid(Guid::default())
+        .title("Title".to_string())
+        .build()
+        .unwrap();
+    let channel = ChannelBuilder::default().items(vec![item]).build().unwrap();
+    assert_eq!(item_children(channel.to_string().as_bytes()), vec![vec!["title", "guid"]]);
+}
+
+#[test]
+fn write_item_links() {
+    let related = LinkBuilder::default()
+        .href("http://example.com/related?a=1&b=2")
+        .rel("related".to_string())
+        .mime_type("text/html".to_string())
+        .build()
+        .unwrap();
+    let item = ItemBuilder::default()
+        .link("http://example.com/post".to_string())
+        .links(vec![related])
+        .build()
+        .unwrap();
+    let channel = ChannelBuilder::default().items(vec![item]).build().unwrap();
+
+    let output = channel.to_string();
+    assert!(output.contains(
+        "<link>http://example.com/post</link>\
+         <link rel=\"related\" type=\"text/html\">http://example.com/related?a=1&amp;b=2</link>"
+    ));
+    test_write!(channel);
+}
+
 #[test]
 fn write_item() {
     let input = include_str!("data/item.xml");
@@ -32,6 +177,25 @@ fn write_content() {
     test_write!(channel);
 }
 
+#[test]
+fn write_content_namespace() {
+    let item = ItemBuilder::default()
+        .content("<p>Body</p>".to_string())
+        .build()
+        .unwrap();
+    let channel = ChannelBuilder::default().items(vec![item]).build().unwrap();
+
+    let output = channel.to_string();
+    assert!(output.contains(
+        "<rss version=\"2.0\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">"
+    ));
+    assert!(output.contains("<content:encoded><![CDATA[<p>Body</p>]]></content:encoded>"));
+
+    let input = include_str!("data/content.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(channel.to_string().matches("xmlns:content=").count(), 1);
+}
+
 #[test]
 fn write_source() {
     let input = include_str!("data/source.xml");
@@ -44,6 +208,11 @@ fn write_guid() {
     let input = include_str!("data/guid.xml");
     let channel = input.parse::<Channel>().expect("failed to parse xml");
     test_write!(channel);
+
+    let output = channel.to_string();
+    assert!(output.contains(r#"<guid isPermaLink="false">abc</guid>"#));
+    assert!(output.contains("<guid>def</guid>"));
+    assert!(output.contains(r#"<guid isPermaLink="true">http://example.com/ghi</guid>"#));
 }
 
 #[test]
@@ -60,6 +229,21 @@ fn write_category() {
     test_write!(channel);
 }
 
+#[test]
+fn write_category_domain() {
+    let input = include_str!("data/category_domain.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+
+    assert!(output.contains(
+        "<category domain=\"http://example.com/tags?a=1&amp;b=2\">Science &amp; Nature</category>"
+    ));
+    assert!(output.contains("<category>Space</category>"));
+    assert!(output.contains("<category domain=\"\">Astronomy</category>"));
+    assert!(output.contains("<category><![CDATA[ Padded ]]></category>"));
+    test_write!(channel);
+}
+
 #[test]
 fn write_image() {
     let input = include_str!("data/image.xml");
@@ -67,6 +251,27 @@ fn write_image() {
     test_write!(channel);
 }
 
+#[test]
+fn write_image_size() {
+    let mut image = Image::default();
+    image.set_url("http://example.com/logo.png".to_string());
+    image.set_width_px(144).unwrap();
+    image.set_height_px(400).unwrap();
+
+    let err = image.set_width_px(145).unwrap_err();
+    assert_eq!(err.dimension(), "width");
+    assert_eq!(err.value(), 145);
+    assert_eq!(err.max(), 144);
+    assert_eq!(err.to_string(), "the image width of 145 is larger than the maximum of 144");
+    assert!(image.set_height_px(401).is_err());
+
+    let mut channel = Channel::default();
+    channel.set_image(image);
+    let output = channel.to_string();
+    assert!(output.contains("<width>144</width><height>400</height>"));
+    test_write!(channel);
+}
+
 #[test]
 fn write_mixed_content() {
     let input = include_str!("data/mixed_content.xml");
@@ -86,6 +291,14 @@ fn write_textinput() {
     let input = include_str!("data/textinput.xml");
     let channel = input.parse::<Channel>().expect("failed to parse xml");
     test_write!(channel);
+
+    let input = include_str!("data/textinput.xml").replace("textInput", "textinput");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    assert!(output.contains(
+        "<textInput><title>Title</title><description>Description</description>\
+         <name>Name</name><link>http://example.com/</link></textInput>"
+    ));
 }
 
 #[test]
@@ -95,10 +308,200 @@ fn write_extension() {
     test_write!(channel);
 }
 
+#[test]
+fn write_extension_declared_on_element() {
+    let input = r#"
+        <rss version="2.0">
+            <channel>
+                <title>Title</title>
+                <ext:rating xmlns:ext="http://example.com/ext">5</ext:rating>
+                <item>
+                    <ext:note xmlns:ext="http://example.com/ext">Note</ext:note>
+                </item>
+            </channel>
+        </rss>
+    "#;
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.namespaces().is_empty());
+
+    let output = channel.write_to(Vec::new()).expect("failed to write channel");
+    let parsed = Channel::read_from(&output[..]).expect("failed to parse xml");
+    assert_eq!(channel, parsed);
+
+    let mut metadata = channel.clone();
+    metadata.set_items(Vec::new());
+    let mut writer = ChannelWriter::new(Vec::new(), &metadata).expect("failed to write channel");
+    writer.write_item(&channel.items()[0]).expect("failed to write item");
+    let output = writer.into_inner().expect("failed to finish channel");
+    assert_eq!(String::from_utf8(output).unwrap(), channel.to_string());
+}
+
+#[test]
+fn write_channel_writer() {
+    let input = include_str!("data/itunes.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let mut metadata = channel.clone();
+    metadata.set_items(Vec::new());
+
+    let mut writer = ChannelWriter::new(Vec::new(), &metadata).expect("failed to write channel");
+    for item in channel.items() {
+        writer.write_item(item).expect("failed to write item");
+    }
+    let output = writer.into_inner().expect("failed to finish channel");
+    assert_eq!(String::from_utf8(output).unwrap(), channel.to_string());
+}
+
+#[test]
+fn write_channel_writer_atom_links() {
+    let input = include_str!("data/atom_links.xml").replace(
+        "<title>Item</title>",
+        r#"<title>Item</title><atom:link rel="related" href="http://example.com/related"/>"#,
+    );
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let mut metadata = channel.clone();
+    metadata.set_items(Vec::new());
+
+    // the links of the channel declare the namespace of the links of the items
+    let mut writer = ChannelWriter::new(Vec::new(), &metadata).expect("failed to write channel");
+    writer.write_item(&channel.items()[0]).expect("failed to write item");
+    let output = writer.into_inner().expect("failed to finish channel");
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(r#"href="http://example.com/related""#));
+    assert_eq!(output, channel.to_string());
+
+    metadata.set_atom_links(Vec::new());
+    metadata.extensions_mut().remove("atom");
+    let mut writer = ChannelWriter::new(Vec::new(), &metadata).expect("failed to write channel");
+    match writer.write_item(&channel.items()[0]) {
+        Err(Error::UndeclaredNamespace(ref prefix)) if prefix == "atom" => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn write_channel_writer_errors() {
+    let channel = ChannelBuilder::default().title("Title").build().unwrap();
+    let mut item = Item::default();
+    item.set_dublin_core_ext(extension::dublincore::DublinCoreExtension::default());
+
+    let mut output = Vec::new();
+    {
+        let mut writer = ChannelWriter::new(&mut output, &channel).unwrap();
+        match writer.write_item(&item) {
+            Err(Error::UndeclaredNamespace(ref prefix)) if prefix == "dc" => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        writer.write_item(&Item::default()).unwrap();
+    }
+    // the writer was dropped without being finished
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.ends_with("<item></item></channel></rss>"));
+
+    let mut channel = channel;
+    channel.add_namespace("dc", extension::dublincore::NAMESPACE);
+    let mut writer = ChannelWriter::new(Vec::new(), &channel).unwrap();
+    writer.write_item(&item).unwrap();
+    writer.finish().unwrap();
+    match writer.write_item(&item) {
+        Err(Error::WriterFinished) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+    writer.finish().unwrap();
+}
+
 #[test]
 fn write_itunes() {
     let input = include_str!("data/itunes.xml");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.to_string();
+    assert!(output.contains("<itunes:explicit>false</itunes:explicit>"));
+    assert!(output.contains("<itunes:explicit>true</itunes:explicit>"));
+    assert!(output.contains(concat!(
+        "<itunes:owner><itunes:name>Name</itunes:name>",
+        "<itunes:email>example@example.com</itunes:email></itunes:owner>"
+    )));
+
+    // known spellings of itunes:explicit are written in their canonical form
+    let mut itunes_ext = channel.itunes_ext().cloned().unwrap();
+    let explicit = itunes_ext.explicit_parsed().map(String::from);
+    itunes_ext.set_explicit(explicit);
+    channel.set_itunes_ext(itunes_ext);
+    for item in channel.items_mut() {
+        if let Some(mut itunes_ext) = item.itunes_ext().cloned() {
+            let explicit = itunes_ext.explicit_parsed().map(String::from);
+            itunes_ext.set_explicit(explicit);
+            item.set_itunes_ext(itunes_ext);
+        }
+    }
+    test_write!(channel);
+}
+
+#[test]
+fn write_itunes_explicit_unknown() {
+    let mut itunes_ext = extension::itunes::ITunesChannelExtension::default();
+    itunes_ext.set_explicit("maybe".to_string());
+    let channel = ChannelBuilder::default()
+        .itunes_ext(itunes_ext)
+        .build()
+        .unwrap();
+
+    assert!(channel.to_string().contains("<itunes:explicit>maybe</itunes:explicit>"));
+    test_write!(channel);
+}
+
+#[test]
+fn write_itunes_categories() {
+    let input = include_str!("data/itunes_categories.xml");
     let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.to_string();
+    assert!(output.contains(concat!(
+        r#"<itunes:category text="Technology"><itunes:category text="Gadgets"/>"#,
+        r#"<itunes:category text="Podcasting"/></itunes:category>"#
+    )));
+    assert!(output.contains(concat!(
+        r#"<itunes:category text="Arts"><itunes:category text="Design">"#,
+        r#"<itunes:category text="Typography"/></itunes:category></itunes:category>"#
+    )));
+    assert!(output.contains(r#"<itunes:category text="Society &amp; Culture"/>"#));
+    test_write!(channel);
+}
+
+#[test]
+fn write_podcast() {
+    let input = include_str!("data/podcast.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.to_string();
+    assert_eq!(output.matches("xmlns:podcast=").count(), 1);
+    let locked = r#"<podcast:locked owner="podcastowner@example.com">yes</podcast:locked>"#;
+    assert!(output.contains(locked));
+    let chapters = r#"<podcast:chapters url="https://example.com/ep3_chapters.json" "#;
+    assert!(output.contains(chapters));
+    test_write!(channel);
+}
+
+#[test]
+fn write_slash() {
+    let input = include_str!("data/slash.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.to_string();
+    assert_eq!(output.matches("xmlns:slash=").count(), 1);
+    assert!(output.contains("<slash:comments>42</slash:comments>"));
+    assert!(output.contains("<slash:comments>many</slash:comments>"));
+    test_write!(channel);
+}
+
+#[test]
+fn write_syndication() {
+    let input = include_str!("data/syndication.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    assert!(output.contains(r#"xmlns:sy="http://purl.org/rss/1.0/modules/syndication/""#));
+    assert!(output.contains("<sy:updatePeriod>hourly</sy:updatePeriod>"));
+    assert!(output.contains("<sy:updateFrequency>4</sy:updateFrequency>"));
     test_write!(channel);
 }
 
@@ -109,6 +512,267 @@ fn write_dublincore() {
     test_write!(channel);
 }
 
+#[test]
+fn write_dublincore_repeated() {
+    let input = include_str!("data/dublincore_repeated.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    test_write!(channel);
+}
+
+#[test]
+fn write_extension_tree() {
+    let input = include_str!("data/extension_tree.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.to_string();
+    assert!(output.contains(concat!(
+        r#"<ext:report kind="a &amp; b" quote="&quot;q&quot;">"#,
+        r#"<ext:section name="one">Before<ext:note>First &lt;note&gt;</ext:note>Between"#,
+        r#"<ext:group><ext:entry id="1"><ext:value>1</ext:value></ext:entry><ext:flag/>"#,
+        r#"<ext:entry id="2"><ext:value>2</ext:value></ext:entry></ext:group>After"#,
+        r#"</ext:section><ext:empty/><ext:section name="two">&lt;raw&gt;</ext:section>"#,
+        r#"</ext:report>"#
+    )));
+    test_write!(channel);
+
+    let parsed = output.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(parsed.to_string(), output);
+}
+
+#[test]
+fn write_extension_builder() {
+    let value = extension::Extension::builder()
+        .name("ext:value")
+        .value("1 < 2".to_string())
+        .build()
+        .unwrap();
+    let entry = extension::Extension::builder()
+        .name("ext:entry")
+        .attr("id", "a&b")
+        .child(value)
+        .build()
+        .unwrap();
+    let flag = extension::Extension::builder()
+        .name("ext:flag")
+        .build()
+        .unwrap();
+    let group = extension::Extension::builder()
+        .name("ext:group")
+        .child(flag)
+        .child(entry)
+        .build()
+        .unwrap();
+
+    let mut map = HashMap::new();
+    map.insert("group".to_string(), vec![group]);
+    let mut extensions = HashMap::new();
+    extensions.insert("ext".to_string(), map);
+
+    let item = ItemBuilder::default()
+        .title("Item".to_string())
+        .extensions(extensions)
+        .build()
+        .unwrap();
+    let mut namespaces = HashMap::new();
+    namespaces.insert("ext".to_string(), "http://example.com/ext".to_string());
+    let channel = ChannelBuilder::default()
+        .namespaces(namespaces)
+        .items(vec![item])
+        .build()
+        .unwrap();
+
+    // children with different names are written sorted by name
+    let output = channel.to_string();
+    assert!(output.contains(concat!(
+        r#"<ext:group><ext:entry id="a&amp;b"><ext:value>1 &lt; 2</ext:value></ext:entry>"#,
+        r#"<ext:flag/></ext:group>"#
+    )));
+    test_write!(channel);
+}
+
+#[test]
+fn write_media() {
+    let input = include_str!("data/media.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    assert!(channel.to_string().contains("xmlns:media=\"http://search.yahoo.com/mrss/\""));
+    test_write!(channel);
+}
+
+#[test]
+fn write_unknown_elements() {
+    let input = include_str!("data/unknown.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    test_write!(channel);
+}
+
+#[test]
+fn write_atom_links() {
+    let input = include_str!("data/atom_links.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    let link = r#"href="http://example.com/feed?page=2" rel="self" type="application/rss+xml""#;
+    assert!(output.contains(&format!("<atom:link {}/>", link)));
+    assert!(output.contains(r#"<atom:link href="http://example.com/donate" rel="payment"/>"#));
+    assert!(output.contains(r#"hreflang="fr""#));
+    test_write!(channel);
+
+    // the links are written with the prefix that the Atom namespace was declared with
+    let input = input.replace("xmlns:atom=", "xmlns:a=").replace("atom:", "a:");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    assert_eq!(output.matches("http://www.w3.org/2005/Atom").count(), 1);
+    assert!(output.contains(r#"<a:link href="http://example.com/donate" rel="payment"/>"#));
+    test_write!(channel);
+
+    let mut channel = ChannelBuilder::default()
+        .title("Title")
+        .build()
+        .unwrap();
+    channel.set_next_page_url("http://example.com/feed?page=2".to_string());
+    channel.set_prev_page_url("http://example.com/feed?page=0".to_string());
+    channel.set_next_page_url("http://example.com/feed?page=3".to_string());
+    assert_eq!(channel.atom_links().len(), 2);
+    assert_eq!(channel.atom_links()[0].rel(), Some("next"));
+    assert_eq!(channel.atom_links()[1].rel(), Some("previous"));
+
+    let output = channel.to_string();
+    assert_eq!(output.matches(r#"xmlns:atom="http://www.w3.org/2005/Atom""#).count(), 1);
+    assert!(output.contains(r#"<atom:link href="http://example.com/feed?page=3" rel="next"/>"#));
+    let link = r#"<atom:link href="http://example.com/feed?page=0" rel="previous"/>"#;
+    assert!(output.contains(link));
+
+    let parsed = output.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(parsed.atom_links(), channel.atom_links());
+
+    channel.set_next_page_url(None);
+    channel.set_prev_page_url(None);
+    assert!(!channel.to_string().contains("xmlns:atom"));
+}
+
+#[test]
+fn write_websub_links() {
+    let input = include_str!("data/websub.xml");
+    let mut channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    assert_eq!(output.matches(r#"rel="hub""#).count(), 2);
+    test_write!(channel);
+
+    // the hubs replace the existing ones where the first of them was
+    channel.set_hubs(vec!["https://hub.example.com/"]);
+    let rels = channel.atom_links().iter().map(|link| link.rel().unwrap()).collect::<Vec<_>>();
+    assert_eq!(rels, vec!["hub", "self"]);
+    assert_eq!(channel.hubs(), vec!["https://hub.example.com/"]);
+
+    channel.set_hubs(Vec::<String>::new());
+    channel.set_atom_link_self("http://example.com/other.xml".to_string());
+    assert!(channel.hubs().is_empty());
+    let output = channel.to_string();
+    assert!(output.contains(r#"<atom:link href="http://example.com/other.xml" rel="self"/>"#));
+
+    let mut channel = ChannelBuilder::default()
+        .title("Title")
+        .build()
+        .unwrap();
+    channel.set_hubs(vec!["https://a.example.com/", "https://b.example.com/"]);
+    channel.set_atom_link_self("http://example.com/feed.xml".to_string());
+    let parsed = channel.to_string().parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(parsed.hubs(), vec!["https://a.example.com/", "https://b.example.com/"]);
+    assert_eq!(parsed.atom_link_self(), Some("http://example.com/feed.xml"));
+}
+
+#[test]
+fn write_atom_link_self() {
+    let mut channel = ChannelBuilder::default()
+        .title("Title")
+        .build()
+        .unwrap();
+    channel.set_atom_link_self("http://example.com/feed.xml".to_string());
+    let output = channel.to_string();
+    assert!(output.contains(r#"xmlns:atom="http://www.w3.org/2005/Atom""#));
+    assert!(output.contains(r#"<atom:link href="http://example.com/feed.xml" rel="self"/>"#));
+
+    // the Atom namespace of the source document is declared once, with its own prefix
+    let input = include_str!("data/atom_self.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    assert_eq!(output.matches("http://www.w3.org/2005/Atom").count(), 1);
+    assert!(output.contains(r#"xmlns:a="http://www.w3.org/2005/Atom""#));
+    let parsed = output.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(parsed.atom_link_self(), Some("http://example.com/feed.xml"));
+    assert_eq!(parsed.atom_links(), channel.atom_links());
+    test_write!(channel);
+}
+
+#[test]
+fn write_declared_namespaces() {
+    let input = include_str!("data/namespaces.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    // the writer declares the supported namespaces itself, and only when they are used
+    assert_eq!(channel.namespaces().len(), 1);
+    assert!(channel.namespaces().get("content").is_none());
+    assert!(channel.namespaces().get("media").is_none());
+
+    let output = channel.to_string();
+    assert!(!output.contains("xmlns:media="));
+    for declaration in &[
+        r#"xmlns:content="http://purl.org/rss/1.0/modules/content/""#,
+        r#"xmlns:ev="http://purl.org/rss/1.0/modules/event/""#,
+    ] {
+        assert_eq!(output.matches(declaration).count(), 1, "{} in {}", declaration, output);
+    }
+    assert!(output.contains("<ev:location>Town Hall</ev:location>"));
+    test_write!(channel);
+
+    // a supported prefix keeps the URI that it was declared with
+    let mut channel = channel;
+    channel.add_namespace("content", "http://example.com/content");
+    let output = channel.to_string();
+    assert!(output.contains(r#"xmlns:content="http://example.com/content""#));
+    assert!(!output.contains("http://purl.org/rss/1.0/modules/content/"));
+
+    // a supported namespace declared with another prefix is only declared once
+    let input = include_str!("data/syndication.xml")
+        .replace("xmlns:sy=", "xmlns:syn=")
+        .replace("sy:", "syn:");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+    let output = channel.to_string();
+    assert_eq!(output.matches("http://purl.org/rss/1.0/modules/syndication/").count(), 1);
+    assert!(output.contains("xmlns:sy="));
+    let parsed = output.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(parsed.syndication_ext(), channel.syndication_ext());
+}
+
+#[test]
+fn write_namespaces() {
+    let mut extension = extension::Extension::default();
+    extension.set_name("custom:rating");
+    extension.set_value("5".to_string());
+
+    let mut map = HashMap::new();
+    map.insert("rating".to_string(), vec![extension]);
+    let mut extensions = HashMap::new();
+    extensions.insert("custom".to_string(), map);
+
+    let mut channel = ChannelBuilder::default()
+        .title("Title")
+        .extensions(extensions)
+        .build()
+        .unwrap();
+
+    match channel.write_to(Vec::new()) {
+        Err(Error::UndeclaredNamespace(ref prefix)) if prefix == "custom" => {}
+        result => panic!("expected an undeclared namespace error, got {:?}", result),
+    }
+    assert!(channel.try_to_string().is_err());
+    assert_eq!(channel.to_string(), "");
+
+    channel.add_namespace("custom", "https://example.com/ns");
+    let output = channel.try_to_string().unwrap();
+    assert!(output.contains(r#"xmlns:custom="https://example.com/ns""#));
+    assert!(output.contains("<custom:rating>5</custom:rating>"));
+    test_write!(channel);
+}
+
 #[test]
 fn verify_write_format() {
     let item = ItemBuilder::default()
@@ -135,3 +799,304 @@ fn verify_write_format() {
 
     assert_eq!(channel.to_string(), output);
 }
+
+#[test]
+fn write_escaped() {
+    let special = "Tom & \"Jerry\" <'s> ]]> Show";
+    let url = "http://example.com/?a=1&b=<2>&c=\"3\"&d='4'";
+
+    let mut attrs = HashMap::new();
+    attrs.insert("attr".to_string(), special.to_string());
+    let ext = extension::ExtensionBuilder::default()
+        .name("ext:tag")
+        .value(special.to_string())
+        .attrs(attrs)
+        .build()
+        .unwrap();
+
+    let mut extensions = HashMap::new();
+    extensions.insert("ext".to_string(), HashMap::new());
+    extensions.get_mut("ext").unwrap().insert("tag".to_string(), vec![ext]);
+
+    let mut source = Source::default();
+    source.set_url(url);
+    source.set_title(special.to_string());
+
+    let mut category = Category::default();
+    category.set_name(special);
+    category.set_domain(url.to_string());
+
+    let enclosure = EnclosureBuilder::default()
+        .url(url)
+        .length("100")
+        .mime_type(special)
+        .build()
+        .unwrap();
+
+    let mut guid = Guid::default();
+    guid.set_value(url);
+
+    let item = ItemBuilder::default()
+        .title(special.to_string())
+        .link(url.to_string())
+        .description(special.to_string())
+        .content(special.to_string())
+        .categories(vec![category.clone()])
+        .enclosure(enclosure)
+        .guid(guid)
+        .source(source)
+        .extensions(extensions)
+        .build()
+        .unwrap();
+
+    let cloud = CloudBuilder::default()
+        .domain(url)
+        .port("80")
+        .path(special)
+        .register_procedure(special)
+        .protocol(special)
+        .build()
+        .unwrap();
+
+    let mut namespaces = HashMap::new();
+    namespaces.insert("ext".to_string(), url.to_string());
+
+    let channel = ChannelBuilder::default()
+        .title(special)
+        .link(url)
+        .description(special)
+        .categories(vec![category])
+        .cloud(cloud)
+        .items(vec![item])
+        .namespaces(namespaces)
+        .build()
+        .unwrap();
+
+    test_write!(channel);
+}
+
+#[test]
+fn pretty_write() {
+    let input = include_str!("data/rss2sample.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.pretty_write_to(Vec::new(), b'\t', 1).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let parsed = output.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(channel, parsed);
+
+    assert!(output.contains("\n\t<channel>\n\t\t<title>Liftoff News</title>\n"));
+    assert_eq!(output.replace("\n", "").replace("\t", ""), channel.to_string());
+}
+
+#[test]
+fn pretty_write_content() {
+    let input = include_str!("data/content.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel.pretty_write_to(Vec::new(), b' ', 4).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let parsed = output.parse::<Channel>().expect("failed to parse xml");
+    assert_eq!(channel, parsed);
+    assert_eq!(
+        channel.items()[0].content(),
+        parsed.items()[0].content()
+    );
+}
+
+#[test]
+fn pretty_write_whitespace_title() {
+    let channel = ChannelBuilder::default()
+        .title(" ")
+        .link("http://example.com/")
+        .description("Description")
+        .build()
+        .unwrap();
+
+    let output = channel.pretty_write_to(Vec::new(), b' ', 2).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("\n    <title> </title>\n    <link>"));
+}
+
+#[test]
+fn write_with_options() {
+    let input = include_str!("data/channel.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let output = channel
+        .write_with_options(Vec::new(), &WriteOptions::default())
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), channel.to_string());
+
+    let options = WriteOptionsBuilder::default()
+        .indent((b' ', 3))
+        .build()
+        .unwrap();
+    let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    let expected = channel.pretty_write_to(Vec::new(), b' ', 3).unwrap();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn write_cdata_content() {
+    let mut item = ItemBuilder::default()
+        .title("<b>Title</b>".to_string())
+        .description("<p>A &amp; B</p> ]]> <p>C</p>".to_string())
+        .content("<p>Content</p>".to_string())
+        .build()
+        .unwrap();
+    let mut itunes = extension::itunes::ITunesItemExtension::default();
+    itunes.set_summary("<i>Summary</i>".to_string());
+    item.set_itunes_ext(itunes);
+    let channel = ChannelBuilder::default()
+        .description("<p>Channel</p>")
+        .items(vec![item])
+        .build()
+        .unwrap();
+
+    let fields = vec!["description".to_string(), "itunes:summary".to_string()];
+    let options = WriteOptionsBuilder::default()
+        .cdata_fields(fields.clone())
+        .build()
+        .unwrap();
+    let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    // only the fields of the items are written as CDATA
+    assert!(output.contains("<description>&lt;p&gt;Channel&lt;/p&gt;</description>"));
+    assert!(output.contains(
+        "<description><![CDATA[<p>A &amp; B</p> ]]]]><![CDATA[> <p>C</p>]]></description>"
+    ));
+    assert!(output.contains("<itunes:summary><![CDATA[<i>Summary</i>]]></itunes:summary>"));
+    assert!(output.contains("<content:encoded><![CDATA[<p>Content</p>]]></content:encoded>"));
+    assert!(output.contains("<title>&lt;b&gt;Title&lt;/b&gt;</title>"));
+    assert_eq!(output.parse::<Channel>().unwrap(), channel);
+
+    let options = WriteOptionsBuilder::default()
+        .cdata_fields(fields)
+        .indent((b' ', 2))
+        .build()
+        .unwrap();
+    let pretty = channel.write_with_options(Vec::new(), &options).unwrap();
+    let pretty = String::from_utf8(pretty).unwrap();
+    assert!(pretty.contains("\n  <channel>\n"));
+    assert!(pretty.contains("\n      <description><![CDATA[<p>A &amp; B</p>"));
+    assert_eq!(pretty.replace("\n", "").replace("  ", ""), output);
+
+    let options = WriteOptionsBuilder::default()
+        .cdata_fields(vec!["title".to_string()])
+        .build()
+        .unwrap();
+    let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("<title><![CDATA[<b>Title</b>]]></title>"));
+    assert!(output.contains("<description>&lt;p&gt;Channel&lt;/p&gt;</description>"));
+    assert!(output.contains("<content:encoded><![CDATA[<p>Content</p>]]></content:encoded>"));
+    assert_eq!(output.parse::<Channel>().unwrap(), channel);
+
+    // the default options write no CDATA fields
+    let output = channel
+        .write_with_options(Vec::new(), &WriteOptions::default())
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), channel.to_string());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rating(String);
+
+impl CustomExtension for Rating {
+    fn to_extensions(&self, prefix: &str) -> Vec<extension::Extension> {
+        let mut rating = extension::Extension::default();
+        rating.set_name(format!("{}:rating", prefix));
+        rating.set_value(self.0.clone());
+        vec![rating]
+    }
+}
+
+fn parse_rating(
+    elements: &mut HashMap<String, Vec<extension::Extension>>,
+) -> Result<Option<Box<dyn CustomExtension>>, Error> {
+    let rating = elements
+        .remove("rating")
+        .and_then(|mut ratings| ratings.remove(0).value().map(String::from));
+    Ok(rating.map(|rating| Box::new(Rating(rating)) as Box<dyn CustomExtension>))
+}
+
+#[test]
+fn write_custom_extension() {
+    let namespace = "http://example.com/vendor";
+
+    let mut item = ItemBuilder::default().title("Item".to_string()).build().unwrap();
+    item.custom_extensions_mut().insert("v", namespace, Rating("R".to_string()));
+
+    let mut channel = ChannelBuilder::default()
+        .title("Title")
+        .items(vec![item])
+        .build()
+        .unwrap();
+    channel.custom_extensions_mut().insert("v", namespace, Rating("PG".to_string()));
+
+    let output = channel.to_string();
+    assert_eq!(output.matches(r#"xmlns:v="http://example.com/vendor""#).count(), 1);
+    assert!(output.contains("<v:rating>PG</v:rating>"));
+    assert!(output.contains("<v:rating>R</v:rating>"));
+
+    let mut options = ParseOptions::default();
+    options.register_extension(namespace, parse_rating);
+    let (parsed, _) = Channel::read_from_with_options(output.as_bytes(), &options).unwrap();
+    channel.add_namespace("v", namespace);
+    assert_eq!(parsed, channel);
+}
+
+#[test]
+fn write_version_rss092() {
+    let input = include_str!("data/downlevel.xml");
+    let channel = input.parse::<Channel>().expect("failed to parse xml");
+
+    let options = WriteOptions::default();
+    let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    assert_eq!(output, channel.write_to(Vec::new()).unwrap());
+
+    let mut options = WriteOptions::default();
+    options.set_version(Version::Rss0_92);
+    let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    let expected = include_str!("data/downlevel_092.xml")
+        .replace("\n", "")
+        .replace("\t", "");
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+}
+
+#[test]
+fn write_invalid_chars() {
+    let mut item = Item::default();
+    item.set_title("Back\u{8}space".to_string());
+    let mut channel = Channel::default();
+    channel.set_title("Tab\tand\u{1}null\u{0}");
+    channel.set_description("Bell\u{7}");
+    channel.set_items(vec![item]);
+
+    let output = channel.to_string();
+    assert!(output.contains("<title>Tab\tandnull</title>"));
+    assert!(output.contains("<description>Bell</description>"));
+    assert!(output.contains("<title>Backspace</title>"));
+    output.parse::<Channel>().expect("failed to parse xml");
+
+    let mut options = WriteOptions::default();
+    assert_eq!(options.invalid_char_policy(), InvalidCharPolicy::Strip);
+    options.set_invalid_char_policy(InvalidCharPolicy::Reference);
+    options.set_cdata_fields(vec!["title".to_string()]);
+    let output = channel.write_with_options(Vec::new(), &options).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("<title>Tab\tand&#x1;null&#x0;</title>"));
+    assert!(output.contains("<title><![CDATA[Back&#x8;space]]></title>"));
+
+    options.set_invalid_char_policy(InvalidCharPolicy::Error);
+    match channel.write_with_options(Vec::new(), &options) {
+        Err(Error::InvalidCharacter('\u{1}')) => {}
+        result => panic!("expected an invalid character, got {:?}", result),
+    }
+
+    channel.set_title("Title");
+    channel.set_description("Description");
+    channel.set_items(Vec::new());
+    channel.write_with_options(Vec::new(), &options).unwrap();
+}